@@ -18,6 +18,14 @@ pub enum AccountError {
     InvalidPassword(AccountAddress),
     #[error("invalid private key")]
     InvalidPrivateKey,
+    #[error("invalid mnemonic, {0}")]
+    InvalidMnemonic(String),
+    #[error("hd wallet seed already exists")]
+    HdWalletSeedAlreadyExist,
+    #[error("hd wallet seed not exists, create an account from mnemonic first")]
+    HdWalletSeedNotExist,
+    #[error("invalid derivation index {0}, only hardened index below 2^31 is supported")]
+    InvalidDerivationIndex(u32),
 
     // logic error
     #[error("transaction sign error, {0:?}")]
@@ -0,0 +1,239 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Password encrypted json keystore of an account private key, like the web3 keystore,
+//! used to move keys between nodes and external wallets.
+
+use crate::{AccountPrivateKey, AccountPublicKey};
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ValidCryptoMaterial;
+use starcoin_decrypt::{EncryptedParts, AES_NONCE_SIZE, PBKDF2_SALT_SIZE};
+use starcoin_types::account_address::AccountAddress;
+use std::convert::TryFrom;
+
+pub use starcoin_decrypt::KeyDerivation;
+
+pub const KEYSTORE_VERSION: u32 = 1;
+pub const KEYSTORE_CIPHER: &str = "aes-256-gcm";
+
+/// Upper bounds of the key derivation cost accepted from a keystore, an imported keystore is
+/// untrusted input and must not make the node spend unbounded time or memory before the password check.
+pub const KEYSTORE_MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+/// In KiB.
+pub const KEYSTORE_MAX_ARGON2_MEM_COST: u32 = 1024 * 1024;
+pub const KEYSTORE_MAX_ARGON2_TIME_COST: u32 = 64;
+pub const KEYSTORE_MAX_ARGON2_LANES: u32 = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeystoreScheme {
+    Ed25519,
+    MultiEd25519,
+}
+
+/// The threshold and the number of private key shards of a MultiEd25519 key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiEd25519Shards {
+    pub threshold: u8,
+    pub shards: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "lowercase")]
+pub enum KdfParams {
+    Pbkdf2 {
+        iterations: u32,
+        salt: String,
+    },
+    Argon2id {
+        mem_cost: u32,
+        time_cost: u32,
+        lanes: u32,
+        salt: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub nonce: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    /// Hex encoded ciphertext of the private key bytes.
+    pub ciphertext: String,
+    pub kdf: KdfParams,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub address: AccountAddress,
+    pub scheme: KeystoreScheme,
+    /// Hex encoded public key.
+    pub public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_ed25519: Option<MultiEd25519Shards>,
+    pub crypto: KeystoreCrypto,
+}
+
+impl Keystore {
+    /// Encrypt the `private_key` of account `address` with the key derived from `password` by `kdf`.
+    pub fn encrypt(
+        address: AccountAddress,
+        private_key: &AccountPrivateKey,
+        password: &str,
+        kdf: KeyDerivation,
+    ) -> Result<Self> {
+        check_kdf(&kdf)?;
+        let encrypted =
+            starcoin_decrypt::encrypt(kdf, password.as_bytes(), &private_key.to_bytes())?;
+        let parts = starcoin_decrypt::split(&encrypted)?;
+        let salt = hex::encode(parts.salt);
+        let kdf = match parts.kdf {
+            KeyDerivation::Pbkdf2 { iterations } => KdfParams::Pbkdf2 { iterations, salt },
+            KeyDerivation::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+            } => KdfParams::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            },
+        };
+        let (scheme, multi_ed25519) = match private_key {
+            AccountPrivateKey::Single(_) => (KeystoreScheme::Ed25519, None),
+            AccountPrivateKey::Multi(shard) => (
+                KeystoreScheme::MultiEd25519,
+                Some(MultiEd25519Shards {
+                    threshold: shard.threshold(),
+                    shards: shard.len(),
+                }),
+            ),
+        };
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address,
+            scheme,
+            public_key: hex::encode(private_key.public_key().to_bytes()),
+            multi_ed25519,
+            crypto: KeystoreCrypto {
+                cipher: KEYSTORE_CIPHER.to_string(),
+                cipherparams: CipherParams {
+                    nonce: hex::encode(parts.nonce),
+                },
+                ciphertext: hex::encode(parts.ciphertext),
+                kdf,
+            },
+        })
+    }
+
+    /// Decrypt the private key, and check it matches the public key of the keystore.
+    pub fn decrypt(&self, password: &str) -> Result<AccountPrivateKey> {
+        ensure!(
+            self.version == KEYSTORE_VERSION,
+            "unsupported keystore version {}",
+            self.version
+        );
+        ensure!(
+            self.crypto.cipher == KEYSTORE_CIPHER,
+            "unsupported keystore cipher {}",
+            self.crypto.cipher
+        );
+        let (kdf, salt) = match &self.crypto.kdf {
+            KdfParams::Pbkdf2 { iterations, salt } => (
+                KeyDerivation::Pbkdf2 {
+                    iterations: *iterations,
+                },
+                salt,
+            ),
+            KdfParams::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            } => (
+                KeyDerivation::Argon2id {
+                    mem_cost: *mem_cost,
+                    time_cost: *time_cost,
+                    lanes: *lanes,
+                },
+                salt,
+            ),
+        };
+        check_kdf(&kdf)?;
+        let parts = EncryptedParts {
+            kdf,
+            salt: decode_hex_array::<PBKDF2_SALT_SIZE>(salt, "salt")?,
+            nonce: decode_hex_array::<AES_NONCE_SIZE>(&self.crypto.cipherparams.nonce, "nonce")?,
+            ciphertext: hex::decode(&self.crypto.ciphertext)?,
+        };
+        let plain = starcoin_decrypt::decrypt(password.as_bytes(), &starcoin_decrypt::join(&parts))
+            .map_err(|_| format_err!("invalid password, cannot decrypt the keystore"))?;
+        let private_key = AccountPrivateKey::try_from(plain.as_slice())?;
+
+        let public_key = AccountPublicKey::try_from(hex::decode(&self.public_key)?.as_slice())?;
+        ensure!(
+            private_key.public_key() == public_key,
+            "the private key does not match the public key of the keystore"
+        );
+        let scheme = match private_key {
+            AccountPrivateKey::Single(_) => KeystoreScheme::Ed25519,
+            AccountPrivateKey::Multi(_) => KeystoreScheme::MultiEd25519,
+        };
+        ensure!(
+            scheme == self.scheme,
+            "the private key does not match the scheme {:?} of the keystore",
+            self.scheme
+        );
+        Ok(private_key)
+    }
+}
+
+fn check_kdf(kdf: &KeyDerivation) -> Result<()> {
+    match *kdf {
+        KeyDerivation::Pbkdf2 { iterations } => ensure!(
+            iterations > 0 && iterations <= KEYSTORE_MAX_PBKDF2_ITERATIONS,
+            "invalid pbkdf2 iterations {}, should be in [1, {}]",
+            iterations,
+            KEYSTORE_MAX_PBKDF2_ITERATIONS
+        ),
+        KeyDerivation::Argon2id {
+            mem_cost,
+            time_cost,
+            lanes,
+        } => {
+            ensure!(
+                mem_cost > 0 && mem_cost <= KEYSTORE_MAX_ARGON2_MEM_COST,
+                "invalid argon2id mem_cost {}, should be in [1, {}]",
+                mem_cost,
+                KEYSTORE_MAX_ARGON2_MEM_COST
+            );
+            ensure!(
+                time_cost > 0 && time_cost <= KEYSTORE_MAX_ARGON2_TIME_COST,
+                "invalid argon2id time_cost {}, should be in [1, {}]",
+                time_cost,
+                KEYSTORE_MAX_ARGON2_TIME_COST
+            );
+            ensure!(
+                lanes > 0 && lanes <= KEYSTORE_MAX_ARGON2_LANES,
+                "invalid argon2id lanes {}, should be in [1, {}]",
+                lanes,
+                KEYSTORE_MAX_ARGON2_LANES
+            );
+        }
+    }
+    Ok(())
+}
+
+fn decode_hex_array<const N: usize>(data: &str, name: &str) -> Result<[u8; N]> {
+    let bytes = hex::decode(data)?;
+    ensure!(bytes.len() == N, "invalid {} length {}", name, bytes.len());
+    let mut result = [0u8; N];
+    result.copy_from_slice(&bytes);
+    Ok(result)
+}
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::AccountInfo;
+use crate::{AccountInfo, MnemonicAccountInfo};
 use anyhow::Result;
 use starcoin_service_registry::ServiceRequest;
 use starcoin_types::account_address::AccountAddress;
@@ -44,6 +44,14 @@ pub enum AccountRequest {
         address: AccountAddress,
         new_password: String,
     },
+    CreateAccountFromMnemonic {
+        mnemonic: Option<String>,
+        password: String,
+    },
+    DeriveAccount {
+        index: u32,
+        password: String,
+    },
 }
 
 impl ServiceRequest for AccountRequest {
@@ -60,5 +68,6 @@ pub enum AccountResponse {
     ExportAccountResponse(Vec<u8>),
     AcceptedTokens(Vec<TokenCode>),
     MessageSignature(Box<AccountSignature>),
+    MnemonicAccountInfo(Box<MnemonicAccountInfo>),
     None,
 }
@@ -4,6 +4,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
+use starcoin_types::account_config::STC_TOKEN_CODE;
 use starcoin_types::contract_event::EventWithProof;
 use starcoin_types::event::EventKey;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction, TransactionPayload};
@@ -54,6 +55,30 @@ pub struct Setting {
     default_expiration_timeout: u64,
     default_gas_price: u64,
     default_gas_token: TokenCode,
+    /// The derivation path of the account derived from the HD wallet seed.
+    derivation_path: Option<String>,
+}
+
+impl Setting {
+    pub fn derivation_path(&self) -> Option<&str> {
+        self.derivation_path.as_deref()
+    }
+
+    pub fn with_derivation_path(mut self, derivation_path: String) -> Self {
+        self.derivation_path = Some(derivation_path);
+        self
+    }
+}
+
+impl Default for Setting {
+    fn default() -> Self {
+        Self {
+            default_expiration_timeout: 3600,
+            default_gas_price: 1,
+            default_gas_token: STC_TOKEN_CODE.clone(),
+            derivation_path: None,
+        }
+    }
 }
 
 pub trait WalletStorageTrait {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::message::{AccountRequest, AccountResponse};
-use crate::AccountInfo;
+use crate::{AccountInfo, MnemonicAccountInfo};
 use anyhow::Result;
 use starcoin_crypto::multi_ed25519::MultiEd25519Signature;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
@@ -61,6 +61,17 @@ pub trait AccountAsyncService:
         address: AccountAddress,
         new_password: String,
     ) -> Result<()>;
+
+    /// Create the HD wallet seed from `mnemonic`, or a new generated mnemonic if it is absent,
+    /// and derive the first account from it.
+    async fn create_account_from_mnemonic(
+        &self,
+        mnemonic: Option<String>,
+        password: String,
+    ) -> Result<MnemonicAccountInfo>;
+
+    /// Derive the account at `index` from the HD wallet seed, `password` is the seed's password.
+    async fn derive_account(&self, index: u32, password: String) -> Result<AccountInfo>;
 }
 
 #[async_trait::async_trait]
@@ -246,4 +257,30 @@ where
             panic!("Unexpected response type.")
         }
     }
+
+    async fn create_account_from_mnemonic(
+        &self,
+        mnemonic: Option<String>,
+        password: String,
+    ) -> Result<MnemonicAccountInfo> {
+        let response = self
+            .send(AccountRequest::CreateAccountFromMnemonic { mnemonic, password })
+            .await??;
+        if let AccountResponse::MnemonicAccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn derive_account(&self, index: u32, password: String) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::DeriveAccount { index, password })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
 }
@@ -23,6 +23,14 @@ pub struct AccountInfo {
     pub public_key: AccountPublicKey,
 }
 
+/// The account derived from the mnemonic of the HD wallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MnemonicAccountInfo {
+    /// Back up the mnemonic to recover all the accounts derived from it.
+    pub mnemonic: String,
+    pub account: AccountInfo,
+}
+
 impl AccountInfo {
     pub fn new(address: AccountAddress, public_key: AccountPublicKey, is_default: bool) -> Self {
         Self {
@@ -156,6 +156,19 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
                 self.manager.change_password(address, new_password)?;
                 AccountResponse::None
             }
+            AccountRequest::CreateAccountFromMnemonic { mnemonic, password } => {
+                let account = self
+                    .manager
+                    .create_account_from_mnemonic(mnemonic.as_deref(), password.as_str())?;
+                AccountResponse::MnemonicAccountInfo(Box::new(account))
+            }
+            AccountRequest::DeriveAccount { index, password } => {
+                AccountResponse::AccountInfo(Box::new(
+                    self.manager
+                        .derive_account(index, password.as_str())?
+                        .info(),
+                ))
+            }
         };
         Ok(response)
     }
@@ -174,7 +187,8 @@ mod tests {
         let registry = RegistryService::launch();
         let vault_config = &config.vault;
         let account_storage =
-            AccountStorage::create_from_path(vault_config.dir(), config.storage.rocksdb_config())?;
+            AccountStorage::create_from_path(vault_config.dir(), config.storage.rocksdb_config())?
+                .with_key_derivation(vault_config.key_derivation());
         registry.put_shared(config).await?;
         registry.put_shared(account_storage).await?;
         let service_ref = registry.register::<AccountService>().await?;
@@ -7,7 +7,6 @@ use anyhow::{format_err, Result};
 use starcoin_account_api::error::AccountError;
 use starcoin_account_api::{AccountInfo, AccountPrivateKey, AccountPublicKey, AccountResult};
 use starcoin_crypto::{PrivateKey, ValidCryptoMaterial};
-use starcoin_storage::storage::StorageInstance;
 use starcoin_types::account_address;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::sign_message::SigningMessage;
@@ -106,7 +105,7 @@ impl Account {
         let private_key = gen_private_key();
         let public_key = private_key.public_key();
         let address = account_address::from_public_key(&public_key);
-        let storage = AccountStorage::new(StorageInstance::new_cache_instance());
+        let storage = AccountStorage::mock();
         Self::create(private_key.into(), Some(address), "".to_string(), storage)
             .map_err(|e| e.into())
     }
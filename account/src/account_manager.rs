@@ -3,11 +3,14 @@
 
 use crate::account::Account;
 use crate::account_storage::AccountStorage;
+use crate::hd_wallet;
 
 use parking_lot::RwLock;
 use rand::prelude::*;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::{AccountInfo, AccountPrivateKey, AccountResult};
+use starcoin_account_api::{
+    AccountInfo, AccountPrivateKey, AccountResult, MnemonicAccountInfo, Setting,
+};
 use starcoin_crypto::ed25519::Ed25519PrivateKey;
 use starcoin_crypto::{Uniform, ValidCryptoMaterial};
 use starcoin_types::sign_message::SigningMessage;
@@ -77,6 +80,65 @@ impl AccountManager {
         self.save_account(address, private_key, password.to_string())
     }
 
+    /// Store the seed of `mnemonic`, or of a new generated mnemonic, as the HD wallet seed,
+    /// and derive the first account from it.
+    pub fn create_account_from_mnemonic(
+        &self,
+        mnemonic: Option<&str>,
+        password: &str,
+    ) -> AccountResult<MnemonicAccountInfo> {
+        if self.store.contain_seed()? {
+            return Err(AccountError::HdWalletSeedAlreadyExist);
+        }
+        let mnemonic = mnemonic
+            .map(|m| m.trim().to_string())
+            .unwrap_or_else(hd_wallet::generate_mnemonic);
+        let seed = hd_wallet::mnemonic_to_seed(mnemonic.as_str())
+            .map_err(|e| AccountError::InvalidMnemonic(e.to_string()))?;
+        // Save the seed first, the wallet is recovered even if the first account exists.
+        self.store.update_seed(seed.as_slice(), password)?;
+        let account = match self.derive_and_save_account(seed.as_slice(), 0, password) {
+            Ok(account) => account.info(),
+            // The address is derived from the key, the existing account is the same one.
+            Err(AccountError::AccountAlreadyExist(address)) => self
+                .account_info(address)?
+                .ok_or(AccountError::AccountNotExist(address))?,
+            Err(e) => return Err(e),
+        };
+        Ok(MnemonicAccountInfo { mnemonic, account })
+    }
+
+    /// Derive the account at `index` from the HD wallet seed.
+    pub fn derive_account(&self, index: u32, password: &str) -> AccountResult<Account> {
+        let seed = self
+            .store
+            .decrypt_seed(password)?
+            .ok_or(AccountError::HdWalletSeedNotExist)?;
+        self.derive_and_save_account(seed.as_slice(), index, password)
+    }
+
+    fn derive_and_save_account(
+        &self,
+        seed: &[u8],
+        index: u32,
+        password: &str,
+    ) -> AccountResult<Account> {
+        let path = hd_wallet::account_derivation_path(index)
+            .map_err(|_| AccountError::InvalidDerivationIndex(index))?;
+        let private_key = Ed25519PrivateKey::try_from(
+            hd_wallet::derive_private_key(seed, path.as_slice())?.as_ref(),
+        )
+        .map_err(|_| AccountError::InvalidPrivateKey)?;
+        let private_key = AccountPrivateKey::Single(private_key);
+        let address = private_key.public_key().derived_address();
+        let account = self.save_account(address, private_key, password.to_string())?;
+        self.store.update_default_settings(
+            address,
+            Setting::default().with_derivation_path(hd_wallet::format_derivation_path(&path)),
+        )?;
+        Ok(account)
+    }
+
     pub fn unlock_account(
         &self,
         address: AccountAddress,
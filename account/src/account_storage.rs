@@ -8,7 +8,8 @@ use serde::Serialize;
 use starcoin_account_api::{AccountPrivateKey, AccountPublicKey, Setting};
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::ValidCryptoMaterial;
-use starcoin_decrypt::{decrypt, encrypt};
+use starcoin_decrypt::{decrypt, encrypt, inspect, FormatVersion, KeyDerivation};
+use starcoin_logger::prelude::*;
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::storage::{KeyCodec, ValueCodec};
@@ -26,6 +27,14 @@ pub const ENCRYPTED_PRIVATE_KEY_PREFIX_NAME: ColumnFamilyName = "encrypted_priva
 pub const PUBLIC_KEY_PREFIX_NAME: ColumnFamilyName = "public_key";
 pub const ACCEPTED_TOKEN_PREFIX_NAME: ColumnFamilyName = "accepted_token";
 pub const GLOBAL_PREFIX_NAME: ColumnFamilyName = "global";
+pub const ENCRYPTED_SEED_PREFIX_NAME: ColumnFamilyName = "encrypted_seed";
+
+/// Cheap key derivation of the mock storage, to keep tests fast.
+const MOCK_KEY_DERIVATION: KeyDerivation = KeyDerivation::Argon2id {
+    mem_cost: 64,
+    time_cost: 1,
+    lanes: 1,
+};
 
 define_storage!(
     AccountSettingStore,
@@ -54,6 +63,13 @@ define_storage!(
     GLOBAL_PREFIX_NAME
 );
 
+define_storage!(
+    SeedStore,
+    GlobalSettingKey,
+    EncryptedSeed,
+    ENCRYPTED_SEED_PREFIX_NAME
+);
+
 define_storage!(
     AcceptedTokenStore,
     AccountAddressWrapper,
@@ -79,6 +95,8 @@ pub enum GlobalSettingKey {
     DefaultAddress,
     /// FIXME: once db support iter, remove this.
     AllAddresses,
+    /// The seed of the HD wallet.
+    HdWalletSeed,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -161,6 +179,24 @@ impl ValueCodec for EncryptedPrivateKey {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedSeed(pub Vec<u8>);
+impl From<Vec<u8>> for EncryptedSeed {
+    fn from(s: Vec<u8>) -> Self {
+        Self(s)
+    }
+}
+
+impl ValueCodec for EncryptedSeed {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.0.clone())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        Ok(EncryptedSeed(data.to_vec()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PublicKeyWrapper(AccountPublicKey);
 impl From<AccountPublicKey> for PublicKeyWrapper {
@@ -186,6 +222,9 @@ pub struct AccountStorage {
     public_key_store: PublicKeyStore,
     global_value_store: GlobalSettingStore,
     accepted_token_store: AcceptedTokenStore,
+    seed_store: SeedStore,
+    /// Used to encrypt private keys, keys encrypted by others are re-encrypted on decryption.
+    key_derivation: KeyDerivation,
 }
 
 impl AccountStorage {
@@ -198,6 +237,7 @@ impl AccountStorage {
                 PUBLIC_KEY_PREFIX_NAME,
                 ACCEPTED_TOKEN_PREFIX_NAME,
                 GLOBAL_PREFIX_NAME,
+                ENCRYPTED_SEED_PREFIX_NAME,
             ],
             false,
             rocksdb_config,
@@ -213,13 +253,20 @@ impl AccountStorage {
             private_key_store: PrivateKeyStore::new(store.clone()),
             public_key_store: PublicKeyStore::new(store.clone()),
             accepted_token_store: AcceptedTokenStore::new(store.clone()),
+            seed_store: SeedStore::new(store.clone()),
             global_value_store: GlobalSettingStore::new(store),
+            key_derivation: KeyDerivation::default(),
         }
     }
 
+    pub fn with_key_derivation(mut self, key_derivation: KeyDerivation) -> Self {
+        self.key_derivation = key_derivation;
+        self
+    }
+
     pub fn mock() -> Self {
         let storage_instance = StorageInstance::new_cache_instance();
-        Self::new(storage_instance)
+        Self::new(storage_instance).with_key_derivation(MOCK_KEY_DERIVATION)
     }
 }
 
@@ -291,6 +338,12 @@ impl AccountStorage {
             .map(|w| w.map(|p| p.0))
     }
 
+    pub fn encrypted_private_key(&self, address: AccountAddress) -> Result<Option<Vec<u8>>> {
+        self.private_key_store
+            .get(address.into())
+            .map(|w| w.map(|k| k.0))
+    }
+
     pub fn decrypt_private_key(
         &self,
         address: AccountAddress,
@@ -301,6 +354,13 @@ impl AccountStorage {
             Some(encrypted_key) => {
                 let plain_key_data = decrypt(password.as_ref().as_bytes(), &encrypted_key.0)?;
                 let private_key = AccountPrivateKey::try_from(plain_key_data.as_slice())?;
+                if inspect(&encrypted_key.0)? != (FormatVersion::LATEST, self.key_derivation) {
+                    info!(
+                        "Re-encrypt the private key of account {} with {:?}",
+                        address, self.key_derivation
+                    );
+                    self.update_key(address, &private_key, password)?;
+                }
                 Ok(Some(private_key))
             }
         }
@@ -312,7 +372,11 @@ impl AccountStorage {
         private_key: &AccountPrivateKey,
         password: impl AsRef<str>,
     ) -> Result<()> {
-        let encrypted_prikey = encrypt(password.as_ref().as_bytes(), &private_key.to_bytes());
+        let encrypted_prikey = encrypt(
+            self.key_derivation,
+            password.as_ref().as_bytes(),
+            &private_key.to_bytes(),
+        )?;
         self.private_key_store
             .put(address.into(), encrypted_prikey.into())?;
         let public_key = private_key.public_key();
@@ -321,7 +385,6 @@ impl AccountStorage {
         Ok(())
     }
 
-    #[allow(unused)]
     pub fn update_default_settings(
         &self,
         address: AccountAddress,
@@ -330,6 +393,41 @@ impl AccountStorage {
         self.setting_store.put(address.into(), setting.into())
     }
 
+    pub fn setting(&self, address: AccountAddress) -> Result<Option<Setting>> {
+        self.setting_store
+            .get(address.into())
+            .map(|w| w.map(|s| s.0))
+    }
+
+    pub fn contain_seed(&self) -> Result<bool> {
+        self.seed_store
+            .get(GlobalSettingKey::HdWalletSeed)
+            .map(|s| s.is_some())
+    }
+
+    pub fn update_seed(&self, seed: &[u8], password: impl AsRef<str>) -> Result<()> {
+        let encrypted_seed = encrypt(self.key_derivation, password.as_ref().as_bytes(), seed)?;
+        self.seed_store
+            .put(GlobalSettingKey::HdWalletSeed, encrypted_seed.into())
+    }
+
+    pub fn decrypt_seed(&self, password: impl AsRef<str>) -> Result<Option<Vec<u8>>> {
+        match self.seed_store.get(GlobalSettingKey::HdWalletSeed)? {
+            None => Ok(None),
+            Some(encrypted_seed) => {
+                let seed = decrypt(password.as_ref().as_bytes(), &encrypted_seed.0)?;
+                if inspect(&encrypted_seed.0)? != (FormatVersion::LATEST, self.key_derivation) {
+                    info!(
+                        "Re-encrypt the hd wallet seed with {:?}",
+                        self.key_derivation
+                    );
+                    self.update_seed(&seed, password)?;
+                }
+                Ok(Some(seed))
+            }
+        }
+    }
+
     pub fn destroy_account(&self, address: AccountAddress) -> Result<()> {
         if self.default_address()?.filter(|a| a == &address).is_some() {
             self.set_default_address(None)?;
@@ -2,12 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_storage::AccountStorage;
+use crate::hd_wallet;
 use crate::Account;
 use crate::AccountManager;
 use anyhow::Result;
 use starcoin_account_api::error::AccountError;
+use starcoin_account_api::keystore::{
+    KdfParams, Keystore, KEYSTORE_MAX_ARGON2_MEM_COST, KEYSTORE_MAX_PBKDF2_ITERATIONS,
+};
+use starcoin_account_api::AccountPrivateKey;
 use starcoin_config::RocksdbConfig;
+use starcoin_crypto::multi_ed25519::genesis_multi_key_pair;
 use starcoin_crypto::{SigningKey, ValidCryptoMaterial};
+use starcoin_decrypt::{inspect, KeyDerivation};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::genesis_config::ChainId;
@@ -16,6 +23,7 @@ use starcoin_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
 use starcoin_types::transaction::{
     RawUserTransaction, Script, SignedUserTransaction, TransactionPayload,
 };
+use std::convert::TryFrom;
 use std::time::Duration;
 
 #[test]
@@ -213,3 +221,190 @@ pub fn test_wallet_account() -> Result<()> {
     println!("txn hash is {:?}", stxn.id());
     Ok(())
 }
+
+#[test]
+pub fn test_re_encrypt_on_unlock() -> Result<()> {
+    let old_kdf = KeyDerivation::Pbkdf2 { iterations: 10 };
+    let new_kdf = KeyDerivation::Argon2id {
+        mem_cost: 64,
+        time_cost: 1,
+        lanes: 1,
+    };
+    let storage = AccountStorage::mock().with_key_derivation(old_kdf);
+    let manager = AccountManager::new(storage.clone())?;
+    let address = *manager.create_account("hello")?.address();
+    let kdf_of = |storage: &AccountStorage| -> Result<KeyDerivation> {
+        Ok(inspect(&storage.encrypted_private_key(address)?.unwrap())?.1)
+    };
+    assert_eq!(kdf_of(&storage)?, old_kdf);
+
+    let storage = storage.with_key_derivation(new_kdf);
+    let manager = AccountManager::new(storage.clone())?;
+    // wrong password should not touch the encrypted key.
+    assert!(manager
+        .unlock_account(address, "bad", Duration::from_secs(10))
+        .is_err());
+    assert_eq!(kdf_of(&storage)?, old_kdf);
+    manager.unlock_account(address, "hello", Duration::from_secs(10))?;
+    assert_eq!(kdf_of(&storage)?, new_kdf);
+    assert!(Account::load(address, "hello", storage)?.is_some());
+    Ok(())
+}
+
+#[test]
+pub fn test_slip10_ed25519_derivation() -> Result<()> {
+    // Test vector 1 of SLIP-0010 for ed25519.
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f")?;
+    let cases = vec![
+        (
+            vec![],
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+        ),
+        (
+            vec![0],
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        ),
+        (
+            vec![0, 1],
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        ),
+    ];
+    for (path, expect) in cases {
+        let key = hd_wallet::derive_private_key(seed.as_slice(), path.as_slice())?;
+        assert_eq!(hex::encode(key), expect);
+    }
+    assert!(hd_wallet::derive_private_key(seed.as_slice(), &[hd_wallet::HARDENED_OFFSET]).is_err());
+    assert_eq!(
+        hd_wallet::format_derivation_path(&hd_wallet::account_derivation_path(3)?),
+        "m/44'/101010'/0'/0'/3'"
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_mnemonic_to_seed() -> Result<()> {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let seed = hd_wallet::mnemonic_to_seed(mnemonic)?;
+    assert_eq!(hex::encode(seed), "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4");
+    assert!(hd_wallet::mnemonic_to_seed("abandon abandon abandon").is_err());
+    let generated = hd_wallet::generate_mnemonic();
+    assert_eq!(generated.split_whitespace().count(), 24);
+    assert!(hd_wallet::mnemonic_to_seed(generated.as_str()).is_ok());
+    Ok(())
+}
+
+#[test]
+pub fn test_hd_wallet() -> Result<()> {
+    let storage = AccountStorage::mock();
+    let manager = AccountManager::new(storage.clone())?;
+    assert!(matches!(
+        manager.derive_account(1, "hello"),
+        Err(AccountError::HdWalletSeedNotExist)
+    ));
+    assert!(matches!(
+        manager.create_account_from_mnemonic(Some("not a mnemonic"), "hello"),
+        Err(AccountError::InvalidMnemonic(_))
+    ));
+
+    let created = manager.create_account_from_mnemonic(None, "hello")?;
+    let first = created.account.address;
+    assert_eq!(
+        storage.setting(first)?.unwrap().derivation_path(),
+        Some("m/44'/101010'/0'/0'/0'")
+    );
+    assert!(matches!(
+        manager.create_account_from_mnemonic(None, "hello"),
+        Err(AccountError::HdWalletSeedAlreadyExist)
+    ));
+    let second = *manager.derive_account(1, "hello")?.address();
+    assert_ne!(first, second);
+    assert!(manager.derive_account(2, "bad").is_err());
+    assert!(matches!(
+        manager.derive_account(hd_wallet::HARDENED_OFFSET, "hello"),
+        Err(AccountError::InvalidDerivationIndex(_))
+    ));
+
+    // recover from the mnemonic in another wallet derives the same accounts.
+    let recovered = AccountManager::new(AccountStorage::mock())?;
+    let account =
+        recovered.create_account_from_mnemonic(Some(created.mnemonic.as_str()), "world")?;
+    assert_eq!(account.account.address, first);
+    assert_eq!(*recovered.derive_account(1, "world")?.address(), second);
+
+    // recover in a wallet which already imported the first account.
+    let imported = AccountManager::new(AccountStorage::mock())?;
+    imported.import_account(first, manager.export_account(first, "hello")?, "other")?;
+    let account =
+        imported.create_account_from_mnemonic(Some(created.mnemonic.as_str()), "world")?;
+    assert_eq!(account.account.address, first);
+    assert_eq!(*imported.derive_account(1, "world")?.address(), second);
+    Ok(())
+}
+
+#[test]
+pub fn test_keystore() -> Result<()> {
+    let kdf = KeyDerivation::Argon2id {
+        mem_cost: 64,
+        time_cost: 1,
+        lanes: 1,
+    };
+    let single: AccountPrivateKey = super::account_manager::gen_private_key().into();
+    let (multi, _) = genesis_multi_key_pair();
+    let multi: AccountPrivateKey = multi.into();
+    for private_key in vec![single, multi] {
+        let address = private_key.public_key().derived_address();
+        let keystore = Keystore::encrypt(address, &private_key, "hello", kdf)?;
+        let json = serde_json::to_string(&keystore)?;
+        let keystore: Keystore = serde_json::from_str(json.as_str())?;
+        assert_eq!(keystore.address, address);
+        assert_eq!(keystore.decrypt("hello")?, private_key);
+        assert!(keystore.decrypt("bad").is_err());
+
+        let mut tampered = keystore.clone();
+        tampered.public_key = hex::encode(
+            AccountPrivateKey::Single(super::account_manager::gen_private_key())
+                .public_key()
+                .to_bytes(),
+        );
+        assert!(tampered.decrypt("hello").is_err());
+
+        // a keystore asking for an unbounded key derivation cost is rejected.
+        let mut tampered = keystore.clone();
+        if let KdfParams::Argon2id { mem_cost, .. } = &mut tampered.crypto.kdf {
+            *mem_cost = KEYSTORE_MAX_ARGON2_MEM_COST + 1;
+        }
+        assert!(tampered.decrypt("hello").is_err());
+        let mut tampered = keystore.clone();
+        if let KdfParams::Argon2id { lanes, .. } = &mut tampered.crypto.kdf {
+            *lanes = 0;
+        }
+        assert!(tampered.decrypt("hello").is_err());
+    }
+    assert!(Keystore::encrypt(
+        AccountAddress::random(),
+        &AccountPrivateKey::Single(super::account_manager::gen_private_key()),
+        "hello",
+        KeyDerivation::Pbkdf2 {
+            iterations: KEYSTORE_MAX_PBKDF2_ITERATIONS + 1,
+        },
+    )
+    .is_err());
+
+    // import the exported keystore into another wallet.
+    let manager = AccountManager::new(AccountStorage::mock())?;
+    let account = manager.create_account("hello")?;
+    let private_key = AccountPrivateKey::try_from(
+        manager
+            .export_account(*account.address(), "hello")?
+            .as_slice(),
+    )?;
+    let keystore = Keystore::encrypt(*account.address(), &private_key, "hello", kdf)?;
+    let other = AccountManager::new(AccountStorage::mock())?;
+    let imported = other.import_account(
+        keystore.address,
+        keystore.decrypt("hello")?.to_bytes(),
+        "world",
+    )?;
+    assert_eq!(imported.address(), account.address());
+    Ok(())
+}
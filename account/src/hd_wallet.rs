@@ -0,0 +1,77 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! BIP39 mnemonic and SLIP-0010 Ed25519 key derivation of the HD wallet.
+//! Ed25519 only supports hardened derivation, so every level of the path is hardened.
+
+use anyhow::{ensure, format_err, Result};
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+
+/// The BIP44 purpose.
+pub const PURPOSE: u32 = 44;
+/// The SLIP-0044 coin type of Starcoin.
+pub const COIN_TYPE: u32 = 101010;
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// Generate a new 24 words English mnemonic.
+pub fn generate_mnemonic() -> String {
+    Mnemonic::new(MnemonicType::Words24, Language::English).into_phrase()
+}
+
+/// Check the mnemonic and convert it to the BIP39 seed, with an empty passphrase.
+pub fn mnemonic_to_seed(phrase: &str) -> Result<Vec<u8>> {
+    let mnemonic = Mnemonic::from_phrase(phrase, Language::English)?;
+    Ok(Seed::new(&mnemonic, "").as_bytes().to_vec())
+}
+
+/// The derivation path of the account at `index`: `m/44'/101010'/0'/0'/{index}'`.
+pub fn account_derivation_path(index: u32) -> Result<Vec<u32>> {
+    ensure!(
+        index < HARDENED_OFFSET,
+        "derivation index {} out of range",
+        index
+    );
+    Ok(vec![PURPOSE, COIN_TYPE, 0, 0, index])
+}
+
+pub fn format_derivation_path(path: &[u32]) -> String {
+    path.iter().fold("m".to_string(), |mut s, index| {
+        s.push_str(&format!("/{}'", index));
+        s
+    })
+}
+
+/// Derive the Ed25519 private key of `path` from `seed`, as described in SLIP-0010.
+pub fn derive_private_key(seed: &[u8], path: &[u32]) -> Result<[u8; 32]> {
+    let (mut key, mut chain_code) = hmac_sha512(ED25519_CURVE_SEED, &[seed])?;
+    for index in path {
+        ensure!(
+            *index < HARDENED_OFFSET,
+            "derivation index {} out of range",
+            index
+        );
+        let hardened_index = (index | HARDENED_OFFSET).to_be_bytes();
+        let (child_key, child_chain_code) =
+            hmac_sha512(&chain_code, &[&[0u8], &key, &hardened_index])?;
+        key = child_key;
+        chain_code = child_chain_code;
+    }
+    Ok(key)
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Result<([u8; 32], [u8; 32])> {
+    let mut mac = Hmac::<Sha512>::new_varkey(key).map_err(|e| format_err!("{:?}", e))?;
+    for d in data {
+        mac.update(d);
+    }
+    let result = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+    Ok((left, right))
+}
@@ -3,6 +3,7 @@
 
 mod account;
 mod account_manager;
+pub mod hd_wallet;
 
 pub use account::Account;
 pub use account_manager::AccountManager;
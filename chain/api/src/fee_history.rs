@@ -0,0 +1,91 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use starcoin_types::block::BlockNumber;
+
+/// The gas usage and gas prices of a block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockFeeInfo {
+    pub number: BlockNumber,
+    pub gas_used: u64,
+    /// The block gas limit of the epoch which the block belongs to.
+    pub gas_limit: u64,
+    /// The gas prices of the user transactions in the block at the requested percentiles,
+    /// all zero for the block without user transaction.
+    pub gas_price_percentiles: Vec<u64>,
+}
+
+/// The fee history of recent blocks on the main chain, ordered from old to new.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FeeHistory {
+    pub blocks: Vec<BlockFeeInfo>,
+}
+
+impl FeeHistory {
+    pub fn oldest_block(&self) -> Option<BlockNumber> {
+        self.blocks.first().map(|block| block.number)
+    }
+}
+
+/// Every percentile should be in [0, 100], and in ascending order.
+pub fn check_percentiles(percentiles: &[f64]) -> Result<()> {
+    let mut last = 0f64;
+    for percentile in percentiles {
+        ensure!(
+            (0f64..=100f64).contains(percentile),
+            "Percentile {} should be in [0, 100]",
+            percentile
+        );
+        ensure!(
+            *percentile >= last,
+            "Percentiles should be in ascending order"
+        );
+        last = *percentile;
+    }
+    Ok(())
+}
+
+/// Get the gas prices at `percentiles` of the transactions, `txns` are `(gas_price, gas_used)` pairs,
+/// every transaction is weighted by its gas used.
+pub fn gas_price_percentiles(mut txns: Vec<(u64, u64)>, percentiles: &[f64]) -> Vec<u64> {
+    if txns.is_empty() {
+        return vec![0; percentiles.len()];
+    }
+    txns.sort_by_key(|(gas_price, _)| *gas_price);
+    let total_gas_used = txns
+        .iter()
+        .fold(0u64, |total, (_, gas_used)| total.saturating_add(*gas_used));
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = (total_gas_used as f64 * percentile / 100f64) as u64;
+            let mut sum_gas_used = 0u64;
+            for (gas_price, gas_used) in &txns {
+                sum_gas_used = sum_gas_used.saturating_add(*gas_used);
+                if sum_gas_used >= threshold {
+                    return *gas_price;
+                }
+            }
+            txns.last().map(|(gas_price, _)| *gas_price).unwrap_or(0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_price_percentiles() {
+        assert_eq!(gas_price_percentiles(vec![], &[10f64, 50f64]), vec![0, 0]);
+        let txns = vec![(3, 100), (1, 100), (2, 200)];
+        assert_eq!(
+            gas_price_percentiles(txns, &[0f64, 25f64, 50f64, 75f64, 100f64]),
+            vec![1, 1, 2, 2, 3]
+        );
+        assert!(check_percentiles(&[10f64, 50f64, 90f64]).is_ok());
+        assert!(check_percentiles(&[50f64, 10f64]).is_err());
+        assert!(check_percentiles(&[101f64]).is_err());
+    }
+}
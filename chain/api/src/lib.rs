@@ -6,7 +6,9 @@ use starcoin_vm_types::transaction::SignedUserTransaction;
 
 mod chain;
 mod errors;
+mod fee_history;
 pub mod message;
+mod replay;
 mod service;
 
 #[derive(Clone, Debug)]
@@ -17,4 +19,6 @@ pub struct ExcludedTxns {
 
 pub use chain::{Chain, ChainReader, ChainWriter, ExecutedBlock, MintedUncleNumber, VerifiedBlock};
 pub use errors::*;
+pub use fee_history::{check_percentiles, gas_price_percentiles, BlockFeeInfo, FeeHistory};
+pub use replay::{BlockReplayReport, ReplayMismatch, TransactionReplayTrace};
 pub use service::{ChainAsyncService, ReadableChainService, WriteableChainService};
@@ -1,11 +1,15 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::FeeHistory;
 use anyhow::Result;
+use starcoin_chain_proof::TransactionProof;
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::BlockSummary;
 use starcoin_types::block::EpochUncleSummary;
+use starcoin_types::peer_info::PeerId;
 use starcoin_types::stress_test::TPS;
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
@@ -36,6 +40,10 @@ pub enum ChainRequest {
     GetTransactionBlock(HashValue),
     GetTransaction(HashValue),
     GetTransactionInfo(HashValue),
+    GetTransactionProof {
+        txn_hash: HashValue,
+        anchor_block_id: Option<HashValue>,
+    },
     GetBlockTransactionInfos(HashValue),
     GetTransactionInfoByBlockAndIndex {
         block_hash: HashValue,
@@ -56,6 +64,20 @@ pub enum ChainRequest {
     GetEpochUnclesByNumber(Option<BlockNumber>),
     UnclePath(HashValue, HashValue),
     EpochUncleSummaryByNumber(Option<BlockNumber>),
+    GetTransactionInfosByAddress {
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    },
+    GetFeeHistory {
+        block_count: u64,
+        percentiles: Vec<f64>,
+    },
+    GetFailedBlocks {
+        cursor: Option<HashValue>,
+        limit: u64,
+    },
+    GetFailedBlock(HashValue),
 }
 
 impl ServiceRequest for ChainRequest {
@@ -80,6 +102,7 @@ pub enum ChainResponse {
     BlockHeaderVec(Vec<BlockHeader>),
     TransactionInfos(Vec<BlockTransactionInfo>),
     TransactionInfo(Option<BlockTransactionInfo>),
+    TransactionProof(Option<Box<TransactionProof>>),
     Events(Vec<ContractEventInfo>),
     MainEvents(Vec<ContractEventInfo>),
     None,
@@ -90,4 +113,8 @@ pub enum ChainResponse {
     TPS(TPS),
     BlockSummaries(Vec<BlockSummary>),
     UncleSummary(EpochUncleSummary),
+    AddressTransactionInfos(Vec<(u64, BlockTransactionInfo)>),
+    FeeHistory(FeeHistory),
+    FailedBlocks(Vec<(Block, Option<PeerId>, String)>),
+    FailedBlock(Option<Box<(Block, Option<PeerId>, String)>>),
 }
@@ -0,0 +1,85 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use starcoin_crypto::HashValue;
+use starcoin_types::block::{BlockHeader, BlockNumber};
+use starcoin_types::transaction::TransactionStatus;
+
+/// The trace of a transaction when replaying a block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionReplayTrace {
+    pub txn_hash: HashValue,
+    pub status: TransactionStatus,
+    pub gas_used: u64,
+    /// The gas used by the kept transactions of the block up to and including this transaction.
+    pub cumulative_gas_used: u64,
+    /// The state root after applying this transaction, None if the transaction is discarded.
+    pub state_root: Option<HashValue>,
+    /// The transaction info id, None if the transaction is discarded.
+    pub txn_info_id: Option<HashValue>,
+    pub event_count: u64,
+}
+
+/// A block header field which does not match the replay result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReplayMismatch {
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl ReplayMismatch {
+    pub fn new<T: ToString>(field: &str, expected: T, actual: T) -> Self {
+        Self {
+            field: field.to_string(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+}
+
+/// The result of re-verifying and re-executing a block on its parent state, nothing is saved.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockReplayReport {
+    pub block_id: HashValue,
+    pub number: BlockNumber,
+    pub parent_hash: HashValue,
+    /// The error of verifying the block against its parent, None if the verification passed.
+    pub verify_error: Option<String>,
+    /// The error of executing the block transactions, None if the vm executed all of them.
+    pub execute_error: Option<String>,
+    /// The traces of executed transactions, the first one is the block metadata transaction.
+    pub txns: Vec<TransactionReplayTrace>,
+    /// The transactions which are not executed because the block gas limit is reached.
+    pub unexecuted_txns: Vec<HashValue>,
+    /// The block header fields which do not match the replay result.
+    pub mismatches: Vec<ReplayMismatch>,
+    /// The index in `txns` of the first transaction where the execution diverged,
+    /// None if the divergence can not be located to a transaction.
+    pub diverged_at: Option<u64>,
+}
+
+impl BlockReplayReport {
+    pub fn new(header: &BlockHeader) -> Self {
+        Self {
+            block_id: header.id(),
+            number: header.number(),
+            parent_hash: header.parent_hash(),
+            verify_error: None,
+            execute_error: None,
+            txns: vec![],
+            unexecuted_txns: vec![],
+            mismatches: vec![],
+            diverged_at: None,
+        }
+    }
+
+    /// Is the replay result consistent with the block.
+    pub fn is_consistent(&self) -> bool {
+        self.verify_error.is_none()
+            && self.execute_error.is_none()
+            && self.unexecuted_txns.is_empty()
+            && self.mismatches.is_empty()
+            && self.diverged_at.is_none()
+    }
+}
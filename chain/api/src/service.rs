@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::message::{ChainRequest, ChainResponse};
+use crate::FeeHistory;
 use anyhow::{bail, Result};
+use starcoin_chain_proof::TransactionProof;
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{BlockSummary, EpochUncleSummary};
 use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
 use starcoin_types::filter::Filter;
+use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
 use starcoin_types::{
@@ -25,6 +29,11 @@ pub trait ReadableChainService {
     fn get_block_info_by_hash(&self, hash: HashValue) -> Result<Option<BlockInfo>>;
     fn get_transaction(&self, hash: HashValue) -> Result<Option<Transaction>>;
     fn get_transaction_info(&self, txn_hash: HashValue) -> Result<Option<BlockTransactionInfo>>;
+    fn get_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_id: Option<HashValue>,
+    ) -> Result<Option<TransactionProof>>;
     fn get_block_txn_infos(&self, block_id: HashValue) -> Result<Vec<BlockTransactionInfo>>;
     fn get_txn_info_by_block_and_index(
         &self,
@@ -59,6 +68,22 @@ pub trait ReadableChainService {
         &self,
         number: Option<BlockNumber>,
     ) -> Result<EpochUncleSummary>;
+    fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, BlockTransactionInfo)>>;
+    fn get_fee_history(&self, block_count: u64, percentiles: Vec<f64>) -> Result<FeeHistory>;
+    fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>>;
+    fn get_failed_block(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Option<(Block, Option<PeerId>, String)>>;
 }
 
 /// Writeable block chain service trait
@@ -86,6 +111,13 @@ pub trait ChainAsyncService:
         &self,
         txn_hash: HashValue,
     ) -> Result<Option<BlockTransactionInfo>>;
+    /// Get the proof of the transaction on main chain, proved to the block accumulator of the
+    /// anchor block, or of the head block if `anchor_block_id` is absent.
+    async fn get_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_id: Option<HashValue>,
+    ) -> Result<Option<TransactionProof>>;
     async fn get_transaction_block(&self, txn_hash: HashValue) -> Result<Option<Block>>;
     async fn get_block_txn_infos(&self, block_hash: HashValue)
         -> Result<Vec<BlockTransactionInfo>>;
@@ -126,6 +158,27 @@ pub trait ChainAsyncService:
         &self,
         number: Option<BlockNumber>,
     ) -> Result<EpochUncleSummary>;
+    /// Get transaction infos related to `address` on main chain, from newer to older,
+    /// each item is `(index, txn_info)`, `index` is the position in the address's history.
+    async fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, BlockTransactionInfo)>>;
+    /// Get the gas usage and the gas price `percentiles` of the latest `block_count` blocks on main chain.
+    async fn get_fee_history(&self, block_count: u64, percentiles: Vec<f64>) -> Result<FeeHistory>;
+    /// Get at most `limit` blocks which failed to verify or execute, with the peer which sent it
+    /// and the failed reason, order by block hash, start after the block hash `cursor`.
+    async fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>>;
+    async fn get_failed_block(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Option<(Block, Option<PeerId>, String)>>;
 }
 
 #[async_trait::async_trait]
@@ -219,6 +272,24 @@ where
         }
     }
 
+    async fn get_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_id: Option<HashValue>,
+    ) -> Result<Option<TransactionProof>> {
+        let response = self
+            .send(ChainRequest::GetTransactionProof {
+                txn_hash,
+                anchor_block_id,
+            })
+            .await??;
+        if let ChainResponse::TransactionProof(proof) = response {
+            Ok(proof.map(|proof| *proof))
+        } else {
+            bail!("get transaction proof error:{:?}", txn_hash)
+        }
+    }
+
     async fn get_transaction_block(&self, txn_hash: HashValue) -> Result<Option<Block>> {
         let response = self
             .send(ChainRequest::GetTransactionBlock(txn_hash))
@@ -433,6 +504,67 @@ where
         }
     }
 
+    async fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, BlockTransactionInfo)>> {
+        let response = self
+            .send(ChainRequest::GetTransactionInfosByAddress {
+                address,
+                cursor,
+                limit,
+            })
+            .await??;
+        if let ChainResponse::AddressTransactionInfos(txn_infos) = response {
+            Ok(txn_infos)
+        } else {
+            bail!("get transaction infos by address error.")
+        }
+    }
+
+    async fn get_fee_history(&self, block_count: u64, percentiles: Vec<f64>) -> Result<FeeHistory> {
+        let response = self
+            .send(ChainRequest::GetFeeHistory {
+                block_count,
+                percentiles,
+            })
+            .await??;
+        if let ChainResponse::FeeHistory(fee_history) = response {
+            Ok(fee_history)
+        } else {
+            bail!("get fee history error.")
+        }
+    }
+
+    async fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>> {
+        let response = self
+            .send(ChainRequest::GetFailedBlocks { cursor, limit })
+            .await??;
+        if let ChainResponse::FailedBlocks(failed_blocks) = response {
+            Ok(failed_blocks)
+        } else {
+            bail!("get failed blocks error.")
+        }
+    }
+
+    async fn get_failed_block(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Option<(Block, Option<PeerId>, String)>> {
+        let response = self.send(ChainRequest::GetFailedBlock(block_id)).await??;
+        if let ChainResponse::FailedBlock(failed_block) = response {
+            Ok(failed_block.map(|failed_block| *failed_block))
+        } else {
+            bail!("get failed block error.")
+        }
+    }
+
     async fn uncle_path(
         &self,
         block_id: HashValue,
@@ -5,16 +5,18 @@ pub mod message;
 
 use crate::message::{ContractEventNotification, Event, Notification, ThinBlock};
 use anyhow::{format_err, Result};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_storage::{Storage, Store};
 use starcoin_types::block::Block;
-use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::system_events::{ChainReorg, NewHeadBlock};
 use std::sync::Arc;
 
 /// ChainNotify watch `NewHeadBlock` message from bus,
 /// and then reproduce `Notification<ThinBlock>` and `Notification<Arc<[Event]>>` message to bus.
 /// User can subscribe the two notification to watch onchain events.
+/// On `ChainReorg`, the events of the retracted blocks are reproduced with `removed` set.
 pub struct ChainNotifyHandlerService {
     store: Arc<dyn Store>,
 }
@@ -37,11 +39,13 @@ impl ServiceFactory<Self> for ChainNotifyHandlerService {
 impl ActorService for ChainNotifyHandlerService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<NewHeadBlock>();
+        ctx.subscribe::<ChainReorg>();
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<NewHeadBlock>();
+        ctx.unsubscribe::<ChainReorg>();
         Ok(())
     }
 }
@@ -64,6 +68,21 @@ impl EventHandler<Self, NewHeadBlock> for ChainNotifyHandlerService {
     }
 }
 
+impl EventHandler<Self, ChainReorg> for ChainNotifyHandlerService {
+    fn handle_event(
+        &mut self,
+        item: ChainReorg,
+        ctx: &mut ServiceContext<ChainNotifyHandlerService>,
+    ) {
+        // notify the removed events of retracted blocks, from new to old.
+        for block_id in item.retracted {
+            if let Err(e) = self.notify_removed_events(block_id, ctx) {
+                error!(target: "pubsub", "fail to notify removed events to client, err: {}", &e);
+            }
+        }
+    }
+}
+
 impl ChainNotifyHandlerService {
     pub fn notify_new_block(&self, block: &Block, ctx: &mut ServiceContext<Self>) {
         let thin_block = ThinBlock::new(
@@ -79,6 +98,31 @@ impl ChainNotifyHandlerService {
         store: Arc<dyn Store>,
         ctx: &mut ServiceContext<Self>,
     ) -> Result<()> {
+        let all_events = Self::block_events(block, store.as_ref())?;
+        let events_notification: ContractEventNotification = Notification(all_events.into());
+        ctx.broadcast(events_notification);
+        Ok(())
+    }
+
+    pub fn notify_removed_events(
+        &self,
+        block_id: HashValue,
+        ctx: &mut ServiceContext<Self>,
+    ) -> Result<()> {
+        let block = self
+            .store
+            .get_block(block_id)?
+            .ok_or_else(|| format_err!("cannot find block by it's id {}", &block_id))?;
+        let removed_events: Vec<Event> = Self::block_events(&block, self.store.as_ref())?
+            .into_iter()
+            .map(Event::into_removed)
+            .collect();
+        let events_notification: ContractEventNotification = Notification(removed_events.into());
+        ctx.broadcast(events_notification);
+        Ok(())
+    }
+
+    fn block_events(block: &Block, store: &dyn Store) -> Result<Vec<Event>> {
         let block_number = block.header().number();
         let block_id = block.id();
         let txn_info_ids = store.get_block_txn_info_ids(block_id)?;
@@ -96,8 +140,6 @@ impl ChainNotifyHandlerService {
                     .map(|evt| Event::new(block_id, block_number, txn_hash, Some(i as u32), evt)),
             );
         }
-        let events_notification: ContractEventNotification = Notification(all_events.into());
-        ctx.broadcast(events_notification);
-        Ok(())
+        Ok(all_events)
     }
 }
@@ -20,6 +20,8 @@ pub struct Event {
     // txn index in block
     pub transaction_index: Option<u32>,
     pub contract_event: ContractEvent,
+    /// The event is removed from the main chain by a chain reorg.
+    pub removed: bool,
 }
 
 impl Event {
@@ -36,6 +38,14 @@ impl Event {
             transaction_hash,
             transaction_index,
             contract_event,
+            removed: false,
+        }
+    }
+
+    pub fn into_removed(self) -> Self {
+        Self {
+            removed: true,
+            ..self
         }
     }
 }
@@ -0,0 +1,120 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The proofs of the transactions included in the chain, clients can verify them with the
+//! block accumulator root they trust, without trusting the node which returns the proofs.
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_accumulator::AccumulatorProof;
+use starcoin_crypto::HashValue;
+use starcoin_types::block::BlockHeader;
+use starcoin_types::transaction::TransactionInfo;
+
+/// A transaction info with its proof in the transaction accumulator.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionInfoWithProof {
+    pub transaction_info: TransactionInfo,
+    /// The index of the transaction info in the transaction accumulator.
+    pub leaf_index: u64,
+    pub proof: AccumulatorProof,
+}
+
+impl TransactionInfoWithProof {
+    pub fn new(
+        transaction_info: TransactionInfo,
+        leaf_index: u64,
+        proof: AccumulatorProof,
+    ) -> Self {
+        Self {
+            transaction_info,
+            leaf_index,
+            proof,
+        }
+    }
+
+    /// Verify the transaction info is in the transaction accumulator whose root is
+    /// `expected_txn_accumulator_root`.
+    pub fn verify(&self, expected_txn_accumulator_root: HashValue) -> Result<()> {
+        self.proof.verify(
+            expected_txn_accumulator_root,
+            self.transaction_info.id(),
+            self.leaf_index,
+        )
+    }
+}
+
+/// The proof of a transaction included in a block, and of the block in the block accumulator.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionProof {
+    /// The header of the block including the transaction.
+    pub block_header: BlockHeader,
+    /// The transaction info, proved to the `txn_accumulator_root` of the block.
+    pub txn_info_proof: TransactionInfoWithProof,
+    /// The id of the anchor block, whose block accumulator the block is proved to.
+    pub anchor_block_id: HashValue,
+    /// The root of the block accumulator of the anchor block, the accumulator includes the anchor block.
+    pub block_accumulator_root: HashValue,
+    /// The proof of the block in the block accumulator, the leaf index is the block number.
+    pub block_proof: AccumulatorProof,
+}
+
+impl TransactionProof {
+    pub fn new(
+        block_header: BlockHeader,
+        txn_info_proof: TransactionInfoWithProof,
+        anchor_block_id: HashValue,
+        block_accumulator_root: HashValue,
+        block_proof: AccumulatorProof,
+    ) -> Self {
+        Self {
+            block_header,
+            txn_info_proof,
+            anchor_block_id,
+            block_accumulator_root,
+            block_proof,
+        }
+    }
+
+    pub fn transaction_info(&self) -> &TransactionInfo {
+        &self.txn_info_proof.transaction_info
+    }
+
+    /// Verify the transaction `txn_hash` is included in the block, and the block is in the
+    /// block accumulator whose root is `expected_block_accumulator_root`.
+    pub fn verify(
+        &self,
+        txn_hash: HashValue,
+        expected_block_accumulator_root: HashValue,
+    ) -> Result<()> {
+        ensure!(
+            self.transaction_info().transaction_hash() == txn_hash,
+            "Transaction hash mismatch, expect: {}, got: {}",
+            txn_hash,
+            self.transaction_info().transaction_hash()
+        );
+        self.txn_info_proof
+            .verify(self.block_header.txn_accumulator_root())?;
+        self.block_proof.verify(
+            expected_block_accumulator_root,
+            self.block_header.id(),
+            self.block_header.number(),
+        )
+    }
+
+    /// Verify the proof with a trusted block header, the header's `block_accumulator_root` is
+    /// the block accumulator root of its parent, so the proof should be anchored to the parent.
+    pub fn verify_by_header(
+        &self,
+        txn_hash: HashValue,
+        trusted_header: &BlockHeader,
+    ) -> Result<()> {
+        ensure!(
+            self.anchor_block_id == trusted_header.parent_hash(),
+            "The proof is anchored to {}, but the parent of the trusted header is {}",
+            self.anchor_block_id,
+            trusted_header.parent_hash()
+        );
+        self.verify(txn_hash, trusted_header.block_accumulator_root())
+    }
+}
@@ -1,10 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{format_err, Error, Result};
+use anyhow::{bail, format_err, Error, Result};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::message::{ChainRequest, ChainResponse};
-use starcoin_chain_api::{ChainReader, ChainWriter, ReadableChainService};
+use starcoin_chain_api::{ChainReader, ChainWriter, FeeHistory, ReadableChainService};
+use starcoin_chain_proof::TransactionProof;
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
@@ -12,9 +13,11 @@ use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
 };
 use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{BlockSummary, EpochUncleSummary, ExecutedBlock, UncleSummary};
 use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
+use starcoin_types::peer_info::PeerId;
 use starcoin_types::system_events::NewHeadBlock;
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
@@ -146,6 +149,14 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::GetTransactionInfo(hash) => Ok(ChainResponse::TransactionInfo(
                 self.inner.get_transaction_info(hash)?,
             )),
+            ChainRequest::GetTransactionProof {
+                txn_hash,
+                anchor_block_id,
+            } => Ok(ChainResponse::TransactionProof(
+                self.inner
+                    .get_transaction_proof(txn_hash, anchor_block_id)?
+                    .map(Box::new),
+            )),
             ChainRequest::GetBlocksByNumber(number, count) => Ok(ChainResponse::BlockVec(
                 self.inner.main_blocks_by_number(number, count)?,
             )),
@@ -190,11 +201,13 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
 
                     events
                         .into_iter()
-                        .map(|evt| ContractEventInfo {
+                        .enumerate()
+                        .map(|(event_index, evt)| ContractEventInfo {
                             block_hash,
                             block_number: block.header().number(),
                             transaction_hash: txn_hash,
                             transaction_index: index as u32,
+                            event_index: event_index as u32,
                             event: evt,
                         })
                         .collect()
@@ -235,6 +248,26 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::EpochUncleSummaryByNumber(number) => Ok(ChainResponse::UncleSummary(
                 self.inner.epoch_uncle_summary_by_number(number)?,
             )),
+            ChainRequest::GetTransactionInfosByAddress {
+                address,
+                cursor,
+                limit,
+            } => Ok(ChainResponse::AddressTransactionInfos(
+                self.inner
+                    .get_transaction_infos_by_address(address, cursor, limit)?,
+            )),
+            ChainRequest::GetFeeHistory {
+                block_count,
+                percentiles,
+            } => Ok(ChainResponse::FeeHistory(
+                self.inner.get_fee_history(block_count, percentiles)?,
+            )),
+            ChainRequest::GetFailedBlocks { cursor, limit } => Ok(ChainResponse::FailedBlocks(
+                self.inner.get_failed_blocks(cursor, limit)?,
+            )),
+            ChainRequest::GetFailedBlock(block_id) => Ok(ChainResponse::FailedBlock(
+                self.inner.get_failed_block(block_id)?.map(Box::new),
+            )),
         }
     }
 }
@@ -337,6 +370,14 @@ impl ReadableChainService for ChainReaderServiceInner {
         self.main.get_transaction_info(txn_hash)
     }
 
+    fn get_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_id: Option<HashValue>,
+    ) -> Result<Option<TransactionProof>> {
+        self.main.get_transaction_proof(txn_hash, anchor_block_id)
+    }
+
     fn get_block_txn_infos(&self, block_id: HashValue) -> Result<Vec<BlockTransactionInfo>, Error> {
         self.storage.get_block_transaction_infos(block_id)
     }
@@ -396,7 +437,12 @@ impl ReadableChainService for ChainReaderServiceInner {
     }
 
     fn get_main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
-        self.main.filter_events(filter)
+        if self.config.storage.enable_event_index() {
+            self.main
+                .filter_indexed_events(filter, self.config.rpc.block_query_max_range())
+        } else {
+            self.main.filter_events(filter)
+        }
     }
 
     fn get_block_ids(
@@ -492,6 +538,38 @@ impl ReadableChainService for ChainReaderServiceInner {
             epoch_uncle_summary,
         ))
     }
+
+    fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, BlockTransactionInfo)>> {
+        if !self.config.storage.enable_address_index() {
+            bail!("Address transaction index is not enabled, see storage config `enable_address_index`.");
+        }
+        self.main
+            .get_transaction_infos_by_address(address, cursor, limit)
+    }
+
+    fn get_fee_history(&self, block_count: u64, percentiles: Vec<f64>) -> Result<FeeHistory> {
+        self.main.fee_history(block_count, percentiles.as_slice())
+    }
+
+    fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>> {
+        self.storage.get_failed_blocks(cursor, limit as usize)
+    }
+
+    fn get_failed_block(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Option<(Block, Option<PeerId>, String)>> {
+        self.storage.get_failed_block_by_id(block_id)
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::verifier::{BlockVerifier, FullVerifier};
-use anyhow::{ensure, format_err, Result};
+use crate::verifier::{
+    BasicVerifier, BlockVerifier, ConsensusVerifier, FullVerifier, NoneVerifier, Verifier,
+};
+use anyhow::{bail, ensure, format_err, Result};
 use consensus::Consensus;
 use crypto::HashValue;
 use logger::prelude::*;
@@ -11,14 +13,16 @@ use starcoin_accumulator::{
     accumulator_info::AccumulatorInfo, node::AccumulatorStoreType, Accumulator, MerkleAccumulator,
 };
 use starcoin_chain_api::{
-    verify_block, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock,
-    MintedUncleNumber, VerifiedBlock, VerifyBlockField,
+    check_percentiles, gas_price_percentiles, verify_block, BlockFeeInfo, BlockReplayReport,
+    ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock, FeeHistory,
+    MintedUncleNumber, ReplayMismatch, TransactionReplayTrace, VerifiedBlock, VerifyBlockField,
 };
+use starcoin_chain_proof::{TransactionInfoWithProof, TransactionProof};
 use starcoin_open_block::OpenedBlock;
 use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, ChainStateWriter};
 use starcoin_statedb::ChainStateDB;
 use starcoin_types::block::BlockIdAndNumber;
-use starcoin_types::contract_event::ContractEventInfo;
+use starcoin_types::contract_event::{ContractEventInfo, EventPosition};
 use starcoin_types::filter::Filter;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::transaction::BlockTransactionInfo;
@@ -27,7 +31,7 @@ use starcoin_types::{
     block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
     contract_event::ContractEvent,
     error::BlockExecutorError,
-    transaction::{SignedUserTransaction, Transaction, TransactionInfo},
+    transaction::{SignedUserTransaction, Transaction, TransactionInfo, TransactionStatus},
     U256,
 };
 use starcoin_vm_types::account_config::genesis_address;
@@ -37,10 +41,16 @@ use starcoin_vm_types::time::TimeService;
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 use std::cmp::min;
 use std::iter::Extend;
+use std::ops::Bound;
 use std::option::Option::{None, Some};
 use std::{collections::HashMap, sync::Arc};
+use storage::event_index::IndexedEvent;
+use storage::storage::ColumnFamilyBatches;
 use storage::Store;
 
+/// The max count of indexed events fetched from the storage at a time.
+const EVENT_INDEX_PAGE_SIZE: usize = 1000;
+
 pub struct ChainStatusWithBlock {
     pub status: ChainStatus,
     pub head: Block,
@@ -56,6 +66,7 @@ pub struct BlockChain {
     time_service: Arc<dyn TimeService>,
     uncles: HashMap<HashValue, MintedUncleNumber>,
     epoch: Epoch,
+    parallel_execution: bool,
 }
 
 impl BlockChain {
@@ -110,6 +121,7 @@ impl BlockChain {
             storage,
             uncles: HashMap::new(),
             epoch,
+            parallel_execution: false,
         };
         watch(CHAIN_WATCH_NAME, "n1251");
         match uncles {
@@ -142,10 +154,16 @@ impl BlockChain {
             &genesis_epoch,
             None,
             genesis_block,
+            false,
         )?;
         Self::new(time_service, executed_block.block.id(), storage)
     }
 
+    /// Execute the user transactions of the applied blocks optimistically in parallel.
+    pub fn set_parallel_execution(&mut self, parallel_execution: bool) {
+        self.parallel_execution = parallel_execution;
+    }
+
     pub fn current_epoch_uncles_size(&self) -> u64 {
         self.uncles.len() as u64
     }
@@ -339,6 +357,20 @@ impl BlockChain {
         self.connect(executed_block)
     }
 
+    /// Apply block with the verifier selected at runtime.
+    pub fn apply_with_verifier_type(
+        &mut self,
+        block: Block,
+        verifier: &Verifier,
+    ) -> Result<ExecutedBlock> {
+        match verifier {
+            Verifier::Basic => self.apply_with_verifier::<BasicVerifier>(block),
+            Verifier::Consensus => self.apply_with_verifier::<ConsensusVerifier>(block),
+            Verifier::Full => self.apply_with_verifier::<FullVerifier>(block),
+            Verifier::None => self.apply_with_verifier::<NoneVerifier>(block),
+        }
+    }
+
     //TODO remove this function.
     pub fn update_chain_head(&mut self, block: Block) -> Result<ExecutedBlock> {
         let block_info = self
@@ -357,6 +389,7 @@ impl BlockChain {
         epoch: &Epoch,
         parent_status: Option<ChainStatus>,
         block: Block,
+        parallel_execution: bool,
     ) -> Result<ExecutedBlock> {
         let header = block.header();
         debug_assert!(header.is_genesis() || parent_status.is_some());
@@ -382,8 +415,15 @@ impl BlockChain {
         };
 
         watch(CHAIN_WATCH_NAME, "n21");
-        let executed_data =
-            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?;
+        let executed_data = if parallel_execution {
+            starcoin_executor::block_execute_parallel(
+                &statedb,
+                txns.clone(),
+                epoch.block_gas_limit(),
+            )?
+        } else {
+            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?
+        };
         watch(CHAIN_WATCH_NAME, "n22");
         let state_root = executed_data.state_root;
         let vec_transaction_info = &executed_data.txn_infos;
@@ -506,6 +546,608 @@ impl BlockChain {
     }
 }
 
+impl BlockChain {
+    /// Collect the address transaction index entries of block `block_id` in transaction order.
+    /// The addresses of a transaction are the sender of user transaction and the creator
+    /// addresses of the events emitted by the transaction.
+    pub fn address_txn_entries(
+        storage: &dyn Store,
+        block_id: HashValue,
+    ) -> Result<Vec<(AccountAddress, HashValue)>> {
+        let mut entries = vec![];
+        for txn_info_id in storage.get_block_txn_info_ids(block_id)? {
+            let txn_info = storage
+                .get_transaction_info(txn_info_id)?
+                .ok_or_else(|| format_err!("Can not find txn info by id {}", txn_info_id))?;
+            let mut addresses = vec![];
+            if let Some(Transaction::UserTransaction(txn)) =
+                storage.get_transaction(txn_info.transaction_hash())?
+            {
+                addresses.push(txn.sender());
+            }
+            for event in storage
+                .get_contract_events(txn_info_id)?
+                .unwrap_or_default()
+            {
+                let address = event.key().get_creator_address();
+                if !addresses.contains(&address) {
+                    addresses.push(address);
+                }
+            }
+            entries.extend(addresses.into_iter().map(|address| (address, txn_info_id)));
+        }
+        Ok(entries)
+    }
+
+    /// Build the batches to update the address transaction index when the main chain switch to
+    /// a new head, they should be written with the startup info of the new head.
+    /// `retracted` blocks are ordered from the old head to the common ancestor (exclusive),
+    /// `enacted` blocks are ordered from the common ancestor (exclusive) to the new head.
+    pub fn address_txn_index_batches(
+        storage: &dyn Store,
+        enacted: &[HashValue],
+        retracted: &[HashValue],
+    ) -> Result<ColumnFamilyBatches> {
+        let mut retracted_entries = vec![];
+        for block_id in retracted {
+            let mut entries = Self::address_txn_entries(storage, *block_id)?;
+            entries.reverse();
+            retracted_entries.extend(entries);
+        }
+        let mut enacted_entries = vec![];
+        for block_id in enacted {
+            enacted_entries.extend(Self::address_txn_entries(storage, *block_id)?);
+        }
+        storage.address_txn_index_batches(retracted_entries, enacted_entries)
+    }
+
+    /// Get at most `limit` transaction infos related to `address` on the main chain, from newer
+    /// to older, start from the index `cursor` of the address's history.
+    pub fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, BlockTransactionInfo)>> {
+        self.storage
+            .get_address_txn_info_ids(address, cursor, limit)?
+            .into_iter()
+            .filter(|(_, txn_info_id)| self.check_exist_transaction_info(*txn_info_id))
+            .map(|(index, txn_info_id)| {
+                let txn_info = self
+                    .storage
+                    .get_transaction_info(txn_info_id)?
+                    .ok_or_else(|| format_err!("Can not find txn info by id {}", txn_info_id))?;
+                Ok((index, txn_info))
+            })
+            .collect()
+    }
+
+    /// Get the proof of the transaction `txn_hash` on the main chain, the transaction info is
+    /// proved to the block including it, and the block is proved to the block accumulator of
+    /// the anchor block `anchor_block_id`, the accumulator of the head block if it is absent.
+    /// The anchor block should be on the main chain and not before the block including the transaction.
+    pub fn get_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_id: Option<HashValue>,
+    ) -> Result<Option<TransactionProof>> {
+        let txn_info = match self.get_transaction_info(txn_hash)? {
+            Some(txn_info) => txn_info,
+            None => return Ok(None),
+        };
+        let block_id = txn_info.block_id();
+        let block_header = self
+            .storage
+            .get_block_header_by_hash(block_id)?
+            .ok_or_else(|| format_err!("Can not find block header by id {}", block_id))?;
+        let block_info = self
+            .storage
+            .get_block_info(block_id)?
+            .ok_or_else(|| format_err!("Can not find block info by id {}", block_id))?;
+        let txn_info_ids = self.storage.get_block_txn_info_ids(block_id)?;
+        let txn_index = txn_info_ids
+            .iter()
+            .position(|txn_info_id| *txn_info_id == txn_info.id())
+            .ok_or_else(|| {
+                format_err!(
+                    "Can not find txn info {} in block {}",
+                    txn_info.id(),
+                    block_id
+                )
+            })?;
+        // The txn infos of the block are the last leaves of the block's txn accumulator.
+        let txn_accumulator_info = block_info.get_txn_accumulator_info();
+        let leaf_index = txn_accumulator_info
+            .get_num_leaves()
+            .checked_sub(txn_info_ids.len() as u64)
+            .and_then(|first_leaf_index| first_leaf_index.checked_add(txn_index as u64))
+            .ok_or_else(|| format_err!("Invalid txn accumulator info of block {}", block_id))?;
+        let txn_accumulator = info_2_accumulator(
+            txn_accumulator_info.clone(),
+            AccumulatorStoreType::Transaction,
+            self.storage.as_ref(),
+        );
+        let txn_proof = txn_accumulator
+            .get_proof(leaf_index)?
+            .ok_or_else(|| format_err!("Can not find txn accumulator proof of {}", txn_hash))?;
+        let (anchor_block_id, block_accumulator_info) = match anchor_block_id {
+            Some(anchor_block_id) => {
+                let anchor_header = self.get_header(anchor_block_id)?.ok_or_else(|| {
+                    format_err!(
+                        "Can not find anchor block {} on main chain",
+                        anchor_block_id
+                    )
+                })?;
+                ensure!(
+                    anchor_header.number() >= block_header.number(),
+                    "The anchor block {} is before the block {} including the transaction",
+                    anchor_block_id,
+                    block_id
+                );
+                let anchor_info =
+                    self.storage
+                        .get_block_info(anchor_block_id)?
+                        .ok_or_else(|| {
+                            format_err!("Can not find block info by id {}", anchor_block_id)
+                        })?;
+                (
+                    anchor_block_id,
+                    anchor_info.get_block_accumulator_info().clone(),
+                )
+            }
+            None => (
+                self.current_header().id(),
+                self.status
+                    .status
+                    .info()
+                    .get_block_accumulator_info()
+                    .clone(),
+            ),
+        };
+        let block_accumulator = info_2_accumulator(
+            block_accumulator_info,
+            AccumulatorStoreType::Block,
+            self.storage.as_ref(),
+        );
+        let block_proof = block_accumulator
+            .get_proof(block_header.number())?
+            .ok_or_else(|| format_err!("Can not find block accumulator proof of {}", block_id))?;
+        let (_, transaction_info): (HashValue, TransactionInfo) = txn_info.into();
+        Ok(Some(TransactionProof::new(
+            block_header,
+            TransactionInfoWithProof::new(transaction_info, leaf_index, txn_proof),
+            anchor_block_id,
+            block_accumulator.root_hash(),
+            block_proof,
+        )))
+    }
+
+    /// Collect the events of block `block_id` with their positions, in the order of the chain.
+    pub fn indexed_events(
+        storage: &dyn Store,
+        block_id: HashValue,
+    ) -> Result<Vec<(ContractEvent, IndexedEvent)>> {
+        let block_number = storage
+            .get_block_header_by_hash(block_id)?
+            .ok_or_else(|| format_err!("Can not find block header by id {}", block_id))?
+            .number();
+        let mut events = vec![];
+        for (txn_index, txn_info_id) in storage
+            .get_block_txn_info_ids(block_id)?
+            .into_iter()
+            .enumerate()
+        {
+            for (event_index, event) in storage
+                .get_contract_events(txn_info_id)?
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+            {
+                let position =
+                    EventPosition::new(block_number, txn_index as u32, event_index as u32);
+                events.push((event, IndexedEvent::new(position, txn_info_id)));
+            }
+        }
+        Ok(events)
+    }
+
+    /// Get the event index start of `storage`, or save it if the event index is just enabled,
+    /// return None if it is disabled.
+    /// The event index starts from the block after the head when it is enabled, and restarts
+    /// when it is enabled again after disabled, as the blocks between are not indexed.
+    pub fn init_event_index_start(
+        enabled: bool,
+        head_number: BlockNumber,
+        storage: &dyn Store,
+    ) -> Result<Option<BlockNumber>> {
+        let index_start = storage.get_event_index_start()?;
+        if !enabled {
+            if index_start.is_some() {
+                storage.save_event_index_start(None)?;
+            }
+            return Ok(None);
+        }
+        match index_start {
+            Some(index_start) => Ok(Some(index_start)),
+            None => {
+                let index_start = head_number.saturating_add(1);
+                info!("Event index starts from block {}", index_start);
+                storage.save_event_index_start(Some(index_start))?;
+                Ok(Some(index_start))
+            }
+        }
+    }
+
+    /// Build the batches to update the event index when the main chain switch to a new head,
+    /// `enacted` and `retracted` are ordered as `address_txn_index_batches`.
+    /// Only the blocks from `index_start` are indexed, the blocks before it are skipped.
+    pub fn event_index_batches(
+        storage: &dyn Store,
+        enacted: &[HashValue],
+        retracted: &[HashValue],
+        index_start: BlockNumber,
+    ) -> Result<ColumnFamilyBatches> {
+        let is_indexed = |(_, indexed): &(ContractEvent, IndexedEvent)| {
+            indexed.position.block_number >= index_start
+        };
+        let mut retracted_events = vec![];
+        for block_id in retracted {
+            let mut events = Self::indexed_events(storage, *block_id)?;
+            events.reverse();
+            retracted_events.extend(events.into_iter().filter(is_indexed));
+        }
+        let mut enacted_events = vec![];
+        for block_id in enacted {
+            enacted_events.extend(
+                Self::indexed_events(storage, *block_id)?
+                    .into_iter()
+                    .filter(is_indexed),
+            );
+        }
+        storage.event_index_batches(retracted_events, enacted_events)
+    }
+
+    /// Filter the events on the main chain like `filter_events`, but search the events in the
+    /// event index of the filter's event keys, or of the type tags if there is no event key,
+    /// fallback to `filter_events` if neither is specified.
+    /// The blocks before the event index start are not indexed, they are scanned by
+    /// `filter_events`, and the scan is refused if they are more than `max_scan_range`.
+    pub fn filter_indexed_events(
+        &self,
+        filter: Filter,
+        max_scan_range: u64,
+    ) -> Result<Vec<ContractEventInfo>> {
+        if filter.event_keys.is_empty() && filter.type_tags.is_empty() {
+            return self.filter_events(filter);
+        }
+        let index_start = self
+            .storage
+            .get_event_index_start()?
+            .unwrap_or(BlockNumber::MAX);
+        if filter.from_block >= index_start {
+            return self.query_event_index(filter);
+        }
+        let unindexed = Filter {
+            to_block: filter.to_block.min(index_start.saturating_sub(1)),
+            ..filter.clone()
+        };
+        let indexed = Filter {
+            from_block: index_start,
+            ..filter.clone()
+        };
+        let mut from_block = unindexed.from_block;
+        let mut to_block = unindexed.to_block.min(self.current_header().number());
+        if let Some(cursor) = filter.cursor {
+            if filter.reverse {
+                to_block = to_block.min(cursor.block_number);
+            } else {
+                from_block = from_block.max(cursor.block_number);
+            }
+        }
+        if to_block
+            .checked_sub(from_block)
+            .filter(|range| *range > max_scan_range)
+            .is_some()
+        {
+            bail!(
+                "The events of block {}~{} are not indexed, max block range to scan is {}",
+                from_block,
+                to_block,
+                max_scan_range
+            );
+        }
+
+        let limit = filter.limit.unwrap_or(usize::MAX);
+        let mut event_with_infos = if filter.reverse {
+            self.query_event_index(indexed)?
+        } else {
+            self.filter_events(unindexed.clone())?
+        };
+        if event_with_infos.len() < limit {
+            let remaining = Some(limit.saturating_sub(event_with_infos.len()));
+            event_with_infos.extend(if filter.reverse {
+                self.filter_events(Filter {
+                    limit: remaining,
+                    ..unindexed
+                })?
+            } else {
+                self.query_event_index(Filter {
+                    limit: remaining,
+                    ..indexed
+                })?
+            });
+        }
+        Ok(event_with_infos)
+    }
+
+    /// Search the events of the filter in the event index, the blocks of the filter must be
+    /// indexed.
+    fn query_event_index(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
+        let reverse = filter.reverse;
+        let max_block_number = self.current_header().number().min(filter.to_block);
+        // quick return.
+        if filter.from_block > max_block_number {
+            return Ok(vec![]);
+        }
+        let mut start = Bound::Included(EventPosition::block_start(filter.from_block));
+        let mut end = Bound::Included(EventPosition::block_end(max_block_number));
+        if let Some(cursor) = filter.cursor {
+            if reverse && cursor <= EventPosition::block_end(max_block_number) {
+                end = Bound::Excluded(cursor);
+            }
+            if !reverse && cursor >= EventPosition::block_start(filter.from_block) {
+                start = Bound::Excluded(cursor);
+            }
+        }
+
+        let limit = filter.limit.unwrap_or(usize::MAX);
+        let mut event_with_infos = vec![];
+        // the indexed events are fetched page by page, as some of them may not match the filter.
+        while event_with_infos.len() < limit {
+            let page_size = limit
+                .saturating_sub(event_with_infos.len())
+                .min(EVENT_INDEX_PAGE_SIZE);
+            let mut page = vec![];
+            if filter.event_keys.is_empty() {
+                for type_tag in &filter.type_tags {
+                    page.extend(self.storage.get_indexed_events_by_type(
+                        type_tag,
+                        (start, end),
+                        reverse,
+                        page_size,
+                    )?);
+                }
+            } else {
+                for event_key in &filter.event_keys {
+                    page.extend(self.storage.get_indexed_events_by_key(
+                        event_key,
+                        (start, end),
+                        reverse,
+                        page_size,
+                    )?);
+                }
+            }
+            page.sort_by_key(|indexed| indexed.position);
+            if reverse {
+                page.reverse();
+            }
+            page.truncate(page_size);
+
+            let last = match page.last() {
+                Some(last) => last.position,
+                None => break,
+            };
+            if reverse {
+                end = Bound::Excluded(last);
+            } else {
+                start = Bound::Excluded(last);
+            }
+            for indexed in page {
+                if let Some(event_info) = self.get_indexed_event_info(indexed)? {
+                    if filter.matching(event_info.block_number, &event_info.event) {
+                        event_with_infos.push(event_info);
+                    }
+                }
+            }
+        }
+        event_with_infos.truncate(limit);
+        Ok(event_with_infos)
+    }
+
+    /// Get the info of the indexed event, None if the event is not on this chain.
+    fn get_indexed_event_info(&self, indexed: IndexedEvent) -> Result<Option<ContractEventInfo>> {
+        let txn_info_id = indexed.txn_info_id;
+        if !self.check_exist_transaction_info(txn_info_id) {
+            return Ok(None);
+        }
+        let txn_info = self
+            .storage
+            .get_transaction_info(txn_info_id)?
+            .ok_or_else(|| format_err!("Can not find txn info by id {}", txn_info_id))?;
+        let position = indexed.position;
+        let event = self
+            .storage
+            .get_contract_events(txn_info_id)?
+            .and_then(|events| events.into_iter().nth(position.event_index as usize))
+            .ok_or_else(|| {
+                format_err!(
+                    "Can not find event {} of txn info {}",
+                    position.event_index,
+                    txn_info_id
+                )
+            })?;
+        Ok(Some(ContractEventInfo {
+            block_hash: txn_info.block_id(),
+            block_number: position.block_number,
+            transaction_hash: txn_info.transaction_hash(),
+            transaction_index: position.transaction_index,
+            event_index: position.event_index,
+            event,
+        }))
+    }
+
+    /// Get the fee history of the latest `block_count` blocks on the main chain, every block's
+    /// gas prices are weighted by the gas used of its user transactions.
+    pub fn fee_history(&self, block_count: u64, percentiles: &[f64]) -> Result<FeeHistory> {
+        check_percentiles(percentiles)?;
+        let head_number = self.current_header().number();
+        let block_count = min(block_count, head_number.saturating_add(1));
+        let mut blocks = vec![];
+        let mut epoch: Option<Epoch> = None;
+        for number in head_number.saturating_add(1).saturating_sub(block_count)..=head_number {
+            let block = self
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+            // The block is executed with the epoch of its parent's state.
+            let block_epoch = match epoch {
+                Some(epoch)
+                    if number >= epoch.start_block_number()
+                        && number < epoch.end_block_number() =>
+                {
+                    epoch
+                }
+                _ => self
+                    .get_epoch_info_by_number(Some(number.saturating_sub(1)))?
+                    .epoch()
+                    .clone(),
+            };
+            let gas_used_by_txn = self
+                .storage
+                .get_block_transaction_infos(block.id())?
+                .into_iter()
+                .map(|txn_info| (txn_info.transaction_hash(), txn_info.gas_used()))
+                .collect::<HashMap<_, _>>();
+            let txns = block
+                .transactions()
+                .iter()
+                .map(|txn| {
+                    let gas_used = gas_used_by_txn.get(&txn.id()).copied().unwrap_or(0);
+                    (txn.gas_unit_price(), gas_used)
+                })
+                .collect::<Vec<_>>();
+            blocks.push(BlockFeeInfo {
+                number,
+                gas_used: block.header().gas_used(),
+                gas_limit: block_epoch.block_gas_limit(),
+                gas_price_percentiles: gas_price_percentiles(txns, percentiles),
+            });
+            epoch = Some(block_epoch);
+        }
+        Ok(FeeHistory { blocks })
+    }
+
+    /// Re-verify and re-execute the `block` on the head state without saving anything, and trace
+    /// every transaction to find where the execution diverged from the block.
+    /// The `block` should be a child of the current head.
+    pub fn replay_block(&self, block: Block) -> Result<BlockReplayReport> {
+        let header = block.header().clone();
+        ensure!(
+            header.parent_hash() == self.status.head.id(),
+            "Block {:?} is not a child of current head {:?}",
+            header.id(),
+            self.status.head.id()
+        );
+        let mut report = BlockReplayReport::new(&header);
+        report.verify_error = FullVerifier::verify_block(self, block.clone())
+            .err()
+            .map(|e| e.to_string());
+
+        let block_metadata = block.to_metadata(self.status.head.header().gas_used());
+        let mut txns = vec![Transaction::BlockMetadata(block_metadata)];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        let statedb = self.statedb.fork();
+        let txn_outputs = match starcoin_executor::execute_block_transactions(
+            &statedb,
+            txns.clone(),
+            self.epoch.block_gas_limit(),
+        ) {
+            Ok(txn_outputs) => txn_outputs,
+            Err(e) => {
+                report.execute_error = Some(e.to_string());
+                return Ok(report);
+            }
+        };
+        report.unexecuted_txns = txns
+            .iter()
+            .skip(txn_outputs.len())
+            .map(|txn| txn.id())
+            .collect();
+
+        let mut block_gas_used = 0u64;
+        let mut txn_info_ids = vec![];
+        for (idx, (txn, output)) in txns.iter().zip(txn_outputs.into_iter()).enumerate() {
+            let txn_hash = txn.id();
+            let (write_set, events, gas_used, status) = output.into_inner();
+            let (state_root, txn_info_id) = match &status {
+                TransactionStatus::Discard(_) => {
+                    if report.diverged_at.is_none() {
+                        report.diverged_at = Some(idx as u64);
+                    }
+                    (None, None)
+                }
+                TransactionStatus::Keep(kept_status) => {
+                    statedb.apply_write_set(write_set)?;
+                    let txn_state_root = statedb.commit()?;
+                    let txn_info = TransactionInfo::new(
+                        txn_hash,
+                        txn_state_root,
+                        events.as_slice(),
+                        gas_used,
+                        kept_status.clone(),
+                    );
+                    block_gas_used = block_gas_used.saturating_add(gas_used);
+                    txn_info_ids.push(txn_info.id());
+                    (Some(txn_state_root), Some(txn_info.id()))
+                }
+            };
+            report.txns.push(TransactionReplayTrace {
+                txn_hash,
+                status,
+                gas_used,
+                cumulative_gas_used: block_gas_used,
+                state_root,
+                txn_info_id,
+                event_count: events.len() as u64,
+            });
+        }
+        if report.diverged_at.is_none() && !report.unexecuted_txns.is_empty() {
+            report.diverged_at = Some(report.txns.len() as u64);
+        }
+
+        let state_root = statedb.state_root();
+        if state_root != header.state_root() {
+            report.mismatches.push(ReplayMismatch::new(
+                "state_root",
+                header.state_root(),
+                state_root,
+            ));
+        }
+        if block_gas_used != header.gas_used() {
+            report.mismatches.push(ReplayMismatch::new(
+                "gas_used",
+                header.gas_used(),
+                block_gas_used,
+            ));
+        }
+        let txn_accumulator_root = self.txn_accumulator.fork().append(&txn_info_ids)?;
+        if txn_accumulator_root != header.txn_accumulator_root() {
+            report.mismatches.push(ReplayMismatch::new(
+                "txn_accumulator_root",
+                header.txn_accumulator_root(),
+                txn_accumulator_root,
+            ));
+        }
+        Ok(report)
+    }
+}
+
 impl ChainReader for BlockChain {
     fn info(&self) -> ChainInfo {
         ChainInfo::new(
@@ -759,6 +1401,7 @@ impl ChainReader for BlockChain {
             &self.epoch,
             Some(self.status.status.clone()),
             verified_block.0,
+            self.parallel_execution,
         )
     }
 }
@@ -767,17 +1410,26 @@ impl BlockChain {
     pub fn filter_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
         let reverse = filter.reverse;
         let chain_header = self.current_header();
-        let max_block_number = chain_header.number().min(filter.to_block);
+        let mut from_block = filter.from_block;
+        let mut max_block_number = chain_header.number().min(filter.to_block);
+        // the blocks out of the cursor have no event to return.
+        if let Some(cursor) = filter.cursor {
+            if reverse {
+                max_block_number = max_block_number.min(cursor.block_number);
+            } else {
+                from_block = from_block.max(cursor.block_number);
+            }
+        }
 
         // quick return.
-        if filter.from_block > max_block_number {
+        if from_block > max_block_number {
             return Ok(vec![]);
         }
 
         let (mut cur_block_number, tail) = if reverse {
-            (max_block_number, filter.from_block)
+            (max_block_number, from_block)
         } else {
-            (filter.from_block, max_block_number)
+            (from_block, max_block_number)
         };
         let mut event_with_infos = vec![];
         'outer: loop {
@@ -809,7 +1461,12 @@ impl BlockChain {
                 })?;
                 let mut filtered_events = events
                     .into_iter()
-                    .filter(|evt| filter.matching(block_number, evt))
+                    .enumerate()
+                    .filter(|(event_index, evt)| {
+                        let position =
+                            EventPosition::new(block_number, *idx as u32, *event_index as u32);
+                        filter.matching(block_number, evt) && filter.is_after_cursor(&position)
+                    })
                     .peekable();
                 if filtered_events.peek().is_none() {
                     continue;
@@ -823,13 +1480,15 @@ impl BlockChain {
                     ))
                 })?;
 
-                let filtered_event_with_info = filtered_events.map(|evt| ContractEventInfo {
-                    block_hash: block_id,
-                    block_number: block.header().number(),
-                    transaction_hash: txn_info.transaction_hash(),
-                    transaction_index: *idx as u32,
-                    event: evt,
-                });
+                let filtered_event_with_info =
+                    filtered_events.map(|(event_index, evt)| ContractEventInfo {
+                        block_hash: block_id,
+                        block_number: block.header().number(),
+                        transaction_hash: txn_info.transaction_hash(),
+                        transaction_index: *idx as u32,
+                        event_index: event_index as u32,
+                        event: evt,
+                    });
                 if reverse {
                     event_with_infos.extend(filtered_event_with_info.rev())
                 } else {
@@ -1,9 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use consensus::Consensus;
-use crypto::{ed25519::Ed25519PrivateKey, Genesis, PrivateKey};
+use crypto::{ed25519::Ed25519PrivateKey, Genesis, HashValue, PrivateKey};
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
@@ -18,6 +18,7 @@ use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::event::EventKey;
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 use std::sync::Arc;
+use storage::ContractEventIndexStore;
 
 #[stest::test(timeout = 120)]
 fn test_chain_filter_events() {
@@ -32,6 +33,7 @@ fn test_chain_filter_events() {
             event_keys: vec![evt_key],
             limit: None,
             reverse: false,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 5);
@@ -48,6 +50,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(5),
             reverse: false,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 5);
@@ -62,6 +65,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(5),
             reverse: true,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 5);
@@ -78,6 +82,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(20),
             reverse: true,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 10);
@@ -94,6 +99,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(20),
             reverse: true,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 10);
@@ -103,6 +109,136 @@ fn test_chain_filter_events() {
     }
 }
 
+#[stest::test(timeout = 120)]
+fn test_chain_filter_indexed_events() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    let times = 10;
+    mock_chain.produce_and_apply_times(times)?;
+    let storage = mock_chain.head().get_storage();
+    let block_ids = (1..=times)
+        .map(|number| {
+            mock_chain
+                .head()
+                .get_hash_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block {}", number))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let events = block_ids
+        .iter()
+        .map(|block_id| BlockChain::indexed_events(storage.as_ref(), *block_id))
+        .collect::<Result<Vec<_>>>()?;
+    storage.append_event_index(events.concat())?;
+    storage.save_event_index_start(Some(1))?;
+
+    let event_filter = Filter {
+        from_block: 1,
+        to_block: 10,
+        event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+        limit: Some(5),
+        reverse: true,
+        ..Default::default()
+    };
+    let evts = mock_chain
+        .head()
+        .filter_indexed_events(event_filter.clone(), 100)?;
+    assert_eq!(evts.len(), 5);
+    assert_eq!(evts, mock_chain.head().filter_events(event_filter.clone())?);
+
+    // the next page after the cursor.
+    let next_filter = Filter {
+        cursor: evts.last().map(|evt| evt.position()),
+        ..event_filter.clone()
+    };
+    let next_evts = mock_chain
+        .head()
+        .filter_indexed_events(next_filter.clone(), 100)?;
+    assert_eq!(next_evts.len(), 5);
+    assert_eq!(next_evts.first().unwrap().block_number, 5);
+    assert_eq!(next_evts, mock_chain.head().filter_events(next_filter)?);
+
+    // filter by type tag and address.
+    let type_filter = Filter {
+        event_keys: vec![],
+        type_tags: vec![evts[0].event.type_tag().clone()],
+        addrs: vec![genesis_address()],
+        ..event_filter.clone()
+    };
+    assert_eq!(
+        mock_chain
+            .head()
+            .filter_indexed_events(type_filter.clone(), 100)?,
+        mock_chain.head().filter_events(type_filter)?
+    );
+
+    // the events of retracted blocks are removed from the index.
+    let mut last_block_events = BlockChain::indexed_events(storage.as_ref(), block_ids[9])?;
+    last_block_events.reverse();
+    storage.rollback_event_index(last_block_events)?;
+    let evts = mock_chain.head().filter_indexed_events(event_filter, 100)?;
+    assert_eq!(evts.first().unwrap().block_number, 9);
+    Ok(())
+}
+
+#[stest::test]
+fn test_chain_filter_partially_indexed_events() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    let times = 10;
+    mock_chain.produce_and_apply_times(times)?;
+    let storage = mock_chain.head().get_storage();
+    // the index is enabled from block 6.
+    let index_start = 6;
+    for number in index_start..=times {
+        let block_id = mock_chain
+            .head()
+            .get_hash_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block {}", number))?;
+        storage.append_event_index(BlockChain::indexed_events(storage.as_ref(), block_id)?)?;
+    }
+    storage.save_event_index_start(Some(index_start))?;
+
+    let event_filter = Filter {
+        from_block: 1,
+        to_block: 10,
+        event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+        reverse: true,
+        ..Default::default()
+    };
+    let evts = mock_chain
+        .head()
+        .filter_indexed_events(event_filter.clone(), 100)?;
+    assert_eq!(evts.len(), 10);
+    assert_eq!(evts, mock_chain.head().filter_events(event_filter.clone())?);
+
+    let forward_filter = Filter {
+        limit: Some(7),
+        reverse: false,
+        ..event_filter.clone()
+    };
+    let evts = mock_chain
+        .head()
+        .filter_indexed_events(forward_filter.clone(), 100)?;
+    assert_eq!(evts.len(), 7);
+    assert_eq!(evts, mock_chain.head().filter_events(forward_filter)?);
+
+    // the blocks before the index start are limited by the scan range.
+    assert!(mock_chain
+        .head()
+        .filter_indexed_events(event_filter.clone(), 2)
+        .is_err());
+    let indexed_filter = Filter {
+        from_block: index_start,
+        ..event_filter
+    };
+    assert_eq!(
+        mock_chain
+            .head()
+            .filter_indexed_events(indexed_filter, 2)?
+            .len(),
+        5
+    );
+    Ok(())
+}
+
 #[stest::test]
 fn test_block_chain() -> Result<()> {
     let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
@@ -449,3 +585,113 @@ fn test_get_blocks_by_number() -> Result<()> {
     assert_eq!(blocks.len(), 11);
     Ok(())
 }
+
+#[stest::test]
+fn test_fee_history() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(3)?;
+    let fee_history = mock_chain.head().fee_history(2, &[50f64])?;
+    assert_eq!(fee_history.oldest_block(), Some(2));
+    assert_eq!(fee_history.blocks.len(), 2);
+    for block in &fee_history.blocks {
+        assert!(block.gas_limit > 0);
+        assert_eq!(block.gas_price_percentiles, vec![0]);
+    }
+    let fee_history = mock_chain.head().fee_history(100, &[])?;
+    assert_eq!(fee_history.oldest_block(), Some(0));
+    assert_eq!(fee_history.blocks.len(), 4);
+    assert!(mock_chain.head().fee_history(1, &[50f64, 10f64]).is_err());
+    Ok(())
+}
+
+#[stest::test]
+fn test_replay_block() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(2)?;
+    let block = mock_chain.produce()?;
+    let report = mock_chain.head().replay_block(block.clone())?;
+    assert!(report.is_consistent(), "{:?}", report);
+    // only the block metadata transaction.
+    assert_eq!(report.txns.len(), 1);
+    assert_eq!(report.txns[0].state_root, Some(block.header().state_root()));
+
+    let header = block.header();
+    let bad_header = BlockHeader::new(
+        header.parent_hash(),
+        header.timestamp(),
+        header.number(),
+        header.author(),
+        header.author_auth_key(),
+        header.txn_accumulator_root(),
+        header.block_accumulator_root(),
+        HashValue::random(),
+        header.gas_used(),
+        header.difficulty(),
+        header.body_hash(),
+        header.chain_id(),
+        header.nonce(),
+        header.extra().clone(),
+    );
+    let bad_block = Block::new(bad_header, block.body.clone());
+    let report = mock_chain.head().replay_block(bad_block)?;
+    assert!(!report.is_consistent());
+    assert_eq!(report.mismatches.len(), 1);
+    assert_eq!(report.mismatches[0].field, "state_root");
+    assert_eq!(report.diverged_at, None);
+    Ok(())
+}
+
+#[stest::test]
+fn test_transaction_proof() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(3)?;
+    let head = mock_chain.head();
+    let block_accumulator_root = *head.current_block_accumulator_info().get_accumulator_root();
+    let genesis_info = head
+        .get_block_info_by_number(0)?
+        .ok_or_else(|| format_err!("Can not find genesis block info"))?;
+    let head_info = head
+        .get_block_info(None)?
+        .ok_or_else(|| format_err!("Can not find head block info"))?;
+    let first_version = genesis_info.get_txn_accumulator_info().get_num_leaves();
+    let last_version = head_info.get_txn_accumulator_info().get_num_leaves();
+    assert!(first_version < last_version);
+    for version in first_version..last_version {
+        let txn_hash = head
+            .get_transaction_info_by_version(version)?
+            .ok_or_else(|| format_err!("Can not find txn info of version {}", version))?
+            .transaction_hash();
+        let proof = head
+            .get_transaction_proof(txn_hash, None)?
+            .ok_or_else(|| format_err!("Can not find txn proof of {}", txn_hash))?;
+        assert_eq!(proof.txn_info_proof.leaf_index, version);
+        assert_eq!(proof.anchor_block_id, head.current_header().id());
+        assert_eq!(proof.block_accumulator_root, block_accumulator_root);
+        proof.verify(txn_hash, block_accumulator_root)?;
+        assert!(proof
+            .verify(HashValue::random(), block_accumulator_root)
+            .is_err());
+        assert!(proof.verify(txn_hash, HashValue::random()).is_err());
+
+        // anchor the proof to the parent of a trusted header after the block including the txn.
+        let block_number = proof.block_header.number();
+        if let Some(trusted_header) = head.get_header_by_number(block_number.saturating_add(1))? {
+            let anchored_proof = head
+                .get_transaction_proof(txn_hash, Some(trusted_header.parent_hash()))?
+                .ok_or_else(|| format_err!("Can not find txn proof of {}", txn_hash))?;
+            assert_eq!(anchored_proof.anchor_block_id, trusted_header.parent_hash());
+            anchored_proof.verify_by_header(txn_hash, &trusted_header)?;
+            assert!(proof.verify_by_header(txn_hash, &trusted_header).is_err());
+        }
+        let before_header = head
+            .get_header_by_number(block_number.saturating_sub(1))?
+            .ok_or_else(|| format_err!("Can not find the block before {}", block_number))?;
+        assert!(head
+            .get_transaction_proof(txn_hash, Some(before_header.id()))
+            .is_err());
+    }
+    assert!(head
+        .get_transaction_proof(HashValue::random(), None)?
+        .is_none());
+    Ok(())
+}
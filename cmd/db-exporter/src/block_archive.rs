@@ -0,0 +1,198 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A portable file format of a range of main chain blocks.
+//! The file is composed of a `BlockArchiveHeader` and the blocks from `from` to `to` in order,
+//! every item is BCS encoded and prefixed with its length as big endian u32.
+
+use crate::{read_item, write_item};
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::{Block, BlockNumber};
+use starcoin_types::genesis_config::ChainId;
+use std::io::{Read, Write};
+
+pub const BLOCK_ARCHIVE_VERSION: u8 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockArchiveHeader {
+    pub version: u8,
+    pub chain_id: ChainId,
+    pub genesis_hash: HashValue,
+    /// The number of the first block, inclusive.
+    pub from: BlockNumber,
+    /// The number of the last block, inclusive.
+    pub to: BlockNumber,
+}
+
+impl BlockArchiveHeader {
+    pub fn new(
+        chain_id: ChainId,
+        genesis_hash: HashValue,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Self {
+        Self {
+            version: BLOCK_ARCHIVE_VERSION,
+            chain_id,
+            genesis_hash,
+            from,
+            to,
+        }
+    }
+
+    pub fn block_count(&self) -> u64 {
+        self.to.saturating_sub(self.from).saturating_add(1)
+    }
+}
+
+pub struct BlockArchiveWriter<W: Write> {
+    writer: W,
+    header: BlockArchiveHeader,
+    next_number: BlockNumber,
+}
+
+impl<W> BlockArchiveWriter<W>
+where
+    W: Write,
+{
+    pub fn new(mut writer: W, header: BlockArchiveHeader) -> Result<Self> {
+        ensure!(
+            header.from <= header.to,
+            "Invalid block range [{}, {}]",
+            header.from,
+            header.to
+        );
+        write_item(&mut writer, &header)?;
+        let next_number = header.from;
+        Ok(Self {
+            writer,
+            header,
+            next_number,
+        })
+    }
+
+    /// Append the next block, blocks must be appended in order of block number.
+    pub fn append(&mut self, block: &Block) -> Result<()> {
+        let number = block.header().number();
+        ensure!(
+            number == self.next_number && number <= self.header.to,
+            "Expect block number {} in range [{}, {}], but got {}",
+            self.next_number,
+            self.header.from,
+            self.header.to,
+            number
+        );
+        write_item(&mut self.writer, block)?;
+        self.next_number = number.saturating_add(1);
+        Ok(())
+    }
+
+    /// Check all blocks in range are appended, and flush the writer.
+    pub fn finish(mut self) -> Result<W> {
+        ensure!(
+            self.next_number > self.header.to,
+            "Block archive is incomplete, expect block {}",
+            self.next_number
+        );
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+pub struct BlockArchiveReader<R: Read> {
+    reader: R,
+    header: BlockArchiveHeader,
+    next_number: BlockNumber,
+}
+
+impl<R> BlockArchiveReader<R>
+where
+    R: Read,
+{
+    pub fn new(mut reader: R) -> Result<Self> {
+        let header: BlockArchiveHeader = read_item(&mut reader)?
+            .ok_or_else(|| format_err!("Block archive header is missing."))?;
+        ensure!(
+            header.version == BLOCK_ARCHIVE_VERSION,
+            "Unsupported block archive version: {}",
+            header.version
+        );
+        let next_number = header.from;
+        Ok(Self {
+            reader,
+            header,
+            next_number,
+        })
+    }
+
+    pub fn header(&self) -> &BlockArchiveHeader {
+        &self.header
+    }
+
+    fn read_block(&mut self) -> Result<Block> {
+        let block: Block = read_item(&mut self.reader)?.ok_or_else(|| {
+            format_err!(
+                "Block archive is incomplete, expect block {}",
+                self.next_number
+            )
+        })?;
+        ensure!(
+            block.header().number() == self.next_number,
+            "Expect block number {}, but got {}",
+            self.next_number,
+            block.header().number()
+        );
+        Ok(block)
+    }
+}
+
+impl<R> Iterator for BlockArchiveReader<R>
+where
+    R: Read,
+{
+    type Item = Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_number > self.header.to {
+            return None;
+        }
+        let result = self.read_block();
+        // stop at the first error.
+        self.next_number = match result {
+            Ok(_) => self.next_number.saturating_add(1),
+            Err(_) => self.header.to.saturating_add(1),
+        };
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_config::ChainNetwork;
+    use starcoin_genesis::Genesis;
+
+    #[test]
+    fn test_block_archive() -> Result<()> {
+        let net = ChainNetwork::new_test();
+        let genesis = Genesis::load_or_build(&net)?;
+        let block = genesis.block().clone();
+        let header = BlockArchiveHeader::new(net.chain_id(), block.id(), 0, 0);
+
+        let mut writer = BlockArchiveWriter::new(vec![], header.clone())?;
+        writer.append(&block)?;
+        let bytes = writer.finish()?;
+
+        let reader = BlockArchiveReader::new(bytes.as_slice())?;
+        assert_eq!(reader.header(), &header);
+        let blocks = reader.collect::<Result<Vec<_>>>()?;
+        assert_eq!(blocks, vec![block]);
+
+        // truncated archive should fail.
+        let reader = BlockArchiveReader::new(&bytes[..bytes.len() - 1])?;
+        assert!(reader.collect::<Result<Vec<_>>>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_archive::{BlockArchiveHeader, BlockArchiveWriter};
+use crate::cli_state::CliState;
+use crate::open_storage;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::StarcoinOpt;
+use starcoin_logger::prelude::*;
+use starcoin_storage::BlockStore;
+use starcoin_types::block::BlockNumber;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Export the main chain blocks in range to a block archive file.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export-blocks")]
+pub struct ExportBlocksOpt {
+    /// The number of the first exported block, inclusive.
+    #[structopt(long, default_value = "1")]
+    from: BlockNumber,
+    /// The number of the last exported block, inclusive, default is the head block.
+    #[structopt(long)]
+    to: Option<BlockNumber>,
+    /// The block archive file path.
+    #[structopt(long, short = "o", parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportBlocksResult {
+    pub output: PathBuf,
+    pub header: BlockArchiveHeader,
+}
+
+/// Export blocks of `chain` from `from` to `to` into `writer`.
+pub fn export_blocks<W: Write>(
+    chain: &BlockChain,
+    from: BlockNumber,
+    to: BlockNumber,
+    writer: W,
+) -> Result<BlockArchiveHeader> {
+    let head_number = chain.current_header().number();
+    ensure!(
+        to <= head_number,
+        "The last block {} is after head block {}",
+        to,
+        head_number
+    );
+    let chain_info = chain.info();
+    let header =
+        BlockArchiveHeader::new(chain_info.chain_id(), chain_info.genesis_hash(), from, to);
+    let mut archive_writer = BlockArchiveWriter::new(writer, header.clone())?;
+    for number in from..=to {
+        let block = chain
+            .get_block_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+        archive_writer.append(&block)?;
+        if number % 1000 == 0 {
+            info!("Exported block {}", number);
+        }
+    }
+    archive_writer.finish()?;
+    Ok(header)
+}
+
+pub struct ExportBlocksCommand;
+
+impl CommandAction for ExportBlocksCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExportBlocksOpt;
+    type ReturnItem = ExportBlocksResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let (config, storage) = open_storage(ctx.global_opt(), true)?;
+        let startup_info = storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info is missing, the storage is not init."))?;
+        let chain = BlockChain::new(config.net().time_service(), startup_info.main, storage)?;
+        let to = opt.to.unwrap_or_else(|| chain.current_header().number());
+        let file = File::create(opt.output.as_path())?;
+        let header = export_blocks(&chain, opt.from, to, BufWriter::new(file))?;
+        Ok(ExportBlocksResult {
+            output: opt.output.clone(),
+            header,
+        })
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::open_storage;
+use crate::state_snapshot::{StateSnapshotHeader, StateSnapshotWriter};
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_config::StarcoinOpt;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_state_tree::StateNodeStore;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::BlockStore;
+use starcoin_types::block::{Block, BlockInfo};
+use starcoin_types::genesis_config::ChainId;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// Export the full chain state at a block to a state snapshot file.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export-state")]
+pub struct ExportStateOpt {
+    /// The id of the block whose state is exported.
+    #[structopt(long)]
+    block_id: HashValue,
+    /// The max account count of a chunk.
+    #[structopt(long, default_value = "1000")]
+    chunk_size: u64,
+    /// The state snapshot file path.
+    #[structopt(long, short = "o", parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportStateResult {
+    pub output: PathBuf,
+    pub header: StateSnapshotHeader,
+    pub account_count: u64,
+}
+
+/// Export the state at `block` from `state_store` into `writer`,
+/// return the snapshot header and the count of exported accounts.
+pub fn export_state<W: Write>(
+    state_store: Arc<dyn StateNodeStore>,
+    chain_id: ChainId,
+    genesis_hash: HashValue,
+    block: Block,
+    block_info: BlockInfo,
+    chunk_size: u64,
+    writer: W,
+) -> Result<(StateSnapshotHeader, u64)> {
+    let statedb = ChainStateDB::new(state_store, Some(block.header().state_root()));
+    let header = StateSnapshotHeader::new(chain_id, genesis_hash, block, block_info, chunk_size);
+    let mut snapshot_writer = StateSnapshotWriter::new(writer, &header)?;
+    statedb.dump_chunks(chunk_size as usize, |chunk| {
+        snapshot_writer.append(&chunk)?;
+        info!("Exported {} accounts", snapshot_writer.account_count());
+        Ok(())
+    })?;
+    let account_count = snapshot_writer.account_count();
+    snapshot_writer.finish()?;
+    Ok((header, account_count))
+}
+
+pub struct ExportStateCommand;
+
+impl CommandAction for ExportStateCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExportStateOpt;
+    type ReturnItem = ExportStateResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let (config, storage) = open_storage(ctx.global_opt(), true)?;
+        let genesis_hash = storage
+            .get_genesis()?
+            .ok_or_else(|| format_err!("Genesis is missing, the storage is not init."))?;
+        let block = storage
+            .get_block_by_hash(opt.block_id)?
+            .ok_or_else(|| format_err!("Can not find block by id {}", opt.block_id))?;
+        let block_info = storage
+            .get_block_info(opt.block_id)?
+            .ok_or_else(|| format_err!("Can not find block info by id {}", opt.block_id))?;
+        let file = File::create(opt.output.as_path())?;
+        let (header, account_count) = export_state(
+            storage,
+            config.net().chain_id(),
+            genesis_hash,
+            block,
+            block_info,
+            opt.chunk_size,
+            BufWriter::new(file),
+        )?;
+        Ok(ExportStateResult {
+            output: opt.output.clone(),
+            header,
+            account_count,
+        })
+    }
+}
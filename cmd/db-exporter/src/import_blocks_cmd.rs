@@ -0,0 +1,163 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_archive::{BlockArchiveHeader, BlockArchiveReader};
+use crate::cli_state::CliState;
+use crate::open_storage;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_chain::verifier::Verifier;
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::StarcoinOpt;
+use starcoin_genesis::Genesis;
+use starcoin_logger::prelude::*;
+use starcoin_types::block::BlockHeader;
+use starcoin_types::startup_info::StartupInfo;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Import blocks from a block archive file, and apply them to the main chain.
+/// The node should be stopped before import.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "import-blocks")]
+pub struct ImportBlocksOpt {
+    /// The block archive file path.
+    #[structopt(long, short = "i", parse(from_os_str))]
+    input: PathBuf,
+    /// Verifier used when apply block: Basic, Consensus, Full, None.
+    #[structopt(
+        long,
+        possible_values = &Verifier::variants(),
+        case_insensitive = true,
+        default_value = "Full"
+    )]
+    verifier: Verifier,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportBlocksResult {
+    pub header: BlockArchiveHeader,
+    pub applied: u64,
+    pub skipped: u64,
+    pub head: BlockHeader,
+}
+
+/// Apply the blocks in archive to `chain`, the blocks which already in the main chain are
+/// skipped, the startup info is updated after every block applied, with the address transaction
+/// index and the event index if they are enabled, like the node's block connector.
+pub fn import_blocks<R: Read>(
+    chain: &mut BlockChain,
+    reader: BlockArchiveReader<R>,
+    verifier: &Verifier,
+    enable_address_index: bool,
+    enable_event_index: bool,
+) -> Result<ImportBlocksResult> {
+    let header = reader.header().clone();
+    let chain_info = chain.info();
+    ensure!(
+        header.chain_id == chain_info.chain_id(),
+        "Chain id mismatch, archive: {}, local: {}",
+        header.chain_id,
+        chain_info.chain_id()
+    );
+    ensure!(
+        header.genesis_hash == chain_info.genesis_hash(),
+        "Genesis mismatch, archive: {}, local: {}",
+        header.genesis_hash,
+        chain_info.genesis_hash()
+    );
+    let storage = chain.get_storage();
+    let event_index_start = BlockChain::init_event_index_start(
+        enable_event_index,
+        chain.current_header().number(),
+        storage.as_ref(),
+    )?;
+    let mut applied = 0u64;
+    let mut skipped = 0u64;
+    for block in reader {
+        let block = block?;
+        let number = block.header().number();
+        let head = chain.current_header();
+        if number <= head.number() {
+            ensure!(
+                chain.get_hash_by_number(number)? == Some(block.id()),
+                "Block {:?} of number {} in archive is not in local main chain",
+                block.id(),
+                number
+            );
+            skipped += 1;
+            continue;
+        }
+        ensure!(
+            block.header().parent_hash() == head.id(),
+            "Block {:?} of number {} is not the child of head {:?}",
+            block.id(),
+            number,
+            head.id()
+        );
+        let executed_block = chain.apply_with_verifier_type(block, verifier)?;
+        let enacted = [executed_block.block().id()];
+        let mut batches = vec![];
+        if enable_address_index {
+            batches.extend(BlockChain::address_txn_index_batches(
+                storage.as_ref(),
+                &enacted,
+                &[],
+            )?);
+        }
+        if let Some(index_start) = event_index_start {
+            batches.extend(BlockChain::event_index_batches(
+                storage.as_ref(),
+                &enacted,
+                &[],
+                index_start,
+            )?);
+        }
+        storage.save_startup_info_with_batches(
+            StartupInfo::new(executed_block.block().id()),
+            batches,
+        )?;
+        applied += 1;
+        if number % 1000 == 0 {
+            info!("Imported block {}", number);
+        }
+    }
+    Ok(ImportBlocksResult {
+        header,
+        applied,
+        skipped,
+        head: chain.current_header(),
+    })
+}
+
+pub struct ImportBlocksCommand;
+
+impl CommandAction for ImportBlocksCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ImportBlocksOpt;
+    type ReturnItem = ImportBlocksResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let (config, storage) = open_storage(ctx.global_opt(), false)?;
+        let (chain_info, _) =
+            Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
+        let reader = BlockArchiveReader::new(BufReader::new(File::open(opt.input.as_path())?))?;
+        let mut chain =
+            BlockChain::new(config.net().time_service(), chain_info.head().id(), storage)?;
+        import_blocks(
+            &mut chain,
+            reader,
+            &opt.verifier,
+            config.storage.enable_address_index(),
+            config.storage.enable_event_index(),
+        )
+    }
+}
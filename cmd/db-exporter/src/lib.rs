@@ -0,0 +1,103 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_config::{NodeConfig, StarcoinOpt};
+use starcoin_storage::cache_storage::CacheStorage;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{Storage, VEC_PREFIX_NAME};
+use std::convert::TryFrom;
+use std::io::{ErrorKind, Read, Write};
+use std::sync::Arc;
+
+/// The max length of an item, a corrupted or malicious file should not make the reader
+/// allocate unbounded memory by the length prefix.
+pub const MAX_ITEM_SIZE: usize = 256 * 1024 * 1024;
+
+pub mod block_archive;
+pub mod cli_state;
+pub mod export_blocks_cmd;
+pub mod export_state_cmd;
+pub mod import_blocks_cmd;
+pub mod restore_state_cmd;
+pub mod state_snapshot;
+#[cfg(test)]
+mod tests;
+
+/// Load the node config by `global_opt` and open the node's storage.
+/// A readonly storage can be opened when the node is running,
+/// otherwise the node should be stopped, RocksDB does not allow multiple writers.
+pub fn open_storage(
+    global_opt: &StarcoinOpt,
+    readonly: bool,
+) -> Result<(NodeConfig, Arc<Storage>)> {
+    let config = NodeConfig::load_with_opt(global_opt)?;
+    let db_storage = if readonly {
+        DBStorage::open_with_cfs(
+            config.storage.dir().join("starcoindb"),
+            VEC_PREFIX_NAME.to_vec(),
+            true,
+            config.storage.rocksdb_config(),
+        )?
+    } else {
+        DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?
+    };
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new_with_capacity(config.storage.cache_size()),
+        db_storage,
+    ))?);
+    Ok((config, storage))
+}
+
+/// Write a BCS encoded item prefixed with its length as big endian u32.
+pub(crate) fn write_item<W, T>(writer: &mut W, item: &T) -> Result<()>
+where
+    W: Write,
+    T: Serialize,
+{
+    let bytes = bcs_ext::to_bytes(item)?;
+    ensure!(
+        bytes.len() <= MAX_ITEM_SIZE,
+        "Item too large, length: {}, max: {}",
+        bytes.len(),
+        MAX_ITEM_SIZE
+    );
+    let len = u32::try_from(bytes.len())
+        .map_err(|_| format_err!("Item too large, length: {}", bytes.len()))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Read an item, return None if reach the end of the reader.
+pub(crate) fn read_item<R, T>(reader: &mut R) -> Result<Option<T>>
+where
+    R: Read,
+    T: for<'a> Deserialize<'a>,
+{
+    let mut len_bytes = [0u8; 4];
+    match reader.read_exact(&mut len_bytes) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    ensure!(
+        len <= MAX_ITEM_SIZE,
+        "Item too large, length: {}, max: {}",
+        len,
+        MAX_ITEM_SIZE
+    );
+    // Read by the actual data instead of allocating by the length prefix.
+    let mut bytes = vec![];
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    ensure!(
+        bytes.len() == len,
+        "Unexpected end of the item, length: {}, read: {}",
+        len,
+        bytes.len()
+    );
+    Ok(Some(bcs_ext::from_bytes(bytes.as_slice())?))
+}
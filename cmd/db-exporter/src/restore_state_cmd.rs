@@ -0,0 +1,184 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::open_storage;
+use crate::state_snapshot::{StateSnapshotHeader, StateSnapshotReader};
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_config::StarcoinOpt;
+use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
+use starcoin_logger::prelude::*;
+use starcoin_statedb::{ChainStateDB, ChainStateReader, ChainStateWriter};
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::startup_info::{ChainInfo, StartupInfo};
+use starcoin_types::state_set::ChainStateSet;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::PathBuf;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// Restore the chain state from a state snapshot file into a fresh database, the state tree is
+/// rebuilt and the root is verified against the block header in the snapshot, then the block
+/// becomes the head block of the node. The node should be stopped before restore.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "restore-state")]
+pub struct RestoreStateOpt {
+    /// The state snapshot file path.
+    #[structopt(long, short = "i", parse(from_os_str))]
+    input: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreStateResult {
+    pub header: StateSnapshotHeader,
+    pub account_count: u64,
+    pub state_root: HashValue,
+}
+
+/// Check the block and block info in the snapshot header match each other.
+/// The total difficulty and the block accumulator of the block info can not be verified by the
+/// header, they are trusted as the snapshot itself.
+fn check_snapshot_block(header: &StateSnapshotHeader) -> Result<()> {
+    let block_header = header.block_header();
+    let block_id = block_header.id();
+    ensure!(
+        block_header.body_hash() == header.block.body.hash(),
+        "The body of block {} mismatch with its header",
+        block_id
+    );
+    ensure!(
+        *header.block_info.block_id() == block_id,
+        "The block info of {} mismatch with block {}",
+        header.block_info.block_id(),
+        block_id
+    );
+    let txn_accumulator_root = *header
+        .block_info
+        .get_txn_accumulator_info()
+        .get_accumulator_root();
+    ensure!(
+        txn_accumulator_root == block_header.txn_accumulator_root(),
+        "The txn accumulator root {} of block info mismatch with block {}'s {}",
+        txn_accumulator_root,
+        block_id,
+        block_header.txn_accumulator_root()
+    );
+    let block_leaves = header
+        .block_info
+        .get_block_accumulator_info()
+        .get_num_leaves();
+    ensure!(
+        block_leaves == block_header.number().saturating_add(1),
+        "The block accumulator of block info has {} leaves, mismatch with block number {}",
+        block_leaves,
+        block_header.number()
+    );
+    Ok(())
+}
+
+/// Check the accounts of a chunk are well formed and not restored before.
+fn check_chunk(chunk: &ChainStateSet, restored: &mut HashSet<AccountAddress>) -> Result<()> {
+    for (address, account_state_set) in chunk.state_sets() {
+        ensure!(
+            account_state_set.resource_set().is_some(),
+            "The resource set of account {} is missing",
+            address
+        );
+        ensure!(
+            restored.insert(*address),
+            "Account {} is duplicated in the state snapshot",
+            address
+        );
+    }
+    Ok(())
+}
+
+/// Rebuild the state tree from `reader` into `storage`, verify the state root, and save the
+/// block of the snapshot as the head block. `chain_info` is the chain info of `storage`,
+/// which should be a fresh database only initialized with the genesis.
+pub fn restore_state<R: Read>(
+    storage: Arc<Storage>,
+    chain_info: &ChainInfo,
+    reader: StateSnapshotReader<R>,
+) -> Result<RestoreStateResult> {
+    let header = reader.header().clone();
+    ensure!(
+        header.chain_id == chain_info.chain_id(),
+        "State snapshot chain id {} mismatch with node's chain id {}",
+        header.chain_id,
+        chain_info.chain_id()
+    );
+    ensure!(
+        header.genesis_hash == chain_info.genesis_hash(),
+        "State snapshot genesis {} mismatch with node's genesis {}",
+        header.genesis_hash,
+        chain_info.genesis_hash()
+    );
+    ensure!(
+        chain_info.status().head().id() == chain_info.genesis_hash(),
+        "The state snapshot should be restored to a fresh database, but the head block is {}",
+        chain_info.status().head().id()
+    );
+    check_snapshot_block(&header)?;
+
+    let statedb = ChainStateDB::new(storage.clone(), None);
+    let mut restored = HashSet::new();
+    let mut account_count = 0u64;
+    // the reader checks the checksum of every chunk and the total count at the end.
+    for chunk in reader {
+        let chunk = chunk?;
+        check_chunk(&chunk, &mut restored)?;
+        account_count = account_count.saturating_add(chunk.len() as u64);
+        statedb.apply(chunk)?;
+        info!("Restored {} accounts", account_count);
+    }
+    let state_root = statedb.state_root();
+    let expect_state_root = header.block_header().state_root();
+    ensure!(
+        state_root == expect_state_root,
+        "Restored state root {} mismatch with block {}'s state root {}",
+        state_root,
+        header.block.id(),
+        expect_state_root
+    );
+
+    let block_id = header.block.id();
+    storage.commit_block(header.block.clone())?;
+    storage.save_block_info(header.block_info.clone())?;
+    storage.save_startup_info(StartupInfo::new(block_id))?;
+    info!("Restored state at block {}", block_id);
+    Ok(RestoreStateResult {
+        header,
+        account_count,
+        state_root,
+    })
+}
+
+pub struct RestoreStateCommand;
+
+impl CommandAction for RestoreStateCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RestoreStateOpt;
+    type ReturnItem = RestoreStateResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let (config, storage) = open_storage(ctx.global_opt(), false)?;
+        let reader = StateSnapshotReader::new(BufReader::new(File::open(opt.input.as_path())?))?;
+        // init the genesis of a fresh database, the genesis is checked with the network's config.
+        let (chain_info, _) =
+            Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
+        restore_state(storage, &chain_info, reader)
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A portable file format of the full chain state at a block.
+//! The file is composed of a `StateSnapshotHeader`, the account states in chunks, and an end item
+//! with the total count of chunks and accounts. Every chunk carries the sha3 checksum of its data,
+//! every item is BCS encoded and prefixed with its length as big endian u32.
+
+use crate::{read_item, write_item};
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::{Block, BlockHeader, BlockInfo};
+use starcoin_types::genesis_config::ChainId;
+use starcoin_types::state_set::ChainStateSet;
+use std::io::{Read, Write};
+
+pub const STATE_SNAPSHOT_VERSION: u8 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshotHeader {
+    pub version: u8,
+    pub chain_id: ChainId,
+    pub genesis_hash: HashValue,
+    /// The block which the state belongs to, the restored node starts from it.
+    pub block: Block,
+    /// The block info of `block`.
+    pub block_info: BlockInfo,
+    /// The max account count of a chunk.
+    pub chunk_size: u64,
+}
+
+impl StateSnapshotHeader {
+    pub fn new(
+        chain_id: ChainId,
+        genesis_hash: HashValue,
+        block: Block,
+        block_info: BlockInfo,
+        chunk_size: u64,
+    ) -> Self {
+        Self {
+            version: STATE_SNAPSHOT_VERSION,
+            chain_id,
+            genesis_hash,
+            block,
+            block_info,
+            chunk_size,
+        }
+    }
+
+    pub fn block_header(&self) -> &BlockHeader {
+        self.block.header()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshotChunk {
+    pub index: u64,
+    /// sha3 of `data`.
+    pub checksum: HashValue,
+    /// BCS encoded `ChainStateSet`.
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StateSnapshotItem {
+    Chunk(StateSnapshotChunk),
+    End {
+        chunk_count: u64,
+        account_count: u64,
+    },
+}
+
+pub struct StateSnapshotWriter<W: Write> {
+    writer: W,
+    chunk_count: u64,
+    account_count: u64,
+}
+
+impl<W> StateSnapshotWriter<W>
+where
+    W: Write,
+{
+    pub fn new(mut writer: W, header: &StateSnapshotHeader) -> Result<Self> {
+        ensure!(
+            header.chunk_size > 0,
+            "Chunk size should be greater than zero."
+        );
+        write_item(&mut writer, header)?;
+        Ok(Self {
+            writer,
+            chunk_count: 0,
+            account_count: 0,
+        })
+    }
+
+    pub fn append(&mut self, chunk: &ChainStateSet) -> Result<()> {
+        let data = bcs_ext::to_bytes(chunk)?;
+        let item = StateSnapshotItem::Chunk(StateSnapshotChunk {
+            index: self.chunk_count,
+            checksum: HashValue::sha3_256_of(data.as_slice()),
+            data,
+        });
+        write_item(&mut self.writer, &item)?;
+        self.chunk_count = self.chunk_count.saturating_add(1);
+        self.account_count = self.account_count.saturating_add(chunk.len() as u64);
+        Ok(())
+    }
+
+    pub fn account_count(&self) -> u64 {
+        self.account_count
+    }
+
+    /// Write the end item and flush the writer.
+    pub fn finish(mut self) -> Result<W> {
+        write_item(
+            &mut self.writer,
+            &StateSnapshotItem::End {
+                chunk_count: self.chunk_count,
+                account_count: self.account_count,
+            },
+        )?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+pub struct StateSnapshotReader<R: Read> {
+    reader: R,
+    header: StateSnapshotHeader,
+    chunk_count: u64,
+    account_count: u64,
+    finished: bool,
+}
+
+impl<R> StateSnapshotReader<R>
+where
+    R: Read,
+{
+    pub fn new(mut reader: R) -> Result<Self> {
+        let header: StateSnapshotHeader = read_item(&mut reader)?
+            .ok_or_else(|| format_err!("State snapshot header is missing."))?;
+        ensure!(
+            header.version == STATE_SNAPSHOT_VERSION,
+            "Unsupported state snapshot version: {}",
+            header.version
+        );
+        Ok(Self {
+            reader,
+            header,
+            chunk_count: 0,
+            account_count: 0,
+            finished: false,
+        })
+    }
+
+    pub fn header(&self) -> &StateSnapshotHeader {
+        &self.header
+    }
+
+    /// The count of accounts read so far.
+    pub fn account_count(&self) -> u64 {
+        self.account_count
+    }
+
+    /// Read the next chunk, return None after the end item is read and checked.
+    fn read_chunk(&mut self) -> Result<Option<ChainStateSet>> {
+        let item: StateSnapshotItem = read_item(&mut self.reader)?.ok_or_else(|| {
+            format_err!(
+                "State snapshot is incomplete, expect chunk {}",
+                self.chunk_count
+            )
+        })?;
+        match item {
+            StateSnapshotItem::Chunk(chunk) => {
+                ensure!(
+                    chunk.index == self.chunk_count,
+                    "Expect chunk {}, but got {}",
+                    self.chunk_count,
+                    chunk.index
+                );
+                let checksum = HashValue::sha3_256_of(chunk.data.as_slice());
+                ensure!(
+                    checksum == chunk.checksum,
+                    "Checksum mismatch of chunk {}, expect {}, but got {}",
+                    chunk.index,
+                    chunk.checksum,
+                    checksum
+                );
+                let state_set: ChainStateSet = bcs_ext::from_bytes(chunk.data.as_slice())?;
+                ensure!(
+                    state_set.len() as u64 <= self.header.chunk_size,
+                    "Chunk {} contains {} accounts, more than chunk size {}",
+                    chunk.index,
+                    state_set.len(),
+                    self.header.chunk_size
+                );
+                self.chunk_count = self.chunk_count.saturating_add(1);
+                self.account_count = self.account_count.saturating_add(state_set.len() as u64);
+                Ok(Some(state_set))
+            }
+            StateSnapshotItem::End {
+                chunk_count,
+                account_count,
+            } => {
+                ensure!(
+                    chunk_count == self.chunk_count && account_count == self.account_count,
+                    "State snapshot expect {} chunks and {} accounts, but got {} chunks and {} accounts",
+                    chunk_count,
+                    account_count,
+                    self.chunk_count,
+                    self.account_count
+                );
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<R> Iterator for StateSnapshotReader<R>
+where
+    R: Read,
+{
+    type Item = Result<ChainStateSet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.read_chunk();
+        // stop at the end item or the first error.
+        match result {
+            Ok(Some(state_set)) => Some(Ok(state_set)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_config::ChainNetwork;
+    use starcoin_genesis::Genesis;
+    use starcoin_statedb::{ChainStateDB, ChainStateReader};
+    use starcoin_storage::BlockStore;
+
+    #[test]
+    fn test_state_snapshot() -> Result<()> {
+        let net = ChainNetwork::new_test();
+        let (storage, _, genesis) = Genesis::init_storage_for_test(&net)?;
+        let block = genesis.block().clone();
+        let block_info = storage
+            .get_block_info(block.id())?
+            .ok_or_else(|| format_err!("Can not find genesis block info"))?;
+        let statedb = ChainStateDB::new(storage, Some(block.header().state_root()));
+        let state_set = statedb.dump()?;
+        let header = StateSnapshotHeader::new(net.chain_id(), block.id(), block, block_info, 2);
+        let chunks = state_set
+            .state_sets()
+            .chunks(2)
+            .map(|chunk| ChainStateSet::new(chunk.to_vec()))
+            .collect::<Vec<_>>();
+
+        let mut writer = StateSnapshotWriter::new(vec![], &header)?;
+        for chunk in &chunks {
+            writer.append(chunk)?;
+        }
+        assert_eq!(writer.account_count(), state_set.len() as u64);
+        let bytes = writer.finish()?;
+
+        let reader = StateSnapshotReader::new(bytes.as_slice())?;
+        assert_eq!(reader.header(), &header);
+        let read_chunks = reader.collect::<Result<Vec<_>>>()?;
+        assert_eq!(read_chunks, chunks);
+
+        // truncated snapshot should fail.
+        let reader = StateSnapshotReader::new(&bytes[..bytes.len() - 1])?;
+        assert!(reader.collect::<Result<Vec<_>>>().is_err());
+
+        // corrupted chunk should fail.
+        let mut corrupted = bytes.clone();
+        let pos = bcs_ext::to_bytes(&header)?.len() + 100;
+        corrupted[pos] ^= 0xff;
+        let reader = StateSnapshotReader::new(corrupted.as_slice())?;
+        assert!(reader.collect::<Result<Vec<_>>>().is_err());
+        Ok(())
+    }
+}
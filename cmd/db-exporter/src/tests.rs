@@ -0,0 +1,228 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_archive::BlockArchiveReader;
+use crate::export_blocks_cmd::export_blocks;
+use crate::export_state_cmd::export_state;
+use crate::import_blocks_cmd::import_blocks;
+use crate::restore_state_cmd::restore_state;
+use crate::state_snapshot::{StateSnapshotReader, StateSnapshotWriter};
+use crate::{read_item, MAX_ITEM_SIZE};
+use anyhow::Result;
+use starcoin_chain::verifier::Verifier;
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_chain_mock::MockChain;
+use starcoin_config::{BuiltinNetworkID, ChainNetwork};
+use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{BlockStore, ContractEventIndexStore, Storage};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::genesis_address;
+use starcoin_types::block::{Block, BlockInfo};
+use starcoin_types::event::EventKey;
+use starcoin_types::filter::Filter;
+use starcoin_types::state_set::{AccountStateSet, ChainStateSet};
+use std::sync::Arc;
+
+#[test]
+fn test_export_and_import_blocks() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let mut mock_chain = MockChain::new(net.clone())?;
+    mock_chain.produce_and_apply_times(10)?;
+    let head = mock_chain.head().current_header();
+
+    let mut archive = vec![];
+    let header = export_blocks(mock_chain.head(), 1, 6, &mut archive)?;
+    assert_eq!(header.block_count(), 6);
+    let mut archive2 = vec![];
+    export_blocks(mock_chain.head(), 4, head.number(), &mut archive2)?;
+    assert!(export_blocks(mock_chain.head(), 1, head.number() + 1, vec![]).is_err());
+
+    let (storage, chain_info, _) = Genesis::init_storage_for_test(&net)?;
+    let mut chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+    let result = import_blocks(
+        &mut chain,
+        BlockArchiveReader::new(archive.as_slice())?,
+        &Verifier::Full,
+        true,
+        true,
+    )?;
+    assert_eq!(result.applied, 6);
+    assert_eq!(result.head.number(), 6);
+
+    // the overlapped blocks are skipped.
+    let result = import_blocks(
+        &mut chain,
+        BlockArchiveReader::new(archive2.as_slice())?,
+        &Verifier::Full,
+        true,
+        true,
+    )?;
+    assert_eq!(result.skipped, 3);
+    assert_eq!(result.applied, head.number() - 6);
+    assert_eq!(result.head, head);
+    assert_eq!(
+        chain.chain_state_reader().state_root(),
+        mock_chain.head().chain_state_reader().state_root()
+    );
+
+    // the imported blocks are indexed.
+    assert_eq!(storage.get_event_index_start()?, Some(1));
+    let event_filter = Filter {
+        from_block: 1,
+        to_block: head.number(),
+        event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+        ..Default::default()
+    };
+    let evts = chain.filter_indexed_events(event_filter.clone(), 0)?;
+    assert_eq!(evts.len() as u64, head.number());
+    assert_eq!(evts, chain.filter_events(event_filter)?);
+    assert_eq!(
+        chain
+            .get_transaction_infos_by_address(genesis_address(), None, 100)?
+            .len() as u64,
+        head.number()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_item_length() -> Result<()> {
+    let oversized = ((MAX_ITEM_SIZE + 1) as u32).to_be_bytes();
+    assert!(read_item::<_, Block>(&mut &oversized[..]).is_err());
+
+    let mut truncated = 100u32.to_be_bytes().to_vec();
+    truncated.extend_from_slice(&[0u8; 10]);
+    assert!(read_item::<_, Block>(&mut truncated.as_slice()).is_err());
+    assert!(read_item::<_, Block>(&mut &[0u8; 0][..])?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_export_and_restore_state() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let mut mock_chain = MockChain::new(net.clone())?;
+    mock_chain.produce_and_apply_times(5)?;
+    let head = mock_chain.head().head_block();
+    let head_info = mock_chain
+        .head()
+        .get_block_info(None)?
+        .expect("head block info should exist");
+    let chain_info = mock_chain.head().info();
+    let export = |block: Block, block_info: BlockInfo| -> Result<(Vec<u8>, u64)> {
+        let mut snapshot = vec![];
+        let (_, account_count) = export_state(
+            mock_chain.head().get_storage().into_super_arc(),
+            chain_info.chain_id(),
+            chain_info.genesis_hash(),
+            block,
+            block_info,
+            2,
+            &mut snapshot,
+        )?;
+        Ok((snapshot, account_count))
+    };
+    let (snapshot, account_count) = export(head.clone(), head_info.clone())?;
+    assert!(account_count > 2);
+
+    // restore to a fresh storage only initialized with the genesis.
+    let (storage, fresh_chain_info, _) = Genesis::init_storage_for_test(&net)?;
+    let result = restore_state(
+        storage.clone(),
+        &fresh_chain_info,
+        StateSnapshotReader::new(snapshot.as_slice())?,
+    )?;
+    assert_eq!(result.header.block, head);
+    assert_eq!(result.account_count, account_count);
+    assert_eq!(result.state_root, head.header().state_root());
+    let restored_chain_info = storage
+        .get_chain_info()?
+        .expect("chain info should exist after restore");
+    assert_eq!(restored_chain_info.status().head(), head.header());
+    let restored_chain = BlockChain::new(net.time_service(), head.id(), storage.clone())?;
+    assert_eq!(restored_chain.current_header(), *head.header());
+
+    // the storage is not fresh after restore.
+    let restored_chain_info = storage
+        .get_chain_info()?
+        .expect("chain info should exist after restore");
+    assert!(restore_state(
+        storage,
+        &restored_chain_info,
+        StateSnapshotReader::new(snapshot.as_slice())?,
+    )
+    .is_err());
+
+    // the snapshot of another chain should fail to restore.
+    let (storage, fresh_chain_info, _) =
+        Genesis::init_storage_for_test(&ChainNetwork::new_builtin(BuiltinNetworkID::Dev))?;
+    assert!(restore_state(
+        storage,
+        &fresh_chain_info,
+        StateSnapshotReader::new(snapshot.as_slice())?,
+    )
+    .is_err());
+
+    // the state of parent block with the head block should fail to restore.
+    let parent = mock_chain
+        .head()
+        .get_block(head.header().parent_hash())?
+        .expect("parent block should exist");
+    let parent_info = mock_chain
+        .head()
+        .get_block_info(Some(parent.id()))?
+        .expect("parent block info should exist");
+    let (snapshot, _) = export(parent, parent_info)?;
+    let reader = StateSnapshotReader::new(snapshot.as_slice())?;
+    let mut header = reader.header().clone();
+    header.block = head.clone();
+    header.block_info = head_info.clone();
+    let mut writer = StateSnapshotWriter::new(vec![], &header)?;
+    for chunk in reader {
+        writer.append(&chunk?)?;
+    }
+    let tampered = writer.finish()?;
+    let (storage, fresh_chain_info, _) = Genesis::init_storage_for_test(&net)?;
+    assert!(restore_state(
+        storage,
+        &fresh_chain_info,
+        StateSnapshotReader::new(tampered.as_slice())?,
+    )
+    .is_err());
+
+    // the block info mismatch with the block should fail to restore.
+    let (snapshot, _) = export(head.clone(), head_info)?;
+    let reader = StateSnapshotReader::new(snapshot.as_slice())?;
+    let mut header = reader.header().clone();
+    header.block_info.block_id = HashValue::random();
+    let mut writer = StateSnapshotWriter::new(vec![], &header)?;
+    for chunk in reader {
+        writer.append(&chunk?)?;
+    }
+    let tampered = writer.finish()?;
+    let (storage, fresh_chain_info, _) = Genesis::init_storage_for_test(&net)?;
+    assert!(restore_state(
+        storage,
+        &fresh_chain_info,
+        StateSnapshotReader::new(tampered.as_slice())?,
+    )
+    .is_err());
+
+    // an account without resource set should fail to restore instead of panic.
+    let reader = StateSnapshotReader::new(snapshot.as_slice())?;
+    let mut writer = StateSnapshotWriter::new(vec![], reader.header())?;
+    writer.append(&ChainStateSet::new(vec![(
+        AccountAddress::random(),
+        AccountStateSet::new(vec![None, None]),
+    )]))?;
+    let malformed = writer.finish()?;
+    let (storage, fresh_chain_info, _) = Genesis::init_storage_for_test(&net)?;
+    assert!(restore_state(
+        storage,
+        &fresh_chain_info,
+        StateSnapshotReader::new(malformed.as_slice())?,
+    )
+    .is_err());
+    Ok(())
+}
@@ -35,7 +35,8 @@ pub fn init_or_load_data_dir(
         Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
     let vault_config = &config.vault;
     let account_storage =
-        AccountStorage::create_from_path(vault_config.dir(), config.storage.rocksdb_config())?;
+        AccountStorage::create_from_path(vault_config.dir(), config.storage.rocksdb_config())?
+            .with_key_derivation(vault_config.key_derivation());
     let manager = AccountManager::new(account_storage)?;
     let account = match manager.default_account_info()? {
         Some(account) => account,
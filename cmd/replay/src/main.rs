@@ -3,7 +3,6 @@
 
 use sp_utils::stop_watch::start_watch;
 use starcoin_chain::verifier::Verifier;
-use starcoin_chain::verifier::{BasicVerifier, ConsensusVerifier, FullVerifier, NoneVerifier};
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_config::RocksdbConfig;
 use starcoin_config::{BuiltinNetworkID, ChainNetwork};
@@ -104,22 +103,9 @@ fn main() {
     .expect("create block chain should success.");
     let begin = SystemTime::now();
     for block in block_vec {
-        match opts.verifier {
-            Verifier::Basic => {
-                chain2.apply_with_verifier::<BasicVerifier>(block).unwrap();
-            }
-            Verifier::Consensus => {
-                chain2
-                    .apply_with_verifier::<ConsensusVerifier>(block)
-                    .unwrap();
-            }
-            Verifier::None => {
-                chain2.apply_with_verifier::<NoneVerifier>(block).unwrap();
-            }
-            Verifier::Full => {
-                chain2.apply_with_verifier::<FullVerifier>(block).unwrap();
-            }
-        };
+        chain2
+            .apply_with_verifier_type(block, &opts.verifier)
+            .unwrap();
     }
     let use_time = SystemTime::now().duration_since(begin).unwrap();
     println!("apply use time: {:?}", use_time.as_nanos());
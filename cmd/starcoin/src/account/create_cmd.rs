@@ -5,6 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
 use starcoin_account_api::AccountInfo;
 use structopt::StructOpt;
 
@@ -13,6 +14,19 @@ use structopt::StructOpt;
 pub struct CreateOpt {
     #[structopt(short = "p")]
     password: String,
+
+    /// Generate a new mnemonic as the HD wallet seed, and create the first account from it.
+    /// Back up the returned mnemonic, it is the only way to recover the accounts derived from it.
+    #[structopt(long = "mnemonic")]
+    mnemonic: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateAccountView {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(flatten)]
+    pub account: AccountInfo,
 }
 
 pub struct CreateCommand;
@@ -21,14 +35,26 @@ impl CommandAction for CreateCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = CreateOpt;
-    type ReturnItem = AccountInfo;
+    type ReturnItem = CreateAccountView;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
-    ) -> Result<AccountInfo> {
+    ) -> Result<CreateAccountView> {
         let client = ctx.state().client();
-        let account = client.account_create(ctx.opt().password.clone())?;
-        Ok(account)
+        let opt = ctx.opt();
+        if opt.mnemonic {
+            let result = client.account_create_from_mnemonic(None, opt.password.clone())?;
+            Ok(CreateAccountView {
+                mnemonic: Some(result.mnemonic),
+                account: result.account,
+            })
+        } else {
+            let account = client.account_create(opt.password.clone())?;
+            Ok(CreateAccountView {
+                mnemonic: None,
+                account,
+            })
+        }
     }
 }
@@ -5,6 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::keystore::{KeyDerivation, Keystore};
 use starcoin_crypto::ValidCryptoMaterialStringExt;
 use starcoin_types::transaction::authenticator::AccountPrivateKey;
 use starcoin_vm_types::account_address::AccountAddress;
@@ -21,6 +22,9 @@ pub struct ExportOpt {
     password: String,
     #[structopt(short = "o", parse(from_os_str))]
     output_file: Option<PathBuf>,
+    /// Export the account as a json keystore file encrypted by the password.
+    #[structopt(long = "keystore", parse(from_os_str), conflicts_with("output-file"))]
+    keystore: Option<PathBuf>,
 }
 
 pub struct ExportCommand;
@@ -36,6 +40,27 @@ impl CommandAction for ExportCommand {
         let opt: &ExportOpt = ctx.opt();
         let data = client.account_export(opt.account_address, opt.password.clone())?;
         let private_key = AccountPrivateKey::try_from(data.as_slice())?;
+        if let Some(keystore_file) = &opt.keystore {
+            if keystore_file.exists() {
+                bail!(
+                    "the keystore file {} is already exists, please change a name",
+                    keystore_file.as_path().display()
+                );
+            }
+            let keystore = Keystore::encrypt(
+                opt.account_address,
+                &private_key,
+                opt.password.as_str(),
+                KeyDerivation::default(),
+            )?;
+            std::fs::write(keystore_file, serde_json::to_string_pretty(&keystore)?)?;
+            println!(
+                "account {} keystore saved to {}",
+                &opt.account_address,
+                keystore_file.as_path().display()
+            );
+            return Ok(());
+        }
         let encoded = private_key.to_encoded_string()?;
         if let Some(output_file) = &opt.output_file {
             if output_file.exists() {
@@ -5,6 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::keystore::Keystore;
 use starcoin_account_api::{AccountInfo, AccountPrivateKey};
 use starcoin_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use starcoin_vm_types::account_address::AccountAddress;
@@ -28,6 +29,14 @@ pub struct ImportOpt {
     )]
     from_file: Option<PathBuf>,
 
+    /// File path of the json keystore, which is decrypted by the password.
+    #[structopt(
+        long = "keystore",
+        parse(from_os_str),
+        conflicts_with_all(&["input", "from-file"])
+    )]
+    keystore: Option<PathBuf>,
+
     /// if account_address is absent, generate address by public_key.
     #[structopt(name = "account_address")]
     account_address: Option<AccountAddress>,
@@ -48,19 +57,33 @@ impl CommandAction for ImportCommand {
         let client = ctx.state().client();
         let opt: &ImportOpt = ctx.opt();
 
-        let private_key = match (opt.from_input.as_ref(), opt.from_file.as_ref()) {
-            (Some(p), _) => AccountPrivateKey::from_encoded_string(p)?,
-            (None, Some(p)) => {
+        let (private_key, keystore_address) = match (
+            opt.from_input.as_ref(),
+            opt.from_file.as_ref(),
+            opt.keystore.as_ref(),
+        ) {
+            (Some(p), _, _) => (AccountPrivateKey::from_encoded_string(p)?, None),
+            (None, Some(p), _) => {
                 let data = std::fs::read_to_string(p)?;
-                AccountPrivateKey::from_encoded_string(data.as_str())?
+                (AccountPrivateKey::from_encoded_string(data.as_str())?, None)
+            }
+            (None, None, Some(p)) => {
+                let keystore: Keystore = serde_json::from_str(&std::fs::read_to_string(p)?)?;
+                (
+                    keystore.decrypt(opt.password.as_str())?,
+                    Some(keystore.address),
+                )
             }
-            (None, None) => {
-                bail!("private key should be specified, use one of <input>, <from-file>")
+            (None, None, None) => {
+                bail!(
+                    "private key should be specified, use one of <input>, <from-file>, <keystore>"
+                )
             }
         };
 
         let address = opt
             .account_address
+            .or(keystore_address)
             .unwrap_or_else(|| private_key.public_key().derived_address());
         let account = client.account_import(
             address,
@@ -11,6 +11,7 @@ pub use export_cmd::*;
 pub use import_cmd::*;
 pub use list_cmd::*;
 pub use lock_cmd::*;
+pub use recover_cmd::*;
 pub use show_cmd::*;
 pub use sign_cmd::*;
 pub use transfer_cmd::*;
@@ -28,6 +29,7 @@ mod import_cmd;
 pub mod import_multisig_cmd;
 mod list_cmd;
 mod lock_cmd;
+mod recover_cmd;
 mod show_cmd;
 mod sign_cmd;
 pub mod sign_multisig_txn_cmd;
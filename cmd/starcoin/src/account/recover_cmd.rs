@@ -0,0 +1,51 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountInfo;
+use structopt::StructOpt;
+
+/// Recover the HD wallet from the mnemonic, and derive the first `count` accounts of it.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "recover")]
+pub struct RecoverOpt {
+    #[structopt(short = "p", default_value = "")]
+    password: String,
+
+    /// The mnemonic words, separated by whitespace.
+    #[structopt(short = "m", long = "mnemonic")]
+    mnemonic: String,
+
+    /// The number of accounts to derive.
+    #[structopt(long = "count", default_value = "1")]
+    count: u32,
+}
+
+pub struct RecoverCommand;
+
+impl CommandAction for RecoverCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RecoverOpt;
+    type ReturnItem = Vec<AccountInfo>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt: &RecoverOpt = ctx.opt();
+        ensure!(opt.count > 0, "count should be greater than 0");
+
+        let first = client
+            .account_create_from_mnemonic(Some(opt.mnemonic.clone()), opt.password.clone())?;
+        let mut accounts = vec![first.account];
+        for index in 1..opt.count {
+            accounts.push(client.account_derive(index, opt.password.clone())?);
+        }
+        Ok(accounts)
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::FailedBlockView;
+use structopt::StructOpt;
+
+/// List the blocks which failed to verify or execute, order by block hash.
+/// If `hash` is present, only show the failed block with full transactions.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "failed_blocks")]
+pub struct FailedBlocksOpt {
+    #[structopt(name = "hash", long)]
+    hash: Option<HashValue>,
+    /// List the failed blocks after the block hash, the last block hash of the previous page.
+    #[structopt(name = "cursor", long)]
+    cursor: Option<HashValue>,
+    #[structopt(name = "limit", long, short = "l", default_value = "10")]
+    limit: u64,
+}
+
+pub struct FailedBlocksCommand;
+
+impl CommandAction for FailedBlocksCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = FailedBlocksOpt;
+    type ReturnItem = Vec<FailedBlockView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        match opt.hash {
+            Some(hash) => {
+                let failed_block = client
+                    .chain_get_failed_block(hash)?
+                    .ok_or_else(|| format_err!("Can not find failed block by hash {}", hash))?;
+                Ok(vec![failed_block])
+            }
+            None => client.chain_list_failed_blocks(opt.cursor, opt.limit),
+        }
+    }
+}
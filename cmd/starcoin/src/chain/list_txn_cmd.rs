@@ -0,0 +1,42 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::AddressTransactionInfoView;
+use starcoin_types::account_address::AccountAddress;
+use structopt::StructOpt;
+
+/// List transactions related to `address` from newer to older, require the node to enable address index.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "list-txn")]
+pub struct ListTxnOpt {
+    #[structopt(name = "address", long, short = "a")]
+    /// the sender or the event creator address of the transactions.
+    address: AccountAddress,
+    #[structopt(name = "cursor", long)]
+    /// the index in the address's transaction history to start from, default is the latest.
+    cursor: Option<u64>,
+    #[structopt(name = "count", long, short = "c", default_value = "10")]
+    count: u64,
+}
+
+pub struct ListTxnCommand;
+
+impl CommandAction for ListTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ListTxnOpt;
+    type ReturnItem = Vec<AddressTransactionInfoView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.chain_get_transactions_by_address(opt.address, opt.cursor, opt.count)
+    }
+}
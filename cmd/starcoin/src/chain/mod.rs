@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod epoch_info;
+mod failed_blocks_cmd;
 mod get_block_by_number_cmd;
 mod get_block_cmd;
 mod get_epoch_info_by_number;
@@ -12,12 +13,14 @@ mod get_txn_cmd;
 mod get_txn_info_cmd;
 mod info_cmd;
 mod list_block_cmd;
+mod list_txn_cmd;
 mod stat;
 mod tps;
 pub mod uncle;
 mod verify;
 
 pub use epoch_info::*;
+pub use failed_blocks_cmd::*;
 pub use get_block_by_number_cmd::*;
 pub use get_block_cmd::*;
 pub use get_epoch_info_by_number::*;
@@ -28,6 +31,7 @@ pub use get_txn_cmd::*;
 pub use get_txn_info_cmd::*;
 pub use info_cmd::*;
 pub use list_block_cmd::*;
+pub use list_txn_cmd::*;
 pub use stat::{StatBlockCommand, StatEpochCommand, StatTPSCommand};
 pub use tps::*;
 pub use verify::*;
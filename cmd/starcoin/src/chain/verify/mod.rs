@@ -4,7 +4,9 @@
 mod block;
 mod epoch_info;
 mod node;
+mod txn;
 
 pub use block::*;
 pub use epoch_info::*;
 pub use node::*;
+pub use txn::*;
@@ -0,0 +1,50 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_chain_proof::TransactionProof;
+use starcoin_crypto::HashValue;
+use structopt::StructOpt;
+
+/// Verify the transaction is included in the main chain by its proof.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "txn")]
+pub struct TxnOpt {
+    #[structopt(name = "txn-hash")]
+    txn_hash: HashValue,
+
+    #[structopt(name = "anchor-block-hash", long)]
+    /// the block whose block accumulator the proof is anchored to, if absent, use the head block.
+    anchor_block_hash: Option<HashValue>,
+
+    #[structopt(name = "block-accumulator-root", long)]
+    /// the trusted block accumulator root of the anchor block to verify the proof with.
+    block_accumulator_root: HashValue,
+}
+
+pub struct VerifyTxnCommand;
+
+impl CommandAction for VerifyTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TxnOpt;
+    type ReturnItem = TransactionProof;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ctx.state()
+            .client()
+            .chain_verify_transaction_proof(
+                opt.txn_hash,
+                opt.anchor_block_hash,
+                opt.block_accumulator_root,
+            )?
+            .ok_or_else(|| format_err!("Can not find transaction {} on main chain", opt.txn_hash))
+    }
+}
@@ -74,7 +74,7 @@ impl CommandAction for GetContractDataCommand {
             } => GetContractDataResult::Resource(
                 ctx.state()
                     .client()
-                    .get_resource(*address, resource_type.0.clone())?
+                    .get_resource(*address, resource_type.0.clone(), None)?
                     .map(Into::into),
             ),
         };
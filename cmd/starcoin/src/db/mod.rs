@@ -0,0 +1,32 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The offline commands to export and import the node's database, they open the storage
+//! directly, so they run without starting or connecting a node.
+
+use starcoin_config::StarcoinOpt;
+use std::ffi::OsString;
+use structopt::clap::{AppSettings, Arg};
+use structopt::StructOpt;
+
+pub use starcoin_db_exporter::cli_state::CliState as DbCliState;
+pub use starcoin_db_exporter::export_blocks_cmd::ExportBlocksCommand;
+pub use starcoin_db_exporter::export_state_cmd::ExportStateCommand;
+pub use starcoin_db_exporter::import_blocks_cmd::ImportBlocksCommand;
+pub use starcoin_db_exporter::restore_state_cmd::RestoreStateCommand;
+
+pub const DB_COMMAND_NAME: &str = "db";
+
+/// Check whether the `args` invoke the `db` command group.
+pub fn is_db_command<I, T>(args: I) -> bool
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    StarcoinOpt::clap()
+        .setting(AppSettings::AllowExternalSubcommands)
+        .arg(Arg::with_name("output-format").short("o").takes_value(true))
+        .get_matches_from_safe(args)
+        .map(|matches| matches.subcommand_name() == Some(DB_COMMAND_NAME))
+        .unwrap_or(false)
+}
@@ -6,7 +6,9 @@ mod get_block_by_uncle;
 mod log_cmd;
 mod move_explain;
 mod panic_cmd;
+mod replay_failed_block_cmd;
 mod sleep_cmd;
+mod trace_transaction_cmd;
 mod txfactory_cmd;
 mod txpool_status;
 
@@ -15,6 +17,8 @@ pub use get_block_by_uncle::*;
 pub use log_cmd::*;
 pub use move_explain::*;
 pub use panic_cmd::*;
+pub use replay_failed_block_cmd::*;
 pub use sleep_cmd::*;
+pub use trace_transaction_cmd::*;
 pub use txfactory_cmd::*;
 pub use txpool_status::*;
@@ -0,0 +1,37 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::BlockReplayView;
+use structopt::StructOpt;
+
+/// Re-verify and re-execute a failed block on its parent state, and show the trace of every
+/// transaction and where the execution diverged from the block.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "replay_failed_block")]
+pub struct ReplayFailedBlockOpt {
+    #[structopt(name = "hash")]
+    hash: HashValue,
+}
+
+pub struct ReplayFailedBlockCommand;
+
+impl CommandAction for ReplayFailedBlockCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReplayFailedBlockOpt;
+    type ReturnItem = BlockReplayView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        ctx.state()
+            .client()
+            .debug_replay_failed_block(ctx.opt().hash)
+    }
+}
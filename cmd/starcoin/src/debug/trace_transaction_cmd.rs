@@ -0,0 +1,37 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::TransactionOutputView;
+use structopt::StructOpt;
+
+/// Re-execute a transaction on the state before it, and show the calls, gas, resources and events
+/// of the execution.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "trace_transaction")]
+pub struct TraceTransactionOpt {
+    #[structopt(name = "txn-hash")]
+    txn_hash: HashValue,
+}
+
+pub struct TraceTransactionCommand;
+
+impl CommandAction for TraceTransactionCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TraceTransactionOpt;
+    type ReturnItem = TransactionOutputView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        ctx.state()
+            .client()
+            .debug_trace_transaction(ctx.opt().txn_hash)
+    }
+}
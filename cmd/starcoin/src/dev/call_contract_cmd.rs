@@ -6,8 +6,10 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_rpc_api::types::{
-    AnnotatedMoveValueView, ContractCall, FunctionIdView, TransactionArgumentView, TypeTagView,
+    AnnotatedMoveValueView, ContractCall, FunctionIdView, StateSelector, TransactionArgumentView,
+    TypeTagView,
 };
+use starcoin_types::block::BlockNumber;
 use structopt::StructOpt;
 
 /// Call Contract command
@@ -17,6 +19,8 @@ use structopt::StructOpt;
 ///  dev call --function 0x1::Block::current_block_number
 ///  # 0x1::Account::balance<0x1::STC::STC>(0x726098b70ba8aa2cc172af19af8804)
 ///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804
+///  # 0x1::Block::get_current_block_number() at the state after block 100
+///  dev call --function 0x1::Block::get_current_block_number --block 100
 ///  ```
 #[derive(Debug, StructOpt)]
 #[structopt(name = "call")]
@@ -38,6 +42,10 @@ pub struct CallContractOpt {
         help = "can specify multi arg"
     )]
     args: Option<Vec<TransactionArgumentView>>,
+
+    #[structopt(long = "block", name = "block-number")]
+    /// call the function at the state after the block on main chain, default to the head block.
+    block: Option<BlockNumber>,
 }
 
 pub struct CallContractCommand;
@@ -60,7 +68,10 @@ impl CommandAction for CallContractCommand {
             args: opt.args.clone().unwrap_or_default(),
         };
 
-        let result = ctx.state().client().contract_call(call)?;
+        let result = ctx
+            .state()
+            .client()
+            .contract_call(call, opt.block.map(StateSelector::BlockNumber))?;
         Ok(result)
     }
 }
@@ -0,0 +1,134 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{bail, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_dev::trace::dry_run_with_trace;
+use starcoin_rpc_api::types::{FunctionIdView, GasProfileView};
+use starcoin_rpc_client::RemoteStateReader;
+use starcoin_state_api::AccountStateReader;
+use starcoin_types::transaction::{
+    parse_transaction_argument, DryRunTransaction, RawUserTransaction, TransactionArgument,
+};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::transaction::ScriptFunction;
+use starcoin_vm_types::transaction_argument::convert_txn_args;
+use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Profile the gas used by an executed transaction, or by a script function in dry run.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "gas-profile")]
+pub struct GasProfileOpt {
+    #[structopt(long = "txn-hash", conflicts_with = "script-function")]
+    /// the hash of an executed transaction to profile.
+    txn_hash: Option<HashValue>,
+
+    #[structopt(long = "function", name = "script-function")]
+    /// script function to dry run and profile, example: 0x1::TransferScripts::peer_to_peer
+    script_function: Option<FunctionIdView>,
+
+    #[structopt(short = "s")]
+    /// if `sender` is absent, use default account.
+    sender: Option<AccountAddress>,
+
+    #[structopt(
+    short = "t",
+    long = "type_tag",
+    name = "type-tag",
+    parse(try_from_str = parse_type_tag)
+    )]
+    /// type tags for the script function
+    type_tags: Option<Vec<TypeTag>>,
+
+    #[structopt(long = "arg", name = "transaction-args", parse(try_from_str = parse_transaction_argument))]
+    /// args for the script function.
+    args: Option<Vec<TransactionArgument>>,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to dry run the script function"
+    )]
+    max_gas_amount: u64,
+
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to dry run the script function"
+    )]
+    gas_price: u64,
+
+    #[structopt(long = "folded", parse(from_os_str))]
+    /// export the profile to the file as folded stacks, which flamegraph tools accept.
+    folded: Option<PathBuf>,
+}
+
+pub struct GasProfileCommand;
+
+impl CommandAction for GasProfileCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GasProfileOpt;
+    type ReturnItem = GasProfileView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let profile = match (opt.txn_hash, opt.script_function.clone()) {
+            (Some(txn_hash), _) => client
+                .debug_trace_transaction(txn_hash)?
+                .gas_profile
+                .ok_or_else(|| format_err!("The node does not return the gas profile"))?,
+            (None, Some(script_function)) => {
+                let node_info = client.node_info()?;
+                let sender = ctx.state().get_account_or_default(opt.sender)?;
+                let chain_state_reader = RemoteStateReader::new(client)?;
+                let account_state_reader = AccountStateReader::new(&chain_state_reader);
+                let account_resource = account_state_reader
+                    .get_account_resource(&sender.address)?
+                    .ok_or_else(|| {
+                        format_err!("account of address {} not exists on chain", sender.address)
+                    })?;
+                let script_function = script_function.0;
+                let raw_txn = RawUserTransaction::new_script_function(
+                    sender.address,
+                    account_resource.sequence_number(),
+                    ScriptFunction::new(
+                        script_function.module,
+                        script_function.function,
+                        opt.type_tags.clone().unwrap_or_default(),
+                        convert_txn_args(&opt.args.clone().unwrap_or_default()),
+                    ),
+                    opt.max_gas_amount,
+                    opt.gas_price,
+                    node_info.now_seconds + 3000,
+                    ctx.state().net().chain_id(),
+                );
+                let (_, _, trace) = dry_run_with_trace(
+                    &chain_state_reader,
+                    DryRunTransaction {
+                        raw_txn,
+                        public_key: sender.public_key,
+                    },
+                )?;
+                trace.gas_profile().into()
+            }
+            (None, None) => bail!("One of --txn-hash and --function is required"),
+        };
+        if let Some(folded) = opt.folded.as_ref() {
+            std::fs::write(folded, profile.to_folded_stacks())?;
+        }
+        Ok(profile)
+    }
+}
@@ -5,6 +5,7 @@ pub use call_contract_cmd::*;
 pub use compile_cmd::*;
 pub use deploy_cmd::*;
 pub use derive_account_address_cmd::*;
+pub use gas_profile_cmd::*;
 pub use get_coin_cmd::*;
 pub use package_cmd::*;
 pub use sign_txn_helper::sign_txn_with_account_by_rpc_client;
@@ -21,6 +22,7 @@ mod call_contract_cmd;
 mod compile_cmd;
 mod deploy_cmd;
 mod derive_account_address_cmd;
+mod gas_profile_cmd;
 mod get_coin_cmd;
 mod package_cmd;
 pub(crate) mod sign_txn_helper;
@@ -3,7 +3,9 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use futures::{StreamExt, TryStream, TryStreamExt};
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::pubsub::{EventFilter, TransactionStatusFilter, TypeTagPatternView};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
 use structopt::StructOpt;
 use tokio::io::AsyncBufReadExt;
@@ -28,6 +30,30 @@ pub struct SubscribeEventOpt {
         multiple = true
     )]
     event_key: Option<Vec<EventKey>>,
+    #[structopt(
+        long = "type-pattern",
+        name = "type_pattern",
+        help = "event type pattern, eg: 0x1::Account::DepositEvent, 0x1::Account::*, 0x1::Token::Token<*>",
+        multiple = true
+    )]
+    type_patterns: Option<Vec<TypeTagPatternView>>,
+    #[structopt(
+        long = "module-addr",
+        name = "module_addr",
+        help = "address of the module defining the event type",
+        multiple = true
+    )]
+    module_addrs: Option<Vec<AccountAddress>>,
+    #[structopt(
+        long = "decode",
+        help = "return the events with the decoded event data"
+    )]
+    decode: bool,
+    #[structopt(
+        long = "include-removed",
+        help = "notify the events of the blocks retracted by chain reorg again with removed: true"
+    )]
+    include_removed: bool,
     #[structopt(
         short = "l",
         long = "limit",
@@ -51,14 +77,30 @@ impl CommandAction for SubscribeEventCommand {
             from_block: ctx.opt().from_block,
             to_block: ctx.opt().to_block,
             event_keys: ctx.opt().event_key.clone().unwrap_or_default(),
+            type_tags: vec![],
+            addrs: vec![],
             limit: ctx.opt().limit,
+            cursor: None,
+            type_patterns: ctx.opt().type_patterns.clone().unwrap_or_default(),
+            module_addrs: ctx.opt().module_addrs.clone().unwrap_or_default(),
+            field_predicates: vec![],
+            decode: ctx.opt().decode,
+            include_removed: ctx.opt().include_removed,
         };
 
-        let event_stream = ctx.state().client().subscribe_events(filter)?;
-        println!("Subscribe successful, Press `q` and Enter to quit");
-        blocking_display_notification(event_stream, |evt| {
-            serde_json::to_string(&evt).expect("should never fail")
-        });
+        if filter.decode {
+            let event_stream = ctx.state().client().subscribe_decoded_events(filter)?;
+            println!("Subscribe successful, Press `q` and Enter to quit");
+            blocking_display_notification(event_stream, |evt| {
+                serde_json::to_string(&evt).expect("should never fail")
+            });
+        } else {
+            let event_stream = ctx.state().client().subscribe_events(filter)?;
+            println!("Subscribe successful, Press `q` and Enter to quit");
+            blocking_display_notification(event_stream, |evt| {
+                serde_json::to_string(&evt).expect("should never fail")
+            });
+        }
 
         Ok(())
     }
@@ -107,6 +149,69 @@ impl CommandAction for SubscribeNewTxnCommand {
     }
 }
 
+#[derive(Debug, StructOpt)]
+#[structopt(name = "chain_reorg")]
+pub struct SubscribeChainReorgOpt {}
+pub struct SubscribeChainReorgCommand;
+impl CommandAction for SubscribeChainReorgCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeChainReorgOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let event_stream = ctx.state().client().subscribe_chain_reorg()?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "txn_status")]
+pub struct SubscribeTxnStatusOpt {
+    #[structopt(
+        long = "txn-hash",
+        name = "txn_hash",
+        help = "hash of the transaction to watch",
+        multiple = true
+    )]
+    txn_hashes: Option<Vec<HashValue>>,
+    #[structopt(
+        short = "s",
+        long = "sender",
+        help = "watch all transactions of the sender"
+    )]
+    sender: Option<AccountAddress>,
+}
+pub struct SubscribeTxnStatusCommand;
+impl CommandAction for SubscribeTxnStatusCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeTxnStatusOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let filter = TransactionStatusFilter {
+            txn_hashes: opt.txn_hashes.clone().unwrap_or_default(),
+            sender: opt.sender,
+        };
+        let event_stream = ctx.state().client().subscribe_txn_status(filter)?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
 fn blocking_display_notification<T, F>(
     mut event_stream: impl TryStream<Ok = T, Error = anyhow::Error> + Unpin,
     display: F,
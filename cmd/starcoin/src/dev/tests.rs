@@ -364,7 +364,7 @@ fn test_upgrade_module() {
         type_args: Vec::new(),
         args: Vec::new(),
     };
-    let result = cli_state.client().contract_call(call).unwrap();
+    let result = cli_state.client().contract_call(call, None).unwrap();
     assert!(!result.is_empty());
     info!("result: {:?}", result);
     if let AnnotatedMoveValueView::Bool(flag) = result.get(0).unwrap() {
@@ -5,6 +5,7 @@ pub mod account;
 pub mod chain;
 pub mod cli_state;
 pub mod contract;
+pub mod db;
 pub mod debug;
 pub mod dev;
 pub mod helper;
@@ -14,6 +15,7 @@ pub mod state;
 mod txpool;
 pub mod view;
 
+use crate::db::{DbCliState, DB_COMMAND_NAME};
 use crate::debug::{GenBlockCommand, SleepCommand, TxPoolStatusCommand};
 pub use cli_state::CliState;
 use scmd::{CmdContext, Command};
@@ -27,6 +29,7 @@ pub fn add_command(
         .command(
             Command::with_name("account")
                 .subcommand(account::CreateCommand)
+                .subcommand(account::RecoverCommand)
                 .subcommand(account::ShowCommand)
                 .subcommand(account::TransferCommand)
                 .subcommand(account::AcceptTokenCommand)
@@ -80,6 +83,11 @@ pub fn add_command(
                     .subcommand(node::network::KnownPeersCommand)
                     .subcommand(node::network::GetAddressCommand)
                     .subcommand(node::network::AddPeerCommand)
+                    .subcommand(node::network::AddReservedPeerCommand)
+                    .subcommand(node::network::RemoveReservedPeerCommand)
+                    .subcommand(node::network::BanPeerCommand)
+                    .subcommand(node::network::UnbanPeerCommand)
+                    .subcommand(node::network::ListBannedCommand)
                     .subcommand(node::network::CallPeerCommand)
             ),
         )
@@ -88,6 +96,7 @@ pub fn add_command(
                 .subcommand(chain::InfoCommand)
                 .subcommand(chain::GetBlockByNumberCommand)
                 .subcommand(chain::ListBlockCommand)
+                .subcommand(chain::ListTxnCommand)
                 .subcommand(chain::GetTransactionCommand)
                 .subcommand(chain::GetTxnByBlockCommand)
                 .subcommand(chain::GetTransactionInfoCommand)
@@ -97,6 +106,7 @@ pub fn add_command(
                 .subcommand(chain::GetEpochInfoByNumberCommand)
                 .subcommand(chain::GetGlobalTimeByNumberCommand)
                 .subcommand(chain::TPSCommand)
+                .subcommand(chain::FailedBlocksCommand)
                 .subcommand(
                     Command::with_name("uncle")
                         .subcommand(chain::uncle::UnclePathCommand)
@@ -113,7 +123,8 @@ pub fn add_command(
                     Command::with_name("verify")
                         .subcommand(chain::VerifyBlockCommand)
                         .subcommand(chain::VerifyEpochCommand)
-                        .subcommand(chain::VerifyNodeCommand),
+                        .subcommand(chain::VerifyNodeCommand)
+                        .subcommand(chain::VerifyTxnCommand),
                 ),
         )
         .command(
@@ -137,11 +148,14 @@ pub fn add_command(
                 .subcommand(dev::UpgradeVMConfigProposalCommand)
                 .subcommand(dev::PackageCmd)
                 .subcommand(dev::CallContractCommand)
+                .subcommand(dev::GasProfileCommand)
                 .subcommand(
                     Command::with_name("subscribe")
                         .subcommand(dev::SubscribeBlockCommand)
                         .subcommand(dev::SubscribeEventCommand)
-                        .subcommand(dev::SubscribeNewTxnCommand),
+                        .subcommand(dev::SubscribeNewTxnCommand)
+                        .subcommand(dev::SubscribeChainReorgCommand)
+                        .subcommand(dev::SubscribeTxnStatusCommand),
                 ),
         )
         .command(Command::with_name("contract").subcommand(contract::GetContractDataCommand))
@@ -158,6 +172,21 @@ pub fn add_command(
                 .subcommand(TxPoolStatusCommand)
                 .subcommand(SleepCommand)
                 .subcommand(GenBlockCommand)
-                .subcommand(debug::MoveExplain),
+                .subcommand(debug::MoveExplain)
+                .subcommand(debug::ReplayFailedBlockCommand)
+                .subcommand(debug::TraceTransactionCommand),
         )
 }
+
+/// Add the offline `db` commands, they run in a separate context without a node.
+pub fn add_db_command(
+    context: CmdContext<DbCliState, StarcoinOpt>,
+) -> CmdContext<DbCliState, StarcoinOpt> {
+    context.command(
+        Command::with_name(DB_COMMAND_NAME)
+            .subcommand(db::ExportBlocksCommand)
+            .subcommand(db::ImportBlocksCommand)
+            .subcommand(db::ExportStateCommand)
+            .subcommand(db::RestoreStateCommand),
+    )
+}
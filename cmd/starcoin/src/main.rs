@@ -19,6 +19,15 @@ static EXIT_CODE_NEED_HELP: i32 = 120;
 
 fn run() -> Result<()> {
     let logger_handle = starcoin_logger::init();
+    if db::is_db_command(std::env::args_os()) {
+        // The db commands open the node's storage directly, the node should not be started.
+        return add_db_command(CmdContext::<db::DbCliState, StarcoinOpt>::with_state(
+            CRATE_VERSION,
+            Some(APP_VERSION.as_str()),
+            db::DbCliState,
+        ))
+        .exec();
+    }
     let context = CmdContext::<CliState, StarcoinOpt>::with_default_action(
         CRATE_VERSION,
         Some(APP_VERSION.as_str()),
@@ -65,7 +74,18 @@ fn run() -> Result<()> {
                 }
                 Connect::WebSocket(address) => {
                     info!("Try to connect node by websocket: {:?}", address);
-                    let client = RpcClient::connect_websocket(address)?;
+                    let client = match opt.rpc_token.as_ref() {
+                        Some(token) => RpcClient::connect_websocket_with_token(address, token)?,
+                        None => RpcClient::connect_websocket(address)?,
+                    };
+                    (client, None)
+                }
+                Connect::Http(address) => {
+                    info!("Try to connect node by http: {:?}", address);
+                    let client = match opt.rpc_token.as_ref() {
+                        Some(token) => RpcClient::connect_http_with_token(address, token)?,
+                        None => RpcClient::connect_http(address)?,
+                    };
                     (client, None)
                 }
             };
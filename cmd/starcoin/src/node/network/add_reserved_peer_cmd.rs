@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "add_reserved_peer")]
+///Add a reserved peer, which is always kept connected and never evicted by reputation
+pub struct AddReservedPeerOpt {
+    #[structopt(name = "peer")]
+    /// format: multiaddr/p2p/peer_id
+    peer: String,
+}
+
+pub struct AddReservedPeerCommand;
+
+impl CommandAction for AddReservedPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = AddReservedPeerOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_add_reserved_peer(ctx.opt().peer.clone())
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "ban_peer")]
+///Ban a peer, the banned peer is disconnected and refused until the ban expires
+pub struct BanPeerOpt {
+    #[structopt(name = "peer_id")]
+    peer_id: String,
+
+    #[structopt(long = "duration")]
+    /// ban duration in seconds, default to the ban duration of the node config.
+    duration: Option<u64>,
+
+    #[structopt(long = "reason")]
+    reason: Option<String>,
+}
+
+pub struct BanPeerCommand;
+
+impl CommandAction for BanPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = BanPeerOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.network_ban_peer(opt.peer_id.clone(), opt.duration, opt.reason.clone())
+    }
+}
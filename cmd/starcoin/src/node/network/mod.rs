@@ -2,13 +2,23 @@
 // Copyright (c) The Starcoin Core Contributors
 
 mod add_peer_cmd;
+mod add_reserved_peer_cmd;
+mod ban_peer_cmd;
 mod call_peer_cmd;
 mod get_address_cmd;
 mod known_peers_cmd;
+mod list_banned_cmd;
+mod remove_reserved_peer_cmd;
 mod state_cmd;
+mod unban_peer_cmd;
 
 pub use add_peer_cmd::*;
+pub use add_reserved_peer_cmd::*;
+pub use ban_peer_cmd::*;
 pub use call_peer_cmd::*;
 pub use get_address_cmd::*;
 pub use known_peers_cmd::*;
+pub use list_banned_cmd::*;
+pub use remove_reserved_peer_cmd::*;
 pub use state_cmd::*;
+pub use unban_peer_cmd::*;
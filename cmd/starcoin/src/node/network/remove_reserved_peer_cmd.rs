@@ -0,0 +1,33 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "remove_reserved_peer")]
+///Remove a reserved peer
+pub struct RemoveReservedPeerOpt {
+    #[structopt(name = "peer_id")]
+    peer_id: String,
+}
+
+pub struct RemoveReservedPeerCommand;
+
+impl CommandAction for RemoveReservedPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RemoveReservedPeerOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_remove_reserved_peer(ctx.opt().peer_id.clone())
+    }
+}
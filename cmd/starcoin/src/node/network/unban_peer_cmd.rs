@@ -0,0 +1,33 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "unban_peer")]
+///Lift the ban of a peer, return whether the peer was banned
+pub struct UnbanPeerOpt {
+    #[structopt(name = "peer_id")]
+    peer_id: String,
+}
+
+pub struct UnbanPeerCommand;
+
+impl CommandAction for UnbanPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UnbanPeerOpt;
+    type ReturnItem = bool;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_unban_peer(ctx.opt().peer_id.clone())
+    }
+}
@@ -50,10 +50,10 @@ impl CommandAction for GetCommand {
             None => account_struct_tag(),
         };
         let state = client
-            .state_get(AccessPath::resource_access_path(
-                account_addr,
-                struct_tag.clone(),
-            ))?
+            .state_get(
+                AccessPath::resource_access_path(account_addr, struct_tag.clone()),
+                None,
+            )?
             .ok_or_else(|| format_err!("Account with address {} state not exist.", account_addr))?;
         let chain_state_reader = RemoteStateReader::new(client)?;
         let viewer = MoveValueAnnotator::new(&chain_state_reader);
@@ -3,11 +3,11 @@
 
 use crate::accumulator_info::AccumulatorInfo;
 use crate::node_index::NodeIndex;
-use crate::proof::AccumulatorProof;
 use crate::tree::AccumulatorTree;
 use anyhow::{ensure, format_err, Result};
 pub use node::AccumulatorNode;
 use parking_lot::Mutex;
+pub use proof::AccumulatorProof;
 use starcoin_crypto::HashValue;
 use std::sync::Arc;
 pub use tree_store::AccumulatorTreeStore;
@@ -7,21 +7,71 @@ use std::io::{Cursor, Read, Write};
 pub const PBKDF2_DEFAULT_ITERATIONS: usize = 1000;
 pub const PBKDF2_SALT_SIZE: usize = 32;
 pub const AES_NONCE_SIZE: usize = 12;
+/// Memory cost of argon2 in KiB.
+pub const ARGON2_DEFAULT_MEM_COST: u32 = 19 * 1024;
+pub const ARGON2_DEFAULT_TIME_COST: u32 = 2;
+pub const ARGON2_DEFAULT_LANES: u32 = 1;
+
+/// Prefix of the versioned format. The legacy format starts with the pbkdf2 iterations
+/// in big endian, which never reach this value.
+const VERSIONED_MAGIC: [u8; 4] = *b"STCK";
+const KDF_PBKDF2: u8 = 0;
+const KDF_ARGON2ID: u8 = 1;
+
+/// Version of the encrypted data format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FormatVersion {
+    /// No version tag, key is always derived by pbkdf2.
+    Legacy,
+    V1,
+}
+
+impl FormatVersion {
+    pub const LATEST: FormatVersion = FormatVersion::V1;
+
+    fn to_u8(self) -> u8 {
+        match self {
+            FormatVersion::Legacy => 0,
+            FormatVersion::V1 => 1,
+        }
+    }
+}
+
+/// The function used to derive the encryption key from the secret.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyDerivation {
+    Pbkdf2 {
+        iterations: u32,
+    },
+    /// Memory-hard argon2id, `mem_cost` is in KiB.
+    Argon2id {
+        mem_cost: u32,
+        time_cost: u32,
+        lanes: u32,
+    },
+}
+
+impl Default for KeyDerivation {
+    fn default() -> Self {
+        KeyDerivation::Argon2id {
+            mem_cost: ARGON2_DEFAULT_MEM_COST,
+            time_cost: ARGON2_DEFAULT_TIME_COST,
+            lanes: ARGON2_DEFAULT_LANES,
+        }
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct KeyDerivationParams {
-    pbkdf2_iterations: u32,
-    pbkdf2_salt: [u8; PBKDF2_SALT_SIZE],
+    kdf: KeyDerivation,
+    salt: [u8; PBKDF2_SALT_SIZE],
 }
 
 impl KeyDerivationParams {
-    pub fn generate() -> Self {
+    pub fn generate(kdf: KeyDerivation) -> Self {
         let mut salt = [0u8; PBKDF2_SALT_SIZE];
         rand::thread_rng().fill_bytes(&mut salt);
-        Self {
-            pbkdf2_iterations: PBKDF2_DEFAULT_ITERATIONS as u32,
-            pbkdf2_salt: salt,
-        }
+        Self { kdf, salt }
     }
 }
 
@@ -39,60 +89,136 @@ impl EncryptionParams {
     }
 }
 
-const META_LEN: usize = 4usize + PBKDF2_SALT_SIZE + AES_NONCE_SIZE;
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Meta {
+    version: FormatVersion,
+
     key_derive_params: KeyDerivationParams,
 
     encryption_params: EncryptionParams,
 }
 impl Meta {
-    pub fn generate() -> Self {
+    pub fn generate(kdf: KeyDerivation) -> Self {
         Self {
-            key_derive_params: KeyDerivationParams::generate(),
+            version: FormatVersion::LATEST,
+            key_derive_params: KeyDerivationParams::generate(kdf),
             encryption_params: EncryptionParams::generate(),
         }
     }
 
     pub fn encode(&self) -> Vec<u8> {
-        let mut buf = std::io::Cursor::new(Vec::with_capacity(META_LEN));
-        buf.write_u32::<byteorder::BigEndian>(self.key_derive_params.pbkdf2_iterations)
-            .expect("should never fail");
-        buf.write_all(&self.key_derive_params.pbkdf2_salt)
+        let mut buf = std::io::Cursor::new(Vec::new());
+        match self.version {
+            FormatVersion::Legacy => {
+                let iterations = match self.key_derive_params.kdf {
+                    KeyDerivation::Pbkdf2 { iterations } => iterations,
+                    kdf => unreachable!("legacy format does not support {:?}", kdf),
+                };
+                buf.write_u32::<byteorder::BigEndian>(iterations)
+                    .expect("should never fail");
+            }
+            version => {
+                buf.write_all(&VERSIONED_MAGIC).expect("should never fail");
+                buf.write_u8(version.to_u8()).expect("should never fail");
+                match self.key_derive_params.kdf {
+                    KeyDerivation::Pbkdf2 { iterations } => {
+                        buf.write_u8(KDF_PBKDF2).expect("should never fail");
+                        buf.write_u32::<byteorder::BigEndian>(iterations)
+                            .expect("should never fail");
+                    }
+                    KeyDerivation::Argon2id {
+                        mem_cost,
+                        time_cost,
+                        lanes,
+                    } => {
+                        buf.write_u8(KDF_ARGON2ID).expect("should never fail");
+                        for param in &[mem_cost, time_cost, lanes] {
+                            buf.write_u32::<byteorder::BigEndian>(*param)
+                                .expect("should never fail");
+                        }
+                    }
+                }
+            }
+        }
+        buf.write_all(&self.key_derive_params.salt)
             .expect("should never fail");
         buf.write_all(&self.encryption_params.nonce)
             .expect("should never fail");
         buf.into_inner()
     }
 
-    pub fn decode(buf: &[u8]) -> Result<Self> {
+    /// Decode the meta from the head of `buf`, return the meta and its length.
+    pub fn decode(buf: &[u8]) -> Result<(Self, usize)> {
         let mut buf = Cursor::new(buf);
-        let iterations = buf.read_u32::<byteorder::BigEndian>()?;
+        let (version, kdf) = if buf.get_ref().starts_with(&VERSIONED_MAGIC) {
+            buf.set_position(VERSIONED_MAGIC.len() as u64);
+            let version = match buf.read_u8()? {
+                1 => FormatVersion::V1,
+                v => bail!("unsupported encrypted data version {}", v),
+            };
+            let kdf = match buf.read_u8()? {
+                KDF_PBKDF2 => KeyDerivation::Pbkdf2 {
+                    iterations: buf.read_u32::<byteorder::BigEndian>()?,
+                },
+                KDF_ARGON2ID => KeyDerivation::Argon2id {
+                    mem_cost: buf.read_u32::<byteorder::BigEndian>()?,
+                    time_cost: buf.read_u32::<byteorder::BigEndian>()?,
+                    lanes: buf.read_u32::<byteorder::BigEndian>()?,
+                },
+                kdf => bail!("unsupported key derivation function {}", kdf),
+            };
+            (version, kdf)
+        } else {
+            let iterations = buf.read_u32::<byteorder::BigEndian>()?;
+            (FormatVersion::Legacy, KeyDerivation::Pbkdf2 { iterations })
+        };
         let mut salt = [0u8; PBKDF2_SALT_SIZE];
         buf.read_exact(&mut salt)?;
         let mut nonce = [0u8; AES_NONCE_SIZE];
         buf.read_exact(&mut nonce)?;
-        Ok(Self {
-            key_derive_params: KeyDerivationParams {
-                pbkdf2_salt: salt,
-                pbkdf2_iterations: iterations,
+        Ok((
+            Self {
+                version,
+                key_derive_params: KeyDerivationParams { kdf, salt },
+                encryption_params: EncryptionParams { nonce },
             },
-            encryption_params: EncryptionParams { nonce },
-        })
+            buf.position() as usize,
+        ))
     }
 }
 
-fn derive_key(derivation_param: &KeyDerivationParams, secret: &[u8]) -> [u8; 32] {
+fn derive_key(derivation_param: &KeyDerivationParams, secret: &[u8]) -> Result<[u8; 32]> {
     // 256-bit derived key
     let mut dk = [0u8; 32];
     // use secret to derive a key to encrypt plaintext
-    pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(
-        secret,
-        &derivation_param.pbkdf2_salt,
-        derivation_param.pbkdf2_iterations as usize,
-        &mut dk,
-    );
-    dk
+    match derivation_param.kdf {
+        KeyDerivation::Pbkdf2 { iterations } => {
+            pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(
+                secret,
+                &derivation_param.salt,
+                iterations as usize,
+                &mut dk,
+            );
+        }
+        KeyDerivation::Argon2id {
+            mem_cost,
+            time_cost,
+            lanes,
+        } => {
+            let config = argon2::Config {
+                variant: argon2::Variant::Argon2id,
+                mem_cost,
+                time_cost,
+                lanes,
+                hash_length: dk.len() as u32,
+                ..argon2::Config::default()
+            };
+            let hash = argon2::hash_raw(secret, &derivation_param.salt, &config)
+                .map_err(|e| format_err!("argon2 key derivation error: {}", e))?;
+            dk.copy_from_slice(hash.as_slice());
+        }
+    }
+    Ok(dk)
 }
 
 fn aes_encrypt(encryption_param: &EncryptionParams, key: [u8; 32], plain: &[u8]) -> Vec<u8> {
@@ -117,26 +243,74 @@ fn aes_decrypt(
     }
 }
 
-pub fn encrypt(secret: &[u8], plain: &[u8]) -> Vec<u8> {
-    let meta = Meta::generate();
+/// Encrypt `plain` in the latest format, with the key derived from `secret` by `kdf`.
+pub fn encrypt(kdf: KeyDerivation, secret: &[u8], plain: &[u8]) -> Result<Vec<u8>> {
+    let meta = Meta::generate(kdf);
     // 256-bit derived key
-    let dk = derive_key(&meta.key_derive_params, secret);
+    let dk = derive_key(&meta.key_derive_params, secret)?;
     let mut ciphertext = aes_encrypt(&meta.encryption_params, dk, plain);
     let mut result = meta.encode();
     result.append(&mut ciphertext);
-    result
+    Ok(result)
 }
 
+/// Decrypt data of any supported format.
 pub fn decrypt(secret: &[u8], encrypted: &[u8]) -> Result<Vec<u8>> {
-    if encrypted.len() <= META_LEN {
+    let (meta, meta_len) = Meta::decode(encrypted)?;
+    if encrypted.len() <= meta_len {
         bail!("invalid encrypted data");
     }
-    let meta = Meta::decode(&encrypted[0..META_LEN])?;
-    let crypted = &encrypted[META_LEN..];
+    let crypted = &encrypted[meta_len..];
 
-    let dk = derive_key(&meta.key_derive_params, secret);
+    let dk = derive_key(&meta.key_derive_params, secret)?;
     aes_decrypt(&meta.encryption_params, dk, crypted)
 }
 
+/// The parts of encrypted data, for formats which store the parameters separately,
+/// like the json keystore.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EncryptedParts {
+    pub kdf: KeyDerivation,
+    pub salt: [u8; PBKDF2_SALT_SIZE],
+    pub nonce: [u8; AES_NONCE_SIZE],
+    pub ciphertext: Vec<u8>,
+}
+
+/// Split the encrypted data of any supported format into parts.
+pub fn split(encrypted: &[u8]) -> Result<EncryptedParts> {
+    let (meta, meta_len) = Meta::decode(encrypted)?;
+    if encrypted.len() <= meta_len {
+        bail!("invalid encrypted data");
+    }
+    Ok(EncryptedParts {
+        kdf: meta.key_derive_params.kdf,
+        salt: meta.key_derive_params.salt,
+        nonce: meta.encryption_params.nonce,
+        ciphertext: encrypted[meta_len..].to_vec(),
+    })
+}
+
+/// Join the parts into encrypted data of the latest format, the reverse of `split`.
+pub fn join(parts: &EncryptedParts) -> Vec<u8> {
+    let meta = Meta {
+        version: FormatVersion::LATEST,
+        key_derive_params: KeyDerivationParams {
+            kdf: parts.kdf,
+            salt: parts.salt,
+        },
+        encryption_params: EncryptionParams { nonce: parts.nonce },
+    };
+    let mut result = meta.encode();
+    result.extend_from_slice(&parts.ciphertext);
+    result
+}
+
+/// Get the format version and the key derivation function of the encrypted data,
+/// to check whether it should be re-encrypted.
+pub fn inspect(encrypted: &[u8]) -> Result<(FormatVersion, KeyDerivation)> {
+    let (meta, _) = Meta::decode(encrypted)?;
+    Ok((meta.version, meta.key_derive_params.kdf))
+}
+
 #[cfg(test)]
 mod tests;
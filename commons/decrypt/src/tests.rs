@@ -1,12 +1,76 @@
-use crate::{decrypt, encrypt};
+use crate::{
+    aes_encrypt, decrypt, derive_key, encrypt, inspect, join, split, FormatVersion, KeyDerivation,
+    Meta, PBKDF2_DEFAULT_ITERATIONS,
+};
+
+/// Encrypt in the legacy format, like the old version does.
+fn legacy_encrypt(secret: &[u8], plain: &[u8]) -> Vec<u8> {
+    let mut meta = Meta::generate(KeyDerivation::Pbkdf2 {
+        iterations: PBKDF2_DEFAULT_ITERATIONS as u32,
+    });
+    meta.version = FormatVersion::Legacy;
+    let dk = derive_key(&meta.key_derive_params, secret).unwrap();
+    let mut result = meta.encode();
+    result.append(&mut aes_encrypt(&meta.encryption_params, dk, plain));
+    result
+}
 
 #[test]
 fn test_encryption() {
     let secret = "hello";
     let plain = "world";
-    let encrypted = encrypt(secret.as_bytes(), plain.as_bytes());
-    assert_ne!(encrypted.as_slice(), plain.as_bytes());
+    for kdf in vec![
+        KeyDerivation::default(),
+        KeyDerivation::Pbkdf2 { iterations: 10 },
+        KeyDerivation::Argon2id {
+            mem_cost: 64,
+            time_cost: 1,
+            lanes: 2,
+        },
+    ] {
+        let encrypted = encrypt(kdf, secret.as_bytes(), plain.as_bytes()).unwrap();
+        assert_ne!(encrypted.as_slice(), plain.as_bytes());
+        assert_eq!(
+            inspect(encrypted.as_slice()).unwrap(),
+            (FormatVersion::LATEST, kdf)
+        );
+
+        let decrypted = decrypt(secret.as_bytes(), encrypted.as_slice()).unwrap();
+        assert_eq!(decrypted.as_slice(), plain.as_bytes());
+        assert!(decrypt("bad".as_bytes(), encrypted.as_slice()).is_err());
+    }
+}
 
+#[test]
+fn test_decrypt_legacy_format() {
+    let secret = "hello";
+    let plain = "world";
+    let encrypted = legacy_encrypt(secret.as_bytes(), plain.as_bytes());
+    assert_eq!(
+        inspect(encrypted.as_slice()).unwrap(),
+        (
+            FormatVersion::Legacy,
+            KeyDerivation::Pbkdf2 {
+                iterations: PBKDF2_DEFAULT_ITERATIONS as u32
+            }
+        )
+    );
     let decrypted = decrypt(secret.as_bytes(), encrypted.as_slice()).unwrap();
     assert_eq!(decrypted.as_slice(), plain.as_bytes());
 }
+
+#[test]
+fn test_split_and_join() {
+    let kdf = KeyDerivation::Pbkdf2 { iterations: 10 };
+    let encrypted = encrypt(kdf, b"hello", b"world").unwrap();
+    let parts = split(&encrypted).unwrap();
+    assert_eq!(parts.kdf, kdf);
+    assert_eq!(join(&parts), encrypted);
+    assert_eq!(decrypt(b"hello", &join(&parts)).unwrap(), b"world".to_vec());
+
+    // legacy data is joined to the latest format.
+    let legacy = legacy_encrypt(b"hello", b"world");
+    let joined = join(&split(&legacy).unwrap());
+    assert_eq!(inspect(&joined).unwrap().0, FormatVersion::LATEST);
+    assert_eq!(decrypt(b"hello", &joined).unwrap(), b"world".to_vec());
+}
@@ -5,12 +5,57 @@ use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use starcoin_decrypt::{
+    KeyDerivation, ARGON2_DEFAULT_LANES, ARGON2_DEFAULT_MEM_COST, ARGON2_DEFAULT_TIME_COST,
+};
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
 static DEFAULT_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("account_vaults"));
 
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 100_000;
+/// Argon2 memory cost in KiB for test network, to keep tests fast.
+const ARGON2_MEM_COST_FOR_TEST: u32 = 64;
+
+/// The key derivation function used to encrypt the account private keys.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VaultKdf {
+    Pbkdf2,
+    /// Memory-hard argon2id.
+    Argon2id,
+}
+
+impl Default for VaultKdf {
+    fn default() -> Self {
+        VaultKdf::Argon2id
+    }
+}
+
+impl std::fmt::Display for VaultKdf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Pbkdf2 => "pbkdf2",
+            Self::Argon2id => "argon2id",
+        };
+        write!(f, "{}", display)
+    }
+}
+
+impl FromStr for VaultKdf {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbkdf2" => Ok(Self::Pbkdf2),
+            "argon2id" => Ok(Self::Argon2id),
+            other => Err(format!("Unknown vault kdf: {}", other)),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct AccountVaultConfig {
@@ -20,6 +65,31 @@ pub struct AccountVaultConfig {
     /// Default: account_vaults in data_dir
     dir: Option<PathBuf>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-kdf")]
+    /// Key derivation function to encrypt the account private keys, pbkdf2 or argon2id.
+    /// Keys encrypted by other params are re-encrypted on the next unlock.
+    /// Default: argon2id
+    kdf: Option<VaultKdf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-pbkdf2-iterations")]
+    /// Iterations of pbkdf2.
+    /// Default: 100000
+    pbkdf2_iterations: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-argon2-mem-cost")]
+    /// Memory cost of argon2id in KiB.
+    /// Default: 19456
+    argon2_mem_cost: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-argon2-time-cost")]
+    /// Time cost (passes) of argon2id.
+    /// Default: 2
+    argon2_time_cost: Option<u32>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -38,6 +108,29 @@ impl AccountVaultConfig {
             self.base().data_dir().join(path)
         }
     }
+
+    pub fn kdf(&self) -> VaultKdf {
+        self.kdf.unwrap_or_default()
+    }
+
+    pub fn key_derivation(&self) -> KeyDerivation {
+        match self.kdf() {
+            VaultKdf::Pbkdf2 => KeyDerivation::Pbkdf2 {
+                iterations: self.pbkdf2_iterations.unwrap_or(DEFAULT_PBKDF2_ITERATIONS),
+            },
+            VaultKdf::Argon2id => KeyDerivation::Argon2id {
+                mem_cost: self.argon2_mem_cost.unwrap_or_else(|| {
+                    if self.base().net().is_test() {
+                        ARGON2_MEM_COST_FOR_TEST
+                    } else {
+                        ARGON2_DEFAULT_MEM_COST
+                    }
+                }),
+                time_cost: self.argon2_time_cost.unwrap_or(ARGON2_DEFAULT_TIME_COST),
+                lanes: ARGON2_DEFAULT_LANES,
+            },
+        }
+    }
 }
 
 impl ConfigModule for AccountVaultConfig {
@@ -46,6 +139,18 @@ impl ConfigModule for AccountVaultConfig {
         if opt.vault.dir.is_some() {
             self.dir = opt.vault.dir.clone();
         }
+        if opt.vault.kdf.is_some() {
+            self.kdf = opt.vault.kdf;
+        }
+        if opt.vault.pbkdf2_iterations.is_some() {
+            self.pbkdf2_iterations = opt.vault.pbkdf2_iterations;
+        }
+        if opt.vault.argon2_mem_cost.is_some() {
+            self.argon2_mem_cost = opt.vault.argon2_mem_cost;
+        }
+        if opt.vault.argon2_time_cost.is_some() {
+            self.argon2_time_cost = opt.vault.argon2_time_cost;
+        }
         Ok(())
     }
 }
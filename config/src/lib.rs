@@ -43,6 +43,7 @@ mod tests;
 mod txpool_config;
 
 use crate::stratum_config::StratumConfig;
+pub use account_vault_config::VaultKdf;
 pub use api_config::{Api, ApiSet};
 pub use api_quota::{ApiQuotaConfig, QuotaDuration};
 pub use available_port::{
@@ -60,13 +61,16 @@ pub use metrics_config::MetricsConfig;
 pub use miner_config::{MinerClientConfig, MinerConfig};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
 pub use rpc_config::{
-    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
-    WsConfiguration,
+    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcAuthConfiguration, RpcAuthToken,
+    RpcConfig, TcpConfiguration, WsConfiguration,
 };
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_vm_types::time::{MockTimeService, RealTimeService, TimeService};
-pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
-pub use txpool_config::TxPoolConfig;
+pub use storage_config::{
+    RocksdbConfig, StatePruneMode, StorageConfig, DEFAULT_CACHE_SIZE, DEFAULT_STATE_PRUNE_WINDOW,
+};
+pub use sync_config::SyncMode;
+pub use txpool_config::{TxPoolConfig, TxPoolJournalMode};
 
 pub static CRATE_VERSION: &str = crate_version!();
 pub static GIT_VERSION: &str = git_version!(
@@ -135,6 +139,8 @@ pub enum Connect {
     IPC(Option<PathBuf>),
     /// Connect by json rpc address.
     WebSocket(String),
+    /// Connect by http json rpc address, the pubsub commands are not supported.
+    Http(String),
 }
 
 impl Default for Connect {
@@ -152,6 +158,8 @@ impl FromStr for Connect {
         }
         if s.starts_with("ws://") || s.starts_with("wss://") {
             Ok(Connect::WebSocket(s.to_string()))
+        } else if s.starts_with("http://") || s.starts_with("https://") {
+            Ok(Connect::Http(s.to_string()))
         } else {
             Ok(Connect::IPC(Some(PathBuf::from_str(s)?)))
         }
@@ -174,6 +182,11 @@ pub struct StarcoinOpt {
     /// Connect and attach to a node
     pub connect: Option<Connect>,
 
+    #[serde(skip)]
+    #[structopt(long = "rpc-token")]
+    /// Bearer token for connect to a node which enabled rpc auth
+    pub rpc_token: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "data-dir", short = "d", parse(from_os_str))]
     /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
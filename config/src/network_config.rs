@@ -27,10 +27,12 @@ use std::num::NonZeroU32;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
 
 pub static DEFAULT_NETWORK_PORT: u16 = 9840;
 static NETWORK_KEY_FILE: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("network_key"));
+static BANNED_PEERS_FILE_NAME: &str = "banned_peers.json";
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct NetworkRpcQuotaConfiguration {
@@ -186,6 +188,18 @@ pub struct NetworkConfig {
     /// P2P network seed, multi seed should use ',' as delimiter.
     pub seeds: Seeds,
 
+    #[serde(skip_serializing_if = "Seeds::is_empty")]
+    #[serde(default)]
+    #[structopt(long = "reserved-peer", default_value = "")]
+    /// Reserved peers, always kept connected and never evicted by reputation,
+    /// multi peer should use ',' as delimiter.
+    pub reserved_peers: Seeds,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "reserved-only")]
+    /// Only connect to and accept the reserved peers. Default false.
+    pub reserved_only: Option<bool>,
+
     /// Enable peer discovery on local networks.
     /// By default this option is `false`. only support cli option.
     #[serde(skip)]
@@ -227,6 +241,11 @@ pub struct NetworkConfig {
     /// p2p network listen address, Default is /ip4/0.0.0.0/tcp/9840
     listen: Option<Multiaddr>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long)]
+    /// ban duration(s) of the misbehaving peers, such as peers supplying invalid blocks. Default 86400.
+    ban_duration: Option<u64>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -279,6 +298,20 @@ impl NetworkConfig {
         seeds
     }
 
+    pub fn reserved_peers(&self) -> Vec<MultiaddrWithPeerId> {
+        let self_peer_id = self.self_peer_id();
+        self.reserved_peers
+            .clone()
+            .into_vec()
+            .into_iter()
+            .filter(|node| &node.peer_id != self_peer_id.origin())
+            .collect()
+    }
+
+    pub fn reserved_only(&self) -> bool {
+        self.reserved_only.unwrap_or(false)
+    }
+
     pub fn network_keypair(&self) -> &(Ed25519PrivateKey, Ed25519PublicKey) {
         self.network_keypair.as_ref().expect("Config should init.")
     }
@@ -322,6 +355,15 @@ impl NetworkConfig {
         self.max_outgoing_peers.clone().unwrap_or(75)
     }
 
+    pub fn ban_duration(&self) -> Duration {
+        Duration::from_secs(self.ban_duration.unwrap_or(86400))
+    }
+
+    /// The banned peers are persisted in this file, to keep them banned after restart.
+    pub fn banned_peers_file(&self) -> PathBuf {
+        self.base().data_dir().join(BANNED_PEERS_FILE_NAME)
+    }
+
     pub fn node_name(&self) -> String {
         self.node_name.clone().unwrap_or_else(generate_node_name)
     }
@@ -402,6 +444,10 @@ impl ConfigModule for NetworkConfig {
         self.base = Some(base);
 
         self.seeds.merge(&opt.network.seeds);
+        self.reserved_peers.merge(&opt.network.reserved_peers);
+        if opt.network.reserved_only.is_some() {
+            self.reserved_only = opt.network.reserved_only;
+        }
 
         if opt.network.disable_seed {
             self.disable_seed = opt.network.disable_seed;
@@ -441,6 +487,9 @@ impl ConfigModule for NetworkConfig {
         if opt.network.max_outgoing_peers.is_some() {
             self.max_outgoing_peers = opt.network.max_outgoing_peers;
         }
+        if opt.network.ban_duration.is_some() {
+            self.ban_duration = opt.network.ban_duration;
+        }
 
         if opt.network.unsupported_protocols.is_some() {
             let mut protocols: HashSet<String> = self
@@ -6,13 +6,15 @@ use crate::{
     BaseConfig, ConfigModule, QuotaDuration, StarcoinOpt,
 };
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use starcoin_logger::prelude::*;
 use std::collections::HashSet;
 use std::fmt::Formatter;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::num::NonZeroU32;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
@@ -25,6 +27,8 @@ const DEFAULT_WEB_SOCKET_PORT: u16 = 9870;
 // UNSPECIFIED is 0.0.0.0
 const DEFAULT_RPC_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 const DEFAULT_BLOCK_QUERY_MAX_RANGE: u64 = 32;
+const DEFAULT_TXN_QUERY_MAX_RANGE: u64 = 100;
+const DEFAULT_EVENT_QUERY_MAX_LIMIT: usize = 1000;
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct HttpConfiguration {
@@ -339,6 +343,125 @@ impl ApiQuotaConfiguration {
     }
 }
 
+/// A static bearer token of rpc, and the apis which the token is allowed to call.
+/// The string format is `id:token:apis`, eg: `alice:s3cr3t:chain,state`.
+/// The secret token is hidden in `Debug` and `Display`, only written by `Serialize`.
+#[derive(Clone, PartialEq)]
+pub struct RpcAuthToken {
+    /// The identity of the token, used as the user of api quota.
+    pub id: String,
+    pub token: String,
+    pub apis: ApiSet,
+}
+
+impl FromStr for RpcAuthToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.splitn(3, ':').collect();
+        if parts.len() != 3 || parts[0].is_empty() || parts[1].is_empty() {
+            return Err("Invalid rpc auth token, expect format id:token:apis".to_string());
+        }
+        Ok(Self {
+            id: parts[0].to_string(),
+            token: parts[1].to_string(),
+            apis: parts[2].parse()?,
+        })
+    }
+}
+
+impl std::fmt::Display for RpcAuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:***:{}", self.id, self.apis)
+    }
+}
+
+impl std::fmt::Debug for RpcAuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcAuthToken")
+            .field("id", &self.id)
+            .field("token", &"***")
+            .field("apis", &self.apis)
+            .finish()
+    }
+}
+
+impl Serialize for RpcAuthToken {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        format!("{}:{}:{}", self.id, self.token, self.apis).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcAuthToken {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        RpcAuthToken::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
+#[serde(deny_unknown_fields)]
+pub struct RpcAuthConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "rpc-auth-token",
+        long,
+        help = "static rpc bearer token, format: id:token:apis, eg: alice:s3cr3t:chain,state",
+        number_of_values = 1
+    )]
+    pub tokens: Option<Vec<RpcAuthToken>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-auth-hmac-secret", long)]
+    /// The secret for verify HMAC signed rpc bearer token.
+    pub hmac_secret: Option<String>,
+}
+
+impl std::fmt::Debug for RpcAuthConfiguration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcAuthConfiguration")
+            .field("tokens", &self.tokens)
+            .field("hmac_secret", &self.hmac_secret.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+impl RpcAuthConfiguration {
+    pub fn tokens(&self) -> Vec<RpcAuthToken> {
+        self.tokens.clone().unwrap_or_default()
+    }
+
+    pub fn hmac_secret(&self) -> Option<&str> {
+        self.hmac_secret.as_deref()
+    }
+
+    /// Rpc auth is enabled if any static token or hmac secret is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.tokens().is_empty() || self.hmac_secret.is_some()
+    }
+
+    pub fn merge(&mut self, o: &Self) -> Result<()> {
+        if o.tokens.is_some() {
+            let mut tokens = self.tokens();
+            for token in o.tokens() {
+                tokens.retain(|t| t.id != token.id);
+                tokens.push(token);
+            }
+            self.tokens = Some(tokens);
+        }
+        if o.hmac_secret.is_some() {
+            self.hmac_secret = o.hmac_secret.clone();
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct RpcConfig {
@@ -362,6 +485,10 @@ pub struct RpcConfig {
     #[structopt(flatten)]
     pub api_quotas: ApiQuotaConfiguration,
 
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub auth: RpcAuthConfiguration,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "rpc-address")]
     /// Rpc address, default is 0.0.0.0
@@ -371,6 +498,16 @@ pub struct RpcConfig {
     #[structopt(long = "event-query-max-block-range")]
     pub block_query_max_range: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "txn-query-max-range")]
+    /// Max count of transactions returned by one query, default is 100.
+    pub txn_query_max_range: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "event-query-max-limit")]
+    /// Max count of events returned by one query over the event index, default is 1000.
+    pub event_query_max_limit: Option<usize>,
+
     #[serde(skip)]
     #[structopt(skip)]
     http_address: Option<ListenAddress>,
@@ -451,6 +588,16 @@ impl RpcConfig {
             .unwrap_or(DEFAULT_BLOCK_QUERY_MAX_RANGE)
     }
 
+    pub fn txn_query_max_range(&self) -> u64 {
+        self.txn_query_max_range
+            .unwrap_or(DEFAULT_TXN_QUERY_MAX_RANGE)
+    }
+
+    pub fn event_query_max_limit(&self) -> usize {
+        self.event_query_max_limit
+            .unwrap_or(DEFAULT_EVENT_QUERY_MAX_LIMIT)
+    }
+
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
     }
@@ -523,11 +670,18 @@ impl ConfigModule for RpcConfig {
         if opt.rpc.block_query_max_range.is_some() {
             self.block_query_max_range = opt.rpc.block_query_max_range;
         }
+        if opt.rpc.txn_query_max_range.is_some() {
+            self.txn_query_max_range = opt.rpc.txn_query_max_range;
+        }
+        if opt.rpc.event_query_max_limit.is_some() {
+            self.event_query_max_limit = opt.rpc.event_query_max_limit;
+        }
         self.http.merge(&opt.rpc.http)?;
         self.tcp.merge(&opt.rpc.tcp)?;
         self.ws.merge(&opt.rpc.ws)?;
         self.ipc.merge(&opt.rpc.ipc)?;
         self.api_quotas.merge(&opt.rpc.api_quotas)?;
+        self.auth.merge(&opt.rpc.auth)?;
 
         self.generate_address();
 
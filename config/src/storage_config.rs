@@ -6,6 +6,7 @@ use anyhow::Result;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
@@ -55,8 +56,49 @@ impl Default for RocksdbConfig {
     }
 }
 
+/// How the node keeps the history states.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatePruneMode {
+    /// Keep all history states, required for querying state by any history state root.
+    Archive,
+    /// Delete the stale state nodes which are older than the prune window behind the head.
+    Prune,
+}
+
+impl Default for StatePruneMode {
+    fn default() -> Self {
+        StatePruneMode::Archive
+    }
+}
+
+impl std::fmt::Display for StatePruneMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Archive => "archive",
+            Self::Prune => "prune",
+        };
+        write!(f, "{}", display)
+    }
+}
+
+impl FromStr for StatePruneMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "archive" => Ok(Self::Archive),
+            "prune" => Ok(Self::Prune),
+            other => Err(format!("Unknown state prune mode: {}", other)),
+        }
+    }
+}
+
 static DEFAULT_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("starcoindb/db"));
 pub const DEFAULT_CACHE_SIZE: usize = 20000;
+pub const DEFAULT_STATE_PRUNE_WINDOW: u64 = 1000;
+/// The prune window should not be less than the max block count of a chain reorganization.
+pub const MIN_STATE_PRUNE_WINDOW: u64 = 32;
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -77,6 +119,45 @@ pub struct StorageConfig {
     #[structopt(name = "cache-sizes", long, help = "cache sizes")]
     pub cache_size: Option<usize>,
 
+    /// Index main chain transactions by the sender and the event creator addresses,
+    /// for querying transaction history of an address. Default is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "enable-address-index",
+        long,
+        help = "index transactions by address, default false."
+    )]
+    pub enable_address_index: Option<bool>,
+
+    /// Index main chain events by the event key and by the event type,
+    /// for querying events of a wide block range. Default is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "enable-event-index",
+        long,
+        help = "index events by event key and type, default false."
+    )]
+    pub enable_event_index: Option<bool>,
+
+    /// Keep all history states (archive), or prune the stale states (prune). Default is archive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "state-prune-mode",
+        long,
+        help = "state prune mode, archive or prune, default archive."
+    )]
+    pub state_prune_mode: Option<StatePruneMode>,
+
+    /// The states of the latest `state_prune_window` main chain blocks are kept when prune
+    /// mode is enabled, the states of the forks whose head is in the window are kept too.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "state-prune-window",
+        long,
+        help = "how many latest block states are kept in prune mode, default 1000."
+    )]
+    pub state_prune_window: Option<u64>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -103,6 +184,24 @@ impl StorageConfig {
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+
+    pub fn enable_address_index(&self) -> bool {
+        self.enable_address_index.unwrap_or(false)
+    }
+
+    pub fn enable_event_index(&self) -> bool {
+        self.enable_event_index.unwrap_or(false)
+    }
+
+    pub fn state_prune_mode(&self) -> StatePruneMode {
+        self.state_prune_mode.unwrap_or_default()
+    }
+
+    pub fn state_prune_window(&self) -> u64 {
+        self.state_prune_window
+            .unwrap_or(DEFAULT_STATE_PRUNE_WINDOW)
+            .max(MIN_STATE_PRUNE_WINDOW)
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -117,6 +216,18 @@ impl ConfigModule for StorageConfig {
         if opt.storage.cache_size.is_some() {
             self.cache_size = opt.storage.cache_size;
         }
+        if opt.storage.enable_address_index.is_some() {
+            self.enable_address_index = opt.storage.enable_address_index;
+        }
+        if opt.storage.enable_event_index.is_some() {
+            self.enable_event_index = opt.storage.enable_event_index;
+        }
+        if opt.storage.state_prune_mode.is_some() {
+            self.state_prune_mode = opt.storage.state_prune_mode;
+        }
+        if opt.storage.state_prune_window.is_some() {
+            self.state_prune_window = opt.storage.state_prune_window;
+        }
         Ok(())
     }
 }
@@ -5,9 +5,49 @@ use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use network_api::PeerStrategy;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
+/// How a node catches up with the network.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncMode {
+    /// Download and execute every block from the local head.
+    Full,
+    /// Download the state at a recent pivot block from peers, then execute the blocks after it.
+    /// Only take effect when the local head is genesis.
+    Fast,
+}
+
+impl Default for SyncMode {
+    fn default() -> Self {
+        SyncMode::Full
+    }
+}
+
+impl std::fmt::Display for SyncMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Full => "full",
+            Self::Fast => "fast",
+        };
+        write!(f, "{}", display)
+    }
+}
+
+impl FromStr for SyncMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "fast" => Ok(Self::Fast),
+            other => Err(format!("Unknown sync mode: {}", other)),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct SyncConfig {
@@ -28,6 +68,24 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// sync mode, full or fast
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "sync-mode",
+        long,
+        help = "sync mode, full or fast, fast sync download the state of a recent block from peers instead of executing all blocks from genesis, default full."
+    )]
+    sync_mode: Option<SyncMode>,
+
+    /// execute the user transactions of blocks optimistically in parallel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "parallel-execution",
+        long,
+        help = "execute the user transactions of blocks optimistically in parallel, default false."
+    )]
+    parallel_execution: Option<bool>,
 }
 
 impl SyncConfig {
@@ -38,6 +96,14 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn sync_mode(&self) -> SyncMode {
+        self.sync_mode.unwrap_or_default()
+    }
+
+    pub fn parallel_execution(&self) -> bool {
+        self.parallel_execution.unwrap_or(false)
+    }
 }
 
 impl ConfigModule for SyncConfig {
@@ -50,6 +116,14 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.sync_mode.is_some() {
+            self.sync_mode = opt.sync.sync_mode;
+        }
+
+        if opt.sync.parallel_execution.is_some() {
+            self.parallel_execution = opt.sync.parallel_execution;
+        }
+
         Ok(())
     }
 }
@@ -4,6 +4,7 @@
 use super::*;
 use crate::helper::to_toml;
 use starcoin_vm_types::gas_schedule::GasAlgebra;
+use std::collections::HashSet;
 
 #[test]
 fn test_generate_and_load() -> Result<()> {
@@ -64,6 +65,38 @@ fn test_api_quota_config() {
     assert_eq!("1000/s", config.to_string().as_str());
 }
 
+#[test]
+fn test_rpc_auth_token() {
+    let token = "alice:s3cr3t:chain,state".parse::<RpcAuthToken>().unwrap();
+    assert_eq!(token.id.as_str(), "alice");
+    assert_eq!(token.token.as_str(), "s3cr3t");
+    assert_eq!(
+        token.apis.list_apis(),
+        vec![Api::Chain, Api::State]
+            .into_iter()
+            .collect::<HashSet<_>>()
+    );
+    // the secret is only kept by serialize.
+    assert!(!token.to_string().contains("s3cr3t"));
+    assert!(!format!("{:?}", token).contains("s3cr3t"));
+    let json = serde_json::to_string(&token).unwrap();
+    assert_eq!(
+        token,
+        serde_json::from_str::<RpcAuthToken>(json.as_str()).unwrap()
+    );
+    assert!("alice:s3cr3t".parse::<RpcAuthToken>().is_err());
+    assert!(":s3cr3t:chain".parse::<RpcAuthToken>().is_err());
+    assert!("alice:s3cr3t:unknown".parse::<RpcAuthToken>().is_err());
+}
+
+#[test]
+fn test_rpc_auth_config_unknown_field() {
+    let config = toml::from_str::<RpcConfig>("[auth]\nhmac_secret = \"s3cr3t\"\n").unwrap();
+    assert!(config.auth.is_enabled());
+    // a misspelled auth key must not silently disable the rpc auth.
+    assert!(toml::from_str::<RpcConfig>("[auth]\nhmac_secrets = \"s3cr3t\"\n").is_err());
+}
+
 #[test]
 fn test_example_config_compact() -> Result<()> {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
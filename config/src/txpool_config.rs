@@ -5,11 +5,64 @@ use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use starcoin_system::get_free_mem_size;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
 
+static TXPOOL_JOURNAL_FILE_NAME: &str = "txpool.journal";
+
+/// Which pending transactions of the pool are journaled to disk, and re-imported on startup.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxPoolJournalMode {
+    /// Do not journal any transaction.
+    None,
+    /// Only journal the local transactions, which are added by the node itself,
+    /// the transactions submitted by rpc or received from peers are not local.
+    Local,
+    /// Journal all the pending transactions.
+    All,
+}
+
+impl TxPoolJournalMode {
+    pub fn is_enabled(self) -> bool {
+        self != Self::None
+    }
+}
+
+impl Default for TxPoolJournalMode {
+    fn default() -> Self {
+        TxPoolJournalMode::None
+    }
+}
+
+impl std::fmt::Display for TxPoolJournalMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::None => "none",
+            Self::Local => "local",
+            Self::All => "all",
+        };
+        write!(f, "{}", display)
+    }
+}
+
+impl FromStr for TxPoolJournalMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "local" => Ok(Self::Local),
+            "all" => Ok(Self::All),
+            other => Err(format!("Unknown txpool journal mode: {}", other)),
+        }
+    }
+}
+
 #[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct TxPoolConfig {
@@ -37,6 +90,25 @@ pub struct TxPoolConfig {
     #[structopt(name = "txpool-min-gas-price", long)]
     /// reject transaction whose gas_price is less than the min_gas_price. default to 1.
     min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-min-gas-price-bump", long)]
+    /// minimal gas price bump in percent to replace a transaction with the same sender and sequence number. default to 10.
+    min_gas_price_bump: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-journal", long)]
+    /// journal pending transactions to disk and re-import them on restart, none, local or all. default to none.
+    journal: Option<TxPoolJournalMode>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-journal-interval", long)]
+    /// interval(s) of rewriting the txpool journal. default to 60.
+    journal_interval: Option<u64>,
+
+    #[structopt(skip)]
+    #[serde(skip)]
+    journal_path: Option<PathBuf>,
 }
 
 impl TxPoolConfig {
@@ -69,10 +141,26 @@ impl TxPoolConfig {
     pub fn min_gas_price(&self) -> u64 {
         self.min_gas_price.unwrap_or(1)
     }
+    pub fn min_gas_price_bump(&self) -> u64 {
+        self.min_gas_price_bump.unwrap_or(10)
+    }
+    pub fn set_journal(&mut self, journal: TxPoolJournalMode) {
+        self.journal = Some(journal);
+    }
+    pub fn journal(&self) -> TxPoolJournalMode {
+        self.journal.unwrap_or_default()
+    }
+    pub fn journal_interval(&self) -> u64 {
+        self.journal_interval.unwrap_or(60)
+    }
+    pub fn journal_path(&self) -> PathBuf {
+        self.journal_path.clone().expect("Config should init.")
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.journal_path = Some(base.data_dir().join(TXPOOL_JOURNAL_FILE_NAME));
         let txpool_opt = &opt.txpool;
         if let Some(m) = txpool_opt.max_mem_usage.as_ref() {
             self.max_mem_usage = Some(*m);
@@ -89,6 +177,15 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.min_gas_price.as_ref() {
             self.min_gas_price = Some(*m);
         }
+        if let Some(m) = txpool_opt.min_gas_price_bump.as_ref() {
+            self.min_gas_price_bump = Some(*m);
+        }
+        if let Some(m) = txpool_opt.journal.as_ref() {
+            self.journal = Some(*m);
+        }
+        if let Some(m) = txpool_opt.journal_interval.as_ref() {
+            self.journal_interval = Some(*m);
+        }
         Ok(())
     }
 }
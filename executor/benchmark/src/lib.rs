@@ -25,12 +25,17 @@ use types::{
     account_address,
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
-    transaction::{Transaction, TransactionPayload},
+    transaction::{RawUserTransaction, Transaction, TransactionPayload},
 };
 
+/// The max gas of a transfer, the init balance of the accounts must be able to pay it.
+const TRANSFER_MAX_GAS_AMOUNT: u64 = 100_000;
+
 struct AccountData {
+    private_key: Ed25519PrivateKey,
     public_key: Ed25519PublicKey,
     address: AccountAddress,
+    sequence_number: u64,
 }
 
 impl AccountData {
@@ -39,11 +44,13 @@ impl AccountData {
     }
     pub fn random() -> Self {
         let mut key_gen = KeyGen::from_os_rng();
-        let (_private_key, public_key) = key_gen.generate_keypair();
+        let (private_key, public_key) = key_gen.generate_keypair();
         let address = account_address::from_public_key(&public_key);
         AccountData {
+            private_key,
             public_key,
             address,
+            sequence_number: 0,
         }
     }
 }
@@ -82,8 +89,10 @@ impl TransactionGenerator {
             let public_key = private_key.public_key();
             let address = account_address::from_public_key(&public_key);
             let account = AccountData {
+                private_key,
                 public_key,
                 address,
+                sequence_number: 0,
             };
             accounts.push(account);
         }
@@ -148,7 +157,8 @@ impl TransactionGenerator {
         }
     }
 
-    /// Generates transactions for random pairs of accounts.
+    /// Generates transactions for random pairs of accounts, the transactions in a block are sent by
+    /// different accounts unless two pairs share the sender, so they can be executed in parallel.
     fn gen_transfer_transactions(&mut self, block_size: usize, num_blocks: usize) {
         for _i in 0..num_blocks {
             self.net.time_service().sleep(1000);
@@ -168,26 +178,27 @@ impl TransactionGenerator {
             transactions.push(Transaction::BlockMetadata(block_meta));
 
             for j in 0..block_size {
-                let indices = rand::seq::index::sample(&mut self.rng, self.accounts.len(), 1);
-                //                let sender_idx = indices.index(0);
-                let receiver_idx = indices.index(0);
+                let indices = rand::seq::index::sample(&mut self.rng, self.accounts.len(), 2);
+                let sender_idx = indices.index(0);
+                let receiver_idx = indices.index(1);
 
-                //                let sender = &self.accounts[sender_idx];
                 let receiver = &self.accounts[receiver_idx];
-                let txn = create_transaction(
-                    self.sequence,
-                    encode_transfer_script_function(
-                        self.net.stdlib_version(),
-                        receiver.address,
-                        Some(AuthenticationKey::ed25519(&receiver.public_key)),
-                        1, /* amount */
-                    ),
+                let program = encode_transfer_script_function(
+                    self.net.stdlib_version(),
+                    receiver.address,
+                    Some(AuthenticationKey::ed25519(&receiver.public_key)),
+                    1, /* amount */
+                );
+                let sender = &mut self.accounts[sender_idx];
+                let txn = create_transaction_by_account(
+                    sender,
+                    program,
                     self.net.time_service().now_secs() + j as u64 + 1,
                     &self.net,
                 );
                 transactions.push(txn);
 
-                self.sequence += 1;
+                sender.sequence_number += 1;
             }
 
             self.block_sender
@@ -204,18 +215,23 @@ impl TransactionGenerator {
     }
 }
 
+/// Executes every block both sequentially and in parallel on two states from the same genesis,
+/// and checks the executed data are the same.
 struct TxnExecutor<'test> {
-    chain_state: &'test dyn ChainState,
+    chain_state: &'test ChainStateDB,
+    parallel_chain_state: &'test ChainStateDB,
     block_receiver: mpsc::Receiver<Vec<Transaction>>,
 }
 
 impl<'test> TxnExecutor<'test> {
     fn new(
-        chain_state: &'test dyn ChainState,
+        chain_state: &'test ChainStateDB,
+        parallel_chain_state: &'test ChainStateDB,
         block_receiver: mpsc::Receiver<Vec<Transaction>>,
     ) -> Self {
         Self {
             chain_state,
+            parallel_chain_state,
             block_receiver,
         }
     }
@@ -227,22 +243,35 @@ impl<'test> TxnExecutor<'test> {
             let num_txns = transactions.len();
             version += num_txns as u64;
 
-            let _ = executor::block_execute(self.chain_state, transactions, u64::MAX)
-                .expect("Execute transactions fail.");
+            let executed_data =
+                executor::block_execute(self.chain_state, transactions.clone(), u64::MAX)
+                    .expect("Execute transactions fail.");
             self.chain_state.flush().expect("flush state should be ok");
 
             let execute_time = std::time::Instant::now().duration_since(execute_start);
-            let commit_start = std::time::Instant::now();
-
-            let commit_time = std::time::Instant::now().duration_since(commit_start);
-            let total_time = execute_time + commit_time;
+            let parallel_execute_start = std::time::Instant::now();
+
+            let parallel_executed_data =
+                executor::block_execute_parallel(self.parallel_chain_state, transactions, u64::MAX)
+                    .expect("Execute transactions in parallel fail.");
+            self.parallel_chain_state
+                .flush()
+                .expect("flush state should be ok");
+
+            let parallel_execute_time =
+                std::time::Instant::now().duration_since(parallel_execute_start);
+            assert_eq!(
+                executed_data, parallel_executed_data,
+                "Parallel execution must produce the same executed data as sequential execution."
+            );
 
             info!(
-                "Version: {}. execute time: {} ms. commit time: {} ms. TPS: {}.",
+                "Version: {}. execute time: {} ms, TPS: {}. parallel execute time: {} ms, TPS: {}.",
                 version,
                 execute_time.as_millis(),
-                commit_time.as_millis(),
-                num_txns as u128 * 1_000_000_000 / total_time.as_nanos(),
+                num_txns as u128 * 1_000_000_000 / execute_time.as_nanos(),
+                parallel_execute_time.as_millis(),
+                num_txns as u128 * 1_000_000_000 / parallel_execute_time.as_nanos(),
             );
         }
     }
@@ -255,12 +284,9 @@ pub fn run_benchmark(
     block_size: usize,
     num_transfer_blocks: usize,
 ) {
-    let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
-
-    let chain_state = ChainStateDB::new(storage, None);
     let net = ChainNetwork::new_test();
-    let genesis_txn = Genesis::build_genesis_transaction(&net).unwrap();
-    let _txn_info = Genesis::execute_genesis_txn(&chain_state, genesis_txn).unwrap();
+    let chain_state = genesis_chain_state(&net);
+    let parallel_chain_state = genesis_chain_state(&net);
 
     let (block_sender, block_receiver) = mpsc::sync_channel(50 /* bound */);
 
@@ -276,7 +302,7 @@ pub fn run_benchmark(
     let exe_thread = std::thread::Builder::new()
         .name("txn_executor".to_string())
         .spawn(move || {
-            let mut exe = TxnExecutor::new(&chain_state, block_receiver);
+            let mut exe = TxnExecutor::new(&chain_state, &parallel_chain_state, block_receiver);
             exe.run();
         })
         .expect("Failed to spawn transaction executor thread.");
@@ -289,6 +315,14 @@ pub fn run_benchmark(
     exe_thread.join().unwrap();
 }
 
+fn genesis_chain_state(net: &ChainNetwork) -> ChainStateDB {
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+    let chain_state = ChainStateDB::new(storage, None);
+    let genesis_txn = Genesis::build_genesis_transaction(net).unwrap();
+    let _txn_info = Genesis::execute_genesis_txn(&chain_state, genesis_txn).unwrap();
+    chain_state
+}
+
 fn create_transaction(
     sequence_number: u64,
     program: ScriptFunction,
@@ -306,6 +340,28 @@ fn create_transaction(
     Transaction::UserTransaction(signed_txn)
 }
 
+fn create_transaction_by_account(
+    account: &AccountData,
+    program: ScriptFunction,
+    expiration_timestamp_secs: u64,
+    net: &ChainNetwork,
+) -> Transaction {
+    let raw_txn = RawUserTransaction::new_with_default_gas_token(
+        account.address,
+        account.sequence_number,
+        TransactionPayload::ScriptFunction(program),
+        TRANSFER_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net.chain_id(),
+    );
+    let signed_txn = raw_txn
+        .sign(&account.private_key, account.public_key.clone())
+        .expect("Sign txn should work.")
+        .into_inner();
+    Transaction::UserTransaction(signed_txn)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
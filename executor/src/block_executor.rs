@@ -6,7 +6,7 @@ use starcoin_state_api::ChainState;
 use starcoin_types::error::BlockExecutorError;
 use starcoin_types::error::ExecutorResult;
 use starcoin_types::transaction::TransactionStatus;
-use starcoin_types::transaction::{Transaction, TransactionInfo};
+use starcoin_types::transaction::{Transaction, TransactionInfo, TransactionOutput};
 use starcoin_vm_types::contract_event::ContractEvent;
 use vm_runtime::metrics::TXN_STATUS_COUNTERS;
 
@@ -35,7 +35,30 @@ pub fn block_execute(
     let txn_outputs =
         crate::execute_block_transactions(chain_state.as_super(), txns.clone(), block_gas_limit)
             .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+    apply_block_outputs(chain_state, txns, txn_outputs)
+}
+
+/// Execute the block like `block_execute`, but execute the user transactions optimistically in
+/// parallel, the executed data is the same as `block_execute`.
+pub fn block_execute_parallel<S>(
+    chain_state: &S,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+) -> ExecutorResult<BlockExecutedData>
+where
+    S: ChainState + Sync,
+{
+    let txn_outputs =
+        crate::execute_block_transactions_parallel(chain_state, txns.clone(), block_gas_limit)
+            .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+    apply_block_outputs(chain_state, txns, txn_outputs)
+}
 
+fn apply_block_outputs(
+    chain_state: &dyn ChainState,
+    txns: Vec<Transaction>,
+    txn_outputs: Vec<TransactionOutput>,
+) -> ExecutorResult<BlockExecutedData> {
     let mut executed_data = BlockExecutedData::default();
     for (txn, output) in txns
         .iter()
@@ -57,6 +80,8 @@ pub fn block_execute(
                     .apply_write_set(write_set)
                     .map_err(BlockExecutorError::BlockChainStateErr)?;
 
+                // the transaction info records the state root after the transaction, so the
+                // state is committed per transaction even if the block is executed in parallel.
                 let txn_state_root = chain_state
                     .commit()
                     .map_err(BlockExecutorError::BlockChainStateErr)?;
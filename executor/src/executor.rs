@@ -26,6 +26,29 @@ pub fn execute_block_transactions(
     do_execute_block_transactions(chain_state, txns, Some(block_gas_limit))
 }
 
+/// Execute a block transactions with gas_limit like `execute_block_transactions`,
+/// but execute the user transactions optimistically in parallel.
+pub fn execute_block_transactions_parallel(
+    chain_state: &(dyn StateView + Sync),
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+) -> Result<Vec<TransactionOutput>> {
+    let timer = TXN_EXECUTION_HISTOGRAM
+        .with_label_values(&["execute_block_transactions_parallel"])
+        .start_timer();
+    let mut vm = StarcoinVM::new();
+    let result = vm
+        .execute_block_transactions_parallel(chain_state, txns, Some(block_gas_limit))?
+        .into_iter()
+        .map(|(_, output)| {
+            debug! {"{:?}", output};
+            output
+        })
+        .collect();
+    timer.observe_duration();
+    Ok(result)
+}
+
 fn do_execute_block_transactions(
     chain_state: &dyn StateView,
     txns: Vec<Transaction>,
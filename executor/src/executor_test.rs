@@ -33,11 +33,12 @@ use test_helper::executor::{
 
 use test_helper::executor::{
     compile_modules_with_address, execute_and_apply, get_balance, get_sequence_number,
-    prepare_genesis,
+    prepare_customized_genesis, prepare_genesis,
 };
 // use test_helper::Account;
 use starcoin_vm_types::account_config::core_code_address;
 use vm_runtime::starcoin_vm::StarcoinVM;
+use vm_runtime::trace::{CallKind, ExecutionTrace, GasCategory};
 
 #[derive(Default)]
 pub struct NullStateView;
@@ -853,6 +854,94 @@ fn test_gas_used() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+fn test_trace_transaction() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+
+    let account = Account::new();
+    let txn = crate::build_transfer_from_association(
+        *account.address(),
+        Some(account.auth_key()),
+        0,
+        1000,
+        1,
+        &net,
+    );
+    let mut vm = StarcoinVM::new();
+    vm.enable_trace();
+    let (_, output) = vm
+        .execute_block_transactions(&chain_state, vec![txn], None)?
+        .pop()
+        .unwrap();
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let trace = vm.take_trace().unwrap();
+    assert_eq!(CallKind::Prologue, trace.calls.first().unwrap().kind);
+    assert_eq!(CallKind::Epilogue, trace.calls.last().unwrap().kind);
+    assert!(trace.calls.iter().all(|call| call.error.is_none()));
+    let calls_gas: u64 = trace.calls.iter().map(|call| call.gas_used).sum();
+    assert_eq!(
+        output.gas_used(),
+        calls_gas + trace.intrinsic_gas + trace.global_write_gas
+    );
+    // the trace is taken.
+    assert_eq!(Some(ExecutionTrace::default()), vm.take_trace());
+
+    Ok(())
+}
+
+#[stest::test]
+fn test_trace_transaction_by_gas_category() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+
+    let account = Account::new();
+    let txn = crate::build_transfer_from_association(
+        *account.address(),
+        Some(account.auth_key()),
+        0,
+        1000,
+        1,
+        &net,
+    );
+    let trace = |category: Option<GasCategory>| -> Result<ExecutionTrace> {
+        let mut vm = StarcoinVM::new();
+        vm.enable_trace();
+        if let Some(category) = category {
+            vm.charge_only(category);
+        }
+        let (_, output) = vm
+            .execute_block_transactions(&chain_state, vec![txn.clone()], None)?
+            .pop()
+            .unwrap();
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+        Ok(vm.take_trace().unwrap())
+    };
+    let total = trace(None)?;
+    let instruction = trace(Some(GasCategory::Instruction))?;
+    let native = trace(Some(GasCategory::Native))?;
+    assert_eq!(total.calls.len(), instruction.calls.len());
+    assert_eq!(total.calls.len(), native.calls.len());
+    // the gas constants are kept.
+    assert_eq!(total.intrinsic_gas, instruction.intrinsic_gas);
+    assert_eq!(total.global_write_gas, native.global_write_gas);
+    for ((call, instruction_call), native_call) in total
+        .calls
+        .iter()
+        .zip(instruction.calls.iter())
+        .zip(native.calls.iter())
+    {
+        assert_eq!(call.kind, instruction_call.kind);
+        assert_eq!(
+            call.gas_used,
+            instruction_call.gas_used + native_call.gas_used
+        );
+    }
+    assert!(instruction.calls.iter().any(|call| call.gas_used > 0));
+    assert!(native.calls.iter().any(|call| call.gas_used > 0));
+
+    Ok(())
+}
+
 #[stest::test]
 fn test_publish_module_and_upgrade() -> Result<()> {
     let (chain_state, net) = prepare_genesis();
@@ -948,3 +1037,78 @@ fn test_block_metadata() -> Result<()> {
 
     Ok(())
 }
+
+#[stest::test]
+fn test_block_execute_parallel() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let parallel_chain_state = prepare_customized_genesis(&net);
+    let accounts = (0..4).map(|_| Account::new()).collect::<Vec<_>>();
+
+    let new_block_meta = |number: u64| {
+        let miner = Account::new();
+        BlockMetadata::new(
+            starcoin_crypto::HashValue::random(),
+            net.time_service().now_millis(),
+            *miner.address(),
+            Some(miner.auth_key()),
+            0,
+            number,
+            net.chain_id(),
+            0,
+        )
+    };
+
+    // the create account txns are all sent by association, so they conflict with each other.
+    let sequence_number = get_sequence_number(account_config::association_address(), &chain_state);
+    let mut txns = vec![Transaction::BlockMetadata(new_block_meta(
+        current_block_number(&chain_state) + 1,
+    ))];
+    txns.extend(accounts.iter().enumerate().map(|(i, account)| {
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            account,
+            sequence_number + i as u64,
+            50_000_000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            &net,
+        ))
+    }));
+
+    // the transfers between different pairs of accounts only conflict on the transaction fee,
+    // which is merged, the last one is sent by the sender of the first one, so conflicts with it.
+    net.time_service().sleep(1000);
+    let mut transfer_txns = vec![Transaction::BlockMetadata(new_block_meta(
+        current_block_number(&chain_state) + 2,
+    ))];
+    transfer_txns.extend(accounts.chunks(2).map(|pair| {
+        Transaction::UserTransaction(peer_to_peer_txn(
+            &pair[0],
+            &pair[1],
+            0,
+            10_000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net.chain_id(),
+        ))
+    }));
+    transfer_txns.push(Transaction::UserTransaction(peer_to_peer_txn(
+        &accounts[0],
+        &Account::new(),
+        1,
+        10_000,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    )));
+
+    for txns in &[txns, transfer_txns] {
+        let txn_num = txns.len();
+        let executed_data = crate::block_execute(&chain_state, txns.clone(), u64::MAX)?;
+        let parallel_executed_data =
+            crate::block_execute_parallel(&parallel_chain_state, txns.clone(), u64::MAX)?;
+        assert_eq!(executed_data.txn_infos.len(), txn_num);
+        assert!(executed_data
+            .txn_infos
+            .iter()
+            .all(|txn_info| txn_info.status() == &KeptVMStatus::Executed));
+        assert_eq!(executed_data, parallel_executed_data);
+    }
+    Ok(())
+}
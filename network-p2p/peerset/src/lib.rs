@@ -471,6 +471,10 @@ impl Peerset {
         drop(reputation);
 
         for set_index in 0..self.data.num_sets() {
+            // Reserved nodes are never evicted because of their reputation.
+            if self.reserved_nodes[set_index].0.contains(&peer_id) {
+                continue;
+            }
             if let peersstate::Peer::Connected(peer) = self.data.peer(set_index, &peer_id) {
                 let peer = peer.disconnect();
                 self.message_queue.push_back(Message::Drop {
@@ -617,7 +621,8 @@ impl Peerset {
 
         self.update_time();
 
-        if self.reserved_nodes[set_id.0].1 && !self.reserved_nodes[set_id.0].0.contains(&peer_id) {
+        let is_reserved = self.reserved_nodes[set_id.0].0.contains(&peer_id);
+        if self.reserved_nodes[set_id.0].1 && !is_reserved {
             self.message_queue.push_back(Message::Reject(index));
             return;
         }
@@ -632,7 +637,7 @@ impl Peerset {
             peersstate::Peer::Unknown(entry) => entry.discover(),
         };
 
-        if not_connected.reputation() < BANNED_THRESHOLD {
+        if not_connected.reputation() < BANNED_THRESHOLD && !is_reserved {
             self.message_queue.push_back(Message::Reject(index));
             return;
         }
@@ -979,4 +984,41 @@ mod tests {
 
         futures::executor::block_on(fut);
     }
+
+    #[test]
+    fn test_peerset_reserved_peer_not_banned() {
+        let reserved_peer = PeerId::random();
+        let (mut peerset, handle) = Peerset::from_config(PeersetConfig {
+            sets: vec![SetConfig {
+                in_peers: 25,
+                out_peers: 25,
+                bootnodes: vec![],
+                reserved_nodes: vec![reserved_peer].into_iter().collect(),
+                reserved_only: true,
+            }],
+        });
+        handle.report_peer(
+            reserved_peer,
+            ReputationChange::new(BANNED_THRESHOLD - 1, ""),
+        );
+
+        let fut = futures::future::poll_fn(move |cx| {
+            // The reserved peer is connected and not dropped after the report.
+            if let Poll::Ready(msg) = Stream::poll_next(Pin::new(&mut peerset), cx) {
+                assert_eq!(
+                    msg.unwrap(),
+                    Message::Connect {
+                        set_id: SetId::from(0),
+                        peer_id: reserved_peer,
+                    }
+                );
+            } else {
+                panic!()
+            }
+            assert_eq!(Stream::poll_next(Pin::new(&mut peerset), cx), Poll::Pending);
+            Poll::Ready(())
+        });
+
+        futures::executor::block_on(fut);
+    }
 }
@@ -187,6 +187,10 @@ impl NetworkWorker {
                 Ok(())
             }
         })?;
+        // Reserved nodes are connected by their addresses, like the bootnodes.
+        for reserved in params.network_config.reserved_nodes.iter() {
+            known_addresses.push((reserved.peer_id, reserved.multiaddr.clone()));
+        }
         // Private and public keys configuration.
         let local_identity = params.network_config.node_key.clone().into_keypair()?;
         let local_public = local_identity.public();
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::messages::{
-    GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerMessage, PeerReputations,
-    ReportReputation,
+    BanPeer, GetBannedPeers, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage,
+    PeerMessage, PeerReputations, ReportReputation, UnbanPeer,
 };
 use anyhow::*;
 use futures::future::BoxFuture;
@@ -42,10 +42,13 @@ pub trait NetworkActor:
     + EventHandler<Self, PeerMessage>
     + EventHandler<Self, NotificationMessage>
     + EventHandler<Self, ReportReputation>
+    + EventHandler<Self, BanPeer>
     + ServiceHandler<Self, GetPeerSet>
     + ServiceHandler<Self, PeerReputations>
     + ServiceHandler<Self, GetSelfPeer>
     + ServiceHandler<Self, GetPeerById>
+    + ServiceHandler<Self, UnbanPeer>
+    + ServiceHandler<Self, GetBannedPeers>
 {
 }
 
@@ -83,6 +86,16 @@ where
         })
         .boxed()
     }
+
+    fn ban_peer(&self, peer_id: PeerId, reason: String) {
+        if let Err(e) = self.notify(BanPeer {
+            peer_id,
+            duration: None,
+            reason,
+        }) {
+            debug!("ban_peer error: {}.", e);
+        }
+    }
 }
 
 impl<S> NetworkService for ServiceRef<S>
@@ -15,6 +15,7 @@ use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::SignedUserTransaction;
 use std::borrow::Cow;
 use std::convert::{TryFrom, TryInto};
+use std::time::Duration;
 
 pub const TXN_PROTOCOL_NAME: &str = "/starcoin/txn/1";
 pub const BLOCK_PROTOCOL_NAME: &str = "/starcoin/block/1";
@@ -351,3 +352,38 @@ pub struct GetSelfPeer;
 impl ServiceRequest for GetSelfPeer {
     type Response = PeerInfo;
 }
+
+/// Ban a peer for `duration`, the configured ban duration is used if it is absent.
+/// The banned peer is disconnected, and refused until the ban expires.
+/// The reserved peers can not be banned.
+#[derive(Clone, Debug)]
+pub struct BanPeer {
+    pub peer_id: PeerId,
+    pub duration: Option<Duration>,
+    pub reason: String,
+}
+
+/// Lift the ban of a peer and restore its reputation, response whether the peer was banned.
+#[derive(Clone, Debug)]
+pub struct UnbanPeer {
+    pub peer_id: PeerId,
+}
+
+impl ServiceRequest for UnbanPeer {
+    type Response = Result<bool>;
+}
+
+#[derive(Clone, Debug)]
+pub struct GetBannedPeers;
+
+impl ServiceRequest for GetBannedPeers {
+    type Response = Vec<BannedPeer>;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BannedPeer {
+    pub peer_id: PeerId,
+    /// The ban expires at this unix timestamp in seconds.
+    pub banned_until: u64,
+    pub reason: String,
+}
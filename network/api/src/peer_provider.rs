@@ -32,6 +32,9 @@ pub trait PeerProvider: Send + Sync + std::marker::Unpin {
 
     fn report_peer(&self, peer_id: PeerId, cost_benefit: ReputationChange);
 
+    /// Ban the misbehaving peer for the configured ban duration.
+    fn ban_peer(&self, peer_id: PeerId, reason: String);
+
     fn reputations(
         &self,
         reputation_threshold: i32,
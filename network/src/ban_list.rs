@@ -0,0 +1,138 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The banned peers, persisted as a json file, so misbehaving peers are still banned after restart.
+
+use crate::helper::get_unix_ts_as_secs;
+use anyhow::Result;
+use log::info;
+use network_api::messages::BannedPeer;
+use starcoin_types::peer_info::PeerId;
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct BanList {
+    path: PathBuf,
+    peers: HashMap<PeerId, BannedPeer>,
+}
+
+impl BanList {
+    /// Load the ban list from `path`, the expired bans are dropped.
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let peers: Vec<BannedPeer> = match fs::read(path.as_path()) {
+            Ok(data) => serde_json::from_slice(data.as_slice())?,
+            Err(e) if e.kind() == ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+        let now = get_unix_ts_as_secs();
+        let peers = peers
+            .into_iter()
+            .filter(|peer| peer.banned_until > now)
+            .map(|peer| (peer.peer_id.clone(), peer))
+            .collect::<HashMap<_, _>>();
+        if !peers.is_empty() {
+            info!("Load {} banned peers from {:?}", peers.len(), path);
+        }
+        Ok(Self { path, peers })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Ban the peer for `duration` from now, the ban of a banned peer is replaced.
+    pub fn ban(
+        &mut self,
+        peer_id: PeerId,
+        duration: Duration,
+        reason: String,
+    ) -> Result<BannedPeer> {
+        let banned_peer = BannedPeer {
+            peer_id: peer_id.clone(),
+            banned_until: get_unix_ts_as_secs().saturating_add(duration.as_secs()),
+            reason,
+        };
+        self.peers.insert(peer_id, banned_peer.clone());
+        self.save()?;
+        Ok(banned_peer)
+    }
+
+    /// Lift the ban of the peer, return whether the peer was banned.
+    pub fn unban(&mut self, peer_id: &PeerId) -> Result<bool> {
+        let banned = self.is_banned(peer_id);
+        if self.peers.remove(peer_id).is_some() {
+            self.save()?;
+        }
+        Ok(banned)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.peers
+            .get(peer_id)
+            .map(|peer| peer.banned_until > get_unix_ts_as_secs())
+            .unwrap_or(false)
+    }
+
+    /// The peers still banned, sorted by the ban expire time.
+    pub fn banned_peers(&self) -> Vec<BannedPeer> {
+        let now = get_unix_ts_as_secs();
+        let mut peers = self
+            .peers
+            .values()
+            .filter(|peer| peer.banned_until > now)
+            .cloned()
+            .collect::<Vec<_>>();
+        peers.sort_by_key(|peer| peer.banned_until);
+        peers
+    }
+
+    /// Write the unexpired bans to a temp file, then rename it to the ban list file.
+    fn save(&self) -> Result<()> {
+        let tmp_path = self.path.with_extension("new");
+        fs::write(
+            tmp_path.as_path(),
+            serde_json::to_vec_pretty(&self.banned_peers())?,
+        )?;
+        fs::rename(tmp_path.as_path(), self.path.as_path())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ban_list() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("banned_peers.json");
+        let peer = PeerId::random();
+        let expired_peer = PeerId::random();
+
+        let mut ban_list = BanList::load(path.clone())?;
+        assert!(ban_list.banned_peers().is_empty());
+        ban_list.ban(peer.clone(), Duration::from_secs(3600), "test".to_string())?;
+        ban_list.ban(
+            expired_peer.clone(),
+            Duration::from_secs(0),
+            "test".to_string(),
+        )?;
+        assert!(ban_list.is_banned(&peer));
+        assert!(!ban_list.is_banned(&expired_peer));
+
+        // the ban survives reload.
+        let mut ban_list = BanList::load(path.clone())?;
+        assert!(ban_list.is_banned(&peer));
+        assert_eq!(ban_list.banned_peers().len(), 1);
+
+        assert!(ban_list.unban(&peer)?);
+        assert!(!ban_list.unban(&peer)?);
+        let ban_list = BanList::load(path)?;
+        assert!(!ban_list.is_banned(&peer));
+        Ok(())
+    }
+}
@@ -13,6 +13,10 @@ pub fn get_unix_ts_as_millis() -> u128 {
     get_unix_duration().as_millis()
 }
 
+pub fn get_unix_ts_as_secs() -> u64 {
+    get_unix_duration().as_secs()
+}
+
 fn get_unix_duration() -> Duration {
     let start = SystemTime::now();
     start
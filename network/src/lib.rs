@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #![deny(clippy::integer_arithmetic)]
+pub mod ban_list;
 mod broadcast_score_metrics;
 pub mod helper;
 mod network_metrics;
@@ -11,7 +12,7 @@ pub mod worker;
 
 pub use network_api::messages::*;
 
-pub use helper::{get_unix_ts, get_unix_ts_as_millis};
+pub use helper::{get_unix_ts, get_unix_ts_as_millis, get_unix_ts_as_secs};
 pub use service::NetworkActorService;
 pub use service_ref::NetworkServiceRef;
 pub use worker::build_network_worker;
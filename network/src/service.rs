@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::ban_list::BanList;
 use crate::broadcast_score_metrics::BROADCAST_SCORE_METRICS;
 use crate::network_metrics::NetworkMetrics;
 use crate::{build_network_worker, Announcement};
@@ -8,14 +9,15 @@ use anyhow::{format_err, Result};
 use bytes::Bytes;
 use futures::future::{abortable, AbortHandle};
 use futures::FutureExt;
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
 use lru::LruCache;
 use network_api::messages::{
-    AnnouncementType, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerEvent,
-    PeerMessage, PeerReputations, ReportReputation, TransactionsMessage,
+    AnnouncementType, BanPeer, GetBannedPeers, GetPeerById, GetPeerSet, GetSelfPeer,
+    NotificationMessage, PeerEvent, PeerMessage, PeerReputations, ReportReputation,
+    TransactionsMessage, UnbanPeer,
 };
 use network_api::peer_score::{BlockBroadcastEntry, HandleState, LinearScore, Score};
-use network_api::{BroadcastProtocolFilter, NetworkActor, PeerMessageHandler};
+use network_api::{BroadcastProtocolFilter, NetworkActor, PeerMessageHandler, ReputationChange};
 use network_p2p::{Event, NetworkWorker};
 use rand::prelude::SliceRandom;
 use starcoin_config::NodeConfig;
@@ -37,6 +39,7 @@ use std::sync::Arc;
 pub struct NetworkActorService {
     worker: Option<NetworkWorker>,
     inner: Inner,
+    ban_list: BanList,
 
     network_worker_handle: Option<AbortHandle>,
 }
@@ -60,11 +63,13 @@ impl NetworkActorService {
             rpc,
         )?;
         let service = worker.service().clone();
+        let ban_list = BanList::load(config.network.banned_peers_file())?;
         //let self_info = PeerInfo::new(config.network.self_peer_id(), chain_info);
         let inner = Inner::new(config, self_info, service, peer_message_handler)?;
         Ok(Self {
             worker: Some(worker),
             inner,
+            ban_list,
             network_worker_handle: None,
         })
     }
@@ -72,6 +77,57 @@ impl NetworkActorService {
     pub fn network_service(&self) -> Arc<network_p2p::NetworkService> {
         self.inner.network_service.clone()
     }
+
+    /// Drop the connection of the banned peer, and lower its reputation to stop the peerset
+    /// reconnecting it.
+    fn disconnect_banned_peer(&self, peer_id: PeerId) {
+        let peer_id: network_p2p::PeerId = peer_id.into();
+        self.inner
+            .network_service
+            .report_peer(peer_id, ReputationChange::new_fatal("Banned"));
+        for protocol in self.inner.config.network.supported_network_protocols() {
+            self.inner
+                .network_service
+                .disconnect_peer(peer_id, protocol);
+        }
+    }
+
+    /// Raise the reputation lowered by the ban back to neutral, so the peerset connects the
+    /// unbanned peer again.
+    fn restore_unbanned_peer(&self, peer_id: PeerId, ctx: &mut ServiceContext<Self>) {
+        let network_service = self.inner.network_service.clone();
+        let peer_id: network_p2p::PeerId = peer_id.into();
+        let rx = network_service.reputations(i32::min_value());
+        ctx.spawn(async move {
+            let reputation = match rx.await {
+                Ok(reputations) => reputations
+                    .into_iter()
+                    .find(|(id, _)| *id == peer_id)
+                    .map(|(_, reputation)| reputation),
+                Err(e) => {
+                    debug!("Get reputation of peer {:?} failed: {}", peer_id, e);
+                    None
+                }
+            };
+            if let Some(reputation) = reputation.filter(|reputation| *reputation < 0) {
+                network_service.report_peer(
+                    peer_id,
+                    ReputationChange::new(reputation.saturating_neg(), "Unbanned"),
+                );
+            }
+        });
+    }
+
+    /// The reserved peers are always reconnected by the peerset, so they can not be banned.
+    fn is_reserved_peer(&self, peer_id: &PeerId) -> bool {
+        let peer_id: network_p2p::PeerId = peer_id.clone().into();
+        self.inner
+            .config
+            .network
+            .reserved_peers()
+            .iter()
+            .any(|peer| peer.peer_id == peer_id)
+    }
 }
 
 impl ActorService for NetworkActorService {
@@ -84,6 +140,14 @@ impl ActorService for NetworkActorService {
             .ok_or_else(|| format_err!("Network worker should init before started."))?;
         let event_stream = self.inner.network_service.event_stream("network");
         ctx.add_stream(event_stream);
+        for banned_peer in self.ban_list.banned_peers() {
+            if self.is_reserved_peer(&banned_peer.peer_id) {
+                info!("Unban reserved peer {:?}", banned_peer.peer_id);
+                self.ban_list.unban(&banned_peer.peer_id)?;
+            } else {
+                self.disconnect_banned_peer(banned_peer.peer_id);
+            }
+        }
         let (fut, abort_handle) = abortable(worker);
         self.network_worker_handle = Some(abort_handle);
         ctx.spawn(fut.then(|result| async {
@@ -125,6 +189,12 @@ impl EventHandler<Self, Event> for NetworkActorService {
                 notif_protocols,
                 rpc_protocols,
             } => {
+                let peer_id: PeerId = remote.clone().into();
+                if self.ban_list.is_banned(&peer_id) {
+                    debug!("Refuse banned peer {:?}", peer_id);
+                    self.disconnect_banned_peer(peer_id);
+                    return;
+                }
                 //TODO Refactor PeerEvent for handle protocol and substream.
                 // Currently, every notification stream open will trigger a PeerEvent, so it will trigger repeat event.
                 debug!(
@@ -172,6 +242,33 @@ impl EventHandler<Self, ReportReputation> for NetworkActorService {
     }
 }
 
+impl EventHandler<Self, BanPeer> for NetworkActorService {
+    fn handle_event(&mut self, msg: BanPeer, _ctx: &mut ServiceContext<NetworkActorService>) {
+        if self.is_reserved_peer(&msg.peer_id) {
+            warn!(
+                "Refuse to ban reserved peer {:?}, reason: {}",
+                msg.peer_id, msg.reason
+            );
+            return;
+        }
+        let duration = msg
+            .duration
+            .unwrap_or_else(|| self.inner.config.network.ban_duration());
+        info!(
+            "Ban peer {:?} for {:?}, reason: {}",
+            msg.peer_id, duration, msg.reason
+        );
+        if let Err(e) = self.ban_list.ban(msg.peer_id.clone(), duration, msg.reason) {
+            warn!(
+                "Save ban list to {:?} failed: {:?}",
+                self.ban_list.path(),
+                e
+            );
+        }
+        self.disconnect_banned_peer(msg.peer_id);
+    }
+}
+
 impl EventHandler<Self, NotificationMessage> for NetworkActorService {
     fn handle_event(
         &mut self,
@@ -257,6 +354,31 @@ impl ServiceHandler<Self, GetPeerById> for NetworkActorService {
     }
 }
 
+impl ServiceHandler<Self, UnbanPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: UnbanPeer,
+        ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <UnbanPeer as ServiceRequest>::Response {
+        info!("Unban peer {:?}", msg.peer_id);
+        let banned = self.ban_list.unban(&msg.peer_id)?;
+        if banned {
+            self.restore_unbanned_peer(msg.peer_id, ctx);
+        }
+        Ok(banned)
+    }
+}
+
+impl ServiceHandler<Self, GetBannedPeers> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: GetBannedPeers,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <GetBannedPeers as ServiceRequest>::Response {
+        self.ban_list.banned_peers()
+    }
+}
+
 impl ServiceHandler<Self, GetSelfPeer> for NetworkActorService {
     fn handle(
         &mut self,
@@ -9,7 +9,7 @@ use futures::channel::oneshot::Receiver;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use log::warn;
-use network_api::messages::NotificationMessage;
+use network_api::messages::{BanPeer, BannedPeer, GetBannedPeers, NotificationMessage, UnbanPeer};
 use network_api::{NetworkService, PeerProvider, ReputationChange, SupportedRpcProtocol};
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::{IfDisconnected, Multiaddr, RequestFailure};
@@ -19,6 +19,7 @@ use starcoin_types::peer_info::PeerId;
 use starcoin_types::peer_info::PeerInfo;
 use std::borrow::Cow;
 use std::sync::Arc;
+use std::time::Duration;
 
 //TODO Service registry should support custom service ref.
 #[derive(Clone)]
@@ -55,6 +56,10 @@ impl PeerProvider for NetworkServiceRef {
         self.service_ref.report_peer(peer_id, cost_benefit)
     }
 
+    fn ban_peer(&self, peer_id: PeerId, reason: String) {
+        self.service_ref.ban_peer(peer_id, reason)
+    }
+
     fn reputations(
         &self,
         reputation_threshold: i32,
@@ -128,6 +133,40 @@ impl NetworkServiceRef {
             .map_err(|e| format_err!("{:?}", e))
     }
 
+    /// Add a reserved peer, which is always kept connected and never evicted by reputation.
+    pub fn add_reserved_peer(&self, peer: String) -> Result<()> {
+        self.network_service
+            .add_reserved_peer(peer)
+            .map_err(|e| format_err!("{:?}", e))
+    }
+
+    pub fn remove_reserved_peer(&self, peer_id: PeerId) {
+        self.network_service.remove_reserved_peer(peer_id.into())
+    }
+
+    /// Ban the peer for `duration`, or the configured ban duration if it is absent.
+    pub fn ban_peer_for(
+        &self,
+        peer_id: PeerId,
+        duration: Option<Duration>,
+        reason: String,
+    ) -> Result<()> {
+        self.service_ref.notify(BanPeer {
+            peer_id,
+            duration,
+            reason,
+        })?;
+        Ok(())
+    }
+
+    pub async fn unban_peer(&self, peer_id: PeerId) -> Result<bool> {
+        self.service_ref.send(UnbanPeer { peer_id }).await?
+    }
+
+    pub async fn banned_peers(&self) -> Result<Vec<BannedPeer>> {
+        self.service_ref.send(GetBannedPeers).await
+    }
+
     pub async fn network_state(&self) -> Result<NetworkState> {
         self.network_service
             .network_state()
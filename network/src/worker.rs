@@ -7,7 +7,7 @@ use futures::channel::mpsc::channel;
 use futures::prelude::*;
 use log::{debug, error, info};
 use network_api::PeerInfo;
-use network_p2p::config::{RequestResponseConfig, TransportConfig};
+use network_p2p::config::{NonReservedPeerMode, RequestResponseConfig, TransportConfig};
 use network_p2p::{
     identity, NetworkConfiguration, NetworkWorker, NodeKeyConfig, Params, ProtocolId, Secret,
 };
@@ -75,6 +75,13 @@ pub fn build_network_worker(
     let boot_nodes = network_config.seeds();
 
     info!("Final bootstrap seeds: {:?}", boot_nodes);
+    let reserved_nodes = network_config.reserved_peers();
+    let non_reserved_mode = if network_config.reserved_only() {
+        info!("Only connect to reserved peers: {:?}", reserved_nodes);
+        NonReservedPeerMode::Deny
+    } else {
+        NonReservedPeerMode::Accept
+    };
     let self_info = PeerInfo::new(
         network_config.self_peer_id(),
         chain_info.clone(),
@@ -96,6 +103,8 @@ pub fn build_network_worker(
         },
         in_peers: network_config.max_incoming_peers(),
         out_peers: network_config.max_outgoing_peers(),
+        reserved_nodes,
+        non_reserved_mode,
         notifications_protocols: protocols,
         request_response_protocols: rpc_protocols,
         transport: transport_config,
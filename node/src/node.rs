@@ -15,7 +15,7 @@ use starcoin_account_service::{AccountEventService, AccountService, AccountStora
 use starcoin_block_relayer::BlockRelayer;
 use starcoin_chain_notify::ChainNotifyHandlerService;
 use starcoin_chain_service::ChainReaderService;
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, StatePruneMode};
 use starcoin_genesis::{Genesis, GenesisError};
 use starcoin_logger::prelude::*;
 use starcoin_logger::structured_log::set_global_logger;
@@ -200,10 +200,13 @@ impl NodeService {
         registry.put_shared(logger_handle).await?;
 
         let bus = registry.service_ref::<BusService>().await?;
-        let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
-            CacheStorage::new_with_capacity(config.storage.cache_size()),
-            DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
-        ))?);
+        let storage = Arc::new(
+            Storage::new(StorageInstance::new_cache_and_db_instance(
+                CacheStorage::new_with_capacity(config.storage.cache_size()),
+                DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
+            ))?
+            .with_state_prune(config.storage.state_prune_mode() == StatePruneMode::Prune),
+        );
         registry.put_shared(storage.clone()).await?;
         let (chain_info, genesis) =
             Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
@@ -218,7 +221,8 @@ impl NodeService {
 
         let vault_config = &config.vault;
         let account_storage =
-            AccountStorage::create_from_path(vault_config.dir(), config.storage.rocksdb_config())?;
+            AccountStorage::create_from_path(vault_config.dir(), config.storage.rocksdb_config())?
+                .with_key_derivation(vault_config.key_derivation());
         registry
             .put_shared::<AccountStorage>(account_storage.clone())
             .await?;
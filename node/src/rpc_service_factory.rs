@@ -48,13 +48,19 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                 ChainRpcImpl::new(config.clone(), genesis.block().id(), service_ref.clone())
             });
         let txpool_service = ctx.get_shared::<TxPoolService>()?;
-        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        let chain_service = ctx.service_ref::<ChainReaderService>()?.clone();
+        let txpool_api = Some(TxPoolRpcImpl::new(
+            config.clone(),
+            txpool_service.clone(),
+            chain_service.clone(),
+        ));
 
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
-            .map(|service_ref| StateRpcImpl::new(service_ref.clone(), storage.clone()));
+            .map(|service_ref| {
+                StateRpcImpl::new(service_ref.clone(), chain_service.clone(), storage.clone())
+            });
         let chain_state_service = ctx.service_ref::<ChainStateService>()?.clone();
-        let chain_service = ctx.service_ref::<ChainReaderService>()?.clone();
         let account_service = ctx.service_ref_opt::<AccountService>()?.cloned();
         let account_api = account_service.clone().map(|service_ref| {
             AccountRpcImpl::new(
@@ -67,23 +73,26 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         });
         let pubsub_service = ctx.service_ref::<PubSubService>()?.clone();
         let pubsub_api = Some(PubSubImpl::new(pubsub_service));
-        let debug_api = Some(DebugRpcImpl::new(config.clone(), log_handler));
+        let dev_playground = PlaygroudService::new(storage.clone());
+        let debug_api = Some(DebugRpcImpl::new(
+            config.clone(),
+            log_handler,
+            chain_service.clone(),
+            storage,
+            dev_playground.clone(),
+        ));
         let miner_api = ctx
             .service_ref_opt::<MinerService>()?
             .map(|service_ref| MinerRpcImpl::new(service_ref.clone()));
 
-        let contract_api = {
-            let dev_playground = PlaygroudService::new(storage);
-
-            ContractRpcImpl::new(
-                config.clone(),
-                account_service,
-                txpool_service,
-                chain_state_service,
-                chain_service,
-                dev_playground,
-            )
-        };
+        let contract_api = ContractRpcImpl::new(
+            config.clone(),
+            account_service,
+            txpool_service,
+            chain_state_service,
+            chain_service,
+            dev_playground,
+        );
 
         Ok(RpcService::new_with_api(
             config,
@@ -6,7 +6,7 @@ use jsonrpc_derive::rpc;
 pub use self::gen_client::Client as AccountClient;
 use crate::types::{StrView, TransactionRequest};
 use crate::FutureResult;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, MnemonicAccountInfo};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
@@ -77,4 +77,17 @@ pub trait AccountApi {
 
     #[rpc(name = "account.accepted_tokens")]
     fn accepted_tokens(&self, address: AccountAddress) -> FutureResult<Vec<TokenCode>>;
+
+    /// Create the HD wallet from `mnemonic`, a new mnemonic is generated if it is absent,
+    /// and return the mnemonic with the first account derived from it.
+    #[rpc(name = "account.create_from_mnemonic")]
+    fn create_from_mnemonic(
+        &self,
+        mnemonic: Option<String>,
+        password: String,
+    ) -> FutureResult<MnemonicAccountInfo>;
+
+    /// Derive the account at `index` from the HD wallet, `password` is the password of the HD wallet.
+    #[rpc(name = "account.derive")]
+    fn derive(&self, index: u32, password: String) -> FutureResult<AccountInfo>;
 }
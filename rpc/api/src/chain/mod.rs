@@ -4,13 +4,15 @@
 pub use self::gen_client::Client as ChainClient;
 use crate::types::pubsub::EventFilter;
 use crate::types::{
-    BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventView, TransactionInfoView, TransactionView,
+    AddressTransactionInfoView, BlockHeaderView, BlockSummaryView, BlockView, ChainId,
+    ChainInfoView, EpochUncleSummaryView, FailedBlockView, FeeHistoryView, TransactionEventView,
+    TransactionInfoView, TransactionProofView, TransactionView,
 };
 use crate::FutureResult;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{BlockInfo, BlockNumber};
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
 
@@ -48,6 +50,18 @@ pub trait ChainApi {
         transaction_hash: HashValue,
     ) -> FutureResult<Option<TransactionInfoView>>;
 
+    /// Get the proof of the transaction on main chain, the transaction info is proved to the
+    /// `txn_accumulator_root` of the block including it, and the block is proved to the block
+    /// accumulator of the anchor block `anchor_block_hash`, if it is absent, use the head block.
+    /// The returned `block_accumulator_root` comes from the node, the proof only proves the
+    /// transaction when it is verified with a trusted root.
+    #[rpc(name = "chain.get_transaction_proof")]
+    fn get_transaction_proof(
+        &self,
+        transaction_hash: HashValue,
+        anchor_block_hash: Option<HashValue>,
+    ) -> FutureResult<Option<TransactionProofView>>;
+
     /// Get chain transactions infos by block id
     #[rpc(name = "chain.get_block_txn_infos")]
     fn get_block_txn_infos(&self, block_hash: HashValue) -> FutureResult<Vec<TransactionInfoView>>;
@@ -60,6 +74,35 @@ pub trait ChainApi {
         idx: u64,
     ) -> FutureResult<Option<TransactionInfoView>>;
 
+    /// Get transaction infos related to `address`, from newer to older.
+    /// `cursor` is the index in the address's transaction history to start from, if it is absent, start from the latest.
+    /// Only available when the node enables the address transaction index.
+    #[rpc(name = "chain.get_transactions_by_address")]
+    fn get_transactions_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> FutureResult<Vec<AddressTransactionInfoView>>;
+
+    /// Get the gas usage and the gas prices at `percentiles` of the latest `block_count` blocks.
+    /// Every percentile should be in [0, 100], and in ascending order.
+    #[rpc(name = "chain.fee_history")]
+    fn fee_history(&self, block_count: u64, percentiles: Vec<f64>) -> FutureResult<FeeHistoryView>;
+
+    /// Get at most `limit` blocks which failed to verify or execute, order by block hash,
+    /// start after the block hash `cursor`, pass the last block hash of a page to get the next page.
+    #[rpc(name = "chain.list_failed_blocks")]
+    fn list_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> FutureResult<Vec<FailedBlockView>>;
+
+    /// Get the failed block by hash.
+    #[rpc(name = "chain.get_failed_block")]
+    fn get_failed_block(&self, block_hash: HashValue) -> FutureResult<Option<FailedBlockView>>;
+
     #[rpc(name = "chain.get_events_by_txn_hash")]
     fn get_events_by_txn_hash(
         &self,
@@ -3,7 +3,7 @@ use jsonrpc_derive::rpc;
 pub use self::gen_client::Client as ContractClient;
 use crate::types::{
     AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunTransactionRequest,
-    StrView, TransactionOutputView,
+    StateSelector, StrView, TransactionOutputView,
 };
 use crate::FutureResult;
 use starcoin_vm_types::account_address::AccountAddress;
@@ -15,18 +15,30 @@ pub trait ContractApi {
     #[rpc(name = "contract.get_code")]
     fn get_code(&self, module_id: StrView<ModuleId>) -> FutureResult<Option<StrView<Vec<u8>>>>;
 
-    /// get resource data of `addr`
+    /// get resource data of `addr`, at the `state` if present, otherwise at the head state.
     #[rpc(name = "contract.get_resource")]
     fn get_resource(
         &self,
         addr: AccountAddress,
         resource_type: StrView<StructTag>,
+        state: Option<StateSelector>,
     ) -> FutureResult<Option<AnnotatedMoveStructView>>;
 
     /// Call a move contract, return returned move values.
+    /// The contract is called at the `state` if present, otherwise at the head state.
     #[rpc(name = "contract.call")]
-    fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>>;
+    fn call(
+        &self,
+        call: ContractCall,
+        state: Option<StateSelector>,
+    ) -> FutureResult<Vec<AnnotatedMoveValueView>>;
 
+    /// Dry run the txn at the `state` if present, otherwise at the head state.
+    /// The absent fields of the txn are always filled by the head state.
     #[rpc(name = "contract.dry_run")]
-    fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<TransactionOutputView>;
+    fn dry_run(
+        &self,
+        txn: DryRunTransactionRequest,
+        state: Option<StateSelector>,
+    ) -> FutureResult<TransactionOutputView>;
 }
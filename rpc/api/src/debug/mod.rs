@@ -3,10 +3,12 @@
 
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use starcoin_crypto::HashValue;
 use starcoin_logger::LogPattern;
 
 pub use self::gen_client::Client as DebugClient;
-use crate::types::FactoryAction;
+use crate::types::{BlockReplayView, FactoryAction, TransactionOutputView};
+use crate::FutureResult;
 
 #[rpc]
 pub trait DebugApi {
@@ -26,6 +28,17 @@ pub trait DebugApi {
     #[rpc(name = "debug.sleep")]
     fn sleep(&self, time: u64) -> Result<()>;
 
+    /// Re-verify and re-execute the failed block on its parent state, and trace every transaction
+    /// to find where the execution diverged from the block. Nothing is saved.
+    #[rpc(name = "debug.replay_failed_block")]
+    fn replay_failed_block(&self, block_hash: HashValue) -> FutureResult<BlockReplayView>;
+
+    /// Re-execute the transaction on the state before it, and return the output with the
+    /// adapter-level trace, the calls into the Move VM but not the Move functions called inside
+    /// them, and the gas profile of the execution. Nothing is saved.
+    #[rpc(name = "debug.trace_transaction")]
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionOutputView>;
+
     /// Get and set txn factory status.
     #[rpc(name = "txfactory.status")]
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool>;
@@ -5,6 +5,7 @@ pub use self::gen_client::Client as NetworkManagerClient;
 use crate::types::StrView;
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
+use network_api::messages::BannedPeer;
 use network_p2p_types::network_state::NetworkState;
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use std::borrow::Cow;
@@ -23,6 +24,30 @@ pub trait NetworkManagerApi {
     #[rpc(name = "network_manager.add_peer")]
     fn add_peer(&self, peer: String) -> FutureResult<()>;
 
+    /// Add a reserved peer, which is always kept connected and never evicted by reputation.
+    /// The format of `peer` is multiaddr/p2p/peer_id.
+    #[rpc(name = "network_manager.add_reserved_peer")]
+    fn add_reserved_peer(&self, peer: String) -> FutureResult<()>;
+
+    #[rpc(name = "network_manager.remove_reserved_peer")]
+    fn remove_reserved_peer(&self, peer_id: String) -> FutureResult<()>;
+
+    /// Ban the peer for `duration` seconds, the ban duration of the node config is used if it is absent.
+    #[rpc(name = "network_manager.ban_peer")]
+    fn ban_peer(
+        &self,
+        peer_id: String,
+        duration: Option<u64>,
+        reason: Option<String>,
+    ) -> FutureResult<()>;
+
+    /// Lift the ban of the peer, return whether the peer was banned.
+    #[rpc(name = "network_manager.unban_peer")]
+    fn unban_peer(&self, peer_id: String) -> FutureResult<bool>;
+
+    #[rpc(name = "network_manager.list_banned")]
+    fn list_banned(&self) -> FutureResult<Vec<BannedPeer>>;
+
     /// Call peer's network rpc method.
     #[rpc(name = "network_manager.call")]
     fn call_peer(
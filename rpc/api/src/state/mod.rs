@@ -9,12 +9,17 @@ use starcoin_types::{
 };
 
 pub use self::gen_client::Client as StateClient;
-use crate::types::{AccountStateSetView, StateWithProofView};
+use crate::types::{AccountStateSetView, StateSelector, StateWithProofView};
 
 #[rpc]
 pub trait StateApi {
+    /// Get the state of `access_path`, at the `state` if present, otherwise at the head state.
     #[rpc(name = "state.get")]
-    fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>>;
+    fn get(
+        &self,
+        access_path: AccessPath,
+        state: Option<StateSelector>,
+    ) -> FutureResult<Option<Vec<u8>>>;
 
     #[rpc(name = "state.get_with_proof")]
     fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProofView>;
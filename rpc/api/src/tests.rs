@@ -1,4 +1,11 @@
-use crate::types::{ContractCall, TransactionArgumentView, TypeTagView};
+use crate::types::pubsub::{FieldPredicate, PredicateOp, TypeTagPattern};
+use crate::types::{
+    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, StrView,
+    TransactionArgumentView, TypeTagView,
+};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::TypeTag;
+use starcoin_vm_types::parser::parse_type_tag;
 use starcoin_vm_types::token::stc::stc_type_tag;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
 
@@ -32,3 +39,84 @@ fn test_deserialize() {
     let v = serde_json::from_str::<ContractCall>(s).unwrap();
     println!("{:?}", v);
 }
+
+#[test]
+fn test_type_tag_pattern() {
+    let token_type = parse_type_tag("0x1::Token::Token<0x1::STC::STC>").unwrap();
+    let matches = |pattern: &str, type_tag: &TypeTag| {
+        pattern.parse::<TypeTagPattern>().unwrap().matches(type_tag)
+    };
+    assert!(matches("*", &token_type));
+    assert!(matches("0x1::Token::Token", &token_type));
+    assert!(matches("0x1::Token::*", &token_type));
+    assert!(matches("0x1::Token::Token<*>", &token_type));
+    assert!(matches("0x1::Token::Token<0x1::STC::STC>", &token_type));
+    assert!(!matches(
+        "0x1::Token::Token<0x1::Dummy::DummyToken>",
+        &token_type
+    ));
+    assert!(!matches("0x1::Token::Token<*, *>", &token_type));
+    assert!(!matches("0x1::Account::*", &token_type));
+    assert!(!matches("u64", &token_type));
+    assert!(matches("u64", &TypeTag::U64));
+    assert!(matches(
+        "vector<0x1::Token::*>",
+        &TypeTag::Vector(Box::new(token_type.clone()))
+    ));
+
+    let pattern: TypeTagPattern = "0x1::Token::Token<vector<*>>".parse().unwrap();
+    assert_eq!(pattern, pattern.to_string().parse().unwrap());
+    assert!("0x1::Token::*<*>".parse::<TypeTagPattern>().is_err());
+}
+
+#[test]
+fn test_field_predicate() {
+    let data = AnnotatedMoveValueView::Struct(AnnotatedMoveStructView {
+        abilities: 0,
+        type_: StrView(
+            parse_type_tag("0x1::Account::DepositEvent")
+                .map(|type_tag| match type_tag {
+                    TypeTag::Struct(struct_tag) => struct_tag,
+                    _ => unreachable!(),
+                })
+                .unwrap(),
+        ),
+        value: vec![
+            (
+                Identifier::new("amount").unwrap(),
+                AnnotatedMoveValueView::U128(StrView(100)),
+            ),
+            (
+                Identifier::new("metadata").unwrap(),
+                AnnotatedMoveValueView::Bytes(StrView(b"memo".to_vec())),
+            ),
+            (
+                Identifier::new("flags").unwrap(),
+                AnnotatedMoveValueView::Vector(vec![AnnotatedMoveValueView::Bool(true)]),
+            ),
+        ],
+    });
+    let matches = |path: &str, op: PredicateOp, value: &str| {
+        FieldPredicate {
+            path: path.to_string(),
+            op,
+            value: value.to_string(),
+        }
+        .matches(&data)
+    };
+    assert!(matches("amount", PredicateOp::Eq, "100"));
+    assert!(matches("amount", PredicateOp::Ge, "100"));
+    assert!(matches("amount", PredicateOp::Lt, "101"));
+    assert!(!matches("amount", PredicateOp::Gt, "100"));
+    assert!(matches("metadata", PredicateOp::Eq, "memo"));
+    assert!(matches("metadata", PredicateOp::Eq, "0x6d656d6f"));
+    assert!(!matches("metadata", PredicateOp::Gt, "memo"));
+    assert!(matches("flags.0", PredicateOp::Eq, "true"));
+    assert!(!matches("flags.1", PredicateOp::Eq, "true"));
+    assert!(!matches("flags", PredicateOp::Eq, "true"));
+    assert!(!matches("unknown", PredicateOp::Ne, "0"));
+
+    let predicate: FieldPredicate =
+        serde_json::from_str(r#"{"path":"amount","op":"ge","value":"10"}"#).unwrap();
+    assert_eq!(predicate.op, PredicateOp::Ge);
+}
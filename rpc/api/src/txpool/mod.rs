@@ -19,7 +19,7 @@ pub trait TxPoolApi {
     #[rpc(name = "txpool.submit_hex_transaction")]
     fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue>;
 
-    /// return current gas price
+    /// Estimate the gas price by the gas prices of recent blocks and the pool pressure.
     #[rpc(name = "txpool.gas_price")]
     fn gas_price(&self) -> FutureResult<StrView<u64>>;
     /// get all pending txns in txpool of given sender.
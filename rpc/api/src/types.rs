@@ -12,7 +12,10 @@ use jsonrpc_core_client::RpcChannel;
 use serde::de::Error;
 use serde::{Deserialize, Serializer};
 use serde::{Deserializer, Serialize};
+use starcoin_accumulator::AccumulatorProof;
+use starcoin_chain_proof::{TransactionInfoWithProof, TransactionProof};
 use starcoin_crypto::{CryptoMaterialError, HashValue, ValidCryptoMaterialStringExt};
+use starcoin_dev::gas_profile::GasProfile;
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
 use starcoin_service_registry::ServiceRequest;
 use starcoin_state_api::{StateProof, StateWithProof};
@@ -51,7 +54,7 @@ use std::str::FromStr;
 
 pub type ByteCode = Vec<u8>;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AnnotatedMoveStructView {
     pub abilities: u8,
     pub type_: StructTagView,
@@ -71,7 +74,7 @@ impl From<AnnotatedMoveStruct> for AnnotatedMoveStructView {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum AnnotatedMoveValueView {
     U8(u8),
     U64(StrView<u64>),
@@ -171,6 +174,12 @@ pub struct DryRunTransactionRequest {
     pub transaction: TransactionRequest,
     /// Sender's public key
     pub sender_public_key: Option<StrView<AccountPublicKey>>,
+    /// Trace the execution of the transaction.
+    #[serde(default)]
+    pub trace: bool,
+    /// Profile the gas used by the transaction.
+    #[serde(default)]
+    pub gas_profile: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -755,6 +764,96 @@ impl From<DiscardedVMStatus> for TransactionVMStatus {
     }
 }
 
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AddressTransactionInfoView {
+    /// The position of the transaction in the address's transaction history,
+    /// `index - 1` is the cursor of the next page.
+    pub index: StrView<u64>,
+    pub transaction_info: TransactionInfoView,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockFeeView {
+    pub number: StrView<BlockNumber>,
+    pub gas_used: StrView<u64>,
+    pub gas_limit: StrView<u64>,
+    /// `gas_used / gas_limit` of the block.
+    pub gas_used_ratio: f64,
+    /// Gas prices at the requested percentiles, weighted by the gas used of user transactions.
+    pub gas_price_percentiles: Vec<StrView<u64>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeeHistoryView {
+    pub oldest_block: Option<StrView<BlockNumber>>,
+    /// Ordered from the oldest block to the newest block.
+    pub blocks: Vec<BlockFeeView>,
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FailedBlockView {
+    pub block: BlockView,
+    /// The peer which sent the block, None if the block is not from network.
+    pub peer_id: Option<PeerId>,
+    /// The reason why the block failed.
+    pub failed: String,
+}
+
+impl FailedBlockView {
+    pub fn try_from_failed_block(
+        failed_block: (Block, Option<PeerId>, String),
+        thin: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let (block, peer_id, failed) = failed_block;
+        Ok(Self {
+            block: BlockView::try_from_block(block, thin)?,
+            peer_id,
+            failed,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReplayTraceView {
+    pub transaction_hash: HashValue,
+    pub status: TransactionVMStatus,
+    pub gas_used: StrView<u64>,
+    /// The gas used by the kept transactions of the block up to and including this transaction.
+    pub cumulative_gas_used: StrView<u64>,
+    /// The state root after this transaction, absent if the transaction is discarded.
+    pub state_root_hash: Option<HashValue>,
+    pub transaction_info_id: Option<HashValue>,
+    pub event_count: StrView<u64>,
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ReplayMismatchView {
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockReplayView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    pub parent_hash: HashValue,
+    /// The reason recorded when the block failed.
+    pub failed: String,
+    /// The error of verifying the block against its parent, absent if passed.
+    pub verify_error: Option<String>,
+    /// The error of executing the block transactions, absent if all of them are executed.
+    pub execute_error: Option<String>,
+    /// The first one is the block metadata transaction.
+    pub transactions: Vec<TransactionReplayTraceView>,
+    /// The transactions not executed because the block gas limit is reached.
+    pub unexecuted_transactions: Vec<HashValue>,
+    /// The block header fields which do not match the replay result.
+    pub mismatches: Vec<ReplayMismatchView>,
+    /// The index in `transactions` of the first transaction where the execution diverged.
+    pub diverged_at: Option<StrView<u64>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 pub struct TransactionEventView {
     pub block_hash: Option<HashValue>,
@@ -762,11 +861,18 @@ pub struct TransactionEventView {
     pub transaction_hash: Option<HashValue>,
     // txn index in block
     pub transaction_index: Option<u32>,
+    // event index in txn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_index: Option<u32>,
 
     pub data: StrView<Vec<u8>>,
     pub type_tag: TypeTag,
     pub event_key: EventKey,
     pub event_seq_number: StrView<u64>,
+    /// The event is removed from the main chain by a chain reorg, only present in the events
+    /// subscription.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
 }
 
 impl From<ContractEventInfo> for TransactionEventView {
@@ -776,10 +882,12 @@ impl From<ContractEventInfo> for TransactionEventView {
             block_number: Some(info.block_number.into()),
             transaction_hash: Some(info.transaction_hash),
             transaction_index: Some(info.transaction_index),
+            event_index: Some(info.event_index),
             data: StrView(info.event.event_data().to_vec()),
             type_tag: info.event.type_tag().clone(),
             event_key: *info.event.key(),
             event_seq_number: info.event.sequence_number().into(),
+            removed: false,
         }
     }
 }
@@ -790,10 +898,12 @@ impl From<ContractEvent> for TransactionEventView {
             block_number: None,
             transaction_hash: None,
             transaction_index: None,
+            event_index: None,
             data: StrView(event.event_data().to_vec()),
             type_tag: event.type_tag().clone(),
             event_key: *event.key(),
             event_seq_number: event.sequence_number().into(),
+            removed: false,
         }
     }
 }
@@ -811,10 +921,12 @@ impl TransactionEventView {
             block_number: block_number.map(Into::into),
             transaction_hash,
             transaction_index,
+            event_index: None,
             data: StrView(contract_event.event_data().to_vec()),
             type_tag: contract_event.type_tag().clone(),
             event_key: *contract_event.key(),
             event_seq_number: contract_event.sequence_number().into(),
+            removed: false,
         }
     }
 }
@@ -824,6 +936,12 @@ pub struct TransactionOutputView {
     pub gas_used: StrView<u64>,
     pub status: TransactionVMStatus,
     pub write_set: Vec<TransactionOutputAction>,
+    /// The execution trace, only present when the trace is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<TransactionTraceView>,
+    /// The gas profile, only present when the gas profile is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfileView>,
 }
 
 impl From<TransactionOutput> for TransactionOutputView {
@@ -840,10 +958,103 @@ impl From<TransactionOutput> for TransactionOutputView {
                     action: w.into(),
                 })
                 .collect(),
+            trace: None,
+            gas_profile: None,
         }
     }
 }
 
+/// A call the adapter made into the Move VM, its gas includes the Move functions called inside it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CallTraceView {
+    /// One of prologue, script, script_function, publish_module, init_script, epilogue and block_prologue.
+    pub kind: String,
+    pub module: Option<ModuleIdView>,
+    pub function: Option<Identifier>,
+    pub ty_args: Vec<TypeTagView>,
+    pub gas_used: StrView<u64>,
+    /// The gas of the call by category, instruction and native, empty if it is not attributed.
+    pub gas_by_category: BTreeMap<String, StrView<u64>>,
+    /// The error returned by the call, absent if the call succeeded.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResourceTraceView {
+    pub address: AccountAddress,
+    pub struct_tag: StructTagView,
+    /// The value before the transaction, absent if the resource does not exist.
+    pub before: Option<AnnotatedMoveStructView>,
+    /// The value after the transaction, absent if the resource is deleted or only read.
+    pub after: Option<AnnotatedMoveStructView>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventTraceView {
+    #[serde(flatten)]
+    pub event: TransactionEventView,
+    pub decoded_data: AnnotatedMoveValueView,
+}
+
+/// The execution steps of a transaction at the level of the vm adapter, which are the calls into
+/// the Move VM, and the resources and events the transaction touched. It is not a call stack, the
+/// Move functions called inside a call are not recorded, the gas of a call includes them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionTraceView {
+    pub calls: Vec<CallTraceView>,
+    /// The gas charged for the transaction size.
+    pub intrinsic_gas: StrView<u64>,
+    /// The gas charged for writing the global storage.
+    pub global_write_gas: StrView<u64>,
+    pub resources_read: Vec<ResourceTraceView>,
+    pub resources_written: Vec<ResourceTraceView>,
+    pub events: Vec<EventTraceView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GasProfileEntryView {
+    /// The path charged the gas, the outermost element first: the charge category, or the kind
+    /// and the function of a call followed by the gas category of it.
+    pub stack: Vec<String>,
+    pub gas_used: StrView<u64>,
+}
+
+/// The gas of a transaction attributed by charge category, by the function called by the vm, and
+/// by the instruction and native gas categories of the call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GasProfileView {
+    pub gas_used: StrView<u64>,
+    pub entries: Vec<GasProfileEntryView>,
+}
+
+impl From<GasProfile> for GasProfileView {
+    fn from(profile: GasProfile) -> Self {
+        Self {
+            gas_used: profile.gas_used().into(),
+            entries: profile
+                .entries
+                .into_iter()
+                .map(|entry| GasProfileEntryView {
+                    stack: entry.stack,
+                    gas_used: entry.gas_used.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl GasProfileView {
+    /// Format the profile as folded stacks, the input format of flamegraph tools,
+    /// one `path;path gas` line for every entry which used gas.
+    pub fn to_folded_stacks(&self) -> String {
+        self.entries
+            .iter()
+            .filter(|entry| entry.gas_used.0 > 0)
+            .map(|entry| format!("{} {}\n", entry.stack.join(";"), entry.gas_used.0))
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionOutputAction {
     #[serde(flatten)]
@@ -1003,6 +1214,66 @@ impl From<StateWithProofView> for StateWithProof {
     }
 }
 
+/// The proof of a transaction on main chain, the block header and the transaction info are bcs
+/// encoded, so the client can verify the proof without trusting the node.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionProofView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    pub transaction_hash: HashValue,
+    /// The bcs encoded header of the block including the transaction.
+    pub block_header: StrView<Vec<u8>>,
+    /// The bcs encoded transaction info.
+    pub transaction_info: StrView<Vec<u8>>,
+    /// The index of the transaction info in the transaction accumulator.
+    pub leaf_index: StrView<u64>,
+    pub txn_info_proof: AccumulatorProof,
+    /// The block whose block accumulator the block is proved to.
+    pub anchor_block_hash: HashValue,
+    /// The root of the block accumulator of the anchor block, returned by the node,
+    /// so it must be compared with a trusted one.
+    pub block_accumulator_root: HashValue,
+    pub block_proof: AccumulatorProof,
+}
+
+impl TryFrom<TransactionProof> for TransactionProofView {
+    type Error = anyhow::Error;
+
+    fn try_from(proof: TransactionProof) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_hash: proof.block_header.id(),
+            block_number: proof.block_header.number().into(),
+            transaction_hash: proof.transaction_info().transaction_hash(),
+            block_header: StrView(proof.block_header.encode()?),
+            transaction_info: StrView(proof.txn_info_proof.transaction_info.encode()?),
+            leaf_index: proof.txn_info_proof.leaf_index.into(),
+            txn_info_proof: proof.txn_info_proof.proof,
+            anchor_block_hash: proof.anchor_block_id,
+            block_accumulator_root: proof.block_accumulator_root,
+            block_proof: proof.block_proof,
+        })
+    }
+}
+
+impl TryFrom<TransactionProofView> for TransactionProof {
+    type Error = anyhow::Error;
+
+    /// Only the bcs encoded fields are used, the plain fields of the view are not trusted.
+    fn try_from(view: TransactionProofView) -> Result<Self, Self::Error> {
+        Ok(TransactionProof::new(
+            BlockHeader::decode(view.block_header.0.as_slice())?,
+            TransactionInfoWithProof::new(
+                TransactionInfo::decode(view.transaction_info.0.as_slice())?,
+                view.leaf_index.0,
+                view.txn_info_proof,
+            ),
+            view.anchor_block_hash,
+            view.block_accumulator_root,
+            view.block_proof,
+        ))
+    }
+}
+
 #[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
 pub struct StrView<T>(pub T);
 
@@ -1260,6 +1531,17 @@ pub struct ContractCall {
     pub args: Vec<TransactionArgumentView>,
 }
 
+/// Select a state of the chain to read, the state after the block with the given number on the
+/// main chain, the state after the block with the given hash on the main chain,
+/// or the state with the given root.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateSelector {
+    BlockNumber(BlockNumber),
+    BlockHash(HashValue),
+    StateRoot(HashValue),
+}
+
 #[derive(Debug, Clone)]
 pub struct ConnectLocal;
 
@@ -1269,7 +1551,8 @@ impl ServiceRequest for ConnectLocal {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{ByteCodeOrScriptFunction, FunctionId};
+    use crate::types::{ByteCodeOrScriptFunction, FunctionId, GasProfileView};
+    use starcoin_dev::gas_profile::{GasProfile, GasProfileEntry};
     use starcoin_types::account_address::AccountAddress;
 
     #[test]
@@ -1290,4 +1573,44 @@ mod tests {
         let bytecode: ByteCodeOrScriptFunction = "0x123432ab34".parse().unwrap();
         assert!(matches!(bytecode, ByteCodeOrScriptFunction::ByteCode(_)));
     }
+
+    #[test]
+    fn test_gas_profile_view() {
+        let profile = GasProfile {
+            entries: vec![
+                GasProfileEntry {
+                    stack: vec!["intrinsic".to_string()],
+                    gas_used: 10,
+                },
+                GasProfileEntry {
+                    stack: vec![
+                        "script_function".to_string(),
+                        "0x1::M::f".to_string(),
+                        "instruction".to_string(),
+                    ],
+                    gas_used: 15,
+                },
+                GasProfileEntry {
+                    stack: vec![
+                        "script_function".to_string(),
+                        "0x1::M::f".to_string(),
+                        "native".to_string(),
+                    ],
+                    gas_used: 5,
+                },
+                GasProfileEntry {
+                    stack: vec!["global_write".to_string()],
+                    gas_used: 0,
+                },
+            ],
+        };
+        let view = GasProfileView::from(profile);
+        assert_eq!(view.gas_used.0, 30);
+        assert_eq!(view.entries.len(), 4);
+        // the entries without gas are not in the folded stacks.
+        assert_eq!(
+            view.to_folded_stacks(),
+            "intrinsic 10\nscript_function;0x1::M::f;instruction 15\nscript_function;0x1::M::f;native 5\n"
+        );
+    }
 }
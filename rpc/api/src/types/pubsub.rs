@@ -2,17 +2,30 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors;
-use crate::types::{BlockView, TransactionEventView};
+use crate::types::{
+    AnnotatedMoveValueView, BlockHeaderView, BlockView, EventTraceView, StrView,
+    TransactionEventView, TransactionInfoView, TypeTagView,
+};
 use jsonrpc_core::error::Error as JsonRpcError;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{from_value, Value};
 use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::contract_event::EventPosition;
 use starcoin_types::event::EventKey;
 use starcoin_types::filter::Filter;
+use starcoin_types::system_events::ChainReorg;
+use starcoin_types::transaction::TxStatus;
 use starcoin_types::U256;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::TypeTag;
+use starcoin_vm_types::parser::parse_type_tag;
+use std::cmp::Ordering;
 use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
 
 /// Subscription kind.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
@@ -28,6 +41,10 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Chain reorg subscription.
+    ChainReorg,
+    /// Transaction status subscription.
+    TransactionStatus,
 }
 
 /// Subscription result.
@@ -38,7 +55,11 @@ pub enum Result {
     /// Transaction hash
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventView>),
+    /// Event with the decoded event data.
+    DecodedEvent(Box<EventTraceView>),
     MintBlock(Box<MintBlock>),
+    ChainReorg(Box<ChainReorgView>),
+    TransactionStatus(Box<TransactionStatusView>),
 }
 
 impl Serialize for Result {
@@ -49,8 +70,11 @@ impl Serialize for Result {
         match *self {
             Result::Block(ref header) => header.serialize(serializer),
             Result::Event(ref evt) => evt.serialize(serializer),
+            Result::DecodedEvent(ref evt) => evt.serialize(serializer),
             Result::TransactionHash(ref hash) => hash.serialize(serializer),
             Result::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Result::ChainReorg(ref reorg) => reorg.serialize(serializer),
+            Result::TransactionStatus(ref status) => status.serialize(serializer),
         }
     }
 }
@@ -62,6 +86,8 @@ pub enum Params {
     None,
     /// Log parameters.
     Events(EventFilter),
+    /// Transaction status parameters.
+    TransactionStatus(TransactionStatusFilter),
 }
 
 impl Default for Params {
@@ -81,9 +107,12 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Params::None);
         }
         // Err(D::Error::custom("Invalid Pub-Sub parameters"));
-        from_value(v)
-            .map(Params::Events)
-            .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
+        match from_value(v.clone()) {
+            Ok(filter) => Ok(Params::Events(filter)),
+            Err(e) => from_value(v)
+                .map(Params::TransactionStatus)
+                .map_err(|_| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e))),
+        }
     }
 }
 
@@ -100,9 +129,60 @@ pub struct EventFilter {
     /// Event keys
     #[serde(default)]
     pub event_keys: Vec<EventKey>,
+    /// Event types
+    #[serde(default)]
+    pub type_tags: Vec<TypeTagView>,
+    /// Emitting addresses, the creator addresses of the event keys
+    #[serde(default)]
+    pub addrs: Vec<AccountAddress>,
     /// Limit: from latest to oldest
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Cursor: only return the events older than the position, for pagination
+    #[serde(default)]
+    pub cursor: Option<EventPosition>,
+    /// Event type patterns, only supported by the events subscription.
+    #[serde(default)]
+    pub type_patterns: Vec<TypeTagPatternView>,
+    /// Addresses of the modules defining the event types, only supported by the events subscription.
+    #[serde(default)]
+    pub module_addrs: Vec<AccountAddress>,
+    /// Predicates on the decoded event data, only supported by the events subscription.
+    #[serde(default)]
+    pub field_predicates: Vec<FieldPredicate>,
+    /// Return the events with the decoded event data, only supported by the events subscription.
+    /// An event which can not be decoded is notified as an error.
+    #[serde(default)]
+    pub decode: bool,
+    /// Notify the events of the blocks retracted by a chain reorg again with `removed: true`,
+    /// only supported by the events subscription.
+    #[serde(default)]
+    pub include_removed: bool,
+}
+
+impl EventFilter {
+    /// Whether the filter uses the fields only supported by the events subscription.
+    pub fn has_subscription_filters(&self) -> bool {
+        !self.type_patterns.is_empty()
+            || !self.module_addrs.is_empty()
+            || !self.field_predicates.is_empty()
+            || self.decode
+            || self.include_removed
+    }
+
+    pub fn subscription_filter(&self) -> EventSubscriptionFilter {
+        EventSubscriptionFilter {
+            type_patterns: self
+                .type_patterns
+                .iter()
+                .map(|pattern| pattern.0.clone())
+                .collect(),
+            module_addrs: self.module_addrs.clone(),
+            field_predicates: self.field_predicates.clone(),
+            decode: self.decode,
+            include_removed: self.include_removed,
+        }
+    }
 }
 
 impl TryInto<Filter> for EventFilter {
@@ -122,12 +202,372 @@ impl TryInto<Filter> for EventFilter {
             from_block: self.from_block.unwrap_or(0),
             to_block: self.to_block.unwrap_or(std::u64::MAX),
             event_keys: self.event_keys,
+            type_tags: self
+                .type_tags
+                .into_iter()
+                .map(|type_tag| type_tag.0)
+                .collect(),
+            addrs: self.addrs,
             limit: self.limit,
             reverse: true,
+            cursor: self.cursor,
+        })
+    }
+}
+
+/// The filters of the events subscription on the event type and the decoded event data,
+/// applied in addition to the `Filter`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventSubscriptionFilter {
+    /// If not empty, the event type must match one of the patterns.
+    pub type_patterns: Vec<TypeTagPattern>,
+    /// If not empty, the event type must be defined by a module at one of the addresses.
+    pub module_addrs: Vec<AccountAddress>,
+    /// The decoded event data must satisfy all the predicates.
+    pub field_predicates: Vec<FieldPredicate>,
+    pub decode: bool,
+    pub include_removed: bool,
+}
+
+impl EventSubscriptionFilter {
+    pub fn matching_type(&self, type_tag: &TypeTag) -> bool {
+        (self.type_patterns.is_empty()
+            || self
+                .type_patterns
+                .iter()
+                .any(|pattern| pattern.matches(type_tag)))
+            && (self.module_addrs.is_empty()
+                || matches!(type_tag, TypeTag::Struct(s) if self.module_addrs.contains(&s.address)))
+    }
+
+    pub fn matching_data(&self, data: &AnnotatedMoveValueView) -> bool {
+        self.field_predicates
+            .iter()
+            .all(|predicate| predicate.matches(data))
+    }
+
+    /// Whether the event data should be decoded to apply the filter or to return it.
+    pub fn need_decode(&self) -> bool {
+        self.decode || !self.field_predicates.is_empty()
+    }
+}
+
+/// A pattern of type tags.
+/// `*` matches any type, and a struct pattern without type arguments matches any instantiation
+/// of the struct, eg: `0x1::Account::DepositEvent`, `0x1::Account::*`, `0x1::Token::TokenCode`,
+/// `0x1::Token::Token<*>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeTagPattern {
+    Any,
+    Exact(TypeTag),
+    Vector(Box<TypeTagPattern>),
+    Struct {
+        address: AccountAddress,
+        module: Identifier,
+        /// None matches any struct of the module.
+        name: Option<Identifier>,
+        /// None matches any instantiation of the struct.
+        type_args: Option<Vec<TypeTagPattern>>,
+    },
+}
+
+impl TypeTagPattern {
+    pub fn matches(&self, type_tag: &TypeTag) -> bool {
+        match (self, type_tag) {
+            (TypeTagPattern::Any, _) => true,
+            (TypeTagPattern::Exact(expected), _) => expected == type_tag,
+            (TypeTagPattern::Vector(pattern), TypeTag::Vector(type_tag)) => {
+                pattern.matches(type_tag)
+            }
+            (
+                TypeTagPattern::Struct {
+                    address,
+                    module,
+                    name,
+                    type_args,
+                },
+                TypeTag::Struct(struct_tag),
+            ) => {
+                address == &struct_tag.address
+                    && module == &struct_tag.module
+                    && name.iter().all(|name| name == &struct_tag.name)
+                    && type_args.iter().all(|type_args| {
+                        type_args.len() == struct_tag.type_params.len()
+                            && type_args
+                                .iter()
+                                .zip(struct_tag.type_params.iter())
+                                .all(|(pattern, type_tag)| pattern.matches(type_tag))
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for TypeTagPattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "*" {
+            return Ok(TypeTagPattern::Any);
+        }
+        if let Some(inner) = s
+            .strip_prefix("vector<")
+            .and_then(|inner| inner.strip_suffix('>'))
+        {
+            return Ok(TypeTagPattern::Vector(Box::new(inner.parse()?)));
+        }
+        if !s.contains("::") {
+            return Ok(TypeTagPattern::Exact(parse_type_tag(s)?));
+        }
+        let (path, type_args) = match s.find('<') {
+            Some(start) => {
+                let args = s[start..]
+                    .strip_prefix('<')
+                    .and_then(|args| args.strip_suffix('>'))
+                    .ok_or_else(|| anyhow::format_err!("invalid type tag pattern: {}", s))?;
+                let type_args = split_type_args(args)
+                    .into_iter()
+                    .map(TypeTagPattern::from_str)
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                (&s[..start], Some(type_args))
+            }
+            None => (s, None),
+        };
+        let parts: Vec<&str> = path.split("::").map(str::trim).collect();
+        if parts.len() != 3 {
+            anyhow::bail!("invalid type tag pattern: {}", s);
+        }
+        let name = match parts[2] {
+            "*" if type_args.is_none() => None,
+            "*" => anyhow::bail!("invalid type tag pattern: {}", s),
+            name => Some(Identifier::new(name)?),
+        };
+        Ok(TypeTagPattern::Struct {
+            address: AccountAddress::from_str(parts[0])?,
+            module: Identifier::new(parts[1])?,
+            name,
+            type_args,
+        })
+    }
+}
+
+/// Split the type arguments at the top level commas.
+fn split_type_args(args: &str) -> Vec<&str> {
+    let mut splits = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                splits.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    splits.push(&args[start..]);
+    splits
+}
+
+impl fmt::Display for TypeTagPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeTagPattern::Any => write!(f, "*"),
+            TypeTagPattern::Exact(type_tag) => write!(f, "{}", type_tag),
+            TypeTagPattern::Vector(pattern) => write!(f, "vector<{}>", pattern),
+            TypeTagPattern::Struct {
+                address,
+                module,
+                name,
+                type_args,
+            } => {
+                write!(f, "0x{}::{}::", address.to_hex(), module)?;
+                match name {
+                    Some(name) => write!(f, "{}", name)?,
+                    None => write!(f, "*")?,
+                }
+                if let Some(type_args) = type_args {
+                    let type_args: Vec<String> =
+                        type_args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "<{}>", type_args.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub type TypeTagPatternView = StrView<TypeTagPattern>;
+
+impl FromStr for TypeTagPatternView {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+impl fmt::Display for TypeTagPatternView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PredicateOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl PredicateOp {
+    fn test(self, ordering: Ordering) -> bool {
+        match self {
+            PredicateOp::Eq => ordering == Ordering::Equal,
+            PredicateOp::Ne => ordering != Ordering::Equal,
+            PredicateOp::Gt => ordering == Ordering::Greater,
+            PredicateOp::Ge => ordering != Ordering::Less,
+            PredicateOp::Lt => ordering == Ordering::Less,
+            PredicateOp::Le => ordering != Ordering::Greater,
+        }
+    }
+}
+
+/// A predicate on a field of the decoded event data.
+/// Integers are compared numerically, bool, address and bytes fields only support `eq` and `ne`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldPredicate {
+    /// The dot separated path of the field, a number selects an element of a vector,
+    /// eg: `amount`, `token_code.name`, `items.0`.
+    pub path: String,
+    pub op: PredicateOp,
+    /// The value to compare with, bytes are in hex with a `0x` prefix or in utf8 text.
+    pub value: String,
+}
+
+impl FieldPredicate {
+    pub fn matches(&self, data: &AnnotatedMoveValueView) -> bool {
+        let mut field = data;
+        for segment in self.path.split('.').filter(|segment| !segment.is_empty()) {
+            let next = match field {
+                AnnotatedMoveValueView::Struct(s) => s
+                    .value
+                    .iter()
+                    .find(|(name, _)| name.as_str() == segment)
+                    .map(|(_, value)| value),
+                AnnotatedMoveValueView::Vector(values) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| values.get(index)),
+                _ => None,
+            };
+            match next {
+                Some(next) => field = next,
+                None => return false,
+            }
+        }
+        let ordering = match field {
+            AnnotatedMoveValueView::U8(v) => self.compare_integer(u128::from(*v)),
+            AnnotatedMoveValueView::U64(v) => self.compare_integer(u128::from(v.0)),
+            AnnotatedMoveValueView::U128(v) => self.compare_integer(v.0),
+            AnnotatedMoveValueView::Bool(v) => self.compare_eq(v, bool::from_str),
+            AnnotatedMoveValueView::Address(v) => self.compare_eq(v, AccountAddress::from_str),
+            AnnotatedMoveValueView::Bytes(v) => {
+                let expected = match self.value.strip_prefix("0x") {
+                    Some(hex_value) => hex::decode(hex_value).ok(),
+                    None => Some(self.value.as_bytes().to_vec()),
+                };
+                self.compare_eq(&v.0, |_| expected.ok_or(()))
+            }
+            AnnotatedMoveValueView::Vector(_) | AnnotatedMoveValueView::Struct(_) => None,
+        };
+        ordering.map_or(false, |ordering| self.op.test(ordering))
+    }
+
+    fn compare_integer(&self, v: u128) -> Option<Ordering> {
+        self.value
+            .parse::<u128>()
+            .ok()
+            .map(|expected| v.cmp(&expected))
+    }
+
+    /// Compare the values only for `eq` and `ne`.
+    fn compare_eq<T, E, F>(&self, v: &T, parse: F) -> Option<Ordering>
+    where
+        T: PartialEq,
+        F: FnOnce(&str) -> std::result::Result<T, E>,
+    {
+        if !matches!(self.op, PredicateOp::Eq | PredicateOp::Ne) {
+            return None;
+        }
+        let expected = parse(self.value.as_str()).ok()?;
+        Some(if v == &expected {
+            Ordering::Equal
+        } else {
+            Ordering::Less
         })
     }
 }
 
+/// Filter of the transaction status subscription, at least one of the fields should be specified.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionStatusFilter {
+    /// Hashes of the transactions to watch.
+    #[serde(default)]
+    pub txn_hashes: Vec<HashValue>,
+    /// Watch all the transactions sent by the sender.
+    #[serde(default)]
+    pub sender: Option<AccountAddress>,
+}
+
+/// A status transition of a watched transaction in txpool, the inclusion of it in the main chain,
+/// or the removal of the including block from the main chain.
+/// The current status of every watched transaction is notified first when subscribed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatusView {
+    pub transaction_hash: HashValue,
+    /// The status of the transaction in txpool, absent when the transaction is included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txpool_status: Option<TxStatus>,
+    /// The transaction info once the transaction is included in the main chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txn_info: Option<TransactionInfoView>,
+    /// The block which included the transaction but is retracted from the main chain by a reorg.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retracted_block: Option<HashValue>,
+}
+
+/// The main chain is switched to another branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorgView {
+    /// The common ancestor of the old and the new main chain.
+    pub ancestor: BlockHeaderView,
+    /// The ids of the blocks removed from the main chain, ordered from new to old.
+    pub retracted: Vec<HashValue>,
+    /// The ids of the blocks added to the main chain, ordered from old to new.
+    pub enacted: Vec<HashValue>,
+}
+
+impl From<ChainReorg> for ChainReorgView {
+    fn from(reorg: ChainReorg) -> Self {
+        Self {
+            ancestor: reorg.ancestor.into(),
+            retracted: reorg.retracted,
+            enacted: reorg.enacted,
+        }
+    }
+}
+
 /// Block for minting
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
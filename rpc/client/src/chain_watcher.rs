@@ -9,6 +9,7 @@ use jsonrpc_core_client::RpcError;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
+use starcoin_rpc_api::chain::ChainClient;
 use starcoin_rpc_api::types::{BlockHeaderView, BlockView};
 use starcoin_types::block::BlockNumber;
 use std::collections::HashMap;
@@ -31,20 +32,87 @@ impl From<BlockView> for ThinHeadBlock {
     }
 }
 
+/// The interval of polling new blocks, when the connection does not support pubsub.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub struct ChainWatcher {
     watched_blocks: HashMap<BlockNumber, Vec<Responder>>,
     watched_txns: HashMap<HashValue, Responder>,
+    poll_handle: Option<SpawnHandle>,
+    /// Is a poll in progress.
+    polling: bool,
+    /// The head block number of last poll.
+    last_polled_number: Option<BlockNumber>,
 }
+
 impl ChainWatcher {
     pub fn launch() -> Addr<Self> {
         let actor = Self {
             watched_txns: Default::default(),
             watched_blocks: Default::default(),
+            poll_handle: None,
+            polling: false,
+            last_polled_number: None,
         };
         actor.start()
     }
 
+    /// Poll the blocks after last poll, the blocks are only fetched when there are watchers.
+    fn poll(&mut self, client: ChainClient, ctx: &mut Context<Self>) {
+        if self.polling {
+            return;
+        }
+        self.polling = true;
+        let last_polled_number = self.last_polled_number;
+        let fetch_blocks = !self.watched_blocks.is_empty() || !self.watched_txns.is_empty();
+        async move {
+            let head_number = client.info().await?.head.number.0;
+            let mut blocks = vec![];
+            if fetch_blocks {
+                let from = last_polled_number
+                    .map(|number| number.saturating_add(1))
+                    .unwrap_or(head_number);
+                for number in from..=head_number {
+                    if let Some(block) = client.get_block_by_number(number).await? {
+                        blocks.push(block);
+                    }
+                }
+            }
+            Ok::<_, RpcError>((head_number, blocks))
+        }
+        .into_actor(self)
+        .map(|res, act, _ctx| {
+            act.polling = false;
+            match res {
+                Ok((head_number, blocks)) => {
+                    act.last_polled_number = Some(head_number);
+                    for block in blocks {
+                        act.on_new_block(block);
+                    }
+                }
+                Err(e) => {
+                    warn!("fail to poll new blocks, err: {}", &e);
+                }
+            }
+        })
+        .spawn(ctx);
+    }
+
+    fn on_new_block(&mut self, b: BlockView) {
+        let b: ThinHeadBlock = b.into();
+        if let Some(responders) = self.watched_blocks.remove(&b.header.number.0) {
+            for r in responders {
+                let _ = r.send(Ok(b.clone()));
+            }
+        }
+        for txn in &b.txn_hashes {
+            if let Some(r) = self.watched_txns.remove(txn) {
+                let _ = r.send(Ok(b.clone()));
+            }
+        }
+    }
+
     fn start_subscribe(&mut self, client: PubSubClient, ctx: &mut Context<Self>) {
         let inner_client = client.clone();
         async move { inner_client.subscribe_new_block().await }
@@ -93,6 +161,28 @@ impl Handler<StartSubscribe> for ChainWatcher {
     }
 }
 
+/// Watch the chain by polling, for the connection which does not support pubsub.
+pub(crate) struct StartPolling {
+    pub(crate) client: ChainClient,
+}
+
+impl Message for StartPolling {
+    type Result = ();
+}
+
+impl Handler<StartPolling> for ChainWatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: StartPolling, ctx: &mut Self::Context) {
+        if let Some(handle) = self.poll_handle.take() {
+            ctx.cancel_future(handle);
+        }
+        let client = msg.client;
+        self.poll_handle =
+            Some(ctx.run_interval(POLL_INTERVAL, move |act, ctx| act.poll(client.clone(), ctx)));
+    }
+}
+
 pub type WatchResult = Result<ThinHeadBlock, anyhow::Error>;
 type Responder = oneshot::Sender<WatchResult>;
 
@@ -141,19 +231,7 @@ type BlockEvent = Result<BlockView, RpcError>;
 impl actix::StreamHandler<BlockEvent> for ChainWatcher {
     fn handle(&mut self, item: BlockEvent, _ctx: &mut Self::Context) {
         match item {
-            Ok(b) => {
-                let b: ThinHeadBlock = b.into();
-                if let Some(responders) = self.watched_blocks.remove(&b.header.number.0) {
-                    for r in responders {
-                        let _ = r.send(Ok(b.clone()));
-                    }
-                }
-                for txn in &b.txn_hashes {
-                    if let Some(r) = self.watched_txns.remove(txn) {
-                        let _ = r.send(Ok(b.clone()));
-                    }
-                }
-            }
+            Ok(b) => self.on_new_block(b),
             Err(e) => {
                 for (_, responders) in self.watched_blocks.drain() {
                     for r in responders {
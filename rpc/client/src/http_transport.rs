@@ -0,0 +1,117 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+//! Http transport which carries a bearer token in the `Authorization` header of every request,
+//! the http transport of jsonrpc client can not set request headers.
+
+use futures::channel::mpsc;
+use futures::StreamExt;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use hyper::{Body, Client, Request, Uri};
+use hyper_tls::HttpsConnector;
+use jsonrpc_client_transports::transports::duplex;
+use jsonrpc_client_transports::{RpcChannel, RpcError};
+use jsonrpc_core::{Error, ErrorCode, MethodCall, Output};
+
+type HttpsClient = Client<HttpsConnector<HttpConnector>>;
+
+/// Connect to the http or https `url` with the bearer `token`.
+pub(crate) async fn connect_with_token(url: &str, token: &str) -> Result<RpcChannel, RpcError> {
+    let uri = parse_url(url)?;
+    let authorization = HeaderValue::from_str(format!("Bearer {}", token).as_str())
+        .map_err(|e| RpcError::Other(Box::new(e)))?;
+    let client: HttpsClient = Client::builder().build(HttpsConnector::new());
+    let (request_sender, request_receiver) = mpsc::unbounded::<String>();
+    let (response_sender, response_receiver) = mpsc::unbounded::<String>();
+    let worker = request_receiver.for_each_concurrent(None, move |request| {
+        let client = client.clone();
+        let uri = uri.clone();
+        let authorization = authorization.clone();
+        let response_sender = response_sender.clone();
+        async move {
+            let response = match post(&client, uri, authorization, request.clone()).await {
+                Ok(response) => response,
+                // Reply the error to the call, otherwise the call waits for the response forever.
+                Err(e) => match error_response(request.as_str(), e) {
+                    Some(response) => response,
+                    None => return,
+                },
+            };
+            // The response of a notification is empty.
+            if !response.is_empty() {
+                let _ = response_sender.unbounded_send(response);
+            }
+        }
+    });
+    tokio::spawn(worker);
+    let (client, sender) = duplex(Box::pin(request_sender), Box::pin(response_receiver));
+    tokio::spawn(client);
+    Ok(sender)
+}
+
+/// Parse the `url`, only the http and https schemes are supported.
+fn parse_url(url: &str) -> Result<Uri, RpcError> {
+    let uri: Uri = url.parse().map_err(|e| RpcError::Other(Box::new(e)))?;
+    match uri.scheme_str() {
+        Some("http") | Some("https") => Ok(uri),
+        _ => Err(RpcError::Client(format!(
+            "Unsupported url {}, the scheme should be http or https",
+            url
+        ))),
+    }
+}
+
+async fn post(
+    client: &HttpsClient,
+    uri: Uri,
+    authorization: HeaderValue,
+    request: String,
+) -> Result<String, String> {
+    let request = Request::post(uri)
+        .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+        .header(AUTHORIZATION, authorization)
+        .body(Body::from(request))
+        .map_err(|e| e.to_string())?;
+    let response = client.request(request).await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Unexpected http response status: {}",
+            response.status()
+        ));
+    }
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(body.to_vec()).map_err(|e| e.to_string())
+}
+
+/// Build the error response of the method call `request`, None if it is not a method call.
+fn error_response(request: &str, error: String) -> Option<String> {
+    let call: MethodCall = serde_json::from_str(request).ok()?;
+    let output = Output::from(
+        Err(Error {
+            code: ErrorCode::InternalError,
+            message: error,
+            data: None,
+        }),
+        call.id,
+        call.jsonrpc,
+    );
+    serde_json::to_string(&output).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        let uri = parse_url("https://barnard.seed.starcoin.org:9850").unwrap();
+        assert_eq!(uri.scheme_str(), Some("https"));
+        assert_eq!(uri.port_u16(), Some(9850));
+        assert!(parse_url("http://127.0.0.1:9850").is_ok());
+        assert!(parse_url("ws://127.0.0.1:9870").is_err());
+        assert!(parse_url("127.0.0.1:9850").is_err());
+    }
+}
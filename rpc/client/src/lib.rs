@@ -1,31 +1,36 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::chain_watcher::{ChainWatcher, StartSubscribe, WatchBlock, WatchTxn};
+use crate::chain_watcher::{ChainWatcher, StartPolling, StartSubscribe, WatchBlock, WatchTxn};
 use crate::pubsub_client::PubSubClient;
 use actix::{Addr, System};
 use anyhow::anyhow;
 use futures::channel::oneshot;
 use futures::{TryStream, TryStreamExt};
 use jsonrpc_client_transports::RawClient;
-use jsonrpc_core_client::{transports::ipc, transports::ws, RpcChannel};
+use jsonrpc_core_client::{transports::http, transports::ipc, transports::ws, RpcChannel};
+use network_api::messages::BannedPeer;
 use network_api::PeerStrategy;
 use network_p2p_types::network_state::NetworkState;
 use parking_lot::Mutex;
 use serde_json::Value;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, MnemonicAccountInfo};
+use starcoin_chain_proof::TransactionProof;
 use starcoin_crypto::HashValue;
 use starcoin_logger::{prelude::*, LogPattern};
 use starcoin_rpc_api::node::NodeInfo;
 use starcoin_rpc_api::service::RpcAsyncService;
 use starcoin_rpc_api::types::pubsub::EventFilter;
-use starcoin_rpc_api::types::pubsub::MintBlock;
+use starcoin_rpc_api::types::pubsub::{
+    ChainReorgView, MintBlock, TransactionStatusFilter, TransactionStatusView,
+};
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, AnnotatedMoveValueView, BlockHeaderView,
-    BlockSummaryView, BlockView, ChainId, ChainInfoView, ContractCall, DryRunTransactionRequest,
-    EpochUncleSummaryView, FactoryAction, PeerInfoView, SignedUserTransactionView,
-    StateWithProofView, StrView, TransactionInfoView, TransactionOutputView, TransactionRequest,
-    TransactionView,
+    AccountStateSetView, AddressTransactionInfoView, AnnotatedMoveStructView,
+    AnnotatedMoveValueView, BlockHeaderView, BlockReplayView, BlockSummaryView, BlockView, ChainId,
+    ChainInfoView, ContractCall, DryRunTransactionRequest, EpochUncleSummaryView, EventTraceView,
+    FactoryAction, FailedBlockView, FeeHistoryView, PeerInfoView, SignedUserTransactionView,
+    StateSelector, StateWithProofView, StrView, TransactionInfoView, TransactionOutputView,
+    TransactionProofView, TransactionRequest, TransactionView,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
@@ -39,18 +44,20 @@ use starcoin_txpool_api::TxPoolStatus;
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
-use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::block::{BlockHeader, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
 use starcoin_vm_types::token::token_code::TokenCode;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::path::{Path, PathBuf};
 use std::thread::JoinHandle;
 use std::time::Duration;
 
 pub mod chain_watcher;
+mod http_transport;
 mod pubsub_client;
 mod remote_state_reader;
 
@@ -63,15 +70,26 @@ use tokio::runtime::Runtime;
 #[derive(Clone)]
 enum ConnSource {
     Ipc(PathBuf),
-    WebSocket(String),
+    /// The url and the optional bearer token.
+    WebSocket(String, Option<String>),
+    /// The url and the optional bearer token.
+    Http(String, Option<String>),
     Local(Box<RpcChannel>),
 }
 
+impl ConnSource {
+    /// Http connection does not support pubsub, because pubsub requires a persistent connection.
+    fn support_pubsub(&self) -> bool {
+        !matches!(self, ConnSource::Http(_, _))
+    }
+}
+
 impl std::fmt::Debug for ConnSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConnSource::Ipc(path) => write!(f, "Ipc({})", path.as_path().to_string_lossy()),
-            ConnSource::WebSocket(url) => write!(f, "WebSocket({})", url),
+            ConnSource::WebSocket(url, _) => write!(f, "WebSocket({})", url),
+            ConnSource::Http(url, _) => write!(f, "Http({})", url),
             ConnSource::Local(_) => write!(f, "Local"),
         }
     }
@@ -115,7 +133,21 @@ impl ConnectionProvider {
     ) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
         match self.conn_source.clone() {
             ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
-            ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
+            ConnSource::WebSocket(url, None) => ws::try_connect(url.as_str())?.await,
+            ConnSource::WebSocket(url, Some(token)) => {
+                let url = url::Url::parse(url.as_str())
+                    .map_err(|e| jsonrpc_client_transports::RpcError::Other(Box::new(e)))?;
+                let mut headers = websocket::header::Headers::new();
+                headers.set_raw(
+                    "Authorization",
+                    vec![format!("Bearer {}", token).into_bytes()],
+                );
+                ws::connect_with_headers(&url, headers).await
+            }
+            ConnSource::Http(url, None) => http::connect(url.as_str()).await,
+            ConnSource::Http(url, Some(token)) => {
+                http_transport::connect_with_token(url.as_str(), token.as_str()).await
+            }
             ConnSource::Local(channel) => Ok(*channel),
         }
     }
@@ -126,7 +158,6 @@ impl RpcClient {
         let (tx, rx) = oneshot::channel();
         let provider = ConnectionProvider::new(conn_source, Runtime::new()?);
         let inner: RpcClientInner = provider.get_rpc_channel().map_err(map_err)?.into(); //Self::create_client_inner(conn_source.clone()).map_err(map_err)?;
-        let pubsub_client = inner.pubsub_client.clone();
         let handle = std::thread::spawn(move || {
             let sys = System::new("client-actix-system");
             let watcher = ChainWatcher::launch();
@@ -135,9 +166,7 @@ impl RpcClient {
             let _ = sys.run();
         });
         let watcher = futures::executor::block_on(rx).expect("Init chain watcher fail.");
-        watcher.do_send(StartSubscribe {
-            client: pubsub_client,
-        });
+        start_watch(&watcher, &provider.conn_source, &inner);
         Ok(Self {
             inner: Mutex::new(Some(inner)),
             provider,
@@ -147,7 +176,26 @@ impl RpcClient {
     }
 
     pub fn connect_websocket(url: &str) -> anyhow::Result<Self> {
-        Self::new(ConnSource::WebSocket(url.to_string()))
+        Self::new(ConnSource::WebSocket(url.to_string(), None))
+    }
+
+    /// Connect by websocket with a bearer token, the token is passed by the `Authorization` header.
+    pub fn connect_websocket_with_token(url: &str, token: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::WebSocket(
+            url.to_string(),
+            Some(token.to_string()),
+        ))
+    }
+
+    /// Connect by http, the pubsub methods are not supported,
+    /// and `watch_txn`/`watch_block` are implemented by polling.
+    pub fn connect_http(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(url.to_string(), None))
+    }
+
+    /// Connect by http or https with a bearer token, the token is passed by the `Authorization` header.
+    pub fn connect_http_with_token(url: &str, token: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(url.to_string(), Some(token.to_string())))
     }
 
     pub fn connect_local<S>(rpc_service: S) -> anyhow::Result<Self>
@@ -396,6 +444,24 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_create_from_mnemonic(
+        &self,
+        mnemonic: Option<String>,
+        password: String,
+    ) -> anyhow::Result<MnemonicAccountInfo> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .create_from_mnemonic(mnemonic, password)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_derive(&self, index: u32, password: String) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.derive(index, password))
+            .map_err(map_err)
+    }
+
     pub fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
         let result: Option<StrView<Vec<u8>>> = self
             .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
@@ -407,17 +473,22 @@ impl RpcClient {
         &self,
         addr: AccountAddress,
         resource_type: StructTag,
+        state: Option<StateSelector>,
     ) -> anyhow::Result<Option<AnnotatedMoveStructView>> {
         self.call_rpc_blocking(|inner| {
             inner
                 .contract_client
-                .get_resource(addr, StrView(resource_type))
+                .get_resource(addr, StrView(resource_type), state)
         })
         .map_err(map_err)
     }
 
-    pub fn state_get(&self, access_path: AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get(access_path))
+    pub fn state_get(
+        &self,
+        access_path: AccessPath,
+        state: Option<StateSelector>,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        self.call_rpc_blocking(|inner| inner.state_client.get(access_path, state))
             .map_err(map_err)
     }
 
@@ -463,8 +534,12 @@ impl RpcClient {
             .map_err(map_err)
     }
 
-    pub fn contract_call(&self, call: ContractCall) -> anyhow::Result<Vec<AnnotatedMoveValueView>> {
-        self.call_rpc_blocking(|inner| inner.contract_client.call(call))
+    pub fn contract_call(
+        &self,
+        call: ContractCall,
+        state: Option<StateSelector>,
+    ) -> anyhow::Result<Vec<AnnotatedMoveValueView>> {
+        self.call_rpc_blocking(|inner| inner.contract_client.call(call, state))
             .map_err(map_err)
     }
 
@@ -491,6 +566,22 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn debug_replay_failed_block(
+        &self,
+        block_hash: HashValue,
+    ) -> anyhow::Result<BlockReplayView> {
+        self.call_rpc_blocking(|inner| inner.debug_client.replay_failed_block(block_hash))
+            .map_err(map_err)
+    }
+
+    pub fn debug_trace_transaction(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<TransactionOutputView> {
+        self.call_rpc_blocking(|inner| inner.debug_client.trace_transaction(txn_hash))
+            .map_err(map_err)
+    }
+
     pub fn debug_txfactory_status(&self, action: FactoryAction) -> anyhow::Result<bool> {
         self.call_rpc_blocking(|inner| inner.debug_client.txfactory_status(action))
             .map_err(map_err)
@@ -599,6 +690,92 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    /// Get the proof of the transaction anchored to `anchor_block_hash` without verifying it.
+    pub fn chain_get_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_hash: Option<HashValue>,
+    ) -> anyhow::Result<Option<TransactionProofView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_transaction_proof(txn_hash, anchor_block_hash)
+        })
+        .map_err(map_err)
+    }
+
+    /// Get the proof of the transaction anchored to `anchor_block_hash`, and verify it with the
+    /// trusted `block_accumulator_root` of the anchor block.
+    pub fn chain_verify_transaction_proof(
+        &self,
+        txn_hash: HashValue,
+        anchor_block_hash: Option<HashValue>,
+        block_accumulator_root: HashValue,
+    ) -> anyhow::Result<Option<TransactionProof>> {
+        let proof = match self.chain_get_transaction_proof(txn_hash, anchor_block_hash)? {
+            Some(view) => TransactionProof::try_from(view)?,
+            None => return Ok(None),
+        };
+        proof.verify(txn_hash, block_accumulator_root)?;
+        Ok(Some(proof))
+    }
+
+    /// Get the proof of the transaction and verify it with the `trusted_header`, the proof is
+    /// anchored to the parent of the header, whose block accumulator root is kept in the header.
+    pub fn chain_verify_transaction_proof_by_header(
+        &self,
+        txn_hash: HashValue,
+        trusted_header: &BlockHeader,
+    ) -> anyhow::Result<Option<TransactionProof>> {
+        let proof =
+            match self.chain_get_transaction_proof(txn_hash, Some(trusted_header.parent_hash()))? {
+                Some(view) => TransactionProof::try_from(view)?,
+                None => return Ok(None),
+            };
+        proof.verify_by_header(txn_hash, trusted_header)?;
+        Ok(Some(proof))
+    }
+
+    pub fn chain_get_transactions_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> anyhow::Result<Vec<AddressTransactionInfoView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_transactions_by_address(address, cursor, limit)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn chain_fee_history(
+        &self,
+        block_count: u64,
+        percentiles: Vec<f64>,
+    ) -> anyhow::Result<FeeHistoryView> {
+        self.call_rpc_blocking(|inner| inner.chain_client.fee_history(block_count, percentiles))
+            .map_err(map_err)
+    }
+
+    pub fn chain_list_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> anyhow::Result<Vec<FailedBlockView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.list_failed_blocks(cursor, limit))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_failed_block(
+        &self,
+        block_hash: HashValue,
+    ) -> anyhow::Result<Option<FailedBlockView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_failed_block(block_hash))
+            .map_err(map_err)
+    }
+
     pub fn chain_get_events_by_txn_hash(
         &self,
         txn_hash: HashValue,
@@ -607,6 +784,14 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn chain_get_events(
+        &self,
+        filter: EventFilter,
+    ) -> anyhow::Result<Vec<TransactionEventView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_events(filter))
+            .map_err(map_err)
+    }
+
     pub fn chain_get_block_txn_infos(
         &self,
         block_id: HashValue,
@@ -628,8 +813,12 @@ impl RpcClient {
         .map_err(map_err)
     }
 
-    pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<TransactionOutputView> {
-        self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
+    pub fn dry_run(
+        &self,
+        txn: DryRunTransactionRequest,
+        state: Option<StateSelector>,
+    ) -> anyhow::Result<TransactionOutputView> {
+        self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn, state))
             .map_err(map_err)
     }
     pub fn miner_submit(
@@ -661,15 +850,28 @@ impl RpcClient {
         &self,
         filter: EventFilter,
     ) -> anyhow::Result<impl TryStream<Ok = TransactionEventView, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
         self.call_rpc_blocking(|inner| async move {
             let res = inner.pubsub_client.subscribe_events(filter).await;
             res.map(|s| s.map_err(map_err))
         })
         .map_err(map_err)
     }
+    pub fn subscribe_decoded_events(
+        &self,
+        filter: EventFilter,
+    ) -> anyhow::Result<impl TryStream<Ok = EventTraceView, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_decoded_events(filter).await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
     pub fn subscribe_new_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = BlockView, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
         self.call_rpc_blocking(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_block().await;
             res.map(|s| s.map_err(map_err))
@@ -679,6 +881,7 @@ impl RpcClient {
     pub fn subscribe_new_transactions(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = Vec<HashValue>, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
         self.call_rpc_blocking(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_transactions().await;
             res.map(|s| s.map_err(map_err))
@@ -689,6 +892,7 @@ impl RpcClient {
     pub fn subscribe_new_mint_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlock, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
         self.call_rpc_blocking(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_mint_block().await;
             res.map(|s| s.map_err(map_err))
@@ -699,6 +903,7 @@ impl RpcClient {
     pub async fn subscribe_new_mint_blocks_async(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlock, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
         self.call_rpc_async(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_mint_block().await;
             res.map(|s| s.map_err(map_err))
@@ -707,6 +912,40 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn subscribe_chain_reorg(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = ChainReorgView, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_chain_reorg().await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
+    pub fn subscribe_txn_status(
+        &self,
+        filter: TransactionStatusFilter,
+    ) -> anyhow::Result<impl TryStream<Ok = TransactionStatusView, Error = anyhow::Error>> {
+        self.ensure_pubsub()?;
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_txn_status(filter).await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
+    fn ensure_pubsub(&self) -> anyhow::Result<()> {
+        if self.provider.conn_source.support_pubsub() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Pubsub is not supported by connection {:?}",
+                self.provider.conn_source
+            ))
+        }
+    }
+
     fn call_rpc_blocking<F, T>(
         &self,
         f: impl FnOnce(RpcClientInner) -> F + Send,
@@ -740,9 +979,7 @@ impl RpcClient {
                     .await
                     .map(|c| c.into())?;
                 *(self.inner.lock()) = Some(new_inner.clone());
-                self.chain_watcher.do_send(StartSubscribe {
-                    client: new_inner.pubsub_client.clone(),
-                });
+                start_watch(&self.chain_watcher, &self.provider.conn_source, &new_inner);
                 new_inner
             }
         };
@@ -809,6 +1046,36 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn network_add_reserved_peer(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.add_reserved_peer(peer))
+            .map_err(map_err)
+    }
+
+    pub fn network_ban_peer(
+        &self,
+        peer_id: String,
+        duration: Option<u64>,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.ban_peer(peer_id, duration, reason))
+            .map_err(map_err)
+    }
+
+    pub fn network_unban_peer(&self, peer_id: String) -> anyhow::Result<bool> {
+        self.call_rpc_blocking(|inner| inner.network_client.unban_peer(peer_id))
+            .map_err(map_err)
+    }
+
+    pub fn network_list_banned(&self) -> anyhow::Result<Vec<BannedPeer>> {
+        self.call_rpc_blocking(|inner| inner.network_client.list_banned())
+            .map_err(map_err)
+    }
+
+    pub fn network_remove_reserved_peer(&self, peer_id: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.remove_reserved_peer(peer_id))
+            .map_err(map_err)
+    }
+
     pub fn network_call_peer(
         &self,
         peer_id: String,
@@ -875,6 +1142,23 @@ impl RpcClientInner {
     }
 }
 
+/// Watch the chain by subscription, or by polling if the connection does not support pubsub.
+fn start_watch(
+    chain_watcher: &Addr<ChainWatcher>,
+    conn_source: &ConnSource,
+    inner: &RpcClientInner,
+) {
+    if conn_source.support_pubsub() {
+        chain_watcher.do_send(StartSubscribe {
+            client: inner.pubsub_client.clone(),
+        });
+    } else {
+        chain_watcher.do_send(StartPolling {
+            client: inner.chain_client.clone(),
+        });
+    }
+}
+
 fn map_err(rpc_err: jsonrpc_client_transports::RpcError) -> anyhow::Error {
     anyhow!(format!("{}", rpc_err))
 }
@@ -3,8 +3,12 @@
 
 use jsonrpc_core_client::*;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::pubsub::MintBlock;
-use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventView};
+use starcoin_rpc_api::types::pubsub::{
+    ChainReorgView, MintBlock, TransactionStatusFilter, TransactionStatusView,
+};
+use starcoin_rpc_api::types::{
+    pubsub::EventFilter, pubsub::Kind, BlockView, EventTraceView, TransactionEventView,
+};
 
 const STARCOIN_SUBSCRIPTION: &str = "starcoin_subscription";
 const STARCOIN_SUBSCRIBE: &str = "starcoin_subscribe";
@@ -41,6 +45,19 @@ impl PubSubClient {
             "Event",
         )
     }
+    /// Subscribe events with the decoded event data, the `decode` of the filter should be true.
+    pub async fn subscribe_decoded_events(
+        &self,
+        filter: EventFilter,
+    ) -> Result<TypedSubscriptionStream<EventTraceView>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            (Kind::Events, filter),
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "DecodedEvent",
+        )
+    }
     pub async fn subscribe_new_block(
         &self,
     ) -> Result<TypedSubscriptionStream<BlockView>, RpcError> {
@@ -74,4 +91,27 @@ impl PubSubClient {
             "MintBlock",
         )
     }
+    pub async fn subscribe_chain_reorg(
+        &self,
+    ) -> Result<TypedSubscriptionStream<ChainReorgView>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::ChainReorg],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "ChainReorg",
+        )
+    }
+    pub async fn subscribe_txn_status(
+        &self,
+        filter: TransactionStatusFilter,
+    ) -> Result<TypedSubscriptionStream<TransactionStatusView>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            (Kind::TransactionStatus, filter),
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "TransactionStatus",
+        )
+    }
 }
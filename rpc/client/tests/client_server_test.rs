@@ -6,7 +6,11 @@ use futures::{StreamExt, TryStreamExt};
 use starcoin_config::NodeConfig;
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::types::pubsub::MintBlock;
+use starcoin_rpc_api::types::{
+    AnnotatedMoveValueView, ContractCall, FunctionIdView, StateSelector, StrView,
+};
 use starcoin_rpc_client::RpcClient;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -139,3 +143,119 @@ fn test_client_reconnect_subscribe() -> Result<()> {
     assert_ne!(events2.len(), 0);
     Ok(())
 }
+
+#[stest::test]
+fn test_websocket_auth() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.auth.tokens = Some(vec!["alice:s3cr3t:node,pubsub".parse().unwrap()]);
+    let config = Arc::new(node_config);
+    let url = config.rpc.get_ws_address().unwrap().to_string();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    assert!(RpcClient::connect_websocket(url.as_str()).is_err());
+    assert!(RpcClient::connect_websocket_with_token(url.as_str(), "wrong").is_err());
+    let ws_client = RpcClient::connect_websocket_with_token(url.as_str(), "s3cr3t")?;
+    let status = ws_client.node_info()?;
+    info!("ws_client node_status: {:?}", status);
+    // every call is checked by the token's apis.
+    assert!(ws_client.chain_info().is_err());
+    ws_client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
+
+#[stest::test]
+fn test_http_client() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let url = config.rpc.get_http_address().unwrap().to_string();
+    let config_chain_id = config.net().chain_id().id();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let http_client = RpcClient::connect_http(url.as_str())?;
+    let status = http_client.node_info()?;
+    info!("http_client node_status: {:?}", status);
+    let chain_info = http_client.chain_info()?;
+    assert_eq!(chain_info.chain_id, config_chain_id);
+    // pubsub needs a persistent connection.
+    assert!(http_client.subscribe_new_blocks().is_err());
+    http_client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
+
+#[stest::test]
+fn test_http_auth() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.auth.tokens = Some(vec!["alice:s3cr3t:node".parse().unwrap()]);
+    let config = Arc::new(node_config);
+    let url = config.rpc.get_http_address().unwrap().to_string();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    // the http connection is lazy, the token is checked by every call.
+    let http_client = RpcClient::connect_http(url.as_str())?;
+    assert!(http_client.node_info().is_err());
+    http_client.close();
+    let http_client = RpcClient::connect_http_with_token(url.as_str(), "wrong")?;
+    assert!(http_client.node_info().is_err());
+    http_client.close();
+    let http_client = RpcClient::connect_http_with_token(url.as_str(), "s3cr3t")?;
+    let status = http_client.node_info()?;
+    info!("http_client node_status: {:?}", status);
+    assert!(http_client.chain_info().is_err());
+    http_client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
+
+#[stest::test]
+fn test_call_contract_at_historical_state() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let url = config.rpc.get_ws_address().unwrap().to_string();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    let block1 = node_handle.generate_block()?;
+    node_handle.generate_block()?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let client = RpcClient::connect_websocket(url.as_str())?;
+    let call = ContractCall {
+        function_id: FunctionIdView::from_str("0x1::Block::get_current_block_number")?,
+        type_args: vec![],
+        args: vec![],
+    };
+    let block_number_at = |state: Option<StateSelector>| -> Result<u64> {
+        match client.contract_call(call.clone(), state)?.pop() {
+            Some(AnnotatedMoveValueView::U64(StrView(number))) => Ok(number),
+            v => anyhow::bail!("unexpected return value {:?}", v),
+        }
+    };
+    assert_eq!(block_number_at(None)?, 2);
+    assert_eq!(block_number_at(Some(StateSelector::BlockNumber(1)))?, 1);
+    assert_eq!(
+        block_number_at(Some(StateSelector::BlockHash(block1.id())))?,
+        1
+    );
+    assert_eq!(
+        block_number_at(Some(StateSelector::StateRoot(block1.header().state_root())))?,
+        1
+    );
+    assert!(block_number_at(Some(StateSelector::BlockNumber(100))).is_err());
+
+    client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
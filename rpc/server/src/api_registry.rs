@@ -1,38 +1,64 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::auth_middleware::{JsonApiAuthMiddleware, RpcAuthenticator};
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
 use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
 use starcoin_config::{Api, ApiQuotaConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_middleware::MetricMiddleware;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    MetricMiddleware,
+    JsonApiAuthMiddleware,
+    JsonApiRateLimitMiddleware,
+);
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
     quotas: ApiQuotaConfiguration,
+    authenticator: Option<Arc<RpcAuthenticator>>,
 }
 
 impl ApiRegistry {
-    pub fn new(api_quotas: ApiQuotaConfiguration) -> ApiRegistry {
+    pub fn new(
+        api_quotas: ApiQuotaConfiguration,
+        authenticator: Option<RpcAuthenticator>,
+    ) -> ApiRegistry {
         Self {
             apis: Default::default(),
             quotas: api_quotas,
+            authenticator: authenticator.map(Arc::new),
         }
     }
 
+    pub fn authenticator(&self) -> Option<Arc<RpcAuthenticator>> {
+        self.authenticator.clone()
+    }
+
+    /// If `auth` is true, every call should carry a token allowed to call the api.
+    fn middlewares(&self, auth: bool) -> Middlewares {
+        let authenticator = if auth {
+            self.authenticator.clone()
+        } else {
+            None
+        };
+        (
+            MetricMiddleware,
+            JsonApiAuthMiddleware::new(authenticator),
+            JsonApiRateLimitMiddleware::from_config(self.quotas.clone()),
+        )
+    }
+
     pub fn register<F>(&mut self, api_type: Api, apis: F)
     where
         F: IntoIterator<Item = (String, RemoteProcedure<Metadata>)>,
     {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
+        let middlewares = self.middlewares(false);
         let io_handler = self.apis.entry(api_type).or_insert_with(|| {
-            MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                MetricMiddleware,
-                rate_limit_middleware,
-            ))
+            MetaIoHandler::<Metadata, Middlewares>::with_middleware(middlewares)
         });
         io_handler.extend_with(apis);
     }
@@ -40,16 +66,13 @@ impl ApiRegistry {
     pub fn get_apis(
         &self,
         api_types: impl IntoIterator<Item = Api>,
+        auth: bool,
     ) -> MetaIoHandler<Metadata, Middlewares> {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
         api_types
             .into_iter()
             .map(|api_type| self.apis.get(&api_type))
             .fold(
-                MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                    MetricMiddleware,
-                    rate_limit_middleware,
-                )),
+                MetaIoHandler::<Metadata, Middlewares>::with_middleware(self.middlewares(auth)),
                 |mut init, apis| {
                     if let Some(apis) = apis {
                         init.extend_with(apis.iter().map(|(k, v)| (k.clone(), v.clone())));
@@ -0,0 +1,294 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use hmac::{Hmac, Mac, NewMac};
+use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{Call, Error, ErrorCode, Failure, FutureResponse, Id, Middleware, Output};
+use sha2::Sha256;
+use starcoin_config::{Api, ApiSet, RpcAuthConfiguration};
+use starcoin_rpc_api::metadata::Metadata;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const HMAC_TOKEN_SEPARATOR: char = '.';
+
+/// The identity of an authenticated token, and the apis it is allowed to call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthIdentity {
+    pub id: String,
+    pub apis: HashSet<Api>,
+}
+
+/// Authenticate the rpc bearer token. A token is either a static token in config,
+/// or a HMAC signed token in format `id.apis.expire_at.signature`,
+/// `expire_at` is unix timestamp in seconds, and `signature` is the hex of HMAC-SHA256 of `id.apis.expire_at`.
+pub struct RpcAuthenticator {
+    tokens: HashMap<String, AuthIdentity>,
+    hmac_secret: Option<Vec<u8>>,
+}
+
+impl std::fmt::Debug for RpcAuthenticator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcAuthenticator")
+            .field("tokens", &self.tokens.values().collect::<Vec<_>>())
+            .field("hmac_secret", &self.hmac_secret.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+impl RpcAuthenticator {
+    /// Return None if rpc auth is not enabled.
+    pub fn from_config(config: &RpcAuthConfiguration) -> Option<Self> {
+        if !config.is_enabled() {
+            return None;
+        }
+        let tokens = config
+            .tokens()
+            .into_iter()
+            .map(|token| {
+                (
+                    token.token,
+                    AuthIdentity {
+                        id: token.id,
+                        apis: token.apis.list_apis(),
+                    },
+                )
+            })
+            .collect();
+        Some(Self {
+            tokens,
+            hmac_secret: config
+                .hmac_secret()
+                .map(|secret| secret.as_bytes().to_vec()),
+        })
+    }
+
+    pub fn authenticate(&self, token: &str) -> Result<AuthIdentity> {
+        if let Some(identity) = self.tokens.get(token) {
+            return Ok(identity.clone());
+        }
+        let secret = self
+            .hmac_secret
+            .as_ref()
+            .ok_or_else(|| format_err!("Invalid token"))?;
+        let parts: Vec<&str> = token.split(HMAC_TOKEN_SEPARATOR).collect();
+        ensure!(parts.len() == 4, "Invalid token");
+        let (id, apis, expire_at, signature) = (parts[0], parts[1], parts[2], parts[3]);
+        // the signed payload is the token without the last separator and signature.
+        let payload = &token[..token.len() - signature.len() - 1];
+        let signature = hex::decode(signature).map_err(|_| format_err!("Invalid token"))?;
+        let mut mac = new_hmac(secret.as_slice())?;
+        mac.update(payload.as_bytes());
+        mac.verify(signature.as_slice())
+            .map_err(|_| format_err!("Invalid token"))?;
+        let expire_at: u64 = expire_at.parse()?;
+        ensure!(expire_at > now_seconds(), "Token is expired");
+        Ok(AuthIdentity {
+            id: id.to_string(),
+            apis: apis
+                .parse::<ApiSet>()
+                .map_err(|e| format_err!("{}", e))?
+                .list_apis(),
+        })
+    }
+
+    /// Authenticate the token, and check the token is allowed to call the `method`.
+    pub fn authorize_method(&self, token: &str, method: &str) -> Result<AuthIdentity> {
+        let identity = self.authenticate(token)?;
+        let api = method_api(method).ok_or_else(|| format_err!("Unknown method {}", method))?;
+        ensure!(
+            identity.apis.contains(&api),
+            "Token {} is not allowed to call {} api",
+            identity.id,
+            api
+        );
+        Ok(identity)
+    }
+}
+
+fn new_hmac(secret: &[u8]) -> Result<HmacSha256> {
+    HmacSha256::new_varkey(secret).map_err(|e| format_err!("Invalid hmac secret: {:?}", e))
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Sign a HMAC token for `id`, which is allowed to call `apis` before `expire_at`.
+pub fn sign_hmac_token(secret: &[u8], id: &str, apis: &ApiSet, expire_at: u64) -> Result<String> {
+    ensure!(
+        !id.is_empty() && !id.contains(HMAC_TOKEN_SEPARATOR),
+        "Token id should not be empty or contains '{}'",
+        HMAC_TOKEN_SEPARATOR
+    );
+    let payload = format!(
+        "{}{}{}{}{}",
+        id, HMAC_TOKEN_SEPARATOR, apis, HMAC_TOKEN_SEPARATOR, expire_at
+    );
+    let mut mac = new_hmac(secret)?;
+    mac.update(payload.as_bytes());
+    Ok(format!(
+        "{}{}{}",
+        payload,
+        HMAC_TOKEN_SEPARATOR,
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+/// Get the api group of a rpc method, the method name is `api.method`, except the pubsub methods.
+pub fn method_api(method: &str) -> Option<Api> {
+    match method {
+        "starcoin_subscribe" | "starcoin_unsubscribe" => Some(Api::PubSub),
+        method => method.split('.').next().and_then(|api| api.parse().ok()),
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonApiAuthMiddleware {
+    authenticator: Option<Arc<RpcAuthenticator>>,
+}
+
+impl JsonApiAuthMiddleware {
+    /// The middleware allows all calls if `authenticator` is None.
+    pub fn new(authenticator: Option<Arc<RpcAuthenticator>>) -> Self {
+        Self { authenticator }
+    }
+}
+
+impl Middleware<Metadata> for JsonApiAuthMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    /// Check the token of every call, and use the token identity as the user of the call.
+    fn on_call<F, X>(&self, call: Call, mut meta: Metadata, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, Metadata) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let authenticator = match &self.authenticator {
+            Some(authenticator) => authenticator,
+            None => return Either::Right(next(call, meta)),
+        };
+        let method = match &call {
+            Call::MethodCall(m) => Some((m.method.clone(), m.jsonrpc, m.id.clone())),
+            Call::Notification(n) => Some((n.method.clone(), n.jsonrpc, Id::Null)),
+            Call::Invalid { .. } => None,
+        };
+        if let Some((m, json_version, id)) = method {
+            let result = meta
+                .token
+                .as_ref()
+                .ok_or_else(|| format_err!("Token is missing"))
+                .and_then(|token| authenticator.authorize_method(token, m.as_str()));
+            match result {
+                Ok(identity) => {
+                    meta.user = Some(identity.id);
+                    Either::Right(next(call, meta))
+                }
+                Err(e) => {
+                    let output = Output::Failure(Failure {
+                        jsonrpc: json_version,
+                        error: Error {
+                            code: ErrorCode::ServerError(-10001),
+                            message: format!("Unauthorized: {}", e),
+                            data: None,
+                        },
+                        id,
+                    });
+                    Either::Left(Box::pin(futures::future::ready(Some(output))))
+                }
+            }
+        } else {
+            Either::Right(next(call, meta))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use jsonrpc_core::{MetaIoHandler, Params, Value};
+    use starcoin_config::RpcAuthToken;
+
+    fn authenticator() -> RpcAuthenticator {
+        let config = RpcAuthConfiguration {
+            tokens: Some(vec!["alice:s3cr3t:chain,state"
+                .parse::<RpcAuthToken>()
+                .unwrap()]),
+            hmac_secret: Some("hmac_secret".to_string()),
+        };
+        RpcAuthenticator::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_authenticate() -> Result<()> {
+        assert!(RpcAuthenticator::from_config(&RpcAuthConfiguration::default()).is_none());
+        let authenticator = authenticator();
+
+        let identity = authenticator.authorize_method("s3cr3t", "chain.info")?;
+        assert_eq!(identity.id.as_str(), "alice");
+        assert!(authenticator
+            .authorize_method("s3cr3t", "account.list")
+            .is_err());
+        assert!(authenticator
+            .authorize_method("wrong", "chain.info")
+            .is_err());
+
+        let apis = ApiSet::List(vec![Api::Account].into_iter().collect());
+        let token = sign_hmac_token(b"hmac_secret", "bob", &apis, now_seconds() + 60)?;
+        let identity = authenticator.authorize_method(token.as_str(), "account.list")?;
+        assert_eq!(identity.id.as_str(), "bob");
+        assert!(authenticator
+            .authorize_method(token.as_str(), "chain.info")
+            .is_err());
+        assert!(authenticator
+            .authorize_method(token.as_str(), "starcoin_subscribe")
+            .is_err());
+
+        // token signed by other secret.
+        let token = sign_hmac_token(b"other_secret", "bob", &apis, now_seconds() + 60)?;
+        assert!(authenticator.authenticate(token.as_str()).is_err());
+        // tampered token.
+        let token = sign_hmac_token(b"hmac_secret", "bob", &apis, now_seconds() + 60)?;
+        let tampered = token.replacen("account", "chain", 1);
+        assert!(authenticator.authenticate(tampered.as_str()).is_err());
+        // expired token.
+        let token = sign_hmac_token(b"hmac_secret", "bob", &apis, now_seconds() - 1)?;
+        assert!(authenticator.authenticate(token.as_str()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_auth_middleware() {
+        let mut io_handler = MetaIoHandler::with_middleware(JsonApiAuthMiddleware::new(Some(
+            Arc::new(authenticator()),
+        )));
+        io_handler.add_method_with_meta(
+            "chain.info",
+            |_params: Params, meta: Metadata| async move {
+                Ok(Value::String(meta.user.unwrap_or_default()))
+            },
+        );
+        let request = r#"{"jsonrpc":"2.0","method":"chain.info","params":[],"id":1}"#;
+
+        let response = block_on(io_handler.handle_request(request, Metadata::default())).unwrap();
+        assert!(response.contains("Unauthorized"));
+
+        let meta = Metadata {
+            token: Some("s3cr3t".to_string()),
+            ..Default::default()
+        };
+        let response = block_on(io_handler.handle_request(request, meta)).unwrap();
+        assert_eq!(response, r#"{"jsonrpc":"2.0","result":"alice","id":1}"#);
+    }
+}
@@ -4,9 +4,32 @@
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::Session;
 use starcoin_rpc_api::metadata::Metadata;
+use std::cell::RefCell;
 use std::net::IpAddr;
 use std::sync::Arc;
 
+/// Get the token from a `Bearer <token>` authorization header value.
+pub fn bearer_token(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.len() > 7 && value[..7].eq_ignore_ascii_case("bearer ") {
+        Some(value[7..].trim().to_string())
+    } else {
+        None
+    }
+}
+
+thread_local! {
+    /// The bearer token of the websocket handshake request being processed. The websocket server
+    /// extracts the session metadata right after the request middleware in the same handshake,
+    /// so the middleware keeps the token here for `WsExtractor`.
+    static WS_HANDSHAKE_TOKEN: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Keep the bearer token of the websocket handshake request for `WsExtractor`.
+pub fn set_ws_handshake_token(token: Option<String>) {
+    WS_HANDSHAKE_TOKEN.with(|handshake_token| *handshake_token.borrow_mut() = token);
+}
+
 /// Common HTTP & IPC & TCP metadata extractor.
 #[derive(Default)]
 pub struct RpcExtractor {
@@ -27,9 +50,16 @@ impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
             }
         }
 
+        let token = _req
+            .headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(bearer_token);
+
         Metadata {
             session: None,
             user: client_ip.map(|ip| ip.to_string()),
+            token,
         }
     }
 }
@@ -38,6 +68,7 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
             user: None,
+            token: None,
         }
     }
 }
@@ -46,6 +77,7 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            token: None,
         }
     }
 }
@@ -57,6 +89,7 @@ impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
         Metadata {
             session,
             user: None,
+            token: WS_HANDSHAKE_TOKEN.with(|handshake_token| handshake_token.borrow_mut().take()),
         }
     }
 }
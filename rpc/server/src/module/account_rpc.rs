@@ -5,7 +5,7 @@ use crate::module::helpers::TransactionRequestFiller;
 use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
-use starcoin_account_api::{AccountAsyncService, AccountInfo};
+use starcoin_account_api::{AccountAsyncService, AccountInfo, MnemonicAccountInfo};
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_rpc_api::types::{StrView, TransactionRequest};
@@ -141,7 +141,7 @@ where
         let fut = async move {
             let raw_txn = me
                 .txn_request_filler()
-                .fill_transaction(txn_request)
+                .fill_transaction(txn_request, None)
                 .await?;
             let sender = raw_txn.sender();
             let signed_txn = me.account.sign_txn(raw_txn, sender).await?;
@@ -246,4 +246,30 @@ where
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn create_from_mnemonic(
+        &self,
+        mnemonic: Option<String>,
+        password: String,
+    ) -> FutureResult<MnemonicAccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service
+                .create_account_from_mnemonic(mnemonic, password)
+                .await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn derive(&self, index: u32, password: String) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.derive_account(index, password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
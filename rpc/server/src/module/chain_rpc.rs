@@ -9,10 +9,12 @@ use starcoin_crypto::HashValue;
 use starcoin_rpc_api::chain::ChainApi;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
-    BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventView, TransactionInfoView, TransactionView,
+    AddressTransactionInfoView, BlockFeeView, BlockHeaderView, BlockSummaryView, BlockView,
+    ChainId, ChainInfoView, EpochUncleSummaryView, FailedBlockView, FeeHistoryView,
+    TransactionEventView, TransactionInfoView, TransactionProofView, TransactionView,
 };
 use starcoin_rpc_api::FutureResult;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{BlockInfo, BlockNumber};
 use starcoin_types::filter::Filter;
 use starcoin_types::startup_info::ChainInfo;
@@ -210,6 +212,23 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_transaction_proof(
+        &self,
+        transaction_hash: HashValue,
+        anchor_block_hash: Option<HashValue>,
+    ) -> FutureResult<Option<TransactionProofView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            service
+                .get_transaction_proof(transaction_hash, anchor_block_hash)
+                .await?
+                .map(TryInto::try_into)
+                .transpose()
+        };
+
+        Box::pin(fut.boxed().map_err(map_err))
+    }
+
     fn get_txn_info_by_block_and_index(
         &self,
         block_hash: HashValue,
@@ -239,6 +258,115 @@ where
 
         Box::pin(fut.boxed())
     }
+    fn get_transactions_by_address(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> FutureResult<Vec<AddressTransactionInfoView>> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let limit = limit.min(config.rpc.txn_query_max_range());
+            let txn_infos = service
+                .get_transaction_infos_by_address(address, cursor, limit)
+                .await?;
+            let mut views = Vec::with_capacity(txn_infos.len());
+            for (index, txn_info) in txn_infos {
+                let block = service
+                    .get_block_by_hash(txn_info.block_id())
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "cannot find the block {} which include txn {}",
+                            txn_info.block_id(),
+                            txn_info.transaction_hash()
+                        )
+                    })?;
+                views.push(AddressTransactionInfoView {
+                    index: index.into(),
+                    transaction_info: TransactionInfoView::new(
+                        Into::<(_, TransactionInfo)>::into(txn_info).1,
+                        &block,
+                    )?,
+                });
+            }
+            Ok(views)
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn fee_history(&self, block_count: u64, percentiles: Vec<f64>) -> FutureResult<FeeHistoryView> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let block_count = block_count.min(config.rpc.block_query_max_range());
+            let fee_history = service.get_fee_history(block_count, percentiles).await?;
+            Ok(FeeHistoryView {
+                oldest_block: fee_history.oldest_block().map(Into::into),
+                blocks: fee_history
+                    .blocks
+                    .into_iter()
+                    .map(|block| BlockFeeView {
+                        number: block.number.into(),
+                        gas_used: block.gas_used.into(),
+                        gas_limit: block.gas_limit.into(),
+                        gas_used_ratio: if block.gas_limit == 0 {
+                            0f64
+                        } else {
+                            block.gas_used as f64 / block.gas_limit as f64
+                        },
+                        gas_price_percentiles: block
+                            .gas_price_percentiles
+                            .into_iter()
+                            .map(Into::into)
+                            .collect(),
+                    })
+                    .collect(),
+            })
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn list_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: u64,
+    ) -> FutureResult<Vec<FailedBlockView>> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let limit = limit.min(config.rpc.block_query_max_range());
+            service
+                .get_failed_blocks(cursor, limit)
+                .await?
+                .into_iter()
+                .map(|failed_block| FailedBlockView::try_from_failed_block(failed_block, true))
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_failed_block(&self, block_hash: HashValue) -> FutureResult<Option<FailedBlockView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            service
+                .get_failed_block(block_hash)
+                .await?
+                .map(|failed_block| FailedBlockView::try_from_failed_block(failed_block, false))
+                .transpose()
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_events_by_txn_hash(
         &self,
         txn_hash: HashValue,
@@ -257,22 +385,36 @@ where
         let service = self.service.clone();
         let config = self.config.clone();
         let fut = async move {
+            if filter.has_subscription_filters() {
+                return Err(jsonrpc_core::Error::invalid_params(
+                    "type_patterns, module_addrs, field_predicates, decode and include_removed are only supported by the events subscription",
+                )
+                .into());
+            }
             if filter.to_block.is_none() {
                 // if user hasn't specify the `to_block`, we use latest block as the to_block.
                 let header_block_number = service.main_head_header().await?.number();
                 filter.to_block = Some(header_block_number);
             }
 
-            let filter: Filter = filter.try_into()?;
+            let mut filter: Filter = filter.try_into()?;
 
+            let indexed = config.storage.enable_event_index()
+                && !(filter.event_keys.is_empty() && filter.type_tags.is_empty());
             let max_block_range = config.rpc.block_query_max_range();
-            // if the from~to range is bigger than what we configured, return invalid param error.
-            if filter
+            if indexed {
+                // the events are searched in the event index, so the block range is not limited,
+                // but the count of events is, the blocks before the index start are still
+                // limited by the block range when searching them.
+                let max_limit = config.rpc.event_query_max_limit();
+                filter.limit = Some(filter.limit.unwrap_or(max_limit).min(max_limit));
+            } else if filter
                 .to_block
                 .checked_sub(filter.from_block)
                 .filter(|r| *r > max_block_range)
                 .is_some()
             {
+                // if the from~to range is bigger than what we configured, return invalid param error.
                 return Err(jsonrpc_core::Error::invalid_params(format!(
                     "from_block is too far, max block range is {} ",
                     max_block_range
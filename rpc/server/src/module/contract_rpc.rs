@@ -1,7 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::helpers::TransactionRequestFiller;
+use crate::module::helpers::{
+    output_view_with_trace, resolve_state_root, TransactionRequestFiller,
+};
 use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
@@ -12,7 +14,7 @@ use starcoin_dev::playground::PlaygroudService;
 use starcoin_rpc_api::contract_api::ContractApi;
 use starcoin_rpc_api::types::{
     AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunTransactionRequest,
-    StrView, TransactionOutputView,
+    StateSelector, StrView, TransactionOutputView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::ChainStateAsyncService;
@@ -87,17 +89,27 @@ where
         &self,
         addr: AccountAddress,
         resource_type: StrView<StructTag>,
+        state: Option<StateSelector>,
     ) -> FutureResult<Option<AnnotatedMoveStructView>> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let playground = self.playground.clone();
         let f = async move {
-            let state_root = service.clone().state_root().await?;
-            let data = service
-                .get(AccessPath::resource_access_path(
-                    addr,
-                    resource_type.0.clone(),
-                ))
-                .await?;
+            let access_path = AccessPath::resource_access_path(addr, resource_type.0.clone());
+            let (state_root, data) = match state {
+                None => (
+                    service.clone().state_root().await?,
+                    service.get(access_path).await?,
+                ),
+                Some(_) => {
+                    let state_root = resolve_state_root(service.clone(), chain, state).await?;
+                    let data = service
+                        .get_with_proof_by_root(access_path, state_root)
+                        .await?
+                        .state;
+                    (state_root, data)
+                }
+            };
             match data {
                 None => Ok(None),
                 Some(d) => {
@@ -109,8 +121,13 @@ where
         };
         Box::pin(f.map_err(map_err).boxed())
     }
-    fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>> {
+    fn call(
+        &self,
+        call: ContractCall,
+        state: Option<StateSelector>,
+    ) -> FutureResult<Vec<AnnotatedMoveValueView>> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let playground = self.playground.clone();
         let ContractCall {
             function_id,
@@ -118,7 +135,7 @@ where
             args,
         } = call;
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = resolve_state_root(service, chain, state).await?;
             let output = playground.call_contract(
                 state_root,
                 function_id.0.module,
@@ -131,19 +148,30 @@ where
         .map_err(map_err);
         Box::pin(f.boxed())
     }
-    fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<TransactionOutputView> {
+    fn dry_run(
+        &self,
+        txn: DryRunTransactionRequest,
+        state: Option<StateSelector>,
+    ) -> FutureResult<TransactionOutputView> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let txn_builder = self.txn_request_filler();
         let playground = self.playground.clone();
         let account_service = self.account.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = resolve_state_root(service, chain, state).await?;
             let DryRunTransactionRequest {
                 transaction,
                 sender_public_key,
+                trace,
+                gas_profile,
             } = txn;
 
-            let txn = txn_builder.fill_transaction(transaction).await?;
+            // the head state is selected if `state` is none, the sequence number can be
+            // filled from the txpool.
+            let txn = txn_builder
+                .fill_transaction(transaction, state.map(|_| state_root))
+                .await?;
             let sender_public_key = match sender_public_key {
                 None => match account_service {
                     Some(account) => account
@@ -158,14 +186,28 @@ where
                 Some(p) => p.0,
             };
 
-            let output = playground.dry_run(
-                state_root,
-                DryRunTransaction {
-                    raw_txn: txn,
-                    public_key: sender_public_key,
-                },
-            )?;
-            Ok(output.1.into())
+            let txn = DryRunTransaction {
+                raw_txn: txn,
+                public_key: sender_public_key,
+            };
+            if trace || gas_profile {
+                let (_, output, txn_trace) = playground.dry_run_with_trace(state_root, txn)?;
+                let profile = if gas_profile {
+                    Some(txn_trace.gas_profile().into())
+                } else {
+                    None
+                };
+                let mut output = if trace {
+                    output_view_with_trace(output, txn_trace)
+                } else {
+                    TransactionOutputView::from(output)
+                };
+                output.gas_profile = profile;
+                Ok(output)
+            } else {
+                let output = playground.dry_run(state_root, txn)?;
+                Ok(output.1.into())
+            }
         }
         .map_err(map_err);
         Box::pin(f.boxed())
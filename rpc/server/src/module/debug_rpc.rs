@@ -1,29 +1,64 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::to_invalid_param_err;
+use crate::module::helpers::output_view_with_trace;
 use crate::module::txfactory_rpc::TxFactoryStatusHandle;
+use crate::module::{map_err, to_invalid_param_err};
+use futures::future::{FutureExt, TryFutureExt};
 use jsonrpc_core::Result;
+use starcoin_chain::BlockChain;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_dev::playground::PlaygroudService;
 use starcoin_logger::prelude::LevelFilter;
 use starcoin_logger::{LogPattern, LoggerHandle};
 use starcoin_rpc_api::debug::DebugApi;
-use starcoin_rpc_api::types::FactoryAction;
+use starcoin_rpc_api::types::{
+    BlockReplayView, FactoryAction, GasProfileView, ReplayMismatchView, TransactionOutputView,
+    TransactionReplayTraceView,
+};
+use starcoin_rpc_api::FutureResult;
+use starcoin_storage::Storage;
 use std::str::FromStr;
 use std::sync::Arc;
 
-pub struct DebugRpcImpl {
+pub struct DebugRpcImpl<S>
+where
+    S: ChainAsyncService + 'static,
+{
     config: Arc<NodeConfig>,
     log_handle: Arc<LoggerHandle>,
+    chain_service: S,
+    storage: Arc<Storage>,
+    playground: PlaygroudService,
 }
 
-impl DebugRpcImpl {
-    pub fn new(config: Arc<NodeConfig>, log_handle: Arc<LoggerHandle>) -> Self {
-        Self { config, log_handle }
+impl<S> DebugRpcImpl<S>
+where
+    S: ChainAsyncService,
+{
+    pub fn new(
+        config: Arc<NodeConfig>,
+        log_handle: Arc<LoggerHandle>,
+        chain_service: S,
+        storage: Arc<Storage>,
+        playground: PlaygroudService,
+    ) -> Self {
+        Self {
+            config,
+            log_handle,
+            chain_service,
+            storage,
+            playground,
+        }
     }
 }
 
-impl DebugApi for DebugRpcImpl {
+impl<S> DebugApi for DebugRpcImpl<S>
+where
+    S: ChainAsyncService,
+{
     fn set_log_level(&self, logger_name: Option<String>, level: String) -> Result<()> {
         let logger_name = logger_name.and_then(|s| {
             let s = s.trim();
@@ -64,6 +99,125 @@ impl DebugApi for DebugRpcImpl {
         Ok(())
     }
 
+    fn replay_failed_block(&self, block_hash: HashValue) -> FutureResult<BlockReplayView> {
+        let service = self.chain_service.clone();
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let fut = async move {
+            let (block, _, failed) =
+                service.get_failed_block(block_hash).await?.ok_or_else(|| {
+                    anyhow::anyhow!("Can not find failed block by hash {:?}", block_hash)
+                })?;
+            // Replay on a chain of the parent block here instead of in the chain service,
+            // the execution should not block the chain service.
+            let parent_hash = block.header().parent_hash();
+            let parent_chain = BlockChain::new(config.net().time_service(), parent_hash, storage)
+                .map_err(|e| {
+                anyhow::anyhow!(
+                    "Can not replay block {:?} without parent {:?}: {:?}",
+                    block_hash,
+                    parent_hash,
+                    e
+                )
+            })?;
+            let report = parent_chain.replay_block(block)?;
+            Ok(BlockReplayView {
+                block_hash: report.block_id,
+                block_number: report.number.into(),
+                parent_hash: report.parent_hash,
+                failed,
+                verify_error: report.verify_error,
+                execute_error: report.execute_error,
+                transactions: report
+                    .txns
+                    .into_iter()
+                    .map(|trace| TransactionReplayTraceView {
+                        transaction_hash: trace.txn_hash,
+                        status: trace.status.into(),
+                        gas_used: trace.gas_used.into(),
+                        cumulative_gas_used: trace.cumulative_gas_used.into(),
+                        state_root_hash: trace.state_root,
+                        transaction_info_id: trace.txn_info_id,
+                        event_count: trace.event_count.into(),
+                    })
+                    .collect(),
+                unexecuted_transactions: report.unexecuted_txns,
+                mismatches: report
+                    .mismatches
+                    .into_iter()
+                    .map(|mismatch| ReplayMismatchView {
+                        field: mismatch.field,
+                        expected: mismatch.expected,
+                        actual: mismatch.actual,
+                    })
+                    .collect(),
+                diverged_at: report.diverged_at.map(Into::into),
+            })
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionOutputView> {
+        let service = self.chain_service.clone();
+        let playground = self.playground.clone();
+        let fut = async move {
+            let txn = service.get_transaction(txn_hash).await?.ok_or_else(|| {
+                anyhow::anyhow!("Can not find transaction by hash {:?}", txn_hash)
+            })?;
+            let txn_info = service
+                .get_transaction_info(txn_hash)
+                .await?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Can not find transaction info by hash {:?}", txn_hash)
+                })?;
+            let block_id = txn_info.block_id();
+            let txn_infos = service.get_block_txn_infos(block_id).await?;
+            let index = txn_infos
+                .iter()
+                .position(|info| info.transaction_hash() == txn_hash)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Can not find transaction {:?} in block {:?}",
+                        txn_hash,
+                        block_id
+                    )
+                })?;
+            // The state before the transaction is the state after the previous transaction
+            // of the block, or the state of the parent block for the first transaction.
+            let state_root = match index.checked_sub(1) {
+                Some(pre_index) => txn_infos[pre_index].state_root_hash(),
+                None => {
+                    let header = service
+                        .get_header_by_hash(&block_id)
+                        .await?
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("Can not find block header by hash {:?}", block_id)
+                        })?;
+                    service
+                        .get_header_by_hash(&header.parent_hash())
+                        .await?
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Can not find block header by hash {:?}",
+                                header.parent_hash()
+                            )
+                        })?
+                        .state_root()
+                }
+            };
+            let (_, output, trace) = playground.trace_transaction(state_root, txn)?;
+            let profile: GasProfileView = trace.gas_profile().into();
+            let mut output = output_view_with_trace(output, trace);
+            output.gas_profile = Some(profile);
+            Ok(output)
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool> {
         Ok(TxFactoryStatusHandle::handle_action(action))
     }
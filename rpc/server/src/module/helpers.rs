@@ -1,11 +1,18 @@
 use starcoin_account_api::AccountAsyncService;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
-use starcoin_rpc_api::types::TransactionRequest;
+use starcoin_crypto::HashValue;
+use starcoin_dev::trace::{ResourceTrace, TransactionTrace};
+use starcoin_rpc_api::types::{
+    CallTraceView, EventTraceView, ResourceTraceView, StateSelector, TransactionOutputView,
+    TransactionRequest, TransactionTraceView,
+};
 use starcoin_state_api::ChainStateAsyncService;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_config::AccountResource;
-use starcoin_types::transaction::{Module, Package, RawUserTransaction, TransactionPayload};
+use starcoin_types::transaction::{
+    Module, Package, RawUserTransaction, TransactionOutput, TransactionPayload,
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -24,9 +31,12 @@ where
     State: ChainStateAsyncService + 'static,
     Chain: ChainAsyncService + 'static,
 {
+    /// Fill the missing fields of `txn_request`, the sequence number is filled from the state of
+    /// `state_root` if it is some, otherwise from the txpool and the head state.
     pub(crate) async fn fill_transaction(
         &self,
         txn_request: TransactionRequest,
+        state_root: Option<HashValue>,
     ) -> anyhow::Result<RawUserTransaction> {
         let payload = if !txn_request.modules.is_empty() {
             let modules = txn_request
@@ -61,19 +71,28 @@ where
                 }
             },
         };
-        let next_seq_number = match txn_request
-            .sequence_number
-            .or_else(|| self.pool.next_sequence_number(sender))
-        {
-            Some(n) => n,
-            None => match self
+        let next_seq_number = match (txn_request.sequence_number, state_root) {
+            (Some(n), _) => n,
+            (None, Some(state_root)) => match self
                 .chain_state
                 .clone()
-                .get_resource::<AccountResource>(sender)
+                .get_resource_by_root::<AccountResource>(sender, state_root)
                 .await?
             {
                 Some(r) => r.sequence_number(),
-                None => anyhow::bail!("cannot find account {} onchain", sender),
+                None => anyhow::bail!("cannot find account {} on the state {}", sender, state_root),
+            },
+            (None, None) => match self.pool.next_sequence_number(sender) {
+                Some(n) => n,
+                None => match self
+                    .chain_state
+                    .clone()
+                    .get_resource::<AccountResource>(sender)
+                    .await?
+                {
+                    Some(r) => r.sequence_number(),
+                    None => anyhow::bail!("cannot find account {} onchain", sender),
+                },
             },
         };
         let max_gas_amount = txn_request.max_gas_amount.unwrap_or(1000000); // default 10_00000
@@ -105,3 +124,96 @@ where
         Ok(raw_txn)
     }
 }
+
+/// Resolve the state root selected by `state`, `None` selects the head state of the main chain.
+pub(crate) async fn resolve_state_root<State, Chain>(
+    chain_state: State,
+    chain: Chain,
+    state: Option<StateSelector>,
+) -> anyhow::Result<HashValue>
+where
+    State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
+{
+    match state {
+        None => chain_state.state_root().await,
+        Some(StateSelector::StateRoot(state_root)) => Ok(state_root),
+        Some(StateSelector::BlockNumber(number)) => chain
+            .main_block_header_by_number(number)
+            .await?
+            .map(|header| header.state_root())
+            .ok_or_else(|| anyhow::anyhow!("cannot find block header by number {}", number)),
+        Some(StateSelector::BlockHash(block_hash)) => {
+            let header = chain
+                .get_header_by_hash(&block_hash)
+                .await?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("cannot find block header by hash {}", block_hash)
+                })?;
+            let main_header = chain.main_block_header_by_number(header.number()).await?;
+            anyhow::ensure!(
+                main_header.map(|main_header| main_header.id()) == Some(block_hash),
+                "block {} is not on the main chain",
+                block_hash
+            );
+            Ok(header.state_root())
+        }
+    }
+}
+
+/// Convert the `output` to view with the `trace` of the execution.
+pub(crate) fn output_view_with_trace(
+    output: TransactionOutput,
+    trace: TransactionTrace,
+) -> TransactionOutputView {
+    let mut view = TransactionOutputView::from(output);
+    view.trace = Some(TransactionTraceView {
+        calls: trace
+            .calls
+            .into_iter()
+            .map(|call| CallTraceView {
+                kind: call.kind.to_string(),
+                module: call.module.map(Into::into),
+                function: call.function,
+                ty_args: call.ty_args.into_iter().map(Into::into).collect(),
+                gas_used: call.gas_used.into(),
+                gas_by_category: call
+                    .gas_by_category
+                    .into_iter()
+                    .map(|(category, gas_used)| (category.to_string(), gas_used.into()))
+                    .collect(),
+                error: call.error,
+            })
+            .collect(),
+        intrinsic_gas: trace.intrinsic_gas.into(),
+        global_write_gas: trace.global_write_gas.into(),
+        resources_read: trace
+            .resources_read
+            .into_iter()
+            .map(resource_trace_view)
+            .collect(),
+        resources_written: trace
+            .resources_written
+            .into_iter()
+            .map(resource_trace_view)
+            .collect(),
+        events: trace
+            .events
+            .into_iter()
+            .map(|event| EventTraceView {
+                event: event.event.into(),
+                decoded_data: event.data.into(),
+            })
+            .collect(),
+    });
+    view
+}
+
+fn resource_trace_view(resource: ResourceTrace) -> ResourceTraceView {
+    ResourceTraceView {
+        address: resource.address,
+        struct_tag: resource.struct_tag.into(),
+        before: resource.before.map(Into::into),
+        after: resource.after.map(Into::into),
+    }
+}
@@ -4,6 +4,7 @@
 use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use network_api::messages::BannedPeer;
 use network_p2p_types::network_state::NetworkState;
 use network_rpc_core::RawRpcClient;
 use starcoin_network::NetworkServiceRef;
@@ -13,6 +14,7 @@ use starcoin_rpc_api::FutureResult;
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use std::borrow::Cow;
 use std::str::FromStr;
+use std::time::Duration;
 
 pub struct NetworkManagerRpcImpl {
     service: NetworkServiceRef,
@@ -58,6 +60,58 @@ impl NetworkManagerApi for NetworkManagerRpcImpl {
         Box::pin(fut.boxed())
     }
 
+    fn add_reserved_peer(&self, peer: String) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move { service.add_reserved_peer(peer) }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn remove_reserved_peer(&self, peer_id: String) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move {
+            let peer_id = PeerId::from_str(peer_id.as_str())?;
+            service.remove_reserved_peer(peer_id);
+            Ok(())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn ban_peer(
+        &self,
+        peer_id: String,
+        duration: Option<u64>,
+        reason: Option<String>,
+    ) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move {
+            let peer_id = PeerId::from_str(peer_id.as_str())?;
+            service.ban_peer_for(
+                peer_id,
+                duration.map(Duration::from_secs),
+                reason.unwrap_or_else(|| "Banned by rpc".to_string()),
+            )
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn unban_peer(&self, peer_id: String) -> FutureResult<bool> {
+        let service = self.service.clone();
+        let fut = async move {
+            let peer_id = PeerId::from_str(peer_id.as_str())?;
+            service.unban_peer(peer_id).await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn list_banned(&self) -> FutureResult<Vec<BannedPeer>> {
+        let service = self.service.clone();
+        let fut = async move { service.banned_peers().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn call_peer(
         &self,
         peer_id: String,
@@ -1,30 +1,42 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::map_err;
 use anyhow::Result;
 use futures::channel::mpsc;
 use futures::future::AbortHandle;
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use jsonrpc_pubsub::typed::Subscriber;
 use jsonrpc_pubsub::SubscriptionId;
 use parking_lot::RwLock;
 use starcoin_chain_notify::message::{Event, Notification, ThinBlock};
+use starcoin_chain_service::{ChainAsyncService, ChainReaderService};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{MinerClientSubscribeRequest, MinerService};
+use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::metadata::Metadata;
-use starcoin_rpc_api::types::pubsub::MintBlock;
-use starcoin_rpc_api::types::{BlockView, TransactionEventView};
+use starcoin_rpc_api::types::pubsub::{
+    EventSubscriptionFilter, MintBlock, TransactionStatusFilter, TransactionStatusView,
+};
+use starcoin_rpc_api::types::{
+    AnnotatedMoveValueView, BlockView, EventTraceView, TransactionEventView, TransactionInfoView,
+};
 use starcoin_rpc_api::{errors, pubsub::StarcoinPubSub, types::pubsub};
 use starcoin_service_registry::{
     ActorService, EventHandler as ActorEventHandler, ServiceContext, ServiceFactory,
     ServiceHandler, ServiceRef, ServiceRequest,
 };
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{BlockStore, BlockTransactionInfoStore, Storage};
 use starcoin_txpool::TxPoolService;
-use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::Block;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::MintBlockEvent;
-use std::collections::HashMap;
+use starcoin_types::system_events::{ChainReorg, MintBlockEvent, NewHeadBlock};
+use starcoin_types::transaction::{SignedUserTransaction, TransactionInfo, TxStatus};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fmt::Debug;
 use std::sync::mpsc::TrySendError;
@@ -102,12 +114,14 @@ impl PubSubImpl {
                 errors::invalid_params("newPendingTransactions", "Expected no parameters."),
             )),
             (pubsub::Kind::Events, Some(pubsub::Params::Events(filter))) => {
+                let subscription_filter = filter.subscription_filter();
                 match filter.try_into() {
                     Ok(f) => self
                         .service
                         .try_send(SubscribeEvents {
                             subscriber,
                             filter: f,
+                            subscription_filter,
                         })
                         .map_err(|e| {
                             let msg = map_send_err(&e);
@@ -126,6 +140,46 @@ impl PubSubImpl {
                 subscriber,
                 errors::invalid_params("events", "Expected a filter object."),
             )),
+            (pubsub::Kind::ChainReorg, None) => self
+                .service
+                .try_send(SubscribeChainReorg(subscriber))
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.0,
+                            TrySendError::Full(t) => t.0,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::TransactionStatus, Some(pubsub::Params::TransactionStatus(filter)))
+                if !filter.txn_hashes.is_empty() || filter.sender.is_some() =>
+            {
+                self.service
+                    .try_send(SubscribeTxnStatus { subscriber, filter })
+                    .map_err(|e| {
+                        let msg = map_send_err(&e);
+                        (
+                            match e {
+                                TrySendError::Disconnected(t) => t.subscriber,
+                                TrySendError::Full(t) => t.subscriber,
+                            },
+                            msg,
+                        )
+                    })
+            }
+            (pubsub::Kind::TransactionStatus, _) => Err((
+                subscriber,
+                errors::invalid_params(
+                    "transactionStatus",
+                    "Expected a filter object with txn_hashes or sender.",
+                ),
+            )),
+            (pubsub::Kind::ChainReorg, _) => Err((
+                subscriber,
+                errors::invalid_params("chainReorg", "Expected no parameters."),
+            )),
             (pubsub::Kind::NewMintBlock, _) => self
                 .service
                 .try_send(SubscribeMintBlock(subscriber))
@@ -183,9 +237,12 @@ pub struct PubSubServiceFactory;
 impl ServiceFactory<PubSubService> for PubSubServiceFactory {
     fn create(ctx: &mut ServiceContext<PubSubService>) -> Result<PubSubService> {
         let miner_service = ctx.service_ref::<MinerService>()?.clone();
+        let chain_service = ctx.service_ref::<ChainReaderService>()?.clone();
         Ok(PubSubService::new(
             ctx.get_shared::<TxPoolService>()?,
             miner_service,
+            chain_service,
+            ctx.get_shared::<Arc<Storage>>()?,
         ))
     }
 }
@@ -194,24 +251,39 @@ pub struct PubSubService {
     subscriber_id: Arc<atomic::AtomicU64>,
     txpool: TxPoolService,
     miner_service: ServiceRef<MinerService>,
+    chain_service: ServiceRef<ChainReaderService>,
+    storage: Arc<Storage>,
 
     new_header_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewHeadNotification>>,
     new_event_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewEventNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
+    chain_reorg_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<ChainReorg>>,
+    txn_status_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<TxnStatusMessage>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
+    txn_status_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
 }
 
 impl PubSubService {
-    fn new(txpool: TxPoolService, miner_service: ServiceRef<MinerService>) -> Self {
+    fn new(
+        txpool: TxPoolService,
+        miner_service: ServiceRef<MinerService>,
+        chain_service: ServiceRef<ChainReaderService>,
+        storage: Arc<Storage>,
+    ) -> Self {
         let subscriber_id = Arc::new(atomic::AtomicU64::new(0));
         Self {
             subscriber_id,
             txpool,
             miner_service,
+            chain_service,
+            storage,
             new_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
+            chain_reorg_subscribers: Default::default(),
+            txn_status_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
+            txn_status_tasks: Arc::new(RwLock::new(HashMap::default())),
         }
     }
     fn next_id(&self) -> SubscriptionId {
@@ -230,6 +302,8 @@ impl ActorService for PubSubService {
         ctx.subscribe::<NewHeadNotification>();
         ctx.subscribe::<NewEventNotification>();
         ctx.subscribe::<MintBlockEvent>();
+        ctx.subscribe::<ChainReorg>();
+        ctx.subscribe::<NewHeadBlock>();
 
         Ok(())
     }
@@ -257,6 +331,25 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
     }
 }
 
+impl ActorEventHandler<Self, ChainReorg> for PubSubService {
+    fn handle_event(&mut self, msg: ChainReorg, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(
+            &mut self.txn_status_subscribers,
+            TxnStatusMessage::ChainReorg(msg.clone()),
+        );
+        send_to_all(&mut self.chain_reorg_subscribers, msg);
+    }
+}
+
+impl ActorEventHandler<Self, NewHeadBlock> for PubSubService {
+    fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(
+            &mut self.txn_status_subscribers,
+            TxnStatusMessage::NewHead(msg),
+        );
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeNewHeads(Subscriber<pubsub::Result>);
 
@@ -280,6 +373,29 @@ impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeChainReorg(Subscriber<pubsub::Result>);
+
+impl ServiceRequest for SubscribeChainReorg {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeChainReorg> for PubSubService {
+    fn handle(&mut self, msg: SubscribeChainReorg, ctx: &mut ServiceContext<Self>) {
+        let SubscribeChainReorg(sink) = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.chain_reorg_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            sink,
+            ChainReorgHandler,
+        ));
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeMintBlock(Subscriber<pubsub::Result>);
 
@@ -323,6 +439,7 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
 struct SubscribeEvents {
     subscriber: Subscriber<pubsub::Result>,
     filter: Filter,
+    subscription_filter: EventSubscriptionFilter,
 }
 
 impl ServiceRequest for SubscribeEvents {
@@ -331,7 +448,11 @@ impl ServiceRequest for SubscribeEvents {
 
 impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
     fn handle(&mut self, msg: SubscribeEvents, ctx: &mut ServiceContext<Self>) {
-        let SubscribeEvents { subscriber, filter } = msg;
+        let SubscribeEvents {
+            subscriber,
+            filter,
+            subscription_filter,
+        } = msg;
         let (sender, receiver) = mpsc::unbounded();
         let subscriber_id = self.next_id();
         self.new_event_subscribers
@@ -340,7 +461,11 @@ impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
             receiver,
             subscriber_id,
             subscriber,
-            ContractEventHandler { filter },
+            ContractEventHandler {
+                filter,
+                subscription_filter,
+                storage: self.storage.clone(),
+            },
         ));
     }
 }
@@ -383,6 +508,58 @@ impl ServiceHandler<Self, SubscribeNewPendingTxns> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeTxnStatus {
+    subscriber: Subscriber<pubsub::Result>,
+    filter: TransactionStatusFilter,
+}
+
+impl ServiceRequest for SubscribeTxnStatus {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeTxnStatus> for PubSubService {
+    fn handle(&mut self, msg: SubscribeTxnStatus, ctx: &mut ServiceContext<Self>) {
+        let SubscribeTxnStatus { subscriber, filter } = msg;
+        let subscriber_id = self.next_id();
+        let (sender, chain_receiver) = mpsc::unbounded();
+        self.txn_status_subscribers
+            .insert(subscriber_id.clone(), sender);
+        let txn_hashes = filter.txn_hashes.clone();
+        let handler = TxnStatusHandler::new(filter, self.txpool.clone(), self.storage.clone());
+        // the changes after subscribed are buffered until the current statuses are notified.
+        let current = current_txn_statuses(
+            txn_hashes,
+            handler.sender_txn_hashes.read().iter().copied().collect(),
+            self.txpool.clone(),
+            self.chain_service.clone(),
+        );
+        let receiver = futures::stream::once(current)
+            .map(TxnStatusMessage::Current)
+            .chain(futures::stream::select(
+                self.txpool
+                    .subscribe_txns()
+                    .map(TxnStatusMessage::TxPoolStatus),
+                chain_receiver,
+            ));
+        let tasks = self.txn_status_tasks.clone();
+        let subscriber_id_clone = subscriber_id.clone();
+        let (f, abort_handle) = futures::future::abortable(async move {
+            run_subscription(receiver, subscriber_id_clone.clone(), subscriber, handler).await;
+            // remove self from task list.
+            tasks.write().remove(&subscriber_id_clone);
+        });
+
+        ctx.spawn(async move {
+            let _ = f.await;
+        });
+
+        self.txn_status_tasks
+            .write()
+            .insert(subscriber_id, abort_handle);
+    }
+}
+
 #[derive(Debug)]
 struct Unsubscribe(SubscriptionId);
 
@@ -395,6 +572,8 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         self.new_header_subscribers.remove(&msg.0);
         self.new_event_subscribers.remove(&msg.0);
         self.mint_block_subscribers.remove(&msg.0);
+        self.chain_reorg_subscribers.remove(&msg.0);
+        self.txn_status_subscribers.remove(&msg.0);
         self.miner_service
             .do_send(MinerClientSubscribeRequest::Remove(
                 self.mint_block_subscribers.len() as u32,
@@ -402,6 +581,9 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         if let Some(h) = self.new_pending_txn_tasks.write().remove(&msg.0) {
             h.abort();
         }
+        if let Some(h) = self.txn_status_tasks.write().remove(&msg.0) {
+            h.abort();
+        }
     }
 }
 
@@ -430,12 +612,13 @@ fn send_to_all<T: Clone>(
     }
 }
 
-async fn run_subscription<M, Handler>(
-    msg_channel: mpsc::UnboundedReceiver<M>,
+async fn run_subscription<S, M, Handler>(
+    msg_channel: S,
     subscriber_id: SubscriptionId,
     subscriber: Subscriber<pubsub::Result>,
     event_handler: Handler,
 ) where
+    S: Stream<Item = M>,
     M: Send + 'static,
     Handler: EventHandler<M> + Send + 'static,
 {
@@ -495,9 +678,319 @@ impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
+enum TxnStatusMessage {
+    /// The statuses of the watched transactions when subscribed.
+    Current(Vec<TransactionStatusView>),
+    TxPoolStatus(TxnStatusFullEvent),
+    NewHead(NewHeadBlock),
+    ChainReorg(ChainReorg),
+}
+
+/// Get the current statuses of the watched transactions, the transactions of `txn_hashes` in
+/// txpool or on the main chain, and the transactions of the sender in txpool.
+async fn current_txn_statuses(
+    txn_hashes: Vec<HashValue>,
+    sender_txn_hashes: Vec<HashValue>,
+    txpool: TxPoolService,
+    chain_service: ServiceRef<ChainReaderService>,
+) -> Vec<TransactionStatusView> {
+    let mut statuses = vec![];
+    for txn_hash in txn_hashes {
+        if txpool.find_txn(&txn_hash).is_some() {
+            statuses.push(TransactionStatusView {
+                transaction_hash: txn_hash,
+                txpool_status: Some(TxStatus::Added),
+                txn_info: None,
+                retracted_block: None,
+            });
+            continue;
+        }
+        match main_txn_info(&chain_service, txn_hash).await {
+            Ok(Some(txn_info)) => statuses.push(TransactionStatusView {
+                transaction_hash: txn_hash,
+                txpool_status: None,
+                txn_info: Some(txn_info),
+                retracted_block: None,
+            }),
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!(target: "rpc", "Fail to get txn info of {}: {}", txn_hash, e);
+            }
+        }
+    }
+    for txn_hash in sender_txn_hashes {
+        if !statuses
+            .iter()
+            .any(|status| status.transaction_hash == txn_hash)
+        {
+            statuses.push(TransactionStatusView {
+                transaction_hash: txn_hash,
+                txpool_status: Some(TxStatus::Added),
+                txn_info: None,
+                retracted_block: None,
+            });
+        }
+    }
+    statuses
+}
+
+async fn main_txn_info(
+    chain_service: &ServiceRef<ChainReaderService>,
+    txn_hash: HashValue,
+) -> Result<Option<TransactionInfoView>> {
+    let txn_info = match chain_service.get_transaction_info(txn_hash).await? {
+        Some(txn_info) => txn_info,
+        None => return Ok(None),
+    };
+    let block = chain_service
+        .get_block_by_hash(txn_info.block_id())
+        .await?
+        .ok_or_else(|| anyhow::format_err!("Can not find block {}", txn_info.block_id()))?;
+    TransactionInfoView::new(Into::<(_, TransactionInfo)>::into(txn_info).1, &block).map(Some)
+}
+
+pub struct TxnStatusHandler {
+    txn_hashes: HashSet<HashValue>,
+    sender: Option<AccountAddress>,
+    /// The transactions of the sender in txpool, watched until included.
+    sender_txn_hashes: RwLock<HashSet<HashValue>>,
+    txpool: TxPoolService,
+    storage: Arc<Storage>,
+}
+
+impl TxnStatusHandler {
+    fn new(filter: TransactionStatusFilter, txpool: TxPoolService, storage: Arc<Storage>) -> Self {
+        let sender_txn_hashes = match filter.sender {
+            Some(sender) => txpool
+                .txns_of_sender(&sender, None)
+                .iter()
+                .map(|txn| txn.id())
+                .collect(),
+            None => HashSet::new(),
+        };
+        Self {
+            txn_hashes: filter.txn_hashes.into_iter().collect(),
+            sender: filter.sender,
+            sender_txn_hashes: RwLock::new(sender_txn_hashes),
+            txpool,
+            storage,
+        }
+    }
+
+    fn is_watched_txn(&self, txn: &SignedUserTransaction) -> bool {
+        self.sender == Some(txn.sender())
+            || self.txn_hashes.contains(&txn.id())
+            || self.sender_txn_hashes.read().contains(&txn.id())
+    }
+
+    fn is_watched(&self, txn_hash: &HashValue, status: TxStatus) -> bool {
+        if self.txn_hashes.contains(txn_hash) || self.sender_txn_hashes.read().contains(txn_hash) {
+            return true;
+        }
+        match (self.sender, status) {
+            (Some(sender), TxStatus::Added) => {
+                let watched = self
+                    .txpool
+                    .find_txn(txn_hash)
+                    .map(|txn| txn.sender() == sender)
+                    .unwrap_or_default();
+                if watched {
+                    self.sender_txn_hashes.write().insert(*txn_hash);
+                }
+                watched
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the infos of the watched transactions included in the block.
+    fn included_txn_infos(&self, block: &Block) -> Result<Vec<TransactionInfoView>> {
+        let included: HashSet<HashValue> = block
+            .transactions()
+            .iter()
+            .filter(|txn| self.is_watched_txn(txn))
+            .map(|txn| txn.id())
+            .collect();
+        if included.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut txn_infos = vec![];
+        for txn_info_id in self.storage.get_block_txn_info_ids(block.id())? {
+            let txn_info = self
+                .storage
+                .get_transaction_info(txn_info_id)?
+                .ok_or_else(|| anyhow::format_err!("Can not find txn info {}", txn_info_id))?;
+            if included.contains(&txn_info.transaction_hash()) {
+                txn_infos.push(TransactionInfoView::new(
+                    Into::<(_, TransactionInfo)>::into(txn_info).1,
+                    block,
+                )?);
+            }
+        }
+        let mut sender_txn_hashes = self.sender_txn_hashes.write();
+        for txn_hash in &included {
+            sender_txn_hashes.remove(txn_hash);
+        }
+        Ok(txn_infos)
+    }
+
+    /// The watched transactions are retracted with the blocks retracted by the reorg, and
+    /// included by the enacted blocks, the new head is handled by its `NewHeadBlock`.
+    fn reorg_txn_statuses(&self, reorg: &ChainReorg) -> Result<Vec<TransactionStatusView>> {
+        let mut statuses = vec![];
+        for block_id in &reorg.retracted {
+            let block = self
+                .storage
+                .get_block_by_hash(*block_id)?
+                .ok_or_else(|| anyhow::format_err!("Can not find block {}", block_id))?;
+            statuses.extend(
+                block
+                    .transactions()
+                    .iter()
+                    .filter(|txn| self.is_watched_txn(txn))
+                    .map(|txn| TransactionStatusView {
+                        transaction_hash: txn.id(),
+                        txpool_status: None,
+                        txn_info: None,
+                        retracted_block: Some(*block_id),
+                    }),
+            );
+        }
+        let enacted_count = reorg.enacted.len().saturating_sub(1);
+        for block_id in &reorg.enacted[..enacted_count] {
+            let block = self
+                .storage
+                .get_block_by_hash(*block_id)?
+                .ok_or_else(|| anyhow::format_err!("Can not find block {}", block_id))?;
+            statuses.extend(
+                self.included_txn_infos(&block)?
+                    .into_iter()
+                    .map(|txn_info| TransactionStatusView {
+                        transaction_hash: txn_info.transaction_hash,
+                        txpool_status: None,
+                        txn_info: Some(txn_info),
+                        retracted_block: None,
+                    }),
+            );
+        }
+        Ok(statuses)
+    }
+}
+
+impl EventHandler<TxnStatusMessage> for TxnStatusHandler {
+    fn handle(&self, msg: TxnStatusMessage) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let statuses = match msg {
+            TxnStatusMessage::Current(statuses) => statuses,
+            TxnStatusMessage::TxPoolStatus(statuses) => statuses
+                .iter()
+                .filter(|(txn_hash, status)| self.is_watched(txn_hash, *status))
+                .map(|(txn_hash, status)| TransactionStatusView {
+                    transaction_hash: *txn_hash,
+                    txpool_status: Some(*status),
+                    txn_info: None,
+                    retracted_block: None,
+                })
+                .collect(),
+            TxnStatusMessage::NewHead(NewHeadBlock(executed_block)) => {
+                match self.included_txn_infos(executed_block.block()) {
+                    Ok(txn_infos) => txn_infos
+                        .into_iter()
+                        .map(|txn_info| TransactionStatusView {
+                            transaction_hash: txn_info.transaction_hash,
+                            txpool_status: None,
+                            txn_info: Some(txn_info),
+                            retracted_block: None,
+                        })
+                        .collect(),
+                    Err(e) => {
+                        log::warn!(target: "rpc", "Fail to get included txn infos: {}", e);
+                        vec![]
+                    }
+                }
+            }
+            TxnStatusMessage::ChainReorg(reorg) => match self.reorg_txn_statuses(&reorg) {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    log::warn!(target: "rpc", "Fail to get reorged txn statuses: {}", e);
+                    vec![]
+                }
+            },
+        };
+        statuses
+            .into_iter()
+            .map(|status| Ok(pubsub::Result::TransactionStatus(Box::new(status))))
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ChainReorgHandler;
+
+impl EventHandler<ChainReorg> for ChainReorgHandler {
+    fn handle(&self, msg: ChainReorg) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        vec![Ok(pubsub::Result::ChainReorg(Box::new(msg.into())))]
+    }
+}
+
 pub struct ContractEventHandler {
     filter: Filter,
+    subscription_filter: EventSubscriptionFilter,
+    storage: Arc<Storage>,
+}
+
+impl ContractEventHandler {
+    /// Decode the event data on the state after the block of the event.
+    fn decode(&self, event: &Event) -> Result<AnnotatedMoveValueView> {
+        let header = self
+            .storage
+            .get_block_header_by_hash(event.block_hash)?
+            .ok_or_else(|| anyhow::format_err!("Can not find block {}", event.block_hash))?;
+        let statedb = ChainStateDB::new(self.storage.clone(), Some(header.state_root()));
+        let annotator = MoveValueAnnotator::new(&statedb);
+        Ok(annotator.view_contract_event(&event.contract_event)?.into())
+    }
+
+    /// Match the event with the filters, and decode the event data if required.
+    /// The decoded data is returned if `decode` is requested, and the event which matches the
+    /// other filters but can not be decoded is reported as an error instead of being sent.
+    fn matching(&self, event: &Event) -> Option<Result<Option<AnnotatedMoveValueView>>> {
+        if event.removed && !self.subscription_filter.include_removed {
+            return None;
+        }
+        if !(self
+            .filter
+            .matching(event.block_number, &event.contract_event)
+            && self
+                .subscription_filter
+                .matching_type(event.contract_event.type_tag()))
+        {
+            return None;
+        }
+        if !self.subscription_filter.need_decode() {
+            return Some(Ok(None));
+        }
+        match self.decode(event) {
+            Ok(data) if self.subscription_filter.matching_data(&data) => {
+                if self.subscription_filter.decode {
+                    Some(Ok(Some(data)))
+                } else {
+                    Some(Ok(None))
+                }
+            }
+            Ok(_) => None,
+            Err(e) => {
+                let e = anyhow::format_err!(
+                    "Fail to decode event of type {} in block {}: {}",
+                    event.contract_event.type_tag(),
+                    event.block_hash,
+                    e
+                );
+                log::warn!(target: "rpc", "{}", e);
+                Some(Err(e))
+            }
+        }
+    }
 }
 
 impl EventHandler<Notification<Arc<[Event]>>> for ContractEventHandler {
@@ -506,7 +999,7 @@ impl EventHandler<Notification<Arc<[Event]>>> for ContractEventHandler {
         let filtered = events
             .as_ref()
             .iter()
-            .filter(|e| self.filter.matching(e.block_number, &e.contract_event));
+            .filter_map(|e| self.matching(e).map(|data| (e, data)));
         let filtered_events: Vec<_> = match self.filter.limit {
             None => filtered.collect(),
             Some(l) => {
@@ -518,16 +1011,24 @@ impl EventHandler<Notification<Arc<[Event]>>> for ContractEventHandler {
 
         filtered_events
             .into_iter()
-            .map(|e| {
-                TransactionEventView::new(
+            .map(|(e, data)| {
+                let data = data.map_err(map_err)?;
+                let mut event = TransactionEventView::new(
                     Some(e.block_hash),
                     Some(e.block_number),
                     Some(e.transaction_hash),
                     e.transaction_index,
                     &e.contract_event,
-                )
+                );
+                event.removed = e.removed;
+                Ok(match data {
+                    Some(decoded_data) => pubsub::Result::DecodedEvent(Box::new(EventTraceView {
+                        event,
+                        decoded_data,
+                    })),
+                    None => pubsub::Result::Event(Box::new(event)),
+                })
             })
-            .map(|e| Ok(pubsub::Result::Event(Box::new(e))))
             .collect()
     }
 }
@@ -3,6 +3,7 @@
 
 use crate::module::{PubSubImpl, PubSubService, PubSubServiceFactory};
 use anyhow::Result;
+use futures::channel::mpsc::UnboundedReceiver;
 use futures::StreamExt;
 use jsonrpc_core::{futures, MetaIoHandler};
 use jsonrpc_pubsub::Session;
@@ -11,6 +12,7 @@ use starcoin_account_api::AccountInfo;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
 use starcoin_chain_notify::ChainNotifyHandlerService;
+use starcoin_chain_service::ChainReaderService;
 use starcoin_consensus::Consensus;
 use starcoin_crypto::{ed25519::Ed25519PrivateKey, Genesis, PrivateKey};
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
@@ -19,12 +21,12 @@ use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_api::pubsub::StarcoinPubSub;
 use starcoin_rpc_api::types::pubsub::MintBlock;
 use starcoin_service_registry::bus::{Bus, BusService};
-use starcoin_service_registry::RegistryAsyncService;
+use starcoin_service_registry::{RegistryAsyncService, RegistryService, ServiceRef};
 use starcoin_state_api::StateReaderExt;
-use starcoin_storage::BlockStore;
+use starcoin_storage::{BlockStore, ContractEventStore};
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::system_events::MintBlockEvent;
 use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::system_events::{ChainReorg, MintBlockEvent};
 use starcoin_types::transaction::authenticator::AuthenticationKey;
 use starcoin_types::{account_address, U256};
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
@@ -32,6 +34,43 @@ use std::sync::Arc;
 use tokio::time::timeout;
 use tokio::time::Duration;
 
+/// Register the pubsub service and the chain service it depends on, return the rpc handler and
+/// the metadata of a session, with the receiver of the notifications of the session.
+async fn start_pubsub(
+    registry: &ServiceRef<RegistryService>,
+) -> Result<(MetaIoHandler<Metadata>, Metadata, UnboundedReceiver<String>)> {
+    registry.register::<ChainReaderService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub.to_delegate());
+
+    let mut metadata = Metadata::default();
+    let (sender, receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+    Ok((io, metadata, receiver))
+}
+
+/// Collect the notifications until no new notification is received in a while.
+async fn collect_notifications(receiver: &mut UnboundedReceiver<String>) -> Vec<Value> {
+    let mut notifications = vec![];
+    while let Ok(Some(res)) = timeout(Duration::from_secs(2), receiver.next()).await {
+        notifications.push(serde_json::from_str::<Value>(res.as_str()).unwrap());
+    }
+    notifications
+}
+
+/// The results of the notifications of the subscription.
+fn results_of(notifications: &[Value], subscription: u64) -> Vec<Value> {
+    notifications
+        .iter()
+        .filter(|notification| notification["params"]["subscription"] == subscription)
+        .map(|notification| notification["params"]["result"].clone())
+        .collect()
+}
+
 #[actix_rt::test]
 pub async fn test_subscribe_to_events() -> Result<()> {
     starcoin_logger::init_for_test();
@@ -83,19 +122,7 @@ pub async fn test_subscribe_to_events() -> Result<()> {
         .await
         .unwrap();
 
-    let service = registry
-        .register_by_factory::<PubSubService, PubSubServiceFactory>()
-        .await?;
-
-    let pubsub = PubSubImpl::new(service);
-    let pubsub = pubsub.to_delegate();
-
-    let mut io = MetaIoHandler::default();
-    io.extend_with(pubsub);
-
-    let mut metadata = Metadata::default();
-    let (sender, receiver) = futures::channel::mpsc::unbounded();
-    metadata.session = Some(Arc::new(Session::new(sender)));
+    let (io, metadata, mut receiver) = start_pubsub(&registry).await?;
 
     // Subscribe
     let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"events"}, {}], "id": 1}"#;
@@ -110,26 +137,30 @@ pub async fn test_subscribe_to_events() -> Result<()> {
     let resp = io.handle_request(request, metadata.clone()).await;
     assert_eq!(response.to_owned(), resp.unwrap());
 
+    // Subscribe the decoded deposit events of the transfer
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"events"}, {"type_patterns":["0x1::Account::DepositEvent"],"field_predicates":[{"path":"amount","op":"eq","value":"10000"}],"decode":true}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":1,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp.unwrap(), response.to_owned());
+
     // send block
     let block_detail = Arc::new(executed_block);
     bus.broadcast(NewHeadBlock(block_detail))?;
 
-    let mut receiver = receiver;
-
-    let res = timeout(Duration::from_secs(5), receiver.next()).await?;
-    assert!(res.is_some());
-
-    let res = res.unwrap();
-    let notification = serde_json::from_str::<jsonrpc_core::Notification>(res.as_str()).unwrap();
-    match notification.params {
-        jsonrpc_core::Params::Map(s) => {
-            let v = s.get("result").unwrap().get("block_number").unwrap();
-            assert_eq!(v.as_str(), Some("1"));
-        }
-        p => {
-            panic!("subscribe return unexpected result, {:?}", &p);
-        }
-    }
+    // the decoded events may be notified before or after the events of the first subscription.
+    let notifications = collect_notifications(&mut receiver).await;
+    let events = results_of(&notifications, 0);
+    assert!(!events.is_empty());
+    assert_eq!(events[0]["block_number"], "1");
+    let decoded_events = results_of(&notifications, 1);
+    assert_eq!(decoded_events.len(), 1);
+    let amount = decoded_events[0]["decoded_data"]["Struct"]["value"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|field| field[0] == "amount")
+        .map(|field| field[1].clone());
+    assert_eq!(amount, Some(serde_json::json!({"U128": "10000"})));
     Ok(())
 }
 
@@ -137,18 +168,7 @@ pub async fn test_subscribe_to_events() -> Result<()> {
 pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     // given
     let (txpool_service, _, config, _, registry) = test_helper::start_txpool().await;
-    let service = registry
-        .register_by_factory::<PubSubService, PubSubServiceFactory>()
-        .await?;
-    let pubsub = PubSubImpl::new(service);
-    let pubsub = pubsub.to_delegate();
-
-    let mut io = MetaIoHandler::default();
-    io.extend_with(pubsub);
-
-    let mut metadata = Metadata::default();
-    let (sender, receiver) = futures::channel::mpsc::unbounded();
-    metadata.session = Some(Arc::new(Session::new(sender)));
+    let (io, metadata, mut receiver) = start_pubsub(&registry).await?;
 
     // Fail if params are provided
     let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newPendingTransactions"}, {}], "id": 1}"#;
@@ -177,7 +197,6 @@ pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     };
     let txn_id = txn.id();
     txpool_service.add_txns(vec![txn]).pop().unwrap().unwrap();
-    let mut receiver = receiver;
     let res = receiver.next().await.unwrap();
     let prefix = r#"{"jsonrpc":"2.0","method":"starcoin_subscription","params":{"subscription":0,"result":[""#;
     let suffix = r#""]}}"#;
@@ -198,18 +217,7 @@ pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
 pub async fn test_subscribe_to_mint_block() -> Result<()> {
     let (_txpool_service, .., registry) = test_helper::start_txpool().await;
     let bus = registry.service_ref::<BusService>().await?;
-    let service = registry
-        .register_by_factory::<PubSubService, PubSubServiceFactory>()
-        .await?;
-    let pubsub = PubSubImpl::new(service);
-    let pubsub = pubsub.to_delegate();
-
-    let mut io = MetaIoHandler::default();
-    io.extend_with(pubsub);
-
-    let mut metadata = Metadata::default();
-    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
-    metadata.session = Some(Arc::new(Session::new(sender)));
+    let (io, metadata, mut receiver) = start_pubsub(&registry).await?;
 
     // Subscribe
     let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newMintBlock"}], "id": 1}"#;
@@ -222,12 +230,9 @@ pub async fn test_subscribe_to_mint_block() -> Result<()> {
     let mint_block_event =
         MintBlockEvent::new(ConsensusStrategy::Dummy, header_hash.clone(), diff, 0);
     bus.broadcast(mint_block_event.clone()).unwrap();
-    let res = timeout(Duration::from_secs(1), receiver.next())
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
-    let r: Value = serde_json::from_str(&res).unwrap();
-    let v = r["params"]["result"].clone();
-    let mint_block: MintBlock = serde_json::from_value(v).unwrap();
+    let results = results_of(&collect_notifications(&mut receiver).await, 0);
+    assert_eq!(results.len(), 1);
+    let mint_block: MintBlock = serde_json::from_value(results[0].clone()).unwrap();
     assert_eq!(mint_block.difficulty, diff);
     assert_eq!(&mint_block.minting_blob, &hex::encode(&header_hash));
     // Unsubscribe
@@ -237,3 +242,201 @@ pub async fn test_subscribe_to_mint_block() -> Result<()> {
     assert_eq!(resp, Some(response.to_owned()));
     Ok(())
 }
+
+#[stest::test]
+pub async fn test_subscribe_to_chain_reorg() -> Result<()> {
+    let (_txpool_service, storage, .., registry) = test_helper::start_txpool().await;
+    let bus = registry.service_ref::<BusService>().await?;
+    let _notify_service = registry.register::<ChainNotifyHandlerService>().await?;
+    let (io, metadata, mut receiver) = start_pubsub(&registry).await?;
+
+    // Subscribe chain reorg, events with and without the removed events.
+    let requests = [
+        r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"chainReorg"}], "id": 1}"#,
+        r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"events"}, {"include_removed":true}], "id": 1}"#,
+        r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"events"}, {}], "id": 1}"#,
+    ];
+    for (i, request) in requests.iter().enumerate() {
+        let response = format!(r#"{{"jsonrpc":"2.0","result":{},"id":1}}"#, i);
+        let resp = io.handle_request(request, metadata.clone()).await;
+        assert_eq!(resp, Some(response));
+    }
+
+    // Retract the genesis block, its events are notified again as removed.
+    let genesis_id = storage.get_startup_info()?.unwrap().main;
+    let genesis_header = storage.get_block_header_by_hash(genesis_id)?.unwrap();
+    let genesis_event_count = storage
+        .get_block_txn_info_ids(genesis_id)?
+        .into_iter()
+        .map(|txn_info_id| {
+            storage
+                .get_contract_events(txn_info_id)
+                .unwrap()
+                .unwrap_or_default()
+                .len()
+        })
+        .sum::<usize>();
+    assert!(genesis_event_count > 0);
+    bus.broadcast(ChainReorg {
+        ancestor: genesis_header,
+        retracted: vec![genesis_id],
+        enacted: vec![],
+    })?;
+
+    let notifications = collect_notifications(&mut receiver).await;
+    let reorgs = results_of(&notifications, 0);
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0]["retracted"][0], serde_json::json!(genesis_id));
+    assert_eq!(
+        reorgs[0]["ancestor"]["block_hash"],
+        serde_json::json!(genesis_id)
+    );
+    let removed_events = results_of(&notifications, 1);
+    assert_eq!(removed_events.len(), genesis_event_count);
+    assert!(removed_events.iter().all(|event| event["removed"] == true));
+    assert!(results_of(&notifications, 2).is_empty());
+    Ok(())
+}
+
+#[stest::test]
+pub async fn test_subscribe_to_txn_status() -> Result<()> {
+    let (txpool_service, _, config, _, registry) = test_helper::start_txpool().await;
+    let (io, metadata, mut receiver) = start_pubsub(&registry).await?;
+
+    // Fail if neither txn hashes nor sender is provided
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"transactionStatus"}, {}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: transactionStatus","data":"\"Expected a filter object with txn_hashes or sender.\""},"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let txn = {
+        let account = AccountInfo::random();
+        let txn = starcoin_executor::build_transfer_from_association(
+            account.address,
+            Some(account.public_key.authentication_key()),
+            0,
+            10000,
+            DEFAULT_EXPIRATION_TIME,
+            config.net(),
+        );
+        txn.as_signed_user_txn()?.clone()
+    };
+    let txn_id = txn.id();
+
+    // Subscribe by the txn hash and by the sender.
+    let requests = [
+        txn_status_request(format!(r#"{{"txn_hashes":["0x{}"]}}"#, txn_id.to_hex())),
+        txn_status_request(format!(
+            r#"{{"sender":{}}}"#,
+            serde_json::to_string(&txn.sender())?
+        )),
+    ];
+    for (i, request) in requests.iter().enumerate() {
+        let response = format!(r#"{{"jsonrpc":"2.0","result":{},"id":1}}"#, i);
+        let resp = io.handle_request(request, metadata.clone()).await;
+        assert_eq!(resp, Some(response));
+    }
+
+    txpool_service.add_txns(vec![txn]).pop().unwrap().unwrap();
+    let notifications = collect_notifications(&mut receiver).await;
+    assert_eq!(notifications.len(), 2);
+    for subscription in 0..2u64 {
+        let results = results_of(&notifications, subscription);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["transaction_hash"], serde_json::json!(txn_id));
+        assert_eq!(results[0]["txpool_status"], "added");
+        assert!(results[0].get("txn_info").is_none());
+    }
+
+    // The current status is notified when subscribed.
+    let request = txn_status_request(format!(r#"{{"txn_hashes":["0x{}"]}}"#, txn_id.to_hex()));
+    let response = r#"{"jsonrpc":"2.0","result":2,"id":1}"#;
+    let resp = io.handle_request(&request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+    let results = results_of(&collect_notifications(&mut receiver).await, 2);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["transaction_hash"], serde_json::json!(txn_id));
+    assert_eq!(results[0]["txpool_status"], "added");
+    Ok(())
+}
+
+#[stest::test]
+pub async fn test_subscribe_to_txn_status_with_reorg() -> Result<()> {
+    let (_txpool_service, storage, config, _, registry) = test_helper::start_txpool().await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let genesis_header = storage
+        .get_block_header_by_hash(startup_info.main)?
+        .unwrap();
+    let net = config.net();
+    let mut block_chain = BlockChain::new(net.time_service(), startup_info.main, storage)?;
+    let miner_account = AccountInfo::random();
+    let txn = {
+        let account = AccountInfo::random();
+        let txn = starcoin_executor::build_transfer_from_association(
+            account.address,
+            Some(account.public_key.authentication_key()),
+            0,
+            10000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net,
+        );
+        txn.as_signed_user_txn()?.clone()
+    };
+    let txn_id = txn.id();
+    // the txn is included by the first block of the branch.
+    let mut branch = vec![];
+    for txns in vec![vec![txn], vec![]] {
+        let (block_template, _) = block_chain.create_block_template(
+            *miner_account.address(),
+            Some(miner_account.public_key.authentication_key()),
+            None,
+            txns,
+            vec![],
+            None,
+        )?;
+        let block = block_chain
+            .consensus()
+            .create_block(block_template, net.time_service().as_ref())?;
+        branch.push(block_chain.apply(block)?.block().id());
+    }
+
+    let bus = registry.service_ref::<BusService>().await?;
+    let (io, metadata, mut receiver) = start_pubsub(&registry).await?;
+    let request = txn_status_request(format!(r#"{{"txn_hashes":["0x{}"]}}"#, txn_id.to_hex()));
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(&request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    // The inclusion by an enacted block before the new head.
+    bus.broadcast(ChainReorg {
+        ancestor: genesis_header.clone(),
+        retracted: vec![],
+        enacted: branch.clone(),
+    })?;
+    let results = results_of(&collect_notifications(&mut receiver).await, 0);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["transaction_hash"], serde_json::json!(txn_id));
+    assert_eq!(
+        results[0]["txn_info"]["block_hash"],
+        serde_json::json!(branch[0])
+    );
+
+    // The including block is retracted.
+    bus.broadcast(ChainReorg {
+        ancestor: genesis_header,
+        retracted: branch.iter().rev().copied().collect(),
+        enacted: vec![],
+    })?;
+    let results = results_of(&collect_notifications(&mut receiver).await, 0);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["transaction_hash"], serde_json::json!(txn_id));
+    assert_eq!(results[0]["retracted_block"], serde_json::json!(branch[0]));
+    Ok(())
+}
+
+fn txn_status_request(filter: String) -> String {
+    format!(
+        r#"{{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{{"type_name":"transactionStatus"}}, {}], "id": 1}}"#,
+        filter
+    )
+}
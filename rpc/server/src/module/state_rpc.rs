@@ -1,15 +1,18 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::helpers::resolve_state_root;
 use crate::module::map_err;
 use bcs_ext::BCSCodec;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::state::StateApi;
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, StateWithProofView, StrView, StructTagView,
+    AccountStateSetView, AnnotatedMoveStructView, StateSelector, StateWithProofView, StrView,
+    StructTagView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::ChainStateAsyncService;
@@ -20,36 +23,57 @@ use starcoin_types::{
 };
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::state_view::StateView;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-pub struct StateRpcImpl<S>
+pub struct StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService + 'static,
+    C: ChainAsyncService + 'static,
 {
     service: S,
+    chain_service: C,
     state_store: Arc<dyn StateNodeStore>,
 }
 
-impl<S> StateRpcImpl<S>
+impl<S, C> StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    pub fn new(service: S, state_store: Arc<dyn StateNodeStore>) -> Self {
+    pub fn new(service: S, chain_service: C, state_store: Arc<dyn StateNodeStore>) -> Self {
         Self {
             service,
+            chain_service,
             state_store,
         }
     }
 }
 
-impl<S> StateApi for StateRpcImpl<S>
+impl<S, C> StateApi for StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>> {
-        let fut = self.service.clone().get(access_path).map_err(map_err);
-        Box::pin(fut)
+    fn get(
+        &self,
+        access_path: AccessPath,
+        state: Option<StateSelector>,
+    ) -> FutureResult<Option<Vec<u8>>> {
+        if state.is_none() {
+            let fut = self.service.clone().get(access_path).map_err(map_err);
+            return Box::pin(fut);
+        }
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let db = self.state_store.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(service, chain_service, state).await?;
+            let statedb = ChainStateDB::new(db, Some(state_root));
+            statedb.get(&access_path)
+        };
+        Box::pin(fut.map_err(map_err).boxed())
     }
 
     fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProofView> {
@@ -3,6 +3,9 @@
 
 use crate::module::{convert_to_rpc_error, map_err};
 use bcs_ext::BCSCodec;
+use futures::future::{FutureExt, TryFutureExt};
+use starcoin_chain_api::{ChainAsyncService, FeeHistory};
+use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
@@ -12,29 +15,97 @@ use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::transaction::SignedUserTransaction;
 use std::convert::TryInto;
+use std::sync::Arc;
 
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
 
-pub struct TxPoolRpcImpl<S>
+/// The number of recent blocks sampled by the gas price estimation.
+const GAS_PRICE_SAMPLE_BLOCKS: u64 = 20;
+/// The gas price percentile of every sampled block.
+const GAS_PRICE_SAMPLE_PERCENTILE: f64 = 60f64;
+/// The gas price bump in percent when the pool or the recent blocks are nearly full.
+const GAS_PRICE_PRESSURE_BUMP: u64 = 20;
+/// The pool is nearly full when its transaction count reaches this percent of the max count.
+const POOL_PRESSURE_PERCENT: usize = 80;
+/// The recent blocks are nearly full when their gas used reaches this ratio of the gas limit.
+const BLOCK_PRESSURE_RATIO: f64 = 0.8;
+
+pub struct TxPoolRpcImpl<S, C>
 where
     S: TxPoolSyncService + 'static,
+    C: ChainAsyncService + 'static,
 {
+    config: Arc<NodeConfig>,
     service: S,
+    chain_service: C,
 }
 
-impl<S> TxPoolRpcImpl<S>
+impl<S, C> TxPoolRpcImpl<S, C>
 where
     S: TxPoolSyncService,
+    C: ChainAsyncService,
 {
-    pub fn new(service: S) -> Self {
-        Self { service }
+    pub fn new(config: Arc<NodeConfig>, service: S, chain_service: C) -> Self {
+        Self {
+            config,
+            service,
+            chain_service,
+        }
     }
 }
 
-impl<S> TxPoolApi for TxPoolRpcImpl<S>
+/// Estimate the gas price by the median of the sampled gas prices of recent blocks,
+/// and bump it if the pool or the recent blocks are nearly full.
+/// The result is never lower than `min_gas_price`.
+pub fn estimate_gas_price(
+    fee_history: &FeeHistory,
+    pool_status: &TxPoolStatus,
+    min_gas_price: u64,
+) -> u64 {
+    let mut sampled_prices = fee_history
+        .blocks
+        .iter()
+        .filter_map(|block| block.gas_price_percentiles.first().copied())
+        .filter(|gas_price| *gas_price > 0)
+        .collect::<Vec<_>>();
+    sampled_prices.sort_unstable();
+    let gas_price = sampled_prices
+        .get(sampled_prices.len() / 2)
+        .copied()
+        .unwrap_or(min_gas_price)
+        .max(min_gas_price);
+
+    let pool_pressure = pool_status.is_full
+        || (pool_status.txn_max_count > 0
+            && pool_status.txn_count.saturating_mul(100)
+                >= pool_status
+                    .txn_max_count
+                    .saturating_mul(POOL_PRESSURE_PERCENT));
+    let (total_gas_used, total_gas_limit) =
+        fee_history
+            .blocks
+            .iter()
+            .fold((0u128, 0u128), |(gas_used, gas_limit), block| {
+                (
+                    gas_used + block.gas_used as u128,
+                    gas_limit + block.gas_limit as u128,
+                )
+            });
+    let block_pressure = total_gas_limit > 0
+        && total_gas_used as f64 / total_gas_limit as f64 >= BLOCK_PRESSURE_RATIO;
+
+    if pool_pressure || block_pressure {
+        gas_price.saturating_add((gas_price.saturating_mul(GAS_PRICE_PRESSURE_BUMP) / 100).max(1))
+    } else {
+        gas_price
+    }
+}
+
+impl<S, C> TxPoolApi for TxPoolRpcImpl<S, C>
 where
     S: TxPoolSyncService,
+    C: ChainAsyncService,
 {
     fn submit_transaction(&self, txn: SignedUserTransaction) -> FutureResult<HashValue> {
         let txn_hash = txn.id();
@@ -66,8 +137,17 @@ where
     }
 
     fn gas_price(&self) -> FutureResult<StrView<u64>> {
-        let gas_price = 1u64;
-        Box::pin(futures::future::ok(gas_price.into()))
+        let chain_service = self.chain_service.clone();
+        let pool_status = self.service.status();
+        let min_gas_price = self.config.tx_pool.min_gas_price();
+        let fut = async move {
+            let fee_history = chain_service
+                .get_fee_history(GAS_PRICE_SAMPLE_BLOCKS, vec![GAS_PRICE_SAMPLE_PERCENTILE])
+                .await?;
+            Ok(estimate_gas_price(&fee_history, &pool_status, min_gas_price).into())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
     }
 
     fn pending_txns(
@@ -108,20 +188,60 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use futures::executor::block_on;
     use jsonrpc_core::IoHandler;
+    use starcoin_chain_api::message::{ChainRequest, ChainResponse};
+    use starcoin_chain_api::BlockFeeInfo;
+    use starcoin_chain_service::ChainReaderService;
+    use starcoin_service_registry::mocker::mock;
+    use starcoin_service_registry::{RegistryAsyncService, RegistryService};
     use starcoin_txpool_mock_service::MockTxPoolService;
 
-    #[test]
-    fn test_submit_transaction() {
+    fn block_fee(gas_used: u64, gas_price: u64) -> BlockFeeInfo {
+        BlockFeeInfo {
+            number: 0,
+            gas_used,
+            gas_limit: 1000,
+            gas_price_percentiles: vec![gas_price],
+        }
+    }
+
+    fn pool_status(txn_count: usize, is_full: bool) -> TxPoolStatus {
+        TxPoolStatus {
+            txn_count,
+            txn_max_count: 100,
+            mem: 0,
+            mem_max: 0,
+            senders: 0,
+            is_full,
+        }
+    }
+
+    #[stest::test]
+    async fn test_submit_transaction() {
         let txn = SignedUserTransaction::mock();
         let result = serde_json::to_string(&txn).unwrap();
         let txn1 = serde_json::from_str::<SignedUserTransaction>(result.as_str()).unwrap();
         assert_eq!(txn, txn1);
 
+        let registry = RegistryService::launch();
+        let chain_service = registry
+            .register_mocker::<ChainReaderService, _>(mock(|_request, _ctx| {
+                Box::new(Ok::<_, anyhow::Error>(ChainResponse::FeeHistory(
+                    FeeHistory::default(),
+                )))
+            }))
+            .await
+            .unwrap();
         let mut io = IoHandler::new();
         let txpool_service = MockTxPoolService::new();
-        io.extend_with(TxPoolRpcImpl::new(txpool_service).to_delegate());
+        io.extend_with(
+            TxPoolRpcImpl::new(
+                Arc::new(NodeConfig::random_for_test()),
+                txpool_service,
+                chain_service,
+            )
+            .to_delegate(),
+        );
         let txn = SignedUserTransaction::mock();
         let txn_hash = txn.id();
         let prefix = r#"{"jsonrpc":"2.0","method":"txpool.submit_transaction","params":["#;
@@ -135,9 +255,44 @@ mod tests {
         let response = r#"{"jsonrpc":"2.0","result":"$txn_hash","id":0}"#;
         let response = response.replace("$txn_hash", &txn_hash.to_string());
 
+        assert_eq!(io.handle_request(request.as_str()).await.unwrap(), response);
+
+        let request = r#"{"jsonrpc":"2.0","method":"txpool.gas_price","params":[],"id":0}"#;
+        let response = r#"{"jsonrpc":"2.0","result":"1","id":0}"#;
+        assert_eq!(io.handle_request(request).await.unwrap(), response);
+        registry.shutdown_system().await.unwrap();
+    }
+
+    #[test]
+    fn test_estimate_gas_price() {
+        let idle_pool = pool_status(0, false);
+        assert_eq!(estimate_gas_price(&FeeHistory::default(), &idle_pool, 1), 1);
+
+        let fee_history = FeeHistory {
+            blocks: vec![
+                block_fee(100, 10),
+                block_fee(100, 30),
+                block_fee(100, 20),
+                block_fee(0, 0),
+            ],
+        };
+        assert_eq!(estimate_gas_price(&fee_history, &idle_pool, 1), 20);
+        // never lower than the min gas price.
+        assert_eq!(estimate_gas_price(&fee_history, &idle_pool, 50), 50);
+        // bump when the pool is nearly full.
         assert_eq!(
-            block_on(io.handle_request(request.as_str())).unwrap(),
-            response
+            estimate_gas_price(&fee_history, &pool_status(80, false), 1),
+            24
         );
+        assert_eq!(
+            estimate_gas_price(&fee_history, &pool_status(0, true), 1),
+            24
+        );
+
+        // bump when the recent blocks are nearly full.
+        let full_blocks = FeeHistory {
+            blocks: vec![block_fee(900, 10), block_fee(800, 10)],
+        };
+        assert_eq!(estimate_gas_price(&full_blocks, &idle_pool, 1), 12);
     }
 }
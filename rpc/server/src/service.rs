@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::api_registry::ApiRegistry;
-use crate::extractors::{RpcExtractor, WsExtractor};
-use anyhow::Result;
+use crate::auth_middleware::RpcAuthenticator;
+use crate::extractors::{bearer_token, set_ws_handshake_token, RpcExtractor, WsExtractor};
+use anyhow::{format_err, Result};
 use futures::stream::*;
 use futures::{FutureExt, StreamExt};
 use jsonrpc_core::futures::channel::mpsc;
@@ -15,6 +16,7 @@ use jsonrpc_core_client::{
 use jsonrpc_pubsub::Session;
 use jsonrpc_server_utils::cors::AccessControlAllowOrigin;
 use jsonrpc_server_utils::hosts::DomainsValidation;
+use jsonrpc_ws_server::ws;
 use starcoin_config::{Api, ApiSet, NodeConfig};
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::contract_api::ContractApi;
@@ -98,7 +100,10 @@ impl RpcService {
         M: MinerApi,
         Contract: ContractApi,
     {
-        let mut api_registry = ApiRegistry::new(config.rpc.api_quotas.clone());
+        let mut api_registry = ApiRegistry::new(
+            config.rpc.api_quotas.clone(),
+            RpcAuthenticator::from_config(&config.rpc.auth),
+        );
 
         api_registry.register(Api::Node, NodeApi::to_delegate(node_api));
         if let Some(node_manager_api) = node_manager_api {
@@ -152,7 +157,8 @@ impl RpcService {
         } else {
             let ipc_file = self.config.rpc.get_ipc_file();
             let apis: HashSet<Api> = self.config.rpc.ipc.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            // ipc is a local transport, so do not require token.
+            let io_handler = self.api_registry.get_apis(apis, false);
 
             info!("Ipc rpc server start at :{:?}", ipc_file);
             Some(
@@ -167,7 +173,7 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_http_address() {
             let address = addr.into();
             let apis = self.config.rpc.http.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self.api_registry.get_apis(apis, true);
             let http = jsonrpc_http_server::ServerBuilder::new(io_handler)
                 .meta_extractor(RpcExtractor {
                     http_ip_headers: self.config.rpc.http.ip_headers(),
@@ -190,9 +196,12 @@ impl RpcService {
     fn start_tcp(&self) -> Result<Option<jsonrpc_tcp_server::Server>> {
         Ok(if let Some(addr) = self.config.rpc.get_tcp_address() {
             let address = addr.into();
+            if self.api_registry.authenticator().is_some() {
+                warn!("Rpc auth is enabled, but tcp transport can not carry token, so tcp server is not started.");
+                return Ok(None);
+            }
             let apis = self.config.rpc.tcp.apis().list_apis();
-
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self.api_registry.get_apis(apis, true);
             let tcp_server = jsonrpc_tcp_server::ServerBuilder::new(io_handler)
                 .session_meta_extractor(RpcExtractor::default())
                 .start(&address)?;
@@ -207,11 +216,32 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_ws_address() {
             let address = addr.into();
             let apis = self.config.rpc.ws.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
-            let ws_server = jsonrpc_ws_server::ServerBuilder::new(io_handler)
+            // the token is authenticated when connect, and checked by every call.
+            let io_handler = self.api_registry.get_apis(apis, true);
+            let mut builder = jsonrpc_ws_server::ServerBuilder::new(io_handler)
                 .session_meta_extractor(WsExtractor)
-                .max_payload(self.config.rpc.ws.max_request_body_size())
-                .start(&address)?;
+                .max_payload(self.config.rpc.ws.max_request_body_size());
+            if let Some(authenticator) = self.api_registry.authenticator() {
+                builder = builder.request_middleware(move |req: &ws::Request| {
+                    let token = req
+                        .header("authorization")
+                        .and_then(|v| std::str::from_utf8(v).ok())
+                        .and_then(bearer_token);
+                    set_ws_handshake_token(token.clone());
+                    let result = token
+                        .ok_or_else(|| format_err!("Token is missing"))
+                        .and_then(|token| authenticator.authenticate(token.as_str()));
+                    match result {
+                        Ok(_) => None,
+                        Err(e) => Some(ws::Response::new(
+                            401,
+                            "Unauthorized",
+                            e.to_string().into_bytes(),
+                        )),
+                    }
+                });
+            }
+            let ws_server = builder.start(&address)?;
             info!("Rpc: websocket server start at: {}", address);
             Some(ws_server)
         } else {
@@ -264,7 +294,7 @@ pub fn connect_local(
 impl ServiceHandler<Self, ConnectLocal> for RpcService {
     fn handle(&mut self, _msg: ConnectLocal, ctx: &mut ServiceContext<RpcService>) -> RpcChannel {
         let apis = ApiSet::All.list_apis();
-        let io_handler = self.api_registry.get_apis(apis);
+        let io_handler = self.api_registry.get_apis(apis, false);
         //remove middleware.
         let mut local_io_handler = MetaIoHandler::default();
         local_io_handler.extend_with(io_handler.iter().map(|(n, f)| (n.clone(), f.clone())));
@@ -109,6 +109,12 @@ impl StateWithProof {
     pub fn get_state(&self) -> &Option<Vec<u8>> {
         &self.state
     }
+
+    /// Verify the state at `access_path` with the proof, given the expected state root.
+    pub fn verify(&self, expected_root_hash: HashValue, access_path: AccessPath) -> Result<()> {
+        self.proof
+            .verify(expected_root_hash, access_path, self.state.as_deref())
+    }
 }
 
 pub trait ChainStateReader: StateView {
@@ -41,6 +41,23 @@ pub trait ChainStateAsyncService: Clone + std::marker::Unpin + Send + Sync {
         Ok(r)
     }
 
+    /// Get the resource `R` of `address` on the state of `state_root`.
+    async fn get_resource_by_root<R>(
+        self,
+        address: AccountAddress,
+        state_root: HashValue,
+    ) -> Result<Option<R>>
+    where
+        R: MoveResource + DeserializeOwned,
+    {
+        let access_path = AccessPath::new(address, R::resource_path());
+        self.get_with_proof_by_root(access_path, state_root)
+            .await?
+            .state
+            .map(|state| bcs_ext::from_bytes::<R>(state.as_slice()))
+            .transpose()
+    }
+
     async fn get_account_state(self, address: AccountAddress) -> Result<Option<AccountState>>;
 
     /// get account stateset on state_root(if empty, use current state root).
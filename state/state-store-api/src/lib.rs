@@ -54,4 +54,13 @@ pub trait StateNodeStore: std::marker::Send + std::marker::Sync {
     fn get(&self, hash: &HashValue) -> Result<Option<StateNode>>;
     fn put(&self, key: HashValue, node: StateNode) -> Result<()>;
     fn write_nodes(&self, nodes: BTreeMap<HashValue, StateNode>) -> Result<()>;
+    /// Record the `stale_nodes` which are not referenced since the state `state_root`,
+    /// the record is used for pruning the nodes, store which do not support pruning just ignore it.
+    fn write_stale_nodes(
+        &self,
+        _state_root: HashValue,
+        _stale_nodes: Vec<HashValue>,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
@@ -190,17 +190,31 @@ where
 
     /// commit the state change into underline storage.
     pub fn flush(&self) -> Result<()> {
+        self.flush_with_stale_nodes().map(|_| ())
+    }
+
+    /// commit the state change into underline storage,
+    /// and return the keys of nodes which are not referenced by the new state root anymore.
+    pub fn flush_with_stale_nodes(&self) -> Result<Vec<HashValue>> {
         let (root_hash, change_sets) = self.change_sets();
+        let node_batch = change_sets.node_batch;
 
+        // a node may become stale and then be created again by the later updates.
+        let stale_nodes = change_sets
+            .stale_node_index_batch
+            .into_iter()
+            .map(|index| index.node_key)
+            .filter(|node_key| !node_batch.contains_key(node_key))
+            .collect();
         let mut node_map = BTreeMap::new();
-        for (nk, n) in change_sets.node_batch.into_iter() {
+        for (nk, n) in node_batch.into_iter() {
             node_map.insert(nk, n.try_into()?);
         }
         self.storage.write_nodes(node_map)?;
         // and then advance the storage root hash
         *self.storage_root_hash.write() = root_hash;
         self.cache.lock().reset(root_hash);
-        Ok(())
+        Ok(stale_nodes)
     }
 
     /// Dump tree to state set.
@@ -221,6 +235,33 @@ where
         Ok(StateSet::new(states))
     }
 
+    /// Dump at most `limit` states whose key hashes are not less than `start_key_hash`, in the
+    /// order of the key hash, so a big tree can be dumped page by page.
+    /// Return the states with the key hash of the next state, None if there is no more state.
+    pub fn dump_range(
+        &self,
+        start_key_hash: HashValue,
+        limit: usize,
+    ) -> Result<(StateSet, Option<HashValue>)> {
+        let cur_root_hash = self.root_hash();
+        let mut cache_guard = self.cache.lock();
+        let cache = cache_guard.deref_mut();
+        let reader = CachedTreeReader {
+            store: self.storage.as_ref(),
+            cache,
+        };
+        let iterator = JellyfishMerkleIterator::new(&reader, cur_root_hash, start_key_hash)?;
+        let mut states = vec![];
+        for item in iterator {
+            let (key, blob) = item?;
+            if states.len() >= limit {
+                return Ok((StateSet::new(states), Some(key.key_hash())));
+            }
+            states.push((key.encode_key()?, blob.into()));
+        }
+        Ok((StateSet::new(states), None))
+    }
+
     /// passing None value with a key means delete the key
     fn updates(&self, updates: Vec<(K, Option<Blob>)>) -> Result<HashValue> {
         let cur_root_hash = self.root_hash();
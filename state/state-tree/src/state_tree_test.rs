@@ -166,6 +166,31 @@ pub fn test_state_dump() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_state_dump_range() -> Result<()> {
+    let s = MockStateNodeStore::new();
+    let state = StateTree::new(Arc::new(s), None);
+    for i in 0..10u8 {
+        state.put(HashValueKey(HashValue::random()), vec![i]);
+    }
+    state.commit()?;
+    let all = state.dump()?;
+
+    let mut pages = vec![];
+    let mut start_key_hash = HashValue::zero();
+    loop {
+        let (page, next) = state.dump_range(start_key_hash, 3)?;
+        assert!(page.len() <= 3);
+        pages.extend(page.iter().cloned());
+        match next {
+            Some(next) => start_key_hash = next,
+            None => break,
+        }
+    }
+    assert_eq!(pages, all.iter().cloned().collect::<Vec<_>>());
+    Ok(())
+}
+
 #[test]
 pub fn test_repeat_commit() -> Result<()> {
     let s = MockStateNodeStore::new();
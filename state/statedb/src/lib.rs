@@ -179,13 +179,14 @@ impl AccountStateObject {
         Ok(self.to_state())
     }
 
-    pub fn flush(&self) -> Result<()> {
-        self.resource_tree.lock().flush()?;
+    /// Flush the account's storage trees, and return the stale node keys.
+    pub fn flush(&self) -> Result<Vec<HashValue>> {
+        let mut stale_nodes = self.resource_tree.lock().flush_with_stale_nodes()?;
         if let Some(code_tree) = self.code_tree.lock().as_ref() {
-            code_tree.flush()?;
+            stale_nodes.extend(code_tree.flush_with_stale_nodes()?);
         }
 
-        Ok(())
+        Ok(stale_nodes)
     }
 
     fn to_state_set(&self) -> Result<AccountStateSet> {
@@ -299,6 +300,60 @@ impl ChainStateDB {
         Ok(object)
     }
 
+    /// Dump the states of all accounts, and pass them to `f` in chunks of at most `chunk_size`
+    /// accounts, the accounts are ordered by the hash of address.
+    /// The accounts are read from the state tree chunk by chunk, so only one chunk is in memory.
+    /// NOTICE: Any un-committed modification will not visible to the method.
+    pub fn dump_chunks<F>(&self, chunk_size: usize, mut f: F) -> Result<()>
+    where
+        F: FnMut(ChainStateSet) -> Result<()>,
+    {
+        ensure!(chunk_size > 0, "chunk size should be greater than zero.");
+        let mut start_key_hash = HashValue::zero();
+        loop {
+            let (global_states, next_key_hash) =
+                self.state_tree.dump_range(start_key_hash, chunk_size)?;
+            let mut account_states = vec![];
+            for (address_bytes, account_state_bytes) in global_states.iter() {
+                let account_state: AccountState = account_state_bytes.as_slice().try_into()?;
+                account_states.push((
+                    AccountAddress::decode_key(address_bytes.as_slice())?,
+                    self.dump_account_state(&account_state)?,
+                ));
+            }
+            if !account_states.is_empty() {
+                f(ChainStateSet::new(account_states))?;
+            }
+            match next_key_hash {
+                Some(next_key_hash) => start_key_hash = next_key_hash,
+                None => return Ok(()),
+            }
+        }
+    }
+
+    fn dump_account_state(&self, account_state: &AccountState) -> Result<AccountStateSet> {
+        let mut state_sets = vec![];
+        for (idx, storage_root) in account_state.storage_roots().iter().enumerate() {
+            let state_set = match storage_root {
+                Some(storage_root) => {
+                    let data_type = DataType::from_index(idx as u8)?;
+                    match data_type {
+                        DataType::CODE => {
+                            Some(self.new_state_tree::<ModuleName>(*storage_root).dump()?)
+                        }
+                        DataType::RESOURCE => {
+                            Some(self.new_state_tree::<StructTag>(*storage_root).dump()?)
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            state_sets.push(state_set);
+        }
+        Ok(AccountStateSet::new(state_sets))
+    }
+
     fn get_account_state(&self, account_address: &AccountAddress) -> Result<Option<AccountState>> {
         self.state_tree
             .get(account_address)
@@ -391,38 +446,11 @@ impl ChainStateReader for ChainStateDB {
 
     fn dump(&self) -> Result<ChainStateSet> {
         //TODO check cache dirty object.
-        //TODO performance optimize.
-        let global_states = self.state_tree.dump()?;
         let mut account_states = vec![];
-        for (address_bytes, account_state_bytes) in global_states.iter() {
-            let account_state: AccountState = account_state_bytes.as_slice().try_into()?;
-
-            let mut state_sets = vec![];
-            for (idx, storage_root) in account_state.storage_roots().iter().enumerate() {
-                let state_set = match storage_root {
-                    Some(storage_root) => {
-                        let data_type = DataType::from_index(idx as u8)?;
-                        match data_type {
-                            DataType::CODE => {
-                                Some(self.new_state_tree::<ModuleName>(*storage_root).dump()?)
-                            }
-                            DataType::RESOURCE => {
-                                Some(self.new_state_tree::<StructTag>(*storage_root).dump()?)
-                            }
-                        }
-                    }
-                    None => None,
-                };
-
-                state_sets.push(state_set);
-            }
-            let account_state_set = AccountStateSet::new(state_sets);
-
-            account_states.push((
-                AccountAddress::decode_key(address_bytes.as_slice())?,
-                account_state_set,
-            ));
-        }
+        self.dump_chunks(usize::max_value(), |chunk| {
+            account_states.extend(chunk.into_inner());
+            Ok(())
+        })?;
         Ok(ChainStateSet::new(account_states))
     }
 }
@@ -484,7 +512,7 @@ impl ChainStateWriter for ChainStateDB {
                     state_tree.flush()?;
                     state_tree.root_hash()
                 }
-                (None, None) => unreachable!("this should never happened"),
+                (None, None) => bail!("The resource set of new account {} is missing", address),
             };
             let new_account_state = AccountState::new(code_root, resource_root);
             self.state_tree.put(*address, new_account_state.try_into()?);
@@ -530,13 +558,19 @@ impl ChainStateWriter for ChainStateDB {
     fn flush(&self) -> Result<()> {
         //cache flush
         let mut locks = self.updates.write();
+        let mut stale_nodes = vec![];
         for address in locks.iter() {
             let account_state_object = self.get_account_state_object(address, false)?;
-            account_state_object.flush()?;
+            stale_nodes.extend(account_state_object.flush()?);
         }
         locks.clear();
         // self tree flush
-        self.state_tree.flush()
+        stale_nodes.extend(self.state_tree.flush_with_stale_nodes()?);
+        if !stale_nodes.is_empty() {
+            self.store
+                .write_stale_nodes(self.state_tree.root_hash(), stale_nodes)?;
+        }
+        Ok(())
     }
 }
 
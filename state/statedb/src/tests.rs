@@ -80,6 +80,29 @@ fn test_state_db_dump_and_apply() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_state_db_dump_chunks() -> Result<()> {
+    let storage = MockStateNodeStore::new();
+    let chain_state_db = ChainStateDB::new(Arc::new(storage), None);
+    for _ in 0..5 {
+        chain_state_db
+            .apply_write_set(to_write_set(AccessPath::random_resource(), random_bytes()))?;
+    }
+    chain_state_db.commit()?;
+    chain_state_db.flush()?;
+
+    let mut chunk_sizes = vec![];
+    let mut account_states = vec![];
+    chain_state_db.dump_chunks(2, |chunk| {
+        chunk_sizes.push(chunk.len());
+        account_states.extend(chunk.into_inner());
+        Ok(())
+    })?;
+    assert_eq!(chunk_sizes, vec![2, 2, 1]);
+    assert_eq!(ChainStateSet::new(account_states), chain_state_db.dump()?);
+    Ok(())
+}
+
 #[test]
 fn test_state_version() -> Result<()> {
     let storage = Arc::new(MockStateNodeStore::new());
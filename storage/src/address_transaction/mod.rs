@@ -0,0 +1,198 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::define_storage;
+use crate::storage::{
+    CodecKVStore, CodecWriteBatch, ColumnFamily, ColumnFamilyBatches, KeyCodec, SchemaStorage,
+    StorageInstance, ValueCodec, WriteBatch,
+};
+use crate::{ADDRESS_TRANSACTION_COUNT_PREFIX_NAME, ADDRESS_TRANSACTION_PREFIX_NAME};
+use anyhow::{ensure, Result};
+use byteorder::{BigEndian, ReadBytesExt};
+use crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The key of an address transaction index entry, `index` is the position of the transaction
+/// in the address's transaction history, start from 0.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct AddressTransactionKey {
+    pub address: AccountAddress,
+    pub index: u64,
+}
+
+impl AddressTransactionKey {
+    pub fn new(address: AccountAddress, index: u64) -> Self {
+        Self { address, index }
+    }
+}
+
+impl KeyCodec for AddressTransactionKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.address.to_vec();
+        encoded.extend_from_slice(&self.index.to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == AccountAddress::LENGTH + 8,
+            "invalid address transaction key length: {}",
+            data.len()
+        );
+        let address = AccountAddress::from_bytes(&data[..AccountAddress::LENGTH])?;
+        let index = (&data[AccountAddress::LENGTH..]).read_u64::<BigEndian>()?;
+        Ok(Self { address, index })
+    }
+}
+
+impl KeyCodec for AccountAddress {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(AccountAddress::from_bytes(data)?)
+    }
+}
+
+impl ValueCodec for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    #[allow(clippy::redundant_slicing)]
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok((&data[..]).read_u64::<BigEndian>()?)
+    }
+}
+
+define_storage!(
+    AddressTransactionIndexStorage,
+    AddressTransactionKey,
+    HashValue,
+    ADDRESS_TRANSACTION_PREFIX_NAME
+);
+
+define_storage!(
+    AddressTransactionCountStorage,
+    AccountAddress,
+    u64,
+    ADDRESS_TRANSACTION_COUNT_PREFIX_NAME
+);
+
+#[derive(Clone)]
+pub struct AddressTransactionStorage {
+    index_store: AddressTransactionIndexStorage,
+    count_store: AddressTransactionCountStorage,
+}
+
+impl AddressTransactionStorage {
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            index_store: AddressTransactionIndexStorage::new(instance.clone()),
+            count_store: AddressTransactionCountStorage::new(instance),
+        }
+    }
+
+    pub fn get_count(&self, address: AccountAddress) -> Result<u64> {
+        Ok(self.count_store.get(address)?.unwrap_or(0))
+    }
+
+    /// Append every `(address, txn_info_id)` entry to the tail of the address's history.
+    pub fn append(&self, entries: Vec<(AccountAddress, HashValue)>) -> Result<()> {
+        let batches = self.update_batches(vec![], entries)?;
+        self.index_store.get_store().write_batches(batches)
+    }
+
+    /// Remove entries from the tail of the address's history.
+    /// The `entries` must be in the reverse order of `append`.
+    pub fn rollback(&self, entries: Vec<(AccountAddress, HashValue)>) -> Result<()> {
+        let batches = self.update_batches(entries, vec![])?;
+        self.index_store.get_store().write_batches(batches)
+    }
+
+    /// Build the batches which `rollback` the `retracted` entries then `append` the `enacted`
+    /// entries, so they can be written atomically with other changes.
+    pub fn update_batches(
+        &self,
+        retracted: Vec<(AccountAddress, HashValue)>,
+        enacted: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<ColumnFamilyBatches> {
+        if retracted.is_empty() && enacted.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut counts: HashMap<AccountAddress, u64> = HashMap::new();
+        let mut index_batch = CodecWriteBatch::new();
+        for (address, txn_info_id) in retracted {
+            let count = match counts.get(&address) {
+                Some(count) => *count,
+                None => self.get_count(address)?,
+            };
+            ensure!(
+                count > 0,
+                "address {} has no transaction to rollback",
+                address
+            );
+            let key = AddressTransactionKey::new(address, count - 1);
+            let latest = self.index_store.get(key)?;
+            ensure!(
+                latest == Some(txn_info_id),
+                "address {} latest txn info {:?} mismatch with rollback txn info {}",
+                address,
+                latest,
+                txn_info_id
+            );
+            index_batch.delete(key)?;
+            counts.insert(address, count - 1);
+        }
+        for (address, txn_info_id) in enacted {
+            let count = match counts.get(&address) {
+                Some(count) => *count,
+                None => self.get_count(address)?,
+            };
+            index_batch.put(AddressTransactionKey::new(address, count), txn_info_id)?;
+            counts.insert(address, count + 1);
+        }
+        Ok(vec![
+            (
+                AddressTransactionIndexStorage::name(),
+                WriteBatch::try_from(index_batch)?,
+            ),
+            (
+                AddressTransactionCountStorage::name(),
+                WriteBatch::try_from(CodecWriteBatch::new_puts(counts.into_iter().collect()))?,
+            ),
+        ])
+    }
+
+    /// Get at most `limit` txn info ids of `address`, from newer to older.
+    /// `cursor` is the index of the first returned entry, if it is none, start from the latest.
+    pub fn get_txn_info_ids(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, HashValue)>> {
+        let count = self.get_count(address)?;
+        if count == 0 || limit == 0 {
+            return Ok(vec![]);
+        }
+        let start = match cursor {
+            Some(cursor) => cursor.min(count - 1),
+            None => count - 1,
+        };
+        let end = start.saturating_sub(limit - 1);
+        let keys: Vec<_> = (end..=start)
+            .rev()
+            .map(|index| AddressTransactionKey::new(address, index))
+            .collect();
+        let ids = self.index_store.multiple_get(keys.clone())?;
+        Ok(keys
+            .into_iter()
+            .zip(ids)
+            .filter_map(|(key, id)| id.map(|id| (key.index, id)))
+            .collect())
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use crate::define_storage;
-use crate::storage::{CodecKVStore, StorageInstance, ValueCodec};
+use crate::storage::{CodecKVStore, KeyCodec, SchemaStorage, StorageInstance, ValueCodec};
 use crate::{
     BLOCK_BODY_PREFIX_NAME, BLOCK_HEADER_PREFIX_NAME, BLOCK_PREFIX_NAME,
     BLOCK_TRANSACTIONS_PREFIX_NAME, BLOCK_TRANSACTION_INFOS_PREFIX_NAME, FAILED_BLOCK_PREFIX_NAME,
@@ -237,4 +237,19 @@ impl BlockStorage {
             None => Ok(None),
         }
     }
+
+    /// Get at most `limit` failed blocks, order by block id, start after the block id `cursor`.
+    pub fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>> {
+        let cursor = cursor.map(|block_id| block_id.encode_key()).transpose()?;
+        self.failed_block_storage
+            .get_store()
+            .scan_after(cursor, limit)?
+            .into_iter()
+            .map(|(_, value)| Ok(FailedBlock::decode_value(value.as_slice())?.into()))
+            .collect()
+    }
 }
@@ -3,7 +3,7 @@
 
 use crate::batch::WriteBatch;
 use crate::metrics::{record_metrics, CACHE_ITEMS};
-use crate::storage::{CacheObject, InnerStore, WriteOp};
+use crate::storage::{CacheObject, ColumnFamilyBatches, InnerStore, WriteOp};
 use anyhow::{Error, Result};
 use lru::LruCache;
 use parking_lot::Mutex;
@@ -120,6 +120,13 @@ impl InnerStore for CacheStorage {
         })
     }
 
+    fn write_batches(&self, batches: ColumnFamilyBatches) -> Result<()> {
+        for (prefix_name, batch) in batches {
+            self.write_batch(prefix_name, batch)?;
+        }
+        Ok(())
+    }
+
     fn get_len(&self) -> Result<u64, Error> {
         Ok(self.cache.lock().len() as u64)
     }
@@ -1,10 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::storage::{ColumnFamily, InnerStorage, KVStore};
-use crate::CHAIN_INFO_PREFIX_NAME;
+use crate::storage::{ColumnFamily, ColumnFamilyBatches, InnerStorage, KVStore, WriteBatch};
+use crate::{StatePruneForks, CHAIN_INFO_PREFIX_NAME};
 use anyhow::Result;
 use crypto::HashValue;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::startup_info::StartupInfo;
 use std::convert::TryInto;
 
@@ -25,6 +26,9 @@ pub type ChainInfoStorage = InnerStorage<ChainInfoColumnFamily>;
 impl ChainInfoStorage {
     const STARTUP_INFO_KEY: &'static str = "startup_info";
     const GENESIS_KEY: &'static str = "genesis";
+    const STATE_PRUNED_NUMBER_KEY: &'static str = "state_pruned_number";
+    const STATE_PRUNE_FORKS_KEY: &'static str = "state_prune_forks";
+    const EVENT_INDEX_START_KEY: &'static str = "event_index_start";
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -41,6 +45,20 @@ impl ChainInfoStorage {
         )
     }
 
+    pub fn save_startup_info_with_batches(
+        &self,
+        startup_info: StartupInfo,
+        mut batches: ColumnFamilyBatches,
+    ) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        batch.put(
+            Self::STARTUP_INFO_KEY.as_bytes().to_vec(),
+            startup_info.try_into()?,
+        )?;
+        batches.push((ChainInfoColumnFamily::name(), batch));
+        self.write_batches(batches)
+    }
+
     pub fn get_genesis(&self) -> Result<Option<HashValue>> {
         self.get(Self::GENESIS_KEY.as_bytes())
             .and_then(|bytes| match bytes {
@@ -55,4 +73,52 @@ impl ChainInfoStorage {
             genesis_block_hash.to_vec(),
         )
     }
+
+    pub fn get_state_pruned_number(&self) -> Result<Option<BlockNumber>> {
+        self.get(Self::STATE_PRUNED_NUMBER_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bcs_ext::from_bytes(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_state_pruned_number(&self, number: BlockNumber) -> Result<()> {
+        self.put(
+            Self::STATE_PRUNED_NUMBER_KEY.as_bytes().to_vec(),
+            bcs_ext::to_bytes(&number)?,
+        )
+    }
+
+    pub fn get_state_prune_forks(&self) -> Result<StatePruneForks> {
+        self.get(Self::STATE_PRUNE_FORKS_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(bcs_ext::from_bytes(bytes.as_slice())?),
+                None => Ok(StatePruneForks::new()),
+            })
+    }
+
+    pub fn save_state_prune_forks(&self, forks: &StatePruneForks) -> Result<()> {
+        self.put(
+            Self::STATE_PRUNE_FORKS_KEY.as_bytes().to_vec(),
+            bcs_ext::to_bytes(forks)?,
+        )
+    }
+
+    pub fn get_event_index_start(&self) -> Result<Option<BlockNumber>> {
+        self.get(Self::EVENT_INDEX_START_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bcs_ext::from_bytes(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_event_index_start(&self, number: Option<BlockNumber>) -> Result<()> {
+        match number {
+            Some(number) => self.put(
+                Self::EVENT_INDEX_START_KEY.as_bytes().to_vec(),
+                bcs_ext::to_bytes(&number)?,
+            ),
+            None => self.remove(Self::EVENT_INDEX_START_KEY.as_bytes().to_vec()),
+        }
+    }
 }
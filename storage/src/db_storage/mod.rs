@@ -4,7 +4,7 @@
 use crate::batch::WriteBatch;
 use crate::errors::StorageInitError;
 use crate::metrics::{record_metrics, STORAGE_ITER_BYTES};
-use crate::storage::{ColumnFamilyName, InnerStore, WriteOp};
+use crate::storage::{ColumnFamilyBatches, ColumnFamilyName, InnerStore, WriteOp};
 use crate::{DEFAULT_PREFIX_NAME, VEC_PREFIX_NAME};
 use anyhow::{ensure, format_err, Error, Result};
 use rocksdb::{Options, ReadOptions, WriteBatch as DBWriteBatch, WriteOptions, DB};
@@ -316,6 +316,24 @@ impl InnerStore for DBStorage {
         })
     }
 
+    fn write_batches(&self, batches: ColumnFamilyBatches) -> Result<()> {
+        record_metrics("db", "batches", "write").end_with(|| {
+            let mut db_batch = DBWriteBatch::default();
+            for (prefix_name, batch) in &batches {
+                let cf_handle = self.get_cf_handle(prefix_name)?;
+                for (key, write_op) in &batch.rows {
+                    match write_op {
+                        WriteOp::Value(value) => db_batch.put_cf(cf_handle, key, value),
+                        WriteOp::Deletion => db_batch.delete_cf(cf_handle, key),
+                    };
+                }
+            }
+            self.db
+                .write_opt(db_batch, &Self::default_write_options())?;
+            Ok(())
+        })
+    }
+
     fn get_len(&self) -> Result<u64> {
         unimplemented!()
     }
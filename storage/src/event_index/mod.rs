@@ -0,0 +1,395 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::define_storage;
+use crate::storage::{
+    CodecKVStore, CodecWriteBatch, ColumnFamily, ColumnFamilyBatches, KeyCodec, SchemaStorage,
+    StorageInstance, ValueCodec, WriteBatch,
+};
+use crate::{
+    EVENT_KEY_COUNT_PREFIX_NAME, EVENT_KEY_INDEX_PREFIX_NAME, EVENT_TYPE_COUNT_PREFIX_NAME,
+    EVENT_TYPE_INDEX_PREFIX_NAME,
+};
+use anyhow::{ensure, Result};
+use bcs_ext::BCSCodec;
+use byteorder::{BigEndian, ReadBytesExt};
+use crypto::HashValue;
+use serde::{Deserialize, Serialize};
+use starcoin_types::contract_event::{ContractEvent, EventPosition};
+use starcoin_types::event::EventKey;
+use starcoin_types::language_storage::TypeTag;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::ops::{Bound, RangeBounds};
+
+/// An indexed event, the event can be found in the events of the transaction info.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IndexedEvent {
+    pub position: EventPosition,
+    pub txn_info_id: HashValue,
+}
+
+impl IndexedEvent {
+    pub fn new(position: EventPosition, txn_info_id: HashValue) -> Self {
+        Self {
+            position,
+            txn_info_id,
+        }
+    }
+}
+
+impl ValueCodec for IndexedEvent {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+/// The key of an event key index entry, `seq` is the sequence number of the event.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct EventSeqKey {
+    pub event_key: EventKey,
+    pub seq: u64,
+}
+
+impl EventSeqKey {
+    pub fn new(event_key: EventKey, seq: u64) -> Self {
+        Self { event_key, seq }
+    }
+}
+
+impl KeyCodec for EventSeqKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.event_key.to_vec();
+        encoded.extend_from_slice(&self.seq.to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == EventKey::LENGTH + 8,
+            "invalid event seq key length: {}",
+            data.len()
+        );
+        let event_key = EventKey::decode_key(&data[..EventKey::LENGTH])?;
+        let seq = (&data[EventKey::LENGTH..]).read_u64::<BigEndian>()?;
+        Ok(Self { event_key, seq })
+    }
+}
+
+impl KeyCodec for EventKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == EventKey::LENGTH,
+            "invalid event key length: {}",
+            data.len()
+        );
+        Ok(EventKey::new(data.try_into()?))
+    }
+}
+
+/// The key of an event type index entry, `index` is the position of the event in the events of
+/// the type, start from 0.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct EventTypeKey {
+    pub type_hash: HashValue,
+    pub index: u64,
+}
+
+impl EventTypeKey {
+    pub fn new(type_hash: HashValue, index: u64) -> Self {
+        Self { type_hash, index }
+    }
+}
+
+impl KeyCodec for EventTypeKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.type_hash.to_vec();
+        encoded.extend_from_slice(&self.index.to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == HashValue::LENGTH + 8,
+            "invalid event type key length: {}",
+            data.len()
+        );
+        let type_hash = HashValue::from_slice(&data[..HashValue::LENGTH])?;
+        let index = (&data[HashValue::LENGTH..]).read_u64::<BigEndian>()?;
+        Ok(Self { type_hash, index })
+    }
+}
+
+define_storage!(
+    EventKeyIndexStorage,
+    EventSeqKey,
+    IndexedEvent,
+    EVENT_KEY_INDEX_PREFIX_NAME
+);
+
+define_storage!(
+    EventKeyCountStorage,
+    EventKey,
+    u64,
+    EVENT_KEY_COUNT_PREFIX_NAME
+);
+
+define_storage!(
+    EventTypeIndexStorage,
+    EventTypeKey,
+    IndexedEvent,
+    EVENT_TYPE_INDEX_PREFIX_NAME
+);
+
+define_storage!(
+    EventTypeCountStorage,
+    HashValue,
+    u64,
+    EVENT_TYPE_COUNT_PREFIX_NAME
+);
+
+/// The range of positions to query the indexed events.
+pub type EventPositionRange = (Bound<EventPosition>, Bound<EventPosition>);
+
+fn type_hash(type_tag: &TypeTag) -> Result<HashValue> {
+    Ok(HashValue::sha3_256_of(&type_tag.encode()?))
+}
+
+#[derive(Clone)]
+pub struct ContractEventIndexStorage {
+    key_index_store: EventKeyIndexStorage,
+    key_count_store: EventKeyCountStorage,
+    type_index_store: EventTypeIndexStorage,
+    type_count_store: EventTypeCountStorage,
+}
+
+impl ContractEventIndexStorage {
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            key_index_store: EventKeyIndexStorage::new(instance.clone()),
+            key_count_store: EventKeyCountStorage::new(instance.clone()),
+            type_index_store: EventTypeIndexStorage::new(instance.clone()),
+            type_count_store: EventTypeCountStorage::new(instance),
+        }
+    }
+
+    /// The count of the event key is the sequence number of the next indexed event.
+    fn get_key_count(&self, event_key: &EventKey) -> Result<u64> {
+        Ok(self.key_count_store.get(*event_key)?.unwrap_or(0))
+    }
+
+    fn get_type_count(&self, type_hash: HashValue) -> Result<u64> {
+        Ok(self.type_count_store.get(type_hash)?.unwrap_or(0))
+    }
+
+    /// Index the events in the order of the chain.
+    pub fn append(&self, events: Vec<(ContractEvent, IndexedEvent)>) -> Result<()> {
+        let batches = self.update_batches(vec![], events)?;
+        self.key_index_store.get_store().write_batches(batches)
+    }
+
+    /// Remove the events from the index, `events` must be in the reverse order of `append`.
+    pub fn rollback(&self, events: Vec<(ContractEvent, IndexedEvent)>) -> Result<()> {
+        let batches = self.update_batches(events, vec![])?;
+        self.key_index_store.get_store().write_batches(batches)
+    }
+
+    /// Build the batches which `rollback` the `retracted` events then `append` the `enacted`
+    /// events, so they can be written atomically with other changes.
+    pub fn update_batches(
+        &self,
+        retracted: Vec<(ContractEvent, IndexedEvent)>,
+        enacted: Vec<(ContractEvent, IndexedEvent)>,
+    ) -> Result<ColumnFamilyBatches> {
+        if retracted.is_empty() && enacted.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut key_counts: HashMap<EventKey, u64> = HashMap::new();
+        let mut type_counts: HashMap<HashValue, u64> = HashMap::new();
+        let mut key_index_batch = CodecWriteBatch::new();
+        let mut type_index_batch = CodecWriteBatch::new();
+        for (event, indexed) in retracted {
+            let seq = event.sequence_number();
+            let count = match key_counts.get(event.key()) {
+                Some(count) => *count,
+                None => self.get_key_count(event.key())?,
+            };
+            ensure!(
+                count == seq + 1,
+                "event key {} latest seq {} mismatch with rollback seq {}",
+                event.key(),
+                count,
+                seq
+            );
+            key_index_batch.delete(EventSeqKey::new(*event.key(), seq))?;
+            key_counts.insert(*event.key(), seq);
+
+            let type_hash = type_hash(event.type_tag())?;
+            let count = match type_counts.get(&type_hash) {
+                Some(count) => *count,
+                None => self.get_type_count(type_hash)?,
+            };
+            ensure!(
+                count > 0,
+                "event type {} has no event to rollback",
+                event.type_tag()
+            );
+            let key = EventTypeKey::new(type_hash, count - 1);
+            let latest = self.type_index_store.get(key)?;
+            ensure!(
+                latest == Some(indexed),
+                "event type {} latest event {:?} mismatch with rollback event {:?}",
+                event.type_tag(),
+                latest,
+                indexed
+            );
+            type_index_batch.delete(key)?;
+            type_counts.insert(type_hash, count - 1);
+        }
+        for (event, indexed) in enacted {
+            let seq = event.sequence_number();
+            key_index_batch.put(EventSeqKey::new(*event.key(), seq), indexed)?;
+            key_counts.insert(*event.key(), seq + 1);
+
+            let type_hash = type_hash(event.type_tag())?;
+            let count = match type_counts.get(&type_hash) {
+                Some(count) => *count,
+                None => self.get_type_count(type_hash)?,
+            };
+            type_index_batch.put(EventTypeKey::new(type_hash, count), indexed)?;
+            type_counts.insert(type_hash, count + 1);
+        }
+        Ok(vec![
+            (
+                EventKeyIndexStorage::name(),
+                WriteBatch::try_from(key_index_batch)?,
+            ),
+            (
+                EventTypeIndexStorage::name(),
+                WriteBatch::try_from(type_index_batch)?,
+            ),
+            (
+                EventKeyCountStorage::name(),
+                WriteBatch::try_from(CodecWriteBatch::new_puts(key_counts.into_iter().collect()))?,
+            ),
+            (
+                EventTypeCountStorage::name(),
+                WriteBatch::try_from(CodecWriteBatch::new_puts(type_counts.into_iter().collect()))?,
+            ),
+        ])
+    }
+
+    /// Get at most `limit` indexed events of `event_key` in the `range`,
+    /// in the order of the chain, or the reverse order if `reverse` is true.
+    pub fn get_by_key(
+        &self,
+        event_key: &EventKey,
+        range: EventPositionRange,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<IndexedEvent>> {
+        let count = self.get_key_count(event_key)?;
+        query(
+            count,
+            |seq| EventSeqKey::new(*event_key, seq),
+            &self.key_index_store,
+            range,
+            reverse,
+            limit,
+        )
+    }
+
+    /// Get at most `limit` indexed events of `type_tag` in the `range`,
+    /// in the order of the chain, or the reverse order if `reverse` is true.
+    pub fn get_by_type(
+        &self,
+        type_tag: &TypeTag,
+        range: EventPositionRange,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<IndexedEvent>> {
+        let type_hash = type_hash(type_tag)?;
+        let count = self.get_type_count(type_hash)?;
+        query(
+            count,
+            |index| EventTypeKey::new(type_hash, index),
+            &self.type_index_store,
+            range,
+            reverse,
+            limit,
+        )
+    }
+}
+
+/// Query the indexed events of a list, the entries `[0, count)` of the list are in the order of
+/// the chain. The entries before the index enabled are missing, and treated as before the range.
+fn query<K, F, S>(
+    count: u64,
+    key_of: F,
+    store: &S,
+    range: EventPositionRange,
+    reverse: bool,
+    limit: usize,
+) -> Result<Vec<IndexedEvent>>
+where
+    K: KeyCodec,
+    F: Fn(u64) -> K,
+    S: CodecKVStore<K, IndexedEvent>,
+{
+    if count == 0 || limit == 0 {
+        return Ok(vec![]);
+    }
+    let before_start = |position: &EventPosition| match range.start_bound() {
+        Bound::Included(start) => position < start,
+        Bound::Excluded(start) => position <= start,
+        Bound::Unbounded => false,
+    };
+    let before_end = |position: &EventPosition| match range.end_bound() {
+        Bound::Included(end) => position <= end,
+        Bound::Excluded(end) => position < end,
+        Bound::Unbounded => true,
+    };
+    let start = partition_point(count, &key_of, store, before_start)?;
+    let end = partition_point(count, &key_of, store, before_end)?;
+    if start >= end {
+        return Ok(vec![]);
+    }
+    let keys: Vec<K> = if reverse {
+        (start..end).rev().take(limit).map(&key_of).collect()
+    } else {
+        (start..end).take(limit).map(&key_of).collect()
+    };
+    Ok(store.multiple_get(keys)?.into_iter().flatten().collect())
+}
+
+/// Binary search the first entry which does not satisfy `pred`, the missing entries satisfy it.
+fn partition_point<K, F, S, P>(count: u64, key_of: &F, store: &S, pred: P) -> Result<u64>
+where
+    K: KeyCodec,
+    F: Fn(u64) -> K,
+    S: CodecKVStore<K, IndexedEvent>,
+    P: Fn(&EventPosition) -> bool,
+{
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let satisfied = match store.get(key_of(mid))? {
+            Some(indexed) => pred(&indexed.position),
+            None => true,
+        };
+        if satisfied {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
@@ -4,12 +4,16 @@
 use crate::accumulator::{
     AccumulatorStorage, BlockAccumulatorStorage, TransactionAccumulatorStorage,
 };
+use crate::address_transaction::AddressTransactionStorage;
 use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStorage, BlockInfoStore};
 use crate::chain_info::ChainInfoStorage;
 use crate::contract_event::ContractEventStorage;
-use crate::state_node::StateStorage;
-use crate::storage::{CodecKVStore, CodecWriteBatch, ColumnFamilyName, StorageInstance};
+use crate::event_index::{ContractEventIndexStorage, EventPositionRange, IndexedEvent};
+use crate::state_node::{StaleStateNodeStorage, StateStorage};
+use crate::storage::{
+    CodecKVStore, CodecWriteBatch, ColumnFamilyBatches, ColumnFamilyName, StorageInstance,
+};
 use crate::transaction::TransactionStorage;
 use crate::transaction_info::{TransactionInfoHashStorage, TransactionInfoStorage};
 use anyhow::{bail, format_err, Error, Result};
@@ -18,12 +22,15 @@ use once_cell::sync::Lazy;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorTreeStore;
 use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event::EventKey;
+use starcoin_types::language_storage::TypeTag;
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
 use starcoin_types::{
-    block::{Block, BlockBody, BlockHeader, BlockInfo},
+    block::{Block, BlockBody, BlockHeader, BlockInfo, BlockNumber},
     startup_info::StartupInfo,
 };
 use std::collections::BTreeMap;
@@ -31,6 +38,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::sync::Arc;
 
 pub mod accumulator;
+pub mod address_transaction;
 pub mod batch;
 pub mod block;
 pub mod block_info;
@@ -39,6 +47,7 @@ pub mod chain_info;
 pub mod contract_event;
 pub mod db_storage;
 pub mod errors;
+pub mod event_index;
 mod metrics;
 pub mod state_node;
 pub mod storage;
@@ -65,6 +74,14 @@ pub const TRANSACTION_INFO_PREFIX_NAME: ColumnFamilyName = "transaction_info";
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
 pub const FAILED_BLOCK_PREFIX_NAME: ColumnFamilyName = "failed_block";
+pub const ADDRESS_TRANSACTION_PREFIX_NAME: ColumnFamilyName = "address_txn";
+pub const ADDRESS_TRANSACTION_COUNT_PREFIX_NAME: ColumnFamilyName = "address_txn_count";
+pub const STATE_NODE_STALE_PREFIX_NAME: ColumnFamilyName = "state_node_stale";
+pub const STATE_NODE_STALE_INDEX_PREFIX_NAME: ColumnFamilyName = "state_node_stale_index";
+pub const EVENT_KEY_INDEX_PREFIX_NAME: ColumnFamilyName = "event_key_index";
+pub const EVENT_KEY_COUNT_PREFIX_NAME: ColumnFamilyName = "event_key_count";
+pub const EVENT_TYPE_INDEX_PREFIX_NAME: ColumnFamilyName = "event_type_index";
+pub const EVENT_TYPE_COUNT_PREFIX_NAME: ColumnFamilyName = "event_type_count";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -85,12 +102,27 @@ pub static VEC_PREFIX_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TRANSACTION_INFO_HASH_PREFIX_NAME,
         CONTRACT_EVENT_PREFIX_NAME,
         FAILED_BLOCK_PREFIX_NAME,
+        ADDRESS_TRANSACTION_PREFIX_NAME,
+        ADDRESS_TRANSACTION_COUNT_PREFIX_NAME,
+        STATE_NODE_STALE_PREFIX_NAME,
+        STATE_NODE_STALE_INDEX_PREFIX_NAME,
+        EVENT_KEY_INDEX_PREFIX_NAME,
+        EVENT_KEY_COUNT_PREFIX_NAME,
+        EVENT_TYPE_INDEX_PREFIX_NAME,
+        EVENT_TYPE_COUNT_PREFIX_NAME,
     ]
 });
 
 pub trait BlockStore {
     fn get_startup_info(&self) -> Result<Option<StartupInfo>>;
     fn save_startup_info(&self, startup_info: StartupInfo) -> Result<()>;
+    /// Save the startup info and write the `batches` atomically,
+    /// the index changes of a new head should be committed with its startup info.
+    fn save_startup_info_with_batches(
+        &self,
+        startup_info: StartupInfo,
+        batches: ColumnFamilyBatches,
+    ) -> Result<()>;
 
     fn get_genesis(&self) -> Result<Option<HashValue>>;
 
@@ -138,6 +170,13 @@ pub trait BlockStore {
         &self,
         block_id: HashValue,
     ) -> Result<Option<(Block, Option<PeerId>, String)>>;
+
+    /// Get at most `limit` failed blocks, order by block id, start after the block id `cursor`.
+    fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>>;
 }
 
 pub trait BlockTransactionInfoStore {
@@ -166,6 +205,110 @@ pub trait ContractEventStore {
     fn get_contract_events(&self, txn_info_id: HashValue) -> Result<Option<Vec<ContractEvent>>>;
 }
 
+/// Index of transaction history by address, the address of a transaction is the sender of user
+/// transaction and the creator address of the events emitted by the transaction.
+/// The index is optional, and only maintained for the main chain.
+pub trait AddressTransactionStore {
+    /// Get the count of transactions indexed for `address`.
+    fn get_address_txn_count(&self, address: AccountAddress) -> Result<u64>;
+
+    /// Append `(address, txn_info_id)` entries to the address's history, in order.
+    fn append_address_txn_info_ids(&self, entries: Vec<(AccountAddress, HashValue)>) -> Result<()>;
+
+    /// Remove entries from the tail of the address's history,
+    /// `entries` should be in the reverse order of `append_address_txn_info_ids`.
+    fn rollback_address_txn_info_ids(
+        &self,
+        entries: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()>;
+
+    /// Build the batches of `rollback_address_txn_info_ids(retracted)` then
+    /// `append_address_txn_info_ids(enacted)` without writing them.
+    fn address_txn_index_batches(
+        &self,
+        retracted: Vec<(AccountAddress, HashValue)>,
+        enacted: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<ColumnFamilyBatches>;
+
+    /// Get at most `limit` `(index, txn_info_id)` of `address` from newer to older,
+    /// start from the entry at `cursor`, or the latest entry if `cursor` is none.
+    fn get_address_txn_info_ids(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, HashValue)>>;
+}
+
+/// Index of events by the event key and sequence number, and by the event type.
+/// The index is optional, and only maintained for the main chain.
+pub trait ContractEventIndexStore {
+    /// Index the events with their positions, `events` should be in the order of the chain.
+    fn append_event_index(&self, events: Vec<(ContractEvent, IndexedEvent)>) -> Result<()>;
+
+    /// Remove the events from the index,
+    /// `events` should be in the reverse order of `append_event_index`.
+    fn rollback_event_index(&self, events: Vec<(ContractEvent, IndexedEvent)>) -> Result<()>;
+
+    /// Build the batches of `rollback_event_index(retracted)` then `append_event_index(enacted)`
+    /// without writing them.
+    fn event_index_batches(
+        &self,
+        retracted: Vec<(ContractEvent, IndexedEvent)>,
+        enacted: Vec<(ContractEvent, IndexedEvent)>,
+    ) -> Result<ColumnFamilyBatches>;
+
+    /// Get the number of the first block whose events are indexed,
+    /// None if the index is not enabled. The events of the blocks before it are not indexed.
+    fn get_event_index_start(&self) -> Result<Option<BlockNumber>>;
+
+    /// Save the number of the first indexed block, or remove it if `number` is None.
+    fn save_event_index_start(&self, number: Option<BlockNumber>) -> Result<()>;
+
+    /// Get at most `limit` indexed events of `event_key` whose positions are in `range`,
+    /// in the order of the chain, or the reverse order if `reverse` is true.
+    fn get_indexed_events_by_key(
+        &self,
+        event_key: &EventKey,
+        range: EventPositionRange,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<IndexedEvent>>;
+
+    /// Get at most `limit` indexed events of `type_tag` whose positions are in `range`,
+    /// in the order of the chain, or the reverse order if `reverse` is true.
+    fn get_indexed_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        range: EventPositionRange,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<IndexedEvent>>;
+}
+
+/// The fork branches tracked for state prune,
+/// branch head id -> (branch head number, number of the common ancestor with main chain).
+pub type StatePruneForks = BTreeMap<HashValue, (BlockNumber, BlockNumber)>;
+
+/// Pruning of the state nodes which are not referenced by recent states.
+/// Stale nodes are only recorded when state prune is enabled, see `Storage::with_state_prune`.
+pub trait StatePruneStore {
+    /// Get the number of the latest main chain block whose stale state nodes have been pruned.
+    fn get_state_pruned_number(&self) -> Result<Option<BlockNumber>>;
+
+    fn save_state_pruned_number(&self, number: BlockNumber) -> Result<()>;
+
+    /// Get the fork branches whose states are kept from pruning.
+    fn get_state_prune_forks(&self) -> Result<StatePruneForks>;
+
+    fn save_state_prune_forks(&self, forks: &StatePruneForks) -> Result<()>;
+
+    /// Delete the state nodes which become stale at `state_root`,
+    /// after that, the states before `state_root` may be unreadable.
+    /// Return the count of deleted nodes.
+    fn prune_stale_state_nodes(&self, state_root: HashValue) -> Result<usize>;
+}
+
 pub trait TransactionStore {
     fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<Transaction>>;
     fn save_transaction(&self, txn_info: Transaction) -> Result<()>;
@@ -185,6 +328,10 @@ pub struct Storage {
     block_info_storage: BlockInfoStorage,
     event_storage: ContractEventStorage,
     chain_info_storage: ChainInfoStorage,
+    address_transaction_storage: AddressTransactionStorage,
+    event_index_storage: ContractEventIndexStorage,
+    stale_state_node_storage: StaleStateNodeStorage,
+    state_prune: bool,
 }
 
 impl Storage {
@@ -202,10 +349,20 @@ impl Storage {
                 AccumulatorStorage::new_transaction_accumulator_storage(instance.clone()),
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
-            chain_info_storage: ChainInfoStorage::new(instance),
+            chain_info_storage: ChainInfoStorage::new(instance.clone()),
+            address_transaction_storage: AddressTransactionStorage::new(instance.clone()),
+            event_index_storage: ContractEventIndexStorage::new(instance.clone()),
+            stale_state_node_storage: StaleStateNodeStorage::new(instance),
+            state_prune: false,
         })
     }
 
+    /// Record the stale state nodes when state changes flushed, so they can be pruned later.
+    pub fn with_state_prune(mut self, state_prune: bool) -> Self {
+        self.state_prune = state_prune;
+        self
+    }
+
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
         self.block_accumulator_storage.clone()
     }
@@ -227,9 +384,21 @@ impl StateNodeStore for Storage {
     }
 
     fn write_nodes(&self, nodes: BTreeMap<HashValue, StateNode>) -> Result<()> {
+        if self.state_prune {
+            self.stale_state_node_storage
+                .remove_stale_index(nodes.keys().cloned().collect())?;
+        }
         let batch = CodecWriteBatch::new_puts(nodes.into_iter().collect());
         self.state_node_storage.write_batch(batch)
     }
+
+    fn write_stale_nodes(&self, state_root: HashValue, stale_nodes: Vec<HashValue>) -> Result<()> {
+        if self.state_prune {
+            self.stale_state_node_storage
+                .put_stale_nodes(state_root, stale_nodes)?;
+        }
+        Ok(())
+    }
 }
 
 impl Display for Storage {
@@ -252,6 +421,15 @@ impl BlockStore for Storage {
         self.chain_info_storage.save_startup_info(startup_info)
     }
 
+    fn save_startup_info_with_batches(
+        &self,
+        startup_info: StartupInfo,
+        batches: ColumnFamilyBatches,
+    ) -> Result<()> {
+        self.chain_info_storage
+            .save_startup_info_with_batches(startup_info, batches)
+    }
+
     fn get_genesis(&self) -> Result<Option<HashValue>> {
         self.chain_info_storage.get_genesis()
     }
@@ -349,6 +527,14 @@ impl BlockStore for Storage {
     ) -> Result<Option<(Block, Option<PeerId>, String)>> {
         self.block_storage.get_failed_block_by_id(block_id)
     }
+
+    fn get_failed_blocks(
+        &self,
+        cursor: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Vec<(Block, Option<PeerId>, String)>> {
+        self.block_storage.get_failed_blocks(cursor, limit)
+    }
 }
 
 impl BlockInfoStore for Storage {
@@ -428,6 +614,113 @@ impl TransactionStore for Storage {
     }
 }
 
+impl AddressTransactionStore for Storage {
+    fn get_address_txn_count(&self, address: AccountAddress) -> Result<u64> {
+        self.address_transaction_storage.get_count(address)
+    }
+
+    fn append_address_txn_info_ids(&self, entries: Vec<(AccountAddress, HashValue)>) -> Result<()> {
+        self.address_transaction_storage.append(entries)
+    }
+
+    fn rollback_address_txn_info_ids(
+        &self,
+        entries: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()> {
+        self.address_transaction_storage.rollback(entries)
+    }
+
+    fn address_txn_index_batches(
+        &self,
+        retracted: Vec<(AccountAddress, HashValue)>,
+        enacted: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<ColumnFamilyBatches> {
+        self.address_transaction_storage
+            .update_batches(retracted, enacted)
+    }
+
+    fn get_address_txn_info_ids(
+        &self,
+        address: AccountAddress,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, HashValue)>> {
+        self.address_transaction_storage
+            .get_txn_info_ids(address, cursor, limit)
+    }
+}
+
+impl ContractEventIndexStore for Storage {
+    fn append_event_index(&self, events: Vec<(ContractEvent, IndexedEvent)>) -> Result<()> {
+        self.event_index_storage.append(events)
+    }
+
+    fn rollback_event_index(&self, events: Vec<(ContractEvent, IndexedEvent)>) -> Result<()> {
+        self.event_index_storage.rollback(events)
+    }
+
+    fn event_index_batches(
+        &self,
+        retracted: Vec<(ContractEvent, IndexedEvent)>,
+        enacted: Vec<(ContractEvent, IndexedEvent)>,
+    ) -> Result<ColumnFamilyBatches> {
+        self.event_index_storage.update_batches(retracted, enacted)
+    }
+
+    fn get_event_index_start(&self) -> Result<Option<BlockNumber>> {
+        self.chain_info_storage.get_event_index_start()
+    }
+
+    fn save_event_index_start(&self, number: Option<BlockNumber>) -> Result<()> {
+        self.chain_info_storage.save_event_index_start(number)
+    }
+
+    fn get_indexed_events_by_key(
+        &self,
+        event_key: &EventKey,
+        range: EventPositionRange,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<IndexedEvent>> {
+        self.event_index_storage
+            .get_by_key(event_key, range, reverse, limit)
+    }
+
+    fn get_indexed_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        range: EventPositionRange,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<IndexedEvent>> {
+        self.event_index_storage
+            .get_by_type(type_tag, range, reverse, limit)
+    }
+}
+
+impl StatePruneStore for Storage {
+    fn get_state_pruned_number(&self) -> Result<Option<BlockNumber>> {
+        self.chain_info_storage.get_state_pruned_number()
+    }
+
+    fn save_state_pruned_number(&self, number: BlockNumber) -> Result<()> {
+        self.chain_info_storage.save_state_pruned_number(number)
+    }
+
+    fn get_state_prune_forks(&self) -> Result<StatePruneForks> {
+        self.chain_info_storage.get_state_prune_forks()
+    }
+
+    fn save_state_prune_forks(&self, forks: &StatePruneForks) -> Result<()> {
+        self.chain_info_storage.save_state_prune_forks(forks)
+    }
+
+    fn prune_stale_state_nodes(&self, state_root: HashValue) -> Result<usize> {
+        self.stale_state_node_storage
+            .prune(state_root, &self.state_node_storage)
+    }
+}
+
 /// Chain storage define
 pub trait Store:
     StateNodeStore
@@ -436,6 +729,9 @@ pub trait Store:
     + TransactionStore
     + BlockTransactionInfoStore
     + ContractEventStore
+    + AddressTransactionStore
+    + ContractEventIndexStore
+    + StatePruneStore
     + IntoSuper<dyn StateNodeStore>
 {
     fn get_transaction_info_by_block_and_index(
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::define_storage;
-use crate::storage::ValueCodec;
-use crate::STATE_NODE_PREFIX_NAME;
+use crate::storage::{CodecKVStore, CodecWriteBatch, StorageInstance, ValueCodec};
+use crate::{
+    STATE_NODE_PREFIX_NAME, STATE_NODE_STALE_INDEX_PREFIX_NAME, STATE_NODE_STALE_PREFIX_NAME,
+};
 use anyhow::Result;
 use crypto::HashValue;
 use starcoin_state_store_api::StateNode;
@@ -18,3 +20,77 @@ impl ValueCodec for StateNode {
         Ok(StateNode(data.to_vec()))
     }
 }
+
+define_storage!(
+    StateNodeStaleStorage,
+    HashValue,
+    Vec<HashValue>,
+    STATE_NODE_STALE_PREFIX_NAME
+);
+
+define_storage!(
+    StateNodeStaleIndexStorage,
+    HashValue,
+    HashValue,
+    STATE_NODE_STALE_INDEX_PREFIX_NAME
+);
+
+/// Records which state nodes become stale at a state root.
+/// The nodes are addressed by hash, so a stale node may be created again by a later state,
+/// `index_store` keeps the latest state root at which a node became stale, and the node can
+/// only be deleted when pruning that state root.
+#[derive(Clone)]
+pub struct StaleStateNodeStorage {
+    stale_store: StateNodeStaleStorage,
+    index_store: StateNodeStaleIndexStorage,
+}
+
+impl StaleStateNodeStorage {
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            stale_store: StateNodeStaleStorage::new(instance.clone()),
+            index_store: StateNodeStaleIndexStorage::new(instance),
+        }
+    }
+
+    /// Record `stale_nodes` become stale at `state_root`.
+    pub fn put_stale_nodes(
+        &self,
+        state_root: HashValue,
+        stale_nodes: Vec<HashValue>,
+    ) -> Result<()> {
+        self.index_store.write_batch(CodecWriteBatch::new_puts(
+            stale_nodes
+                .iter()
+                .map(|node_key| (*node_key, state_root))
+                .collect(),
+        ))?;
+        self.stale_store.put(state_root, stale_nodes)
+    }
+
+    /// The `node_keys` are written again, so they are not stale anymore.
+    pub fn remove_stale_index(&self, node_keys: Vec<HashValue>) -> Result<()> {
+        self.index_store.delete_all(node_keys)
+    }
+
+    /// Delete the nodes become stale at `state_root` from `node_store`,
+    /// return the count of deleted nodes.
+    pub fn prune(&self, state_root: HashValue, node_store: &StateStorage) -> Result<usize> {
+        let stale_nodes = match self.stale_store.get(state_root)? {
+            Some(stale_nodes) => stale_nodes,
+            None => return Ok(0),
+        };
+        let indexes = self.index_store.multiple_get(stale_nodes.clone())?;
+        let pruned_nodes: Vec<HashValue> = stale_nodes
+            .into_iter()
+            .zip(indexes)
+            .filter(|(_, index)| index == &Some(state_root))
+            .map(|(node_key, _)| node_key)
+            .collect();
+        let pruned_count = pruned_nodes.len();
+        node_store.delete_all(pruned_nodes.clone())?;
+        self.index_store.delete_all(pruned_nodes)?;
+        self.stale_store.remove(state_root)?;
+        Ok(pruned_count)
+    }
+}
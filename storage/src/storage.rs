@@ -17,6 +17,9 @@ use std::sync::Arc;
 /// Type alias to improve readability.
 pub type ColumnFamilyName = &'static str;
 
+/// The write batches of multiple column families, written atomically by `write_batches`.
+pub type ColumnFamilyBatches = Vec<(ColumnFamilyName, WriteBatch)>;
+
 #[allow(clippy::upper_case_acronyms)]
 pub trait KVStore: Send + Sync {
     fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
@@ -38,6 +41,8 @@ pub trait InnerStore: Send + Sync {
     fn contains_key(&self, prefix_name: &str, key: Vec<u8>) -> Result<bool>;
     fn remove(&self, prefix_name: &str, key: Vec<u8>) -> Result<()>;
     fn write_batch(&self, prefix_name: &str, batch: WriteBatch) -> Result<()>;
+    /// Write the batches of multiple column families atomically.
+    fn write_batches(&self, batches: ColumnFamilyBatches) -> Result<()>;
     fn get_len(&self) -> Result<u64>;
     fn keys(&self) -> Result<Vec<Vec<u8>>>;
 }
@@ -191,6 +196,20 @@ impl InnerStore for StorageInstance {
             }
         }
     }
+    fn write_batches(&self, batches: ColumnFamilyBatches) -> Result<()> {
+        match self {
+            StorageInstance::CACHE { cache } => cache.write_batches(batches),
+            StorageInstance::DB { db } => db.write_batches(batches),
+            StorageInstance::CacheAndDb { cache, db } => {
+                db.write_batches(batches.clone())?;
+                for (prefix_name, batch) in batches {
+                    cache.write_batch_obj(prefix_name, batch)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     fn get_len(&self) -> Result<u64> {
         match self {
             StorageInstance::CACHE { cache } => cache.get_len(),
@@ -236,6 +255,53 @@ where
             cf: PhantomData,
         }
     }
+
+    /// Write the batches of this and other column families of the instance atomically.
+    pub fn write_batches(&self, batches: ColumnFamilyBatches) -> Result<()> {
+        self.instance.write_batches(batches)
+    }
+
+    /// Scan all the raw key value pairs of the column family from db, in key order.
+    pub fn scan(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match &self.instance {
+            StorageInstance::DB { db } | StorageInstance::CacheAndDb { cache: _, db } => {
+                let mut iter = db.iter(self.prefix_name)?;
+                iter.seek_to_first();
+                iter.collect()
+            }
+            _ => bail!("Cache instance not support scan method!"),
+        }
+    }
+
+    /// Scan at most `limit` raw key value pairs of the column family from db, in key order,
+    /// start after the key `after`, or from the first key if it is none.
+    pub fn scan_after(
+        &self,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match &self.instance {
+            StorageInstance::DB { db } | StorageInstance::CacheAndDb { cache: _, db } => {
+                let mut iter = db.iter(self.prefix_name)?;
+                match &after {
+                    Some(key) => iter.seek(key.clone())?,
+                    None => iter.seek_to_first(),
+                }
+                let mut result = vec![];
+                for item in iter {
+                    if result.len() >= limit {
+                        break;
+                    }
+                    let (key, value) = item?;
+                    if Some(&key) != after.as_ref() {
+                        result.push((key, value));
+                    }
+                }
+                Ok(result)
+            }
+            _ => bail!("Cache instance not support scan method!"),
+        }
+    }
 }
 
 impl<CF> KVStore for InnerStorage<CF>
@@ -9,10 +9,10 @@ use crypto::HashValue;
 use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
 use crate::storage::StorageInstance;
-use crate::Storage;
+use crate::{BlockStore, Storage};
 use starcoin_config::RocksdbConfig;
 use starcoin_types::account_address::AccountAddress;
-use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockHeaderExtra};
+use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockHeaderBuilder, BlockHeaderExtra};
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::transaction::SignedUserTransaction;
 use starcoin_uint::U256;
@@ -131,3 +131,42 @@ fn test_block_number() {
     assert!(block2.is_some());
     assert_eq!(block1, block2.unwrap());
 }
+
+#[test]
+fn test_failed_blocks() {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap(),
+    ))
+    .unwrap();
+    for number in 1..4 {
+        let block = Block::new(
+            BlockHeaderBuilder::random().with_number(number).build(),
+            BlockBody::new(vec![SignedUserTransaction::mock()], None),
+        );
+        storage
+            .save_failed_block(block.id(), block, None, format!("failed {}", number))
+            .unwrap();
+    }
+    let failed_blocks = storage.get_failed_blocks(None, 2).unwrap();
+    assert_eq!(failed_blocks.len(), 2);
+    assert!(failed_blocks[0].0.id() < failed_blocks[1].0.id());
+    assert_eq!(
+        failed_blocks[1].2,
+        format!("failed {}", failed_blocks[1].0.header().number())
+    );
+    let block_id = failed_blocks[0].0.id();
+    assert_eq!(
+        storage.get_failed_block_by_id(block_id).unwrap(),
+        Some(failed_blocks[0].clone())
+    );
+    assert_eq!(storage.get_failed_blocks(None, 10).unwrap().len(), 3);
+
+    // the next page starts after the cursor.
+    let next_blocks = storage
+        .get_failed_blocks(Some(failed_blocks[1].0.id()), 2)
+        .unwrap();
+    assert_eq!(next_blocks.len(), 1);
+    assert!(next_blocks[0].0.id() > failed_blocks[1].0.id());
+}
@@ -5,16 +5,25 @@ extern crate chrono;
 
 use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
+use crate::event_index::IndexedEvent;
 use crate::storage::{CodecKVStore, InnerStore, StorageInstance, ValueCodec, CACHE_NONE_OBJECT};
 use crate::{
-    BlockTransactionInfoStore, Storage, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
+    AddressTransactionStore, BlockStore, BlockTransactionInfoStore, ContractEventIndexStore,
+    StatePruneForks, StatePruneStore, Storage, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
     VEC_PREFIX_NAME,
 };
 use anyhow::Result;
 use crypto::HashValue;
 use starcoin_config::RocksdbConfig;
+use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::contract_event::{ContractEvent, EventPosition};
+use starcoin_types::event::EventKey;
+use starcoin_types::language_storage::TypeTag;
+use starcoin_types::startup_info::StartupInfo;
 use starcoin_types::transaction::{BlockTransactionInfo, TransactionInfo};
 use starcoin_types::vm_error::KeptVMStatus;
+use std::ops::Bound;
 
 #[test]
 fn test_reopen() {
@@ -218,3 +227,174 @@ fn test_missing_key_handle() -> Result<()> {
     assert_eq!(contains, false);
     Ok(())
 }
+
+#[test]
+fn test_address_transaction_index() -> Result<()> {
+    let storage = Storage::new(StorageInstance::new_cache_instance())?;
+    let address = AccountAddress::random();
+    let other = AccountAddress::random();
+    let ids: Vec<HashValue> = (0..5).map(|_| HashValue::random()).collect();
+    let entries: Vec<_> = ids
+        .iter()
+        .map(|id| (address, *id))
+        .chain(std::iter::once((other, ids[0])))
+        .collect();
+    storage.append_address_txn_info_ids(entries)?;
+    assert_eq!(storage.get_address_txn_count(address)?, 5);
+    assert_eq!(storage.get_address_txn_count(other)?, 1);
+
+    let latest = storage.get_address_txn_info_ids(address, None, 2)?;
+    assert_eq!(latest, vec![(4, ids[4]), (3, ids[3])]);
+    let next = storage.get_address_txn_info_ids(address, Some(2), 10)?;
+    assert_eq!(next, vec![(2, ids[2]), (1, ids[1]), (0, ids[0])]);
+
+    // rollback must be in the reverse order of append.
+    assert!(storage
+        .rollback_address_txn_info_ids(vec![(address, ids[3])])
+        .is_err());
+    storage.rollback_address_txn_info_ids(vec![(address, ids[4]), (address, ids[3])])?;
+    assert_eq!(storage.get_address_txn_count(address)?, 3);
+    let latest = storage.get_address_txn_info_ids(address, None, 1)?;
+    assert_eq!(latest, vec![(2, ids[2])]);
+    Ok(())
+}
+
+#[test]
+fn test_prune_stale_state_nodes() -> Result<()> {
+    let storage = Storage::new(StorageInstance::new_cache_instance())?.with_state_prune(true);
+    let nodes: Vec<(HashValue, StateNode)> = (0..3)
+        .map(|i| (HashValue::random(), StateNode(vec![i])))
+        .collect();
+    storage.write_nodes(nodes.iter().cloned().collect())?;
+
+    let state_root1 = HashValue::random();
+    let state_root2 = HashValue::random();
+    storage.write_stale_nodes(state_root1, vec![nodes[0].0, nodes[1].0])?;
+    // nodes[1] is created again after it becomes stale, so it should not be pruned.
+    storage.write_nodes(vec![nodes[1].clone()].into_iter().collect())?;
+    storage.write_stale_nodes(state_root2, vec![nodes[2].0])?;
+
+    assert_eq!(storage.prune_stale_state_nodes(state_root1)?, 1);
+    assert!(StateNodeStore::get(&storage, &nodes[0].0)?.is_none());
+    assert!(StateNodeStore::get(&storage, &nodes[1].0)?.is_some());
+    assert!(StateNodeStore::get(&storage, &nodes[2].0)?.is_some());
+    // prune twice is fine.
+    assert_eq!(storage.prune_stale_state_nodes(state_root1)?, 0);
+
+    assert_eq!(storage.prune_stale_state_nodes(state_root2)?, 1);
+    assert!(StateNodeStore::get(&storage, &nodes[2].0)?.is_none());
+
+    assert_eq!(storage.get_state_pruned_number()?, None);
+    storage.save_state_pruned_number(10)?;
+    assert_eq!(storage.get_state_pruned_number()?, Some(10));
+
+    assert!(storage.get_state_prune_forks()?.is_empty());
+    let mut forks = StatePruneForks::new();
+    forks.insert(HashValue::random(), (12, 8));
+    storage.save_state_prune_forks(&forks)?;
+    assert_eq!(storage.get_state_prune_forks()?, forks);
+    Ok(())
+}
+
+#[test]
+fn test_save_startup_info_with_index_batches() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let instance = StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default())?,
+    );
+    let storage = Storage::new(instance)?;
+    let address = AccountAddress::random();
+    let ids: Vec<HashValue> = (0..3).map(|_| HashValue::random()).collect();
+    storage.append_address_txn_info_ids(vec![(address, ids[0]), (address, ids[1])])?;
+
+    // retract ids[1] and enact ids[2], nothing is written until the batches saved.
+    let batches =
+        storage.address_txn_index_batches(vec![(address, ids[1])], vec![(address, ids[2])])?;
+    assert_eq!(
+        storage.get_address_txn_info_ids(address, None, 1)?,
+        vec![(1, ids[1])]
+    );
+    let startup_info = StartupInfo::new(HashValue::random());
+    storage.save_startup_info_with_batches(startup_info.clone(), batches)?;
+    assert_eq!(storage.get_startup_info()?, Some(startup_info));
+    assert_eq!(storage.get_address_txn_count(address)?, 2);
+    assert_eq!(
+        storage.get_address_txn_info_ids(address, None, 2)?,
+        vec![(1, ids[2]), (0, ids[0])]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_stale_state_nodes_not_recorded_in_archive() -> Result<()> {
+    let storage = Storage::new(StorageInstance::new_cache_instance())?;
+    let node_key = HashValue::random();
+    storage.put(node_key, StateNode(vec![1]))?;
+    let state_root = HashValue::random();
+    storage.write_stale_nodes(state_root, vec![node_key])?;
+    assert_eq!(storage.prune_stale_state_nodes(state_root)?, 0);
+    assert!(StateNodeStore::get(&storage, &node_key)?.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_event_index() -> Result<()> {
+    let storage = Storage::new(StorageInstance::new_cache_instance())?;
+    let key1 = EventKey::new_from_address(&AccountAddress::random(), 0);
+    let key2 = EventKey::new_from_address(&AccountAddress::random(), 0);
+    let indexed_event = |event: ContractEvent, block_number: u64, event_index: u32| {
+        let indexed = IndexedEvent::new(
+            EventPosition::new(block_number, 1, event_index),
+            HashValue::random(),
+        );
+        (event, indexed)
+    };
+    // key1 emits a u64 event in every block, key2 emits a bool event in even blocks.
+    let mut blocks = vec![];
+    for block_number in 1..5u64 {
+        let mut events = vec![indexed_event(
+            ContractEvent::new(key1, block_number - 1, TypeTag::U64, vec![]),
+            block_number,
+            0,
+        )];
+        if block_number % 2 == 0 {
+            events.push(indexed_event(
+                ContractEvent::new(key2, block_number / 2 - 1, TypeTag::Bool, vec![]),
+                block_number,
+                1,
+            ));
+        }
+        storage.append_event_index(events.clone())?;
+        blocks.push(events);
+    }
+    let all = (Bound::Unbounded, Bound::Unbounded);
+
+    let latest = storage.get_indexed_events_by_key(&key1, all, true, 2)?;
+    assert_eq!(latest, vec![blocks[3][0].1, blocks[2][0].1]);
+    let range = (
+        Bound::Included(EventPosition::block_start(2)),
+        Bound::Excluded(blocks[3][0].1.position),
+    );
+    let in_range = storage.get_indexed_events_by_key(&key1, range, false, 10)?;
+    assert_eq!(in_range, vec![blocks[1][0].1, blocks[2][0].1]);
+    let by_type = storage.get_indexed_events_by_type(&TypeTag::Bool, all, false, 10)?;
+    assert_eq!(by_type, vec![blocks[1][1].1, blocks[3][1].1]);
+
+    // rollback must be in the reverse order of append.
+    assert!(storage.rollback_event_index(blocks[2].clone()).is_err());
+    let mut last_block = blocks.pop().unwrap();
+    last_block.reverse();
+    storage.rollback_event_index(last_block)?;
+    let latest = storage.get_indexed_events_by_key(&key1, all, true, 1)?;
+    assert_eq!(latest, vec![blocks[2][0].1]);
+    let by_type = storage.get_indexed_events_by_type(&TypeTag::Bool, all, true, 10)?;
+    assert_eq!(by_type, vec![blocks[1][1].1]);
+
+    assert_eq!(storage.get_event_index_start()?, None);
+    storage.save_event_index_start(Some(1))?;
+    assert_eq!(storage.get_event_index_start()?, Some(1));
+    storage.save_event_index_start(None)?;
+    assert_eq!(storage.get_event_index_start()?, None);
+    Ok(())
+}
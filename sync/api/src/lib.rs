@@ -70,6 +70,9 @@ pub struct SyncProgressReport {
     pub target_difficulty: U256,
     pub target_peers: Vec<PeerId>,
     pub current: TaskProgressReport,
+    /// The state download report, only present in fast sync mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_sync: Option<TaskProgressReport>,
 }
 
 impl ServiceRequest for SyncProgressRequest {
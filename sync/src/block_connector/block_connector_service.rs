@@ -150,10 +150,12 @@ impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
                                 );
                             }
 
-                            if let Err(e1) = ctx
-                                .get_shared::<NetworkServiceRef>()
-                                .map(|network| network.report_peer(peer_id, (&e).into()))
-                            {
+                            if let Err(e1) = ctx.get_shared::<NetworkServiceRef>().map(|network| {
+                                network.report_peer(peer_id.clone(), (&e).into());
+                                if let ConnectBlockError::VerifyBlockFailed(..) = &e {
+                                    network.ban_peer(peer_id, format!("{:?}", e));
+                                }
+                            }) {
                                 warn!("Get NetworkServiceRef err: {:?}.", e1);
                             }
                         }
@@ -4,16 +4,18 @@
 use crate::block_connector::WriteBlockChainService;
 use config::NodeConfig;
 use consensus::Consensus;
+use futures::StreamExt;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_chain_service::WriteableChainService;
 use starcoin_genesis::Genesis as StarcoinGenesis;
-use starcoin_service_registry::bus::BusService;
+use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
 use starcoin_storage::Store;
 use starcoin_txpool_mock_service::MockTxPoolService;
 use starcoin_types::block::Block;
 use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::system_events::ChainReorg;
 use starcoin_vm_types::time::TimeService;
 use std::sync::Arc;
 
@@ -217,3 +219,50 @@ async fn test_block_chain_switch_main() {
         2 * times
     );
 }
+
+#[stest::test]
+async fn test_block_chain_switch_main_notify_reorg() {
+    let times = 10;
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
+        .expect("init storage by genesis fail.");
+    let registry = RegistryService::launch();
+    let bus = registry.service_ref::<BusService>().await.unwrap();
+    let mut reorg_receiver = bus.channel::<ChainReorg>().await.unwrap();
+    let mut writeable_block_chain_service = WriteBlockChainService::new(
+        node_config.clone(),
+        StartupInfo::new(chain_info.head().id()),
+        storage,
+        MockTxPoolService::new(),
+        bus,
+    )
+    .unwrap();
+    let net = node_config.net();
+    gen_blocks(
+        times,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let old_head = writeable_block_chain_service
+        .get_main()
+        .current_header()
+        .id();
+
+    gen_fork_block_chain(
+        0,
+        node_config,
+        2 * times,
+        &mut writeable_block_chain_service,
+    );
+
+    let reorg = reorg_receiver.next().await.unwrap();
+    assert_eq!(reorg.ancestor.number(), 0);
+    assert_eq!(reorg.retracted.len() as u64, times);
+    assert_eq!(reorg.retracted[0], old_head);
+    assert!(!reorg.enacted.is_empty());
+    let main = writeable_block_chain_service.get_main();
+    for (i, block_id) in reorg.enacted.iter().enumerate() {
+        let header = main.get_header_by_number(i as u64 + 1).unwrap().unwrap();
+        assert_eq!(header.id(), *block_id);
+    }
+}
@@ -3,19 +3,19 @@
 
 use super::metrics::WRITE_BLOCK_CHAIN_METRICS;
 use anyhow::{format_err, Result};
-use config::NodeConfig;
+use config::{NodeConfig, StatePruneMode};
 use logger::prelude::*;
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, WriteableChainService};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
-use starcoin_storage::Store;
+use starcoin_storage::{StatePruneForks, Store};
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::{
-    block::{Block, BlockHeader, ExecutedBlock},
+    block::{Block, BlockHeader, BlockNumber, ExecutedBlock},
     startup_info::StartupInfo,
-    system_events::{NewBranch, NewHeadBlock},
+    system_events::{ChainReorg, NewBranch, NewHeadBlock},
 };
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::sync::Arc;
@@ -32,6 +32,11 @@ where
     storage: Arc<dyn Store>,
     txpool: P,
     bus: ServiceRef<BusService>,
+    /// The fork branches whose states are kept, only tracked in state prune mode,
+    /// persisted so they survive restarts.
+    forks: StatePruneForks,
+    /// The number of the first block indexed by the event index, None if it is disabled.
+    event_index_start: Option<BlockNumber>,
 }
 
 impl<P> WriteableChainService for WriteBlockChainService<P>
@@ -55,7 +60,18 @@ where
         bus: ServiceRef<BusService>,
     ) -> Result<Self> {
         let net = config.net();
-        let main = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
+        let mut main = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
+        main.set_parallel_execution(config.sync.parallel_execution());
+        let forks = if config.storage.state_prune_mode() == StatePruneMode::Prune {
+            storage.get_state_prune_forks()?
+        } else {
+            StatePruneForks::new()
+        };
+        let event_index_start = BlockChain::init_event_index_start(
+            config.storage.enable_event_index(),
+            main.current_header().number(),
+            storage.as_ref(),
+        )?;
         Ok(Self {
             config,
             startup_info,
@@ -63,6 +79,8 @@ where
             storage,
             txpool,
             bus,
+            forks,
+            event_index_start,
         })
     }
 
@@ -73,27 +91,29 @@ where
             .inc();
         let block_id = header.id();
         let block_exist = self.block_exist(block_id);
-        let block_chain = if block_exist {
+        let fork_from = if block_exist {
             if self.is_main_head(&header.parent_hash()) {
                 None
             } else {
-                let net = self.config.net();
-                Some(BlockChain::new(
-                    net.time_service(),
-                    block_id,
-                    self.storage.clone(),
-                )?)
+                Some(block_id)
             }
         } else if self.block_exist(header.parent_hash()) {
-            let net = self.config.net();
-            Some(BlockChain::new(
-                net.time_service(),
-                header.parent_hash(),
-                self.storage.clone(),
-            )?)
+            Some(header.parent_hash())
         } else {
             None
         };
+        let block_chain = match fork_from {
+            Some(head_block_hash) => {
+                let mut branch = BlockChain::new(
+                    self.config.net().time_service(),
+                    head_block_hash,
+                    self.storage.clone(),
+                )?;
+                branch.set_parallel_execution(self.config.sync.parallel_execution());
+                Some(branch)
+            }
+            None => None,
+        };
         Ok((block_exist, block_chain))
     }
 
@@ -118,12 +138,35 @@ where
             .expect("head block's block info should exist.");
         let executed_block = ExecutedBlock::new(block.clone(), block_info);
         if branch_total_difficulty > main_total_difficulty {
-            let (enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
+            let (enacted_count, enacted_blocks, retracted_count, retracted_blocks, reorg) =
                 if !parent_is_main_head {
-                    self.find_ancestors_from_accumulator(&new_branch)?
+                    let (reorg, enacted_blocks, retracted_blocks) =
+                        self.find_ancestors_from_accumulator(&new_branch)?;
+                    (
+                        reorg.enacted.len() as u64,
+                        enacted_blocks,
+                        reorg.retracted.len() as u64,
+                        retracted_blocks,
+                        Some(reorg),
+                    )
                 } else {
-                    (1, vec![block], 0, vec![])
+                    (1, vec![block], 0, vec![], None)
                 };
+            if self.state_prune_enabled() {
+                let old_head = self.main.current_header();
+                let ancestor_number = block_header.number().saturating_sub(enacted_count);
+                self.forks.remove(&block_header.parent_hash());
+                self.forks.remove(&block_header.id());
+                // the common ancestor of a fork and the new main chain is not after this one.
+                for (_, fork_ancestor_number) in self.forks.values_mut() {
+                    *fork_ancestor_number = (*fork_ancestor_number).min(ancestor_number);
+                }
+                if retracted_count > 0 {
+                    self.forks
+                        .insert(old_head.id(), (old_head.number(), ancestor_number));
+                }
+                self.save_forks();
+            }
             self.main = new_branch;
 
             self.do_new_head(
@@ -132,8 +175,15 @@ where
                 enacted_blocks,
                 retracted_count,
                 retracted_blocks,
+                reorg,
             )?;
         } else {
+            if self.state_prune_enabled() {
+                match self.update_fork(&new_branch) {
+                    Ok(()) => self.save_forks(),
+                    Err(e) => error!("Update fork branch for state prune err: {:?}", e),
+                }
+            }
             //send new branch event
             self.broadcast_new_branch(executed_block);
         }
@@ -147,10 +197,26 @@ where
         enacted_blocks: Vec<Block>,
         retracted_count: u64,
         retracted_blocks: Vec<Block>,
+        reorg: Option<ChainReorg>,
     ) -> Result<()> {
         debug_assert!(!enacted_blocks.is_empty());
         debug_assert_eq!(enacted_blocks.last().unwrap(), executed_block.block());
-        self.update_startup_info(executed_block.header())?;
+        if let Err(e) = self.save_main_head(executed_block.header(), reorg.as_ref()) {
+            // the head is not switched, so reset the main chain to the saved head.
+            let mut main = BlockChain::new(
+                self.config.net().time_service(),
+                self.startup_info.main,
+                self.storage.clone(),
+            )?;
+            main.set_parallel_execution(self.config.sync.parallel_execution());
+            self.main = main;
+            return Err(e);
+        }
+        if self.state_prune_enabled() {
+            if let Err(e) = self.prune_state(executed_block.header().number()) {
+                error!("Prune state err: {:?}", e);
+            }
+        }
         if retracted_count > 0 {
             WRITE_BLOCK_CHAIN_METRICS
                 .rollback_block_size
@@ -166,6 +232,9 @@ where
             .time_service()
             .adjust(GlobalTimeOnChain::new(executed_block.header().timestamp()));
         info!("[chain] Select new head, id: {}, number: {}, total_difficulty: {}, enacted_block_count: {}, retracted_block_count: {}", executed_block.header().id(), executed_block.header().number(), executed_block.block_info().total_difficulty, enacted_count, retracted_count);
+        if let Some(reorg) = reorg.filter(|reorg| !reorg.retracted.is_empty()) {
+            self.broadcast_chain_reorg(reorg);
+        }
         self.broadcast_new_head(executed_block);
         Ok(())
     }
@@ -174,12 +243,111 @@ where
         parent_id == &self.startup_info.main
     }
 
-    fn update_startup_info(&mut self, main_head: &BlockHeader) -> Result<()> {
-        self.startup_info.update_main(main_head);
+    /// Save the startup info of the new main head, and the changes of the enabled address
+    /// transaction index and event index from current main head to `new_head` in the same batch,
+    /// `reorg` is None if `new_head` is a child of current main head.
+    fn save_main_head(&mut self, new_head: &BlockHeader, reorg: Option<&ChainReorg>) -> Result<()> {
+        let (enacted, retracted) = match reorg {
+            Some(reorg) => (reorg.enacted.clone(), reorg.retracted.clone()),
+            None => (vec![new_head.id()], vec![]),
+        };
+        let mut batches = vec![];
+        if self.config.storage.enable_address_index() {
+            batches.extend(BlockChain::address_txn_index_batches(
+                self.storage.as_ref(),
+                &enacted,
+                &retracted,
+            )?);
+        }
+        if let Some(index_start) = self.event_index_start {
+            batches.extend(BlockChain::event_index_batches(
+                self.storage.as_ref(),
+                &enacted,
+                &retracted,
+                index_start,
+            )?);
+        }
+        let mut startup_info = self.startup_info.clone();
+        startup_info.update_main(new_head);
+        self.storage
+            .save_startup_info_with_batches(startup_info.clone(), batches)?;
+        self.startup_info = startup_info;
         WRITE_BLOCK_CHAIN_METRICS
             .current_head_number
-            .set(main_head.number() as i64);
-        self.storage.save_startup_info(self.startup_info.clone())
+            .set(new_head.number() as i64);
+        Ok(())
+    }
+
+    fn state_prune_enabled(&self) -> bool {
+        self.config.storage.state_prune_mode() == StatePruneMode::Prune
+    }
+
+    fn update_fork(&mut self, branch: &BlockChain) -> Result<()> {
+        let head = branch.current_header();
+        let ancestor = self.main.find_ancestor(branch)?.ok_or_else(|| {
+            format_err!(
+                "Can not find ancestors between main chain: {:?} and branch: {:?}",
+                self.main.status(),
+                branch.status()
+            )
+        })?;
+        self.forks.remove(&head.parent_hash());
+        self.forks
+            .insert(head.id(), (head.number(), ancestor.number));
+        Ok(())
+    }
+
+    fn save_forks(&self) {
+        if let Err(e) = self.storage.save_state_prune_forks(&self.forks) {
+            error!("Save fork branches for state prune err: {:?}", e);
+        }
+    }
+
+    /// Prune the stale state nodes of main chain blocks, keep the states of the latest
+    /// `state_prune_window` blocks and the states which the live forks based on.
+    fn prune_state(&mut self, head_number: BlockNumber) -> Result<()> {
+        let window = self.config.storage.state_prune_window();
+        // a fork whose head is out of the window is dead, and a fork whose head has been
+        // enacted to main chain is not a fork any more.
+        let main = &self.main;
+        let fork_count = self.forks.len();
+        self.forks.retain(|fork_head_id, (fork_head_number, _)| {
+            fork_head_number.saturating_add(window) >= head_number
+                && !matches!(main.exist_block(*fork_head_id), Ok(true))
+        });
+        if self.forks.len() != fork_count {
+            self.storage.save_state_prune_forks(&self.forks)?;
+        }
+        let mut prune_to = head_number.saturating_sub(window);
+        if let Some(min_ancestor_number) = self.forks.values().map(|(_, number)| *number).min() {
+            prune_to = prune_to.min(min_ancestor_number);
+        }
+        // the stale nodes are not recorded before prune mode enabled, so start from `prune_to`,
+        // and the blocks before the fast sync pivot have no header or state, skip them.
+        let prune_from = match self.storage.get_state_pruned_number()? {
+            Some(pruned_number) => pruned_number.saturating_add(1),
+            None => prune_to.max(1),
+        };
+        if prune_from > prune_to {
+            return Ok(());
+        }
+        let mut pruned_count = 0;
+        for number in prune_from..=prune_to {
+            let header = match self.main.get_header_by_number(number)? {
+                Some(header) => header,
+                None => continue,
+            };
+            pruned_count = self
+                .storage
+                .prune_stale_state_nodes(header.state_root())?
+                .saturating_add(pruned_count);
+        }
+        self.storage.save_state_pruned_number(prune_to)?;
+        debug!(
+            "Prune state of blocks [{}, {}], pruned state node count: {}",
+            prune_from, prune_to, pruned_count
+        );
+        Ok(())
     }
 
     fn commit_2_txpool(&self, enacted: Vec<Block>, retracted: Vec<Block>) {
@@ -188,10 +356,14 @@ where
         }
     }
 
+    /// Find the reorg from current main chain to `new_branch`, and the latest enacted and
+    /// retracted blocks (at most `MAX_ROLL_BACK_BLOCK` of each) for txpool.
+    /// The block ids of the reorg are read from the block accumulators, so it does not depend on
+    /// the headers before the common ancestor, and it must be called before the main chain switched.
     fn find_ancestors_from_accumulator(
         &self,
         new_branch: &BlockChain,
-    ) -> Result<(u64, Vec<Block>, u64, Vec<Block>)> {
+    ) -> Result<(ChainReorg, Vec<Block>, Vec<Block>)> {
         let ancestor = self.main.find_ancestor(new_branch)?.ok_or_else(|| {
             format_err!(
                 "Can not find ancestors between main chain: {:?} and branch: {:?}",
@@ -200,20 +372,20 @@ where
             )
         })?;
 
-        let ancestor_block = self
-            .main
-            .get_block(ancestor.id)?
-            .ok_or_else(|| format_err!("Can not find block by id:{}", ancestor.id))?;
+        let ancestor_header = self
+            .storage
+            .get_block_header_by_hash(ancestor.id)?
+            .ok_or_else(|| format_err!("Can not find block header by id:{}", ancestor.id))?;
         let enacted_count = new_branch
             .current_header()
             .number()
-            .checked_sub(ancestor_block.header().number())
+            .checked_sub(ancestor.number)
             .ok_or_else(|| format_err!("current_header number should > ancestor_block number."))?;
         let retracted_count = self
             .main
             .current_header()
             .number()
-            .checked_sub(ancestor_block.header().number())
+            .checked_sub(ancestor.number)
             .ok_or_else(|| format_err!("current_header number should > ancestor_block number."))?;
 
         let block_enacted = new_branch.current_header().id();
@@ -223,11 +395,24 @@ where
         let retracted =
             self.find_blocks_until(block_retracted, ancestor.id, MAX_ROLL_BACK_BLOCK)?;
 
+        let reorg = ChainReorg {
+            ancestor: ancestor_header,
+            retracted: self.main.get_block_ids(
+                self.main.current_header().number(),
+                true,
+                retracted_count,
+            )?,
+            enacted: new_branch.get_block_ids(
+                ancestor.number.saturating_add(1),
+                false,
+                enacted_count,
+            )?,
+        };
         debug!(
             "Commit block count:{}, rollback block count:{}",
             enacted_count, retracted_count,
         );
-        Ok((enacted_count, enacted, retracted_count, retracted))
+        Ok((reorg, enacted, retracted))
     }
 
     fn find_blocks_until(
@@ -263,6 +448,12 @@ where
         }
     }
 
+    fn broadcast_chain_reorg(&self, reorg: ChainReorg) {
+        if let Err(e) = self.bus.broadcast(reorg) {
+            error!("Broadcast ChainReorg error: {:?}", e);
+        }
+    }
+
     fn broadcast_new_branch(&self, block: ExecutedBlock) {
         if let Err(e) = self.bus.broadcast(NewBranch(Arc::new(block))) {
             error!("Broadcast NewBranch error: {:?}", e);
@@ -286,7 +477,7 @@ where
                 e
             })?;
             let enacted_blocks = vec![executed_block.block().clone()];
-            self.do_new_head(executed_block, 1, enacted_blocks, 0, vec![])?;
+            self.do_new_head(executed_block, 1, enacted_blocks, 0, vec![], None)?;
             return Ok(());
         }
         let (block_exist, fork) = self.find_or_fork(block.header())?;
@@ -307,7 +498,7 @@ where
             }
             (true, None) => {
                 let executed_block = self.main.update_chain_head(block.clone())?;
-                self.do_new_head(executed_block, 1, vec![block], 0, vec![])?;
+                self.do_new_head(executed_block, 1, vec![block], 0, vec![], None)?;
                 Ok(())
             }
             (false, Some(mut branch)) => {
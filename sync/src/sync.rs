@@ -3,10 +3,12 @@
 
 use crate::block_connector::BlockConnectorService;
 use crate::sync_metrics::SYNC_METRICS;
-use crate::tasks::{full_sync_task, AncestorEvent, SyncFetcher};
+use crate::tasks::{
+    fast_sync_task, full_sync_task, AncestorEvent, SyncFetcher, STATE_NODE_SYNC_TASK_NAME,
+};
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
-use config::NodeConfig;
+use config::{NodeConfig, SyncMode};
 use futures::FutureExt;
 use futures_timer::Delay;
 use logger::prelude::*;
@@ -24,7 +26,7 @@ use starcoin_sync_api::{
     PeerScoreRequest, PeerScoreResponse, SyncCancelRequest, SyncProgressReport,
     SyncProgressRequest, SyncServiceHandler, SyncStartRequest, SyncStatusRequest, SyncTarget,
 };
-use starcoin_types::block::BlockIdAndNumber;
+use starcoin_types::block::{BlockIdAndNumber, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::sync_status::SyncStatus;
@@ -34,6 +36,17 @@ use std::time::Duration;
 use stream_task::{TaskError, TaskEventCounterHandle, TaskHandle};
 
 const REPUTATION_THRESHOLD: i32 = -1000;
+/// The distance between the fast sync pivot block and the sync target.
+const FAST_SYNC_PIVOT_DISTANCE: u64 = 1000;
+
+/// Fast sync only works for a new node, and the target should be far enough.
+fn fast_sync_pivot(current_number: BlockNumber, target_number: BlockNumber) -> Option<BlockNumber> {
+    if current_number == 0 && target_number > FAST_SYNC_PIVOT_DISTANCE {
+        Some(target_number.saturating_sub(FAST_SYNC_PIVOT_DISTANCE))
+    } else {
+        None
+    }
+}
 
 //TODO combine task_handle and task_event_handle in stream_task
 pub struct SyncTaskHandle {
@@ -167,6 +180,11 @@ impl SyncService {
                 .get_startup_info()?
                 .ok_or_else(|| format_err!("Startup info should exist."))?;
             let current_block_id = startup_info.main;
+            let current_block_header = storage
+                .get_block_header_by_hash(current_block_id)?
+                .ok_or_else(|| {
+                    format_err!("Can not find block header by id: {}", current_block_id)
+                })?;
             let current_block_info =
                 storage.get_block_info(current_block_id)?.ok_or_else(|| {
                     format_err!("Can not find block info by id: {}", current_block_id)
@@ -181,18 +199,43 @@ impl SyncService {
             {
                 info!("[sync] Find target({}), total_difficulty:{}, current head({})'s total_difficulty({})", target.target_id.id(), target.block_info.total_difficulty, current_block_id, current_block_info.total_difficulty);
 
-                let (fut, task_handle, task_event_handle) = full_sync_task(
-                    current_block_id,
-                    target.clone(),
-                    skip_pow_verify,
-                    config.net().time_service(),
-                    storage.clone(),
-                    connector_service.clone(),
-                    rpc_client.clone(),
-                    self_ref.clone(),
-                    network.clone(),
-                    config.sync.max_retry_times(),
-                )?;
+                let pivot = match config.sync.sync_mode() {
+                    SyncMode::Fast => {
+                        fast_sync_pivot(current_block_header.number(), target.target_id.number())
+                    }
+                    SyncMode::Full => None,
+                };
+                let (fut, task_handle, task_event_handle) = match pivot {
+                    Some(pivot_number) => {
+                        info!("[sync] Fast sync from pivot block number: {}", pivot_number);
+                        fast_sync_task(
+                            pivot_number,
+                            target.clone(),
+                            skip_pow_verify,
+                            config.sync.parallel_execution(),
+                            config.net().time_service(),
+                            storage.clone(),
+                            connector_service.clone(),
+                            rpc_client.clone(),
+                            self_ref.clone(),
+                            network.clone(),
+                            config.sync.max_retry_times(),
+                        )?
+                    }
+                    None => full_sync_task(
+                        current_block_id,
+                        target.clone(),
+                        skip_pow_verify,
+                        config.sync.parallel_execution(),
+                        config.net().time_service(),
+                        storage.clone(),
+                        connector_service.clone(),
+                        rpc_client.clone(),
+                        self_ref.clone(),
+                        network.clone(),
+                        config.sync.max_retry_times(),
+                    )?,
+                };
 
                 self_ref.notify(SyncBeginEvent {
                     target,
@@ -567,6 +610,11 @@ impl ServiceHandler<Self, SyncProgressRequest> for SyncService {
                     target_difficulty: handle.target.block_info.total_difficulty,
                     target_peers: handle.target.peers.clone(),
                     current: report,
+                    state_sync: handle
+                        .task_event_handle
+                        .get_reports()
+                        .into_iter()
+                        .find(|report| report.task_name == STATE_NODE_SYNC_TASK_NAME),
                 }
             })
         })
@@ -245,7 +245,10 @@ where
                             format!("{:?}", e),
                         )?;
                         if let Some(peer) = peer_id {
-                            self.peer_provider.report_peer(peer, (&e).into());
+                            self.peer_provider.report_peer(peer.clone(), (&e).into());
+                            if let ConnectBlockError::VerifyBlockFailed(..) = &e {
+                                self.peer_provider.ban_peer(peer, format!("{:?}", e));
+                            }
                         }
 
                         Err(e.into())
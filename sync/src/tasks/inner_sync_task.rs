@@ -80,6 +80,7 @@ where
         max_retry_times: u64,
         delay_milliseconds_on_error: u64,
         skip_pow_verify_when_sync: bool,
+        parallel_execution: bool,
     ) -> Result<(BlockChain, TaskHandle), TaskError> {
         let buffer_size = self.target.peers.len();
 
@@ -125,8 +126,9 @@ where
                 self.storage.clone(),
                 1,
             );
-            let chain =
+            let mut chain =
                 BlockChain::new(self.time_service.clone(), ancestor.id, self.storage.clone())?;
+            chain.set_parallel_execution(parallel_execution);
             let block_collector = BlockCollector::new_with_handle(
                 current_block_info.clone(),
                 self.target.clone(),
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::tasks::{
-    BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator, SyncFetcher,
+    AccumulatorNodeFetcher, BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher,
+    PeerOperator, StateNodeFetcher, SyncFetcher,
 };
 use anyhow::{format_err, Context, Result};
 use async_std::task::JoinHandle;
@@ -15,12 +16,14 @@ use network_api::messages::NotificationMessage;
 use network_api::{PeerInfo, PeerSelector, PeerStrategy};
 use network_rpc_core::{NetRpcError, RpcErrorCode};
 use rand::Rng;
-use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, AccumulatorNode, MerkleAccumulator};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::ChainReader;
 use starcoin_chain_mock::MockChain;
 use starcoin_crypto::HashValue;
 use starcoin_network_rpc_api::RPC_INFO;
+use starcoin_state_tree::StateNode;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
@@ -347,3 +350,40 @@ impl BlockInfoFetcher for SyncNodeMocker {
 }
 
 impl SyncFetcher for SyncNodeMocker {}
+
+impl StateNodeFetcher for SyncNodeMocker {
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(PeerId, Option<StateNode>)>> {
+        let result = self.chain().get_storage().get(&node_key);
+        async move {
+            let peer_id = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            Ok((peer_id, result?))
+        }
+        .boxed()
+    }
+}
+
+impl AccumulatorNodeFetcher for SyncNodeMocker {
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(PeerId, AccumulatorNode)>> {
+        let result = self
+            .chain()
+            .get_storage()
+            .get_accumulator_store(accumulator_type)
+            .get_node(node_key);
+        async move {
+            let peer_id = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            let node = result?
+                .ok_or_else(|| format_err!("Can not find accumulator node by {}", node_key))?;
+            Ok((peer_id, node))
+        }
+        .boxed()
+    }
+}
@@ -4,7 +4,7 @@
 use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::inner_sync_task::InnerSyncTask;
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
-use anyhow::{format_err, Error, Result};
+use anyhow::{ensure, format_err, Error, Result};
 use futures::channel::mpsc::UnboundedSender;
 use futures::future::BoxFuture;
 use futures::{FutureExt, TryFutureExt};
@@ -12,10 +12,11 @@ use logger::prelude::*;
 use network_api::{PeerProvider, PeerSelector};
 use network_rpc_core::{NetRpcError, RpcErrorCode};
 use starcoin_accumulator::node::AccumulatorStoreType;
-use starcoin_accumulator::MerkleAccumulator;
+use starcoin_accumulator::{AccumulatorNode, MerkleAccumulator};
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceRef};
+use starcoin_state_tree::StateNode;
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
@@ -359,6 +360,69 @@ impl BlockInfoFetcher for VerifiedRpcClient {
     }
 }
 
+pub trait StateNodeFetcher: Send + Sync {
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(PeerId, Option<StateNode>)>>;
+}
+
+impl<T> StateNodeFetcher for Arc<T>
+where
+    T: StateNodeFetcher,
+{
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(PeerId, Option<StateNode>)>> {
+        StateNodeFetcher::fetch_state_node(self.as_ref(), node_key)
+    }
+}
+
+impl StateNodeFetcher for VerifiedRpcClient {
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(PeerId, Option<StateNode>)>> {
+        self.get_state_node_by_node_hash(node_key)
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
+pub trait AccumulatorNodeFetcher: Send + Sync {
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(PeerId, AccumulatorNode)>>;
+}
+
+impl<T> AccumulatorNodeFetcher for Arc<T>
+where
+    T: AccumulatorNodeFetcher,
+{
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(PeerId, AccumulatorNode)>> {
+        AccumulatorNodeFetcher::fetch_accumulator_node(self.as_ref(), node_key, accumulator_type)
+    }
+}
+
+impl AccumulatorNodeFetcher for VerifiedRpcClient {
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(PeerId, AccumulatorNode)>> {
+        self.get_accumulator_node_by_node_hash(node_key, accumulator_type)
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
 impl SyncFetcher for VerifiedRpcClient {}
 
 pub trait BlockLocalStore: Send + Sync {
@@ -506,6 +570,7 @@ mod find_ancestor_task;
 mod inner_sync_task;
 #[cfg(test)]
 pub(crate) mod mock;
+mod state_sync_task;
 pub mod sync_score_metrics;
 #[cfg(test)]
 mod tests;
@@ -514,11 +579,60 @@ use crate::tasks::sync_score_metrics::SYNC_SCORE_METRICS;
 pub use accumulator_sync_task::{AccumulatorCollector, BlockAccumulatorSyncTask};
 pub use block_sync_task::{BlockCollector, BlockSyncTask};
 pub use find_ancestor_task::{AncestorCollector, FindAncestorTask};
+pub use state_sync_task::{
+    StateSyncTask, ACCUMULATOR_NODE_SYNC_TASK_NAME, STATE_NODE_SYNC_TASK_NAME,
+};
 
 pub fn full_sync_task<H, A, F, N>(
     current_block_id: HashValue,
     target: SyncTarget,
     skip_pow_verify: bool,
+    parallel_execution: bool,
+    time_service: Arc<dyn TimeService>,
+    storage: Arc<dyn Store>,
+    block_event_handle: H,
+    fetcher: Arc<F>,
+    ancestor_event_handle: A,
+    peer_provider: N,
+    max_retry_times: u64,
+) -> Result<(
+    BoxFuture<'static, Result<BlockChain, TaskError>>,
+    TaskHandle,
+    Arc<TaskEventCounterHandle>,
+)>
+where
+    H: BlockConnectedEventHandle + Sync + 'static,
+    A: AncestorEventHandle + Sync + 'static,
+    F: SyncFetcher + 'static,
+    N: PeerProvider + Clone + 'static,
+{
+    let event_handle = Arc::new(TaskEventCounterHandle::new());
+    let sync_fut = full_sync_future(
+        current_block_id,
+        target,
+        skip_pow_verify,
+        parallel_execution,
+        time_service,
+        storage,
+        block_event_handle,
+        fetcher,
+        ancestor_event_handle,
+        peer_provider,
+        max_retry_times,
+        event_handle.clone(),
+    )?;
+    let task = TaskFuture::new(sync_fut);
+    let (fut, handle) = task.with_handle();
+    Ok((fut, handle, event_handle))
+}
+
+/// Download the state and accumulators at block `pivot_number` of the `target` chain from peers
+/// instead of executing all blocks before it, then sync blocks from the pivot to `target`.
+pub fn fast_sync_task<H, A, F, N>(
+    pivot_number: BlockNumber,
+    target: SyncTarget,
+    skip_pow_verify: bool,
+    parallel_execution: bool,
     time_service: Arc<dyn TimeService>,
     storage: Arc<dyn Store>,
     block_event_handle: H,
@@ -531,6 +645,68 @@ pub fn full_sync_task<H, A, F, N>(
     TaskHandle,
     Arc<TaskEventCounterHandle>,
 )>
+where
+    H: BlockConnectedEventHandle + Sync + 'static,
+    A: AncestorEventHandle + Sync + 'static,
+    F: SyncFetcher + StateNodeFetcher + AccumulatorNodeFetcher + 'static,
+    N: PeerProvider + Clone + 'static,
+{
+    ensure!(
+        pivot_number < target.target_id.number(),
+        "Pivot block number {} should < target block number {}",
+        pivot_number,
+        target.target_id.number()
+    );
+    let event_handle = Arc::new(TaskEventCounterHandle::new());
+    let event_handle_clone = event_handle.clone();
+    let delay_milliseconds_on_error = 100;
+    let all_fut = async move {
+        let state_sync_task = StateSyncTask::new(
+            storage.clone(),
+            fetcher.clone(),
+            event_handle_clone.clone(),
+            max_retry_times,
+            delay_milliseconds_on_error,
+        );
+        let pivot = state_sync_task.sync_pivot(pivot_number, &target).await?;
+        full_sync_future(
+            pivot.id(),
+            target,
+            skip_pow_verify,
+            parallel_execution,
+            time_service,
+            storage,
+            block_event_handle,
+            fetcher,
+            ancestor_event_handle,
+            peer_provider,
+            max_retry_times,
+            event_handle_clone,
+        )
+        .map_err(TaskError::BreakError)?
+        .await
+    };
+    let task = TaskFuture::new(all_fut.boxed());
+    let (fut, handle) = task.with_handle();
+    Ok((fut, handle, event_handle))
+}
+
+/// Build the future which finds the ancestor with `target` and sync blocks from it,
+/// the progress is reported to `event_handle`.
+fn full_sync_future<H, A, F, N>(
+    current_block_id: HashValue,
+    target: SyncTarget,
+    skip_pow_verify: bool,
+    parallel_execution: bool,
+    time_service: Arc<dyn TimeService>,
+    storage: Arc<dyn Store>,
+    block_event_handle: H,
+    fetcher: Arc<F>,
+    ancestor_event_handle: A,
+    peer_provider: N,
+    max_retry_times: u64,
+    event_handle: Arc<TaskEventCounterHandle>,
+) -> Result<BoxFuture<'static, Result<BlockChain, TaskError>>>
 where
     H: BlockConnectedEventHandle + Sync + 'static,
     A: AncestorEventHandle + Sync + 'static,
@@ -546,8 +722,6 @@ where
         .get_block_info(current_block_id)?
         .ok_or_else(|| format_err!("Can not find block info by id: {}", current_block_id))?;
 
-    let event_handle = Arc::new(TaskEventCounterHandle::new());
-
     let target_block_number = target.target_id.number();
 
     let current_block_accumulator_info = current_block_info.block_accumulator_info.clone();
@@ -639,6 +813,7 @@ where
                     max_retry_times,
                     delay_milliseconds_on_error,
                     skip_pow_verify,
+                    parallel_execution,
                 )
                 .await?;
             let total_time = Instant::now()
@@ -676,9 +851,7 @@ where
         }
         Ok(latest_block_chain)
     };
-    let task = TaskFuture::new(all_fut.boxed());
-    let (fut, handle) = task.with_handle();
-    Ok((fut, handle, event_handle))
+    Ok(all_fut.boxed())
 }
 
 const MAX_BETTER_PEER_SIZE: u64 = 20;
@@ -0,0 +1,630 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tasks::{AccumulatorNodeFetcher, StateNodeFetcher, SyncFetcher};
+use crate::verified_rpc_client::RpcVerifyError;
+use anyhow::{ensure, format_err, Error, Result};
+use forkable_jellyfish_merkle::node_type::Node;
+use forkable_jellyfish_merkle::RawKey;
+use futures::future::try_join_all;
+use futures_timer::Delay;
+use logger::prelude::*;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
+use starcoin_accumulator::inmemory::InMemoryAccumulator;
+use starcoin_accumulator::node::{AccumulatorStoreType, InternalNode};
+use starcoin_accumulator::node_index::NodeIndex;
+use starcoin_accumulator::{Accumulator, AccumulatorNode, AccumulatorProof, MerkleAccumulator};
+use starcoin_crypto::hash::{ACCUMULATOR_PLACEHOLDER_HASH, SPARSE_MERKLE_PLACEHOLDER_HASH};
+use starcoin_crypto::HashValue;
+use starcoin_state_api::AccountStateReader;
+use starcoin_state_tree::StateNode;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::Store;
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::access_path::DataType;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::{Block, BlockHeader, BlockInfo, BlockNumber};
+use starcoin_vm_types::access_path::ModuleName;
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use stream_task::{TaskError, TaskEventHandle};
+
+pub const STATE_NODE_SYNC_TASK_NAME: &str = "StateNodeSyncTask";
+pub const ACCUMULATOR_NODE_SYNC_TASK_NAME: &str = "AccumulatorNodeSyncTask";
+
+/// How many nodes are requested concurrently.
+const NODE_BATCH_SIZE: usize = 32;
+/// How many blocks before the pivot are requested at once.
+const BLOCK_BATCH_SIZE: usize = 10;
+
+/// The tree a state node belongs to, the global state tree's leaves are `AccountState`s,
+/// which point to the account's code tree and resource tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum StateNodeKind {
+    Account,
+    Storage(DataType),
+}
+
+/// Decode the node as a node of tree with key `K`, and verify its hash.
+fn decode_state_node<K: RawKey>(node_key: HashValue, state_node: &StateNode) -> Result<Node<K>> {
+    let node = Node::<K>::decode(state_node.0.as_slice())?;
+    let node_hash = node.hash();
+    ensure!(
+        node_hash == node_key,
+        "State node hash {:?} and node key {:?} mismatch.",
+        node_hash,
+        node_key
+    );
+    Ok(node)
+}
+
+fn state_node_children(
+    node_key: HashValue,
+    state_node: &StateNode,
+    kind: StateNodeKind,
+) -> Result<Vec<(HashValue, StateNodeKind)>> {
+    let internal_children = |children: Vec<HashValue>| -> Vec<(HashValue, StateNodeKind)> {
+        children.into_iter().map(|child| (child, kind)).collect()
+    };
+    Ok(match kind {
+        StateNodeKind::Account => {
+            match decode_state_node::<AccountAddress>(node_key, state_node)? {
+                Node::Internal(internal) => internal_children(internal.all_child()),
+                Node::Leaf(leaf) => {
+                    let account_state = AccountState::try_from(leaf.blob().as_ref())?;
+                    let mut children = vec![];
+                    for (idx, storage_root) in account_state.storage_roots().iter().enumerate() {
+                        if let Some(storage_root) = storage_root {
+                            let data_type = DataType::from_index(idx as u8)?;
+                            children.push((*storage_root, StateNodeKind::Storage(data_type)));
+                        }
+                    }
+                    children
+                }
+                Node::Null => vec![],
+            }
+        }
+        StateNodeKind::Storage(DataType::CODE) => {
+            match decode_state_node::<ModuleName>(node_key, state_node)? {
+                Node::Internal(internal) => internal_children(internal.all_child()),
+                _ => vec![],
+            }
+        }
+        StateNodeKind::Storage(DataType::RESOURCE) => {
+            match decode_state_node::<StructTag>(node_key, state_node)? {
+                Node::Internal(internal) => internal_children(internal.all_child()),
+                _ => vec![],
+            }
+        }
+    })
+}
+
+fn accumulator_node_children(node: &AccumulatorNode) -> Vec<HashValue> {
+    match node {
+        AccumulatorNode::Internal(internal) => vec![internal.left(), internal.right()]
+            .into_iter()
+            .filter(|hash| hash != &*ACCUMULATOR_PLACEHOLDER_HASH)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Compute the root of the accumulator which consists of the leaves before and at `leaf_index`,
+/// by the `proof` of the leaf in a larger accumulator.
+fn prefix_accumulator_root(
+    leaf: HashValue,
+    leaf_index: u64,
+    proof: &AccumulatorProof,
+) -> HashValue {
+    let root_level = NodeIndex::root_level_from_leaf_count(leaf_index.saturating_add(1));
+    let mut node_index = NodeIndex::from_leaf_index(leaf_index);
+    let mut node_hash = leaf;
+    for sibling in proof.siblings().iter().take(root_level as usize) {
+        // The leaf is the last one, so the right side of it is always empty.
+        node_hash = if node_index.is_left_child() {
+            InternalNode::new(
+                node_index.parent(),
+                node_hash,
+                *ACCUMULATOR_PLACEHOLDER_HASH,
+            )
+            .hash()
+        } else {
+            InternalNode::new(node_index.parent(), *sibling, node_hash).hash()
+        };
+        node_index = node_index.parent();
+    }
+    node_hash
+}
+
+/// Download the state tree and accumulators of a block node by node from peers.
+/// Nodes already in local storage are not requested again, but their children are still visited,
+/// so an interrupted sync can be resumed from the same root.
+pub struct StateSyncTask<F> {
+    storage: Arc<dyn Store>,
+    fetcher: Arc<F>,
+    event_handle: Arc<dyn TaskEventHandle>,
+    max_retry_times: u64,
+    delay_milliseconds_on_error: u64,
+}
+
+impl<F> StateSyncTask<F>
+where
+    F: StateNodeFetcher + AccumulatorNodeFetcher + 'static,
+{
+    pub fn new(
+        storage: Arc<dyn Store>,
+        fetcher: Arc<F>,
+        event_handle: Arc<dyn TaskEventHandle>,
+        max_retry_times: u64,
+        delay_milliseconds_on_error: u64,
+    ) -> Self {
+        Self {
+            storage,
+            fetcher,
+            event_handle,
+            max_retry_times,
+            delay_milliseconds_on_error,
+        }
+    }
+
+    /// Download the global state tree with root `state_root`, and every account's storage trees.
+    pub async fn sync_state(&self, state_root: HashValue) -> Result<(), TaskError> {
+        self.event_handle
+            .on_start(STATE_NODE_SYNC_TASK_NAME.to_string(), None);
+        let mut pending = vec![];
+        if state_root != *SPARSE_MERKLE_PLACEHOLDER_HASH {
+            pending.push((state_root, StateNodeKind::Account));
+        }
+        while !pending.is_empty() {
+            let batch = pending.split_off(pending.len().saturating_sub(NODE_BATCH_SIZE));
+            self.event_handle.on_sub_task();
+            let results = try_join_all(
+                batch
+                    .into_iter()
+                    .map(|(node_key, kind)| self.get_state_node(node_key, kind)),
+            )
+            .await?;
+            let mut new_nodes = BTreeMap::new();
+            for (node_key, node, is_new, children) in results {
+                if is_new {
+                    new_nodes.insert(node_key, node);
+                }
+                pending.extend(children);
+                self.event_handle.on_item();
+            }
+            if !new_nodes.is_empty() {
+                self.storage
+                    .write_nodes(new_nodes)
+                    .map_err(TaskError::BreakError)?;
+            }
+        }
+        self.event_handle
+            .on_finish(STATE_NODE_SYNC_TASK_NAME.to_string());
+        Ok(())
+    }
+
+    /// Download all nodes of the accumulator described by `accumulator_info`.
+    pub async fn sync_accumulator(
+        &self,
+        accumulator_info: &AccumulatorInfo,
+        accumulator_type: AccumulatorStoreType,
+    ) -> Result<(), TaskError> {
+        let task_name = format!(
+            "{}({:?})",
+            ACCUMULATOR_NODE_SYNC_TASK_NAME, accumulator_type
+        );
+        self.event_handle
+            .on_start(task_name.clone(), Some(accumulator_info.num_nodes));
+        let store = self.storage.get_accumulator_store(accumulator_type);
+        let mut pending = vec![];
+        if accumulator_info.num_leaves > 0 {
+            pending.push(accumulator_info.accumulator_root);
+        }
+        while !pending.is_empty() {
+            let batch = pending.split_off(pending.len().saturating_sub(NODE_BATCH_SIZE));
+            self.event_handle.on_sub_task();
+            let local_nodes = store
+                .multiple_get(batch.clone())
+                .map_err(TaskError::BreakError)?;
+            let results = try_join_all(batch.into_iter().zip(local_nodes).map(
+                |(node_key, local_node)| async move {
+                    match local_node {
+                        Some(node) => Ok((node, false)),
+                        None => self
+                            .retry(|| {
+                                self.fetcher
+                                    .fetch_accumulator_node(node_key, accumulator_type)
+                            })
+                            .await
+                            .map(|(_peer_id, node)| (node, true)),
+                    }
+                },
+            ))
+            .await?;
+            let mut new_nodes = vec![];
+            for (node, is_new) in results {
+                pending.extend(accumulator_node_children(&node));
+                if is_new {
+                    new_nodes.push(node);
+                }
+                self.event_handle.on_item();
+            }
+            if !new_nodes.is_empty() {
+                store.save_nodes(new_nodes).map_err(TaskError::BreakError)?;
+            }
+        }
+        self.event_handle.on_finish(task_name);
+        Ok(())
+    }
+
+    /// Download the nodes on the path from the root of the accumulator described by
+    /// `accumulator_info` to the leaf at `leaf_index`, which are enough to get the leaf and its proof.
+    async fn sync_accumulator_path(
+        &self,
+        accumulator_info: &AccumulatorInfo,
+        accumulator_type: AccumulatorStoreType,
+        leaf_index: u64,
+    ) -> Result<(), TaskError> {
+        let store = self.storage.get_accumulator_store(accumulator_type);
+        let leaf = NodeIndex::from_leaf_index(leaf_index);
+        let mut node_index = NodeIndex::root_from_leaf_count(accumulator_info.num_leaves);
+        let mut node_key = accumulator_info.accumulator_root;
+        while !node_index.is_leaf() {
+            let (node, is_new) = match store.get_node(node_key).map_err(TaskError::BreakError)? {
+                Some(node) => (node, false),
+                None => self
+                    .retry(|| {
+                        self.fetcher
+                            .fetch_accumulator_node(node_key, accumulator_type)
+                    })
+                    .await
+                    .map(|(_peer_id, node)| (node, true))?,
+            };
+            let internal = match &node {
+                AccumulatorNode::Internal(internal) if internal.index() == node_index => {
+                    internal.clone()
+                }
+                _ => {
+                    return Err(TaskError::BreakError(format_err!(
+                        "Expect accumulator internal node {} at {:?}, but got {:?}",
+                        node_key,
+                        node_index,
+                        node
+                    )))
+                }
+            };
+            if is_new {
+                store.save_node(node).map_err(TaskError::BreakError)?;
+            }
+            if leaf.to_inorder_index() < node_index.to_inorder_index() {
+                node_index = node_index.left_child();
+                node_key = internal.left();
+            } else {
+                node_index = node_index.right_child();
+                node_key = internal.right();
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the state node from local storage or peers, return the node, whether it is new,
+    /// and its children.
+    async fn get_state_node(
+        &self,
+        node_key: HashValue,
+        kind: StateNodeKind,
+    ) -> Result<(HashValue, StateNode, bool, Vec<(HashValue, StateNodeKind)>), TaskError> {
+        if let Some(node) = self.storage.get(&node_key).map_err(TaskError::BreakError)? {
+            let children =
+                state_node_children(node_key, &node, kind).map_err(TaskError::BreakError)?;
+            return Ok((node_key, node, false, children));
+        }
+        let (peer_id, node) = self
+            .retry(|| self.fetcher.fetch_state_node(node_key))
+            .await?;
+        let node = node.ok_or_else(|| {
+            TaskError::BreakError(format_err!(
+                "State node is none by node key {:?}, peer: {:?}.",
+                node_key,
+                peer_id
+            ))
+        })?;
+        let children = state_node_children(node_key, &node, kind).map_err(|e| {
+            TaskError::BreakError(RpcVerifyError::new(peer_id, format!("{:?}", e)).into())
+        })?;
+        Ok((node_key, node, true, children))
+    }
+
+    async fn retry<T, Fut, M>(&self, fetch: M) -> Result<T, TaskError>
+    where
+        M: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retry_times = 0u64;
+        loop {
+            match fetch().await {
+                Ok(result) => {
+                    self.event_handle.on_ok();
+                    return Ok(result);
+                }
+                Err(e) => {
+                    self.event_handle.on_error();
+                    let e: Error = match e.downcast::<TaskError>() {
+                        Ok(task_err) => return Err(task_err),
+                        Err(e) => e,
+                    };
+                    if retry_times >= self.max_retry_times {
+                        return Err(TaskError::RetryLimitReached(retry_times as usize, e));
+                    }
+                    retry_times = retry_times.saturating_add(1);
+                    debug!(
+                        "[sync] Fetch node failed, retry times: {}, error: {:?}",
+                        retry_times, e
+                    );
+                    self.event_handle.on_retry();
+                    Delay::new(Duration::from_millis(
+                        self.delay_milliseconds_on_error.saturating_mul(retry_times),
+                    ))
+                    .await;
+                }
+            }
+        }
+    }
+}
+
+impl<F> StateSyncTask<F>
+where
+    F: SyncFetcher + StateNodeFetcher + AccumulatorNodeFetcher + 'static,
+{
+    /// Download the block at `pivot_number` of the `target` chain with its state and accumulators,
+    /// and the blocks before it which are required to build a chain from the pivot,
+    /// return the pivot block header.
+    /// The uncles of the blocks after the pivot are verified on the chain of their parents,
+    /// which requires the parents' state, so the pivot is moved to the block before the start of
+    /// its epoch, every uncle's parent is then either the pivot or executed after it.
+    pub async fn sync_pivot(
+        &self,
+        pivot_number: BlockNumber,
+        target: &SyncTarget,
+    ) -> Result<BlockHeader, TaskError> {
+        let (pivot_block, pivot_block_info) = self.fetch_pivot(pivot_number, target).await?;
+        let epoch = self.sync_pivot_state(pivot_block.header()).await?;
+        let epoch_pivot_number = epoch.start_block_number().saturating_sub(1);
+        let (pivot_block, pivot_block_info, epoch) = if epoch_pivot_number < pivot_number {
+            info!(
+                "[sync] Move the fast sync pivot from {} to {}, the block before epoch start.",
+                pivot_number, epoch_pivot_number
+            );
+            // The state nodes shared with the previous pivot are not downloaded again.
+            let (pivot_block, pivot_block_info) =
+                self.fetch_pivot(epoch_pivot_number, target).await?;
+            let epoch = self.sync_pivot_state(pivot_block.header()).await?;
+            (pivot_block, pivot_block_info, epoch)
+        } else {
+            (pivot_block, pivot_block_info, epoch)
+        };
+        let pivot_id = pivot_block.id();
+        let pivot_number = pivot_block.header().number();
+
+        self.sync_accumulator(
+            pivot_block_info.get_block_accumulator_info(),
+            AccumulatorStoreType::Block,
+        )
+        .await?;
+        self.sync_accumulator(
+            pivot_block_info.get_txn_accumulator_info(),
+            AccumulatorStoreType::Transaction,
+        )
+        .await?;
+
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            pivot_block_info.get_block_accumulator_info().clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let leaf = block_accumulator
+            .get_leaf(pivot_number)
+            .map_err(TaskError::BreakError)?;
+        if leaf != Some(pivot_id) {
+            return Err(TaskError::BreakError(format_err!(
+                "Pivot block {} mismatch with the block accumulator leaf {:?}",
+                pivot_id,
+                leaf
+            )));
+        }
+
+        // The chain at pivot requires the blocks of current epoch to verify uncles,
+        // and the blocks in the difficulty window to calculate the next block's difficulty.
+        let start_number = std::cmp::min(
+            epoch.start_block_number(),
+            pivot_number
+                .saturating_add(1)
+                .saturating_sub(epoch.block_difficulty_window()),
+        );
+        let block_ids = block_accumulator
+            .get_leaves(
+                start_number,
+                false,
+                pivot_number.saturating_sub(start_number),
+            )
+            .map_err(TaskError::BreakError)?;
+        for block_ids in block_ids.chunks(BLOCK_BATCH_SIZE) {
+            let blocks = self
+                .retry(|| self.fetcher.fetch_blocks(block_ids.to_vec()))
+                .await?;
+            for (block_id, (block, _)) in block_ids.iter().zip(blocks) {
+                if block.id() != *block_id {
+                    return Err(TaskError::BreakError(format_err!(
+                        "Expect block {}, but got block {}",
+                        block_id,
+                        block.id()
+                    )));
+                }
+                self.storage
+                    .commit_block(block)
+                    .map_err(TaskError::BreakError)?;
+            }
+        }
+        let pivot_header = pivot_block.header().clone();
+        self.storage
+            .commit_block(pivot_block)
+            .map_err(TaskError::BreakError)?;
+        self.storage
+            .save_block_info(pivot_block_info)
+            .map_err(TaskError::BreakError)?;
+        Ok(pivot_header)
+    }
+
+    /// Fetch the block at `pivot_number` of the `target` chain and its block info.
+    /// The block id is proved by the `target` block accumulator before anything is trusted.
+    async fn fetch_pivot(
+        &self,
+        pivot_number: BlockNumber,
+        target: &SyncTarget,
+    ) -> Result<(Block, BlockInfo), TaskError> {
+        let target_accumulator_info = target.block_info.get_block_accumulator_info();
+        if pivot_number >= target_accumulator_info.num_leaves {
+            return Err(TaskError::BreakError(format_err!(
+                "Pivot block number {} is out of the target block accumulator, leaves: {}",
+                pivot_number,
+                target_accumulator_info.num_leaves
+            )));
+        }
+        self.sync_accumulator_path(
+            target_accumulator_info,
+            AccumulatorStoreType::Block,
+            pivot_number,
+        )
+        .await?;
+        let target_accumulator = MerkleAccumulator::new_with_info(
+            target_accumulator_info.clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let pivot_id = target_accumulator
+            .get_leaf(pivot_number)
+            .map_err(TaskError::BreakError)?;
+        let proof = target_accumulator
+            .get_proof(pivot_number)
+            .map_err(TaskError::BreakError)?;
+        let (pivot_id, proof) = match (pivot_id, proof) {
+            (Some(pivot_id), Some(proof)) => (pivot_id, proof),
+            _ => {
+                return Err(TaskError::BreakError(format_err!(
+                    "Can not find pivot block by number {} in the target block accumulator",
+                    pivot_number
+                )))
+            }
+        };
+        proof
+            .verify(
+                target_accumulator_info.accumulator_root,
+                pivot_id,
+                pivot_number,
+            )
+            .map_err(TaskError::BreakError)?;
+
+        let (pivot_block, peer) = self
+            .retry(|| self.fetcher.fetch_blocks(vec![pivot_id]))
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                TaskError::BreakError(format_err!("Can not find pivot block by id {}", pivot_id))
+            })?;
+        let peer = peer.or_else(|| target.peers.first().cloned());
+        let pivot_block_info = self
+            .retry(|| self.fetcher.fetch_block_info(peer.clone(), pivot_id))
+            .await?
+            .ok_or_else(|| {
+                TaskError::BreakError(format_err!(
+                    "Can not find pivot block info by id {}",
+                    pivot_id
+                ))
+            })?;
+        Self::verify_pivot(
+            pivot_id,
+            pivot_number,
+            pivot_block.header(),
+            &pivot_block_info,
+            &proof,
+        )
+        .map_err(TaskError::BreakError)?;
+        Ok((pivot_block, pivot_block_info))
+    }
+
+    /// Download the state of the pivot block, return the epoch at the pivot.
+    async fn sync_pivot_state(&self, pivot_header: &BlockHeader) -> Result<Epoch, TaskError> {
+        info!(
+            "[sync] Fast sync from pivot block: {}, number: {}, state_root: {}",
+            pivot_header.id(),
+            pivot_header.number(),
+            pivot_header.state_root()
+        );
+        self.sync_state(pivot_header.state_root()).await?;
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(pivot_header.state_root()),
+        );
+        AccountStateReader::new(&statedb)
+            .get_epoch()
+            .map_err(TaskError::BreakError)
+    }
+
+    fn verify_pivot(
+        pivot_id: HashValue,
+        pivot_number: BlockNumber,
+        pivot_header: &BlockHeader,
+        pivot_block_info: &BlockInfo,
+        proof: &AccumulatorProof,
+    ) -> Result<()> {
+        ensure!(
+            pivot_header.id() == pivot_id && pivot_header.number() == pivot_number,
+            "Expect pivot block {}({}), but got {}({})",
+            pivot_id,
+            pivot_number,
+            pivot_header.id(),
+            pivot_header.number()
+        );
+        ensure!(
+            pivot_block_info.block_id() == &pivot_id,
+            "Expect pivot block {} info, but got block {} info",
+            pivot_id,
+            pivot_block_info.block_id()
+        );
+        ensure!(
+            pivot_block_info.get_txn_accumulator_info().accumulator_root
+                == pivot_header.txn_accumulator_root(),
+            "Pivot block {} txn accumulator root mismatch",
+            pivot_id
+        );
+        let block_accumulator_info = pivot_block_info.get_block_accumulator_info();
+        ensure!(
+            block_accumulator_info.num_leaves == pivot_number.saturating_add(1),
+            "Pivot block {} block accumulator leaves mismatch",
+            pivot_id
+        );
+        ensure!(
+            block_accumulator_info.accumulator_root
+                == prefix_accumulator_root(pivot_id, pivot_number, proof),
+            "Pivot block {} block accumulator root mismatch with the target block accumulator",
+            pivot_id
+        );
+        let frozen_root = InMemoryAccumulator::new(
+            block_accumulator_info.frozen_subtree_roots.clone(),
+            block_accumulator_info.num_leaves,
+        )?
+        .root_hash();
+        ensure!(
+            block_accumulator_info.accumulator_root == frozen_root,
+            "Pivot block {} block accumulator frozen subtree roots mismatch",
+            pivot_id
+        );
+        Ok(())
+    }
+}
@@ -5,8 +5,9 @@
 use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
-    full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
-    BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask, FindAncestorTask, SyncFetcher,
+    fast_sync_task, full_sync_task, AccumulatorCollector, AncestorCollector,
+    BlockAccumulatorSyncTask, BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask,
+    FindAncestorTask, SyncFetcher, STATE_NODE_SYNC_TASK_NAME,
 };
 use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::Context;
@@ -64,6 +65,7 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender_1,
@@ -93,6 +95,7 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender_1,
@@ -116,6 +119,82 @@ pub async fn test_full_sync_new_node() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_fast_sync_new_node() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 10)?;
+    node1.produce_block(120)?;
+
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+
+    let node2 = SyncNodeMocker::new(net2.clone(), 1, 10)?;
+
+    let target = arc_node1.sync_target();
+    let pivot_number = 100;
+
+    let storage = node2.chain().get_storage();
+    let (sender_1, receiver_1) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let (sync_task, _task_handle, task_event_counter) = fast_sync_task(
+        pivot_number,
+        target.clone(),
+        false,
+        false,
+        net2.time_service(),
+        storage.clone(),
+        sender_1,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+    )?;
+    let join_handle = node2.process_block_connect_event(receiver_1).await;
+    let branch = sync_task.await?;
+    let node2 = join_handle.await;
+    let current_block_header = node2.chain().current_header();
+    assert_eq!(branch.current_header().id(), target.target_id.id());
+    assert_eq!(target.target_id.id(), current_block_header.id());
+    assert_eq!(
+        branch.current_header().state_root(),
+        arc_node1.chain().current_header().state_root()
+    );
+
+    // the pivot is moved to the block before its epoch start, the blocks before it are
+    // downloaded, but not executed.
+    let pivot_block = arc_node1
+        .chain()
+        .get_block_by_number(pivot_number)?
+        .expect("block should exist");
+    let epoch_start_number = arc_node1
+        .chain()
+        .fork(pivot_block.id())?
+        .epoch()
+        .start_block_number();
+    assert!(epoch_start_number > 1 && epoch_start_number <= pivot_number);
+    let pivot_parent = arc_node1
+        .chain()
+        .get_block_by_number(epoch_start_number - 2)?
+        .expect("block should exist");
+    assert!(storage.get_block(pivot_parent.id())?.is_some());
+    assert!(storage.get_block_info(pivot_parent.id())?.is_none());
+    let pivot_next = arc_node1
+        .chain()
+        .get_block_by_number(epoch_start_number)?
+        .expect("block should exist");
+    assert!(storage.get_block_info(pivot_next.id())?.is_some());
+
+    let reports = task_event_counter.get_reports();
+    reports
+        .iter()
+        .for_each(|report| debug!("reports: {}", report));
+    assert!(reports
+        .iter()
+        .any(|report| report.task_name == STATE_NODE_SYNC_TASK_NAME));
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_sync_invalid_target() -> Result<()> {
     let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
@@ -141,6 +220,7 @@ pub async fn test_sync_invalid_target() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender_1,
@@ -222,6 +302,7 @@ pub async fn test_full_sync_fork() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender,
@@ -253,6 +334,7 @@ pub async fn test_full_sync_fork() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage,
         sender,
@@ -300,6 +382,7 @@ pub async fn test_full_sync_fork_from_genesis() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender,
@@ -352,6 +435,7 @@ pub async fn test_full_sync_continue() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender,
@@ -385,6 +469,7 @@ pub async fn test_full_sync_continue() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender,
@@ -435,6 +520,7 @@ pub async fn test_full_sync_cancel() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender,
@@ -876,6 +962,7 @@ async fn test_net_rpc_err() -> Result<()> {
         current_block_header.id(),
         target.clone(),
         false,
+        false,
         net2.time_service(),
         storage.clone(),
         sender,
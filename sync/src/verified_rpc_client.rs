@@ -347,11 +347,14 @@ impl VerifiedRpcClient {
             if node_key == accumulator_node_id {
                 Ok((peer_id, accumulator_node))
             } else {
-                Err(format_err!(
-                    "Accumulator node hash {:?} and node key {:?} mismatch.",
-                    accumulator_node_id,
-                    node_key
-                ))
+                Err(RpcVerifyError::new(
+                    peer_id,
+                    format!(
+                        "Accumulator node hash {:?} and node key {:?} mismatch.",
+                        accumulator_node_id, node_key
+                    ),
+                )
+                .into())
             }
         } else {
             Err(format_err!(
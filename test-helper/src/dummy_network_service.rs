@@ -48,6 +48,10 @@ impl PeerProvider for DummyNetworkService {
         info!("report_peer {:?}: reputation: {:?}", peer_id, cost_benefit);
     }
 
+    fn ban_peer(&self, peer_id: PeerId, reason: String) {
+        info!("ban_peer {:?}: reason: {}", peer_id, reason);
+    }
+
     fn reputations(
         &self,
         _reputation_threshold: i32,
@@ -29,6 +29,16 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
         txns: Vec<SignedUserTransaction>,
     ) -> Vec<Result<(), transaction::TransactionError>>;
 
+    /// Add txns created by this node itself, the txns submitted by rpc are not local.
+    /// Local txns have higher priority and bypass the pool limits,
+    /// and are kept in the journal if it is enabled.
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        self.add_txns(txns)
+    }
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -0,0 +1,102 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction journal, keeps the pending transactions of the pool on disk to survive restarts.
+//! The journal is a sequence of BCS encoded `JournalEntry`, every entry is prefixed with its length
+//! as big endian u32. The whole file is rewritten on every rotation.
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use types::transaction::SignedUserTransaction;
+
+/// Max length of a journal entry, to avoid allocating huge buffer on a corrupted file.
+const MAX_ENTRY_LENGTH: usize = 16 * 1024 * 1024;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Is the transaction submitted to this node by rpc.
+    pub local: bool,
+    pub txn: SignedUserTransaction,
+}
+
+#[derive(Clone, Debug)]
+pub struct TxPoolJournal {
+    path: PathBuf,
+}
+
+impl TxPoolJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Load all entries in the journal, return empty if the journal does not exist.
+    /// A truncated tail, which may be caused by a crash during rotation, is ignored.
+    pub fn load(&self) -> Result<Vec<JournalEntry>> {
+        let file = match File::open(self.path.as_path()) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let mut entries = vec![];
+        loop {
+            match read_entry(&mut reader) {
+                Ok(Some(entry)) => entries.push(entry),
+                Ok(None) => break,
+                Err(e) => {
+                    warn!(
+                        "Stop loading txpool journal {:?} at entry {}, err: {}",
+                        self.path,
+                        entries.len(),
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Replace the journal with `entries`, the entries are written to a temp file first,
+    /// then renamed to the journal, so the old journal is kept if the write fails.
+    pub fn rotate(&self, entries: &[JournalEntry]) -> Result<()> {
+        let tmp_path = self.path.with_extension("new");
+        {
+            let mut writer = BufWriter::new(File::create(tmp_path.as_path())?);
+            for entry in entries {
+                let data = bcs_ext::to_bytes(entry)?;
+                writer.write_all(&(data.len() as u32).to_be_bytes())?;
+                writer.write_all(data.as_slice())?;
+            }
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        fs::rename(tmp_path.as_path(), self.path.as_path())?;
+        Ok(())
+    }
+}
+
+fn read_entry<R: Read>(reader: &mut R) -> Result<Option<JournalEntry>> {
+    let mut len_buf = [0u8; 4];
+    match reader.read_exact(&mut len_buf) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_be_bytes(len_buf) as usize;
+    ensure!(
+        len <= MAX_ENTRY_LENGTH,
+        "Invalid journal entry length {}",
+        len
+    );
+    let mut data = vec![0u8; len];
+    reader.read_exact(data.as_mut_slice())?;
+    Ok(Some(bcs_ext::from_bytes(data.as_slice())?))
+}
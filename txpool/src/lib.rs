@@ -12,6 +12,7 @@ extern crate transaction_pool as tx_pool;
 
 use anyhow::{format_err, Result};
 use counters::{TXPOOL_STATUS_GAUGE_VEC, TXPOOL_TXNS_GAUGE};
+use journal::TxPoolJournal;
 use network_api::messages::PeerTransactionsMessage;
 pub use pool::TxStatus;
 use starcoin_config::NodeConfig;
@@ -30,6 +31,7 @@ use types::{
 };
 
 mod counters;
+mod journal;
 mod pool;
 mod pool_client;
 #[cfg(test)]
@@ -41,6 +43,7 @@ pub struct TxPoolActorService {
     inner: Inner,
     new_txs_received: Arc<AtomicBool>,
     sync_status: Option<SyncStatus>,
+    journal: Option<TxPoolJournal>,
 }
 
 impl std::fmt::Debug for TxPoolActorService {
@@ -54,10 +57,17 @@ const PROPAGATE_FOR_BLOCKS: u64 = 4;
 
 impl TxPoolActorService {
     fn new(inner: Inner) -> Self {
+        let pool_config = &inner.node_config.tx_pool;
+        let journal = if pool_config.journal().is_enabled() {
+            Some(TxPoolJournal::new(pool_config.journal_path()))
+        } else {
+            None
+        };
         Self {
             inner,
             sync_status: None,
             new_txs_received: Arc::new(AtomicBool::new(false)),
+            journal,
         }
     }
 
@@ -138,11 +148,56 @@ impl TxPoolActorService {
         }
     }
 }
+impl TxPoolActorService {
+    fn load_journal(&self) -> Result<()> {
+        if let Some(journal) = self.journal.as_ref() {
+            let entries = journal.load()?;
+            let total = entries.len();
+            let imported = self.inner.import_journal(entries);
+            info!(
+                "[txpool] Re-import {} of {} txns from journal {:?}",
+                imported,
+                total,
+                journal.path()
+            );
+        }
+        Ok(())
+    }
+
+    fn rotate_journal(&self) {
+        if let Some(journal) = self.journal.as_ref() {
+            let mode = self.inner.node_config.tx_pool.journal();
+            let entries = self.inner.journal_entries(mode);
+            if let Err(e) = journal.rotate(entries.as_slice()) {
+                error!(
+                    "[txpool] Fail to rotate journal {:?}: {}",
+                    journal.path(),
+                    e
+                );
+            } else {
+                debug!("[txpool] Journal {} txns", entries.len());
+            }
+        }
+    }
+}
+
 impl ActorService for TxPoolActorService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<SyncStatusChangeEvent>();
         ctx.add_stream(self.inner.subscribe_txns());
 
+        // re-import journaled txns before the journal is rotated.
+        if let Err(e) = self.load_journal() {
+            error!("[txpool] Fail to load journal: {}", e);
+        }
+        if self.journal.is_some() {
+            let myself = self.clone();
+            let interval = self.inner.node_config.tx_pool.journal_interval();
+            ctx.run_interval(Duration::from_secs(interval), move |_ctx| {
+                myself.rotate_journal()
+            });
+        }
+
         // every x seconds, we tick a txn propagation.
         let myself = self.clone();
         let interval = self.inner.node_config.tx_pool.tx_propagate_interval();
@@ -155,6 +210,7 @@ impl ActorService for TxPoolActorService {
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<SyncStatusChangeEvent>();
+        self.rotate_journal();
         Ok(())
     }
 }
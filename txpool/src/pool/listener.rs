@@ -99,7 +99,7 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
         Self::log_status(tx, TxStatus::Added);
         if let Some(old) = old {
-            Self::log_status(old, TxStatus::Dropped);
+            Self::log_status(old, TxStatus::Replaced);
         }
     }
 
@@ -180,8 +180,11 @@ impl fmt::Debug for TransactionsPoolNotifier {
 }
 
 impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
-    fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
+    fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
         self.tx_statuses.push((tx.hash, TxStatus::Added));
+        if let Some(old) = old {
+            self.tx_statuses.push((old.hash, TxStatus::Replaced));
+        }
     }
 
     fn rejected<H: fmt::Debug + fmt::LowerHex>(
@@ -5,7 +5,8 @@
 
 use super::{
     client, listener, local_transactions::LocalTransactionsList, ready, replace, scoring, verifier,
-    PendingOrdering, PendingSettings, PrioritizationStrategy, SeqNumber, TxStatus,
+    PendingOrdering, PendingSettings, PrioritizationStrategy, ScoredTransaction, SeqNumber,
+    TxStatus,
 };
 use crate::pool::ready::Expiration;
 use crate::{pool, pool::PoolTransaction};
@@ -227,17 +228,19 @@ pub struct TransactionQueue {
 
 impl TransactionQueue {
     /// Create new queue with given pool limits and initial verification options.
+    /// `gas_price_bump` is the minimal gas price bump in percent to replace a transaction with the same (sender, nonce).
     pub fn new(
         limits: tx_pool::Options,
         verification_options: verifier::Options,
         strategy: PrioritizationStrategy,
+        gas_price_bump: u64,
     ) -> Self {
         let max_count = limits.max_count;
         TransactionQueue {
             insertion_id: Default::default(),
             pool: RwLock::new(tx_pool::Pool::new(
                 Default::default(),
-                scoring::SeqNumberAndGasPrice(strategy),
+                scoring::SeqNumberAndGasPrice::new(strategy, gas_price_bump),
                 limits,
             )),
             options: RwLock::new(verification_options),
@@ -321,10 +324,18 @@ impl TransactionQueue {
             let imported = verifier
                 .verify_transaction(transaction)
                 .and_then(|verified| {
-                    self.pool
-                        .write()
-                        .import(verified, &replace)
-                        .map_err(convert_error)
+                    let new_gas_price = verified.gas_price();
+                    let mut pool = self.pool.write();
+                    pool.import(verified, &replace).map_err(|err| match err {
+                        // report the gas price of the replaced transaction.
+                        tx_pool::Error::TooCheapToReplace(old_hash, _) => {
+                            transaction::TransactionError::TooCheapToReplace {
+                                prev: pool.find(&old_hash).map(|old| old.gas_price()),
+                                new: Some(new_gas_price),
+                            }
+                        }
+                        err => convert_error(err),
+                    })
                 });
 
             results.push(match imported {
@@ -359,6 +370,12 @@ impl TransactionQueue {
             .collect()
     }
 
+    /// Returns all transactions in the pool, include the future ones, ordered by sequence number of every sender.
+    pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
+        let ready = |_tx: &pool::VerifiedTransaction| tx_pool::Readiness::Ready;
+        self.pool.read().unordered_pending(ready).collect()
+    }
+
     /// Returns current pending transactions ordered by priority.
     ///
     /// NOTE: This may return a cached version of pending transaction set.
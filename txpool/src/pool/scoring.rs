@@ -2,19 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::cmp;
+use std::convert::TryFrom;
 
-use super::{GasPrice, PrioritizationStrategy, ScoredTransaction, VerifiedTransaction};
+use super::{
+    GasPrice, PoolTransaction, PrioritizationStrategy, Priority, ScoredTransaction,
+    VerifiedTransaction,
+};
 use tx_pool::{self, scoring};
-/// Transaction with the same (sender, nonce) can be replaced only if
-/// `new_gas_price >= old_gas_price + old_gas_price >> SHIFT`
-const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
 
-/// TODO: re-consider this, after we settle down the unit of gas price.
-/// Calculate minimal gas price requirement.
+/// Calculate minimal gas price requirement to replace a transaction with the same (sender, nonce).
+/// The gas price should be bumped by at least 1, even if `bump_percent` is 0,
+/// to avoid the pool churning with transactions of the same gas price.
 #[inline]
-fn bump_gas_price(old_gp: GasPrice) -> GasPrice {
-    old_gp
-    // old_gp.saturating_add(old_gp >> GAS_PRICE_BUMP_SHIFT as u64)
+fn bump_gas_price(old_gp: GasPrice, bump_percent: u64) -> GasPrice {
+    let bump = u128::from(old_gp) * u128::from(bump_percent) / 100;
+    let bump = GasPrice::try_from(bump).unwrap_or(GasPrice::MAX);
+    old_gp.saturating_add(cmp::max(bump, 1))
 }
 
 /// Simple, gas-price based scoring for transactions.
@@ -22,31 +25,37 @@ fn bump_gas_price(old_gp: GasPrice) -> GasPrice {
 /// NOTE: Currently penalization does not apply to new transactions that enter the pool.
 /// We might want to store penalization status in some persistent state.
 #[derive(Debug, Clone)]
-pub struct SeqNumberAndGasPrice(pub PrioritizationStrategy);
+pub struct SeqNumberAndGasPrice {
+    strategy: PrioritizationStrategy,
+    /// Transaction with the same (sender, nonce) can be replaced only if
+    /// `new_gas_price >= old_gas_price + old_gas_price * gas_price_bump / 100`
+    gas_price_bump: u64,
+}
 
 impl SeqNumberAndGasPrice {
+    pub fn new(strategy: PrioritizationStrategy, gas_price_bump: u64) -> Self {
+        Self {
+            strategy,
+            gas_price_bump,
+        }
+    }
+
     /// Decide if the transaction should even be considered into the pool (if the pool is full).
     ///
     /// Used by Verifier to quickly reject transactions that don't have any chance to get into the pool later on,
     /// and save time on more expensive checks like sender recovery, etc.
     ///
-    /// NOTE The method is never called for zero-gas-price transactions or local transactions
+    /// NOTE The method is never called for local transactions
     /// (such transactions are always considered to the pool and potentially rejected later on)
-    pub fn should_reject_early(&self, _old: &VerifiedTransaction) -> bool {
-        todo!()
+    pub fn should_reject_early(&self, old: &VerifiedTransaction, new: &PoolTransaction) -> bool {
+        let new_priority = if new.is_retracted() {
+            Priority::Retracted
+        } else {
+            Priority::Regular
+        };
+        // keep sync with the score comparison in `ReplaceByScoreAndReadiness`.
+        (old.priority(), old.gas_price()) >= (new_priority, new.gas_price())
     }
-
-    //    pub fn should_reject_early(
-    //        &self,
-    //        old: &VerifiedTransaction,
-    //        new: &verifier::Transaction,
-    //    ) -> bool {
-    //        if old.priority().is_local() {
-    //            return true;
-    //        }
-    //
-    //        &old.transaction.gas_price > new.gas_price()
-    //    }
 }
 
 impl<P> tx_pool::Scoring<P> for SeqNumberAndGasPrice
@@ -68,7 +77,7 @@ where
         let old_gp = old.gas_price();
         let new_gp = new.gas_price();
 
-        let min_required_gp = bump_gas_price(old_gp);
+        let min_required_gp = bump_gas_price(old_gp, self.gas_price_bump);
 
         match min_required_gp.cmp(&new_gp) {
             cmp::Ordering::Greater => scoring::Choice::RejectNew,
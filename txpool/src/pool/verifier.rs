@@ -9,8 +9,8 @@
 //! May have some overlap with `Readiness` since we don't want to keep around
 //! stalled transactions.
 use crate::pool::{
-    client::Client, scoring, PoolTransaction, Priority, UnverifiedUserTransaction,
-    VerifiedTransaction,
+    client::Client, scoring, PoolTransaction, Priority, ScoredTransaction,
+    UnverifiedUserTransaction, VerifiedTransaction,
 };
 use std::sync::{atomic::AtomicUsize, Arc};
 use types::transaction;
@@ -80,6 +80,18 @@ impl<C: Client> tx_pool::Verifier<PoolTransaction>
         }
         let hash = tx.hash();
         let is_local_txn = tx.is_local();
+        // check early if the pool is full, before the expensive verification.
+        if !is_local_txn {
+            if let Some((ref scoring, ref worst)) = self.transaction_to_replace {
+                if scoring.should_reject_early(worst, &tx) {
+                    debug!(target: "txqueue", "[{:?}] Rejected tx early, pool is full", hash);
+                    return Err(transaction::TransactionError::TooCheapToReplace {
+                        prev: Some(worst.gas_price()),
+                        new: Some(tx.gas_price()),
+                    });
+                }
+            }
+        }
         let is_retracted = tx.is_retracted();
         let verified_txn = match tx {
             PoolTransaction::Unverified(unverified) | PoolTransaction::Retracted(unverified) => {
@@ -1,18 +1,20 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::journal::TxPoolJournal;
 use crate::pool::AccountSeqNumberClient;
-use crate::TxStatus;
+use crate::{TxPoolService, TxStatus};
 use anyhow::Result;
 use crypto::keygen::KeyGen;
 use network_api::messages::{PeerTransactionsMessage, TransactionsMessage};
 use network_api::PeerId;
 use parking_lot::RwLock;
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, TxPoolJournalMode};
 use starcoin_executor::{
     create_signed_txn_with_association_account, encode_transfer_script_function,
     DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
 };
+use starcoin_genesis::Genesis;
 use starcoin_open_block::OpenedBlock;
 use starcoin_state_api::ChainStateWriter;
 use starcoin_statedb::ChainStateDB;
@@ -25,7 +27,7 @@ use types::{
     account_address::{self, AccountAddress},
     account_config,
     transaction::authenticator::AuthenticationKey,
-    transaction::{SignedUserTransaction, Transaction, TransactionPayload},
+    transaction::{SignedUserTransaction, Transaction, TransactionError, TransactionPayload},
     U256,
 };
 
@@ -250,7 +252,109 @@ async fn test_txpool_actor_service() {
     delay_for(Duration::from_millis(300)).await;
 }
 
+#[stest::test]
+async fn test_txpool_journal() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_journal(TxPoolJournalMode::Local);
+    let config = Arc::new(config);
+    let (storage, _, genesis) = Genesis::init_storage_for_test(config.net())?;
+    let header = genesis.block().header().clone();
+    let txpool_service = TxPoolService::new(config.clone(), storage.clone(), header.clone());
+    let local_txn = generate_txn(config.clone(), 0);
+    let remote_txn = generate_txn(config.clone(), 1);
+    txpool_service
+        .add_local_txns(vec![local_txn.clone()])
+        .pop()
+        .unwrap()?;
+    txpool_service.add_txns(vec![remote_txn]).pop().unwrap()?;
+
+    let inner = txpool_service.get_inner();
+    assert!(inner.journal_entries(TxPoolJournalMode::None).is_empty());
+    assert_eq!(inner.journal_entries(TxPoolJournalMode::All).len(), 2);
+    let entries = inner.journal_entries(TxPoolJournalMode::Local);
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].local);
+    assert_eq!(entries[0].txn, local_txn);
+
+    let journal = TxPoolJournal::new(config.tx_pool.journal_path());
+    journal.rotate(entries.as_slice())?;
+    let txpool_service2 = TxPoolService::new(config, storage, header);
+    assert_eq!(
+        txpool_service2.get_inner().import_journal(journal.load()?),
+        1
+    );
+    assert_eq!(txpool_service2.find_txn(&local_txn.id()), Some(local_txn));
+    Ok(())
+}
+
+#[stest::test]
+async fn test_txn_replace_by_gas_price() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    let txn = generate_txn_with_gas_price(config.clone(), 0, 10);
+    txpool_service.add_txns(vec![txn]).pop().unwrap()?;
+
+    // the default gas price bump is 10%.
+    let same_price_txn = generate_txn_with_gas_price(config.clone(), 0, 10);
+    let err = txpool_service
+        .add_txns(vec![same_price_txn])
+        .pop()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TransactionError::TooCheapToReplace {
+            prev: Some(10),
+            new: Some(10)
+        }
+    );
+
+    let bumped_txn = generate_txn_with_gas_price(config, 0, 11);
+    txpool_service
+        .add_txns(vec![bumped_txn.clone()])
+        .pop()
+        .unwrap()?;
+    let pending_txns = txpool_service.get_pending_txns(None, Some(0));
+    assert_eq!(pending_txns, vec![bumped_txn]);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_txn_reject_early() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool_with_size(2).await;
+    let txns = vec![
+        generate_txn_with_gas_price(config.clone(), 0, 2),
+        generate_txn_with_gas_price(config.clone(), 1, 2),
+    ];
+    for result in txpool_service.add_txns(txns) {
+        result?;
+    }
+    assert!(txpool_service.status().is_full);
+
+    let txn = generate_txn_with_gas_price(config, 2, 1);
+    let err = txpool_service
+        .add_txns(vec![txn])
+        .pop()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TransactionError::TooCheapToReplace {
+            prev: Some(2),
+            new: Some(1)
+        }
+    );
+    Ok(())
+}
+
 fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
+    generate_txn_with_gas_price(config, seq, 1)
+}
+
+fn generate_txn_with_gas_price(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+) -> SignedUserTransaction {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let txn = create_signed_txn_with_association_account(
@@ -262,7 +366,7 @@ fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
         )),
         seq,
         DEFAULT_MAX_GAS_AMOUNT,
-        1,
+        gas_price,
         2,
         config.net(),
     );
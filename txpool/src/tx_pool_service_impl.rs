@@ -3,20 +3,21 @@
 
 use crate::{
     counters::TXPOOL_SERVICE_HISTOGRAM,
+    journal::JournalEntry,
     pool,
     pool::{
-        PendingOrdering, PendingSettings, PoolTransaction, PrioritizationStrategy, Status,
-        TxStatus, UnverifiedUserTransaction, VerifiedTransaction,
+        PendingOrdering, PendingSettings, PoolTransaction, PrioritizationStrategy, Priority,
+        ScoredTransaction, Status, TxStatus, UnverifiedUserTransaction, VerifiedTransaction,
     },
     pool_client::{NonceCache, PoolClient},
 };
 
-use crate::pool::{Client, TransactionQueue};
+use crate::pool::{AccountSeqNumberClient, Client, TransactionQueue};
 use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
 use parking_lot::RwLock;
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, TxPoolJournalMode};
 use starcoin_statedb::ChainStateDB;
 use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
 use std::sync::Arc;
@@ -51,6 +52,7 @@ impl TxPoolService {
             },
             verifier_options,
             PrioritizationStrategy::GasPriceOnly,
+            pool_config.min_gas_price_bump(),
         );
         let queue = Arc::new(queue);
         let inner = Inner {
@@ -98,6 +100,16 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.import_txns(txns)
     }
 
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["add_local_txns"])
+            .start_timer();
+        self.inner.import_local_txns(txns)
+    }
+
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction> {
         let _timer = TXPOOL_SERVICE_HISTOGRAM
             .with_label_values(&["remove_txn"])
@@ -241,6 +253,67 @@ impl Inner {
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
         self.queue.import(self.get_pool_client(), txns)
     }
+    pub(crate) fn import_local_txns(
+        &self,
+        txns: Vec<transaction::SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let txns = txns
+            .into_iter()
+            .map(|t| PoolTransaction::Local(transaction::PendingTransaction::from(t)));
+        self.queue.import(self.get_pool_client(), txns)
+    }
+
+    /// Get the txns to journal, the local txns are always included.
+    pub(crate) fn journal_entries(&self, mode: TxPoolJournalMode) -> Vec<JournalEntry> {
+        if !mode.is_enabled() {
+            return vec![];
+        }
+        self.queue
+            .all_transactions()
+            .into_iter()
+            .filter_map(|t| {
+                let local = t.priority() == Priority::Local;
+                if local || mode == TxPoolJournalMode::All {
+                    Some(JournalEntry {
+                        local,
+                        txn: t.signed().clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Re-import the journaled txns, the txns whose sequence number is already used on chain are discarded.
+    /// Return the count of imported txns.
+    pub(crate) fn import_journal(&self, entries: Vec<JournalEntry>) -> usize {
+        let client = self.get_pool_client();
+        let txns = entries
+            .into_iter()
+            .filter(|entry| {
+                entry.txn.sequence_number() >= client.account_seq_number(&entry.txn.sender())
+            })
+            .map(|entry| {
+                if entry.local {
+                    PoolTransaction::Local(transaction::PendingTransaction::from(entry.txn))
+                } else {
+                    PoolTransaction::Unverified(UnverifiedUserTransaction::from(entry.txn))
+                }
+            });
+        self.queue
+            .import(client.clone(), txns)
+            .into_iter()
+            .filter(|r| match r {
+                Ok(_) => true,
+                Err(e) => {
+                    debug!("re-import journaled txn fail: {}", e);
+                    false
+                }
+            })
+            .count()
+    }
+
     pub(crate) fn remove_txn(
         &self,
         txn_hash: HashValue,
@@ -1,5 +1,6 @@
 use crate::block::BlockNumber;
 use crate::contract_event::ContractEvent;
+use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -9,5 +10,45 @@ pub struct ContractEventInfo {
     pub transaction_hash: HashValue,
     // txn index in block
     pub transaction_index: u32,
+    // event index in txn
+    pub event_index: u32,
     pub event: ContractEvent,
 }
+
+impl ContractEventInfo {
+    pub fn position(&self) -> EventPosition {
+        EventPosition::new(self.block_number, self.transaction_index, self.event_index)
+    }
+}
+
+/// The position of an event on a chain, the events of a chain are ordered by the position.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize,
+)]
+pub struct EventPosition {
+    pub block_number: BlockNumber,
+    // txn index in block
+    pub transaction_index: u32,
+    // event index in txn
+    pub event_index: u32,
+}
+
+impl EventPosition {
+    pub fn new(block_number: BlockNumber, transaction_index: u32, event_index: u32) -> Self {
+        Self {
+            block_number,
+            transaction_index,
+            event_index,
+        }
+    }
+
+    /// The first position of the block.
+    pub fn block_start(block_number: BlockNumber) -> Self {
+        Self::new(block_number, 0, 0)
+    }
+
+    /// The last position of the block.
+    pub fn block_end(block_number: BlockNumber) -> Self {
+        Self::new(block_number, u32::MAX, u32::MAX)
+    }
+}
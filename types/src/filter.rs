@@ -1,8 +1,10 @@
 //! Blockchain filter
 
+use crate::account_address::AccountAddress;
 use crate::block::BlockNumber;
-use crate::contract_event::ContractEvent;
+use crate::contract_event::{ContractEvent, EventPosition};
 use crate::event::EventKey;
+use crate::language_storage::TypeTag;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Filter {
@@ -16,6 +18,17 @@ pub struct Filter {
     /// If empty, match all.
     /// If specified, event must produced from one of the event keys.
     pub event_keys: Vec<EventKey>,
+    /// Search events by type.
+    ///
+    /// If empty, match all.
+    /// If specified, event must be one of the types.
+    pub type_tags: Vec<TypeTag>,
+    /// Search events by emitting address.
+    ///
+    /// If empty, match all.
+    /// If specified, event must be emitted from one of the addresses,
+    /// the emitting address is the creator address of the event key.
+    pub addrs: Vec<AccountAddress>,
     /// Events limit
     ///
     /// If None, return all events
@@ -23,6 +36,11 @@ pub struct Filter {
     pub limit: Option<usize>,
     /// return events in reverse order.
     pub reverse: bool,
+    /// Cursor for pagination.
+    ///
+    /// If specified, only return the events after the position in the returned order,
+    /// usually the position of the last event of the previous page.
+    pub cursor: Option<EventPosition>,
 }
 
 impl Default for Filter {
@@ -31,8 +49,11 @@ impl Default for Filter {
             from_block: 0,
             to_block: 0,
             event_keys: vec![],
+            type_tags: vec![],
+            addrs: vec![],
             limit: None,
             reverse: true,
+            cursor: None,
         }
     }
 }
@@ -42,9 +63,20 @@ impl Filter {
         if self.from_block <= block_number
             && block_number <= self.to_block
             && (self.event_keys.is_empty() || self.event_keys.contains(e.key()))
+            && (self.type_tags.is_empty() || self.type_tags.contains(e.type_tag()))
+            && (self.addrs.is_empty() || self.addrs.contains(&e.key().get_creator_address()))
         {
             return true;
         }
         false
     }
+
+    /// Check the event at `position` is after the cursor in the order of the filter.
+    pub fn is_after_cursor(&self, position: &EventPosition) -> bool {
+        match &self.cursor {
+            None => true,
+            Some(cursor) if self.reverse => position < cursor,
+            Some(cursor) => position > cursor,
+        }
+    }
 }
@@ -28,7 +28,7 @@ pub mod block_metadata {
 }
 
 pub mod contract_event {
-    pub use crate::event_info::ContractEventInfo;
+    pub use crate::event_info::{ContractEventInfo, EventPosition};
     pub use starcoin_vm_types::contract_event::*;
 }
 
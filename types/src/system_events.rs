@@ -1,10 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block::{Block, BlockHeaderExtra, ExecutedBlock};
+use crate::block::{Block, BlockHeader, BlockHeaderExtra, ExecutedBlock};
 use crate::sync_status::SyncStatus;
 use crate::U256;
 use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use std::sync::Arc;
 
@@ -15,6 +16,17 @@ pub struct NewHeadBlock(pub Arc<ExecutedBlock>);
 #[derive(Clone, Debug)]
 pub struct NewBranch(pub Arc<ExecutedBlock>);
 
+/// The main chain is switched to another branch, fired before the `NewHeadBlock` of the new head.
+#[derive(Clone, Debug)]
+pub struct ChainReorg {
+    /// The common ancestor of the old and the new main chain.
+    pub ancestor: BlockHeader,
+    /// The ids of the blocks removed from the main chain, ordered from new to old.
+    pub retracted: Vec<HashValue>,
+    /// The ids of the blocks added to the main chain, ordered from old to new.
+    pub enacted: Vec<HashValue>,
+}
+
 #[derive(Clone, Debug)]
 pub struct MinedBlock(pub Arc<Block>);
 
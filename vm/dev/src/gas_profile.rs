@@ -0,0 +1,122 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::trace::TransactionTrace;
+use starcoin_vm_runtime::trace::CallTrace;
+
+/// The gas charged under a path, the outermost element first: the charge category, or the kind and
+/// the function of a call into the Move VM followed by the gas category if it is attributed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GasProfileEntry {
+    pub stack: Vec<String>,
+    pub gas_used: u64,
+}
+
+/// The gas of a transaction attributed by charge category, by the function called by the vm, and
+/// by the instruction and native gas categories of the call.
+///
+/// The Move functions called inside a call are not attributed, the gas is charged to the call.
+//TODO attribute the gas by function frame, which requires instrumenting the `CostStrategy` of
+// move-vm-types, it is a concrete type in the pinned version.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GasProfile {
+    pub entries: Vec<GasProfileEntry>,
+}
+
+impl GasProfile {
+    pub fn new(trace: &TransactionTrace) -> Self {
+        let mut profile = Self::default();
+        profile.add(vec!["intrinsic".to_string()], trace.intrinsic_gas);
+        for call in &trace.calls {
+            let stack = vec![call.kind.to_string(), call_frame(call)];
+            if call.gas_by_category.is_empty() {
+                profile.add(stack, call.gas_used);
+            } else {
+                for (category, gas_used) in &call.gas_by_category {
+                    let mut stack = stack.clone();
+                    stack.push(category.to_string());
+                    profile.add(stack, *gas_used);
+                }
+            }
+        }
+        profile.add(vec!["global_write".to_string()], trace.global_write_gas);
+        profile
+    }
+
+    /// Add the gas to the entry of the stack, the entries are kept in the order first added.
+    fn add(&mut self, stack: Vec<String>, gas_used: u64) {
+        match self.entries.iter_mut().find(|entry| entry.stack == stack) {
+            Some(entry) => entry.gas_used += gas_used,
+            None => self.entries.push(GasProfileEntry { stack, gas_used }),
+        }
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.gas_used).sum()
+    }
+}
+
+fn call_frame(call: &CallTrace) -> String {
+    match (&call.module, &call.function) {
+        (Some(module), Some(function)) => format!("{}::{}", module, function),
+        (Some(module), None) => module.to_string(),
+        _ => call.kind.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_vm_runtime::trace::{CallKind, GasCategory};
+    use starcoin_vm_types::identifier::Identifier;
+    use starcoin_vm_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+
+    fn call(kind: CallKind, module: Option<&ModuleId>, function: &str, gas_used: u64) -> CallTrace {
+        let function = Identifier::new(function).unwrap();
+        let mut call = CallTrace::new(kind, module, Some(function.as_ident_str()), &[]);
+        call.gas_used = gas_used;
+        call
+    }
+
+    #[test]
+    fn test_gas_profile() {
+        let account = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Account").unwrap());
+        let mut transfer = call(CallKind::ScriptFunction, Some(&account), "transfer", 5);
+        transfer.gas_by_category = vec![(GasCategory::Instruction, 4), (GasCategory::Native, 1)];
+        let trace = TransactionTrace {
+            calls: vec![
+                call(CallKind::Prologue, Some(&account), "prologue", 3),
+                call(CallKind::ScriptFunction, Some(&account), "transfer", 10),
+                transfer,
+                call(CallKind::Epilogue, Some(&account), "epilogue", 2),
+            ],
+            intrinsic_gas: 7,
+            global_write_gas: 4,
+            resources_read: vec![],
+            resources_written: vec![],
+            events: vec![],
+        };
+        let profile = trace.gas_profile();
+        let stacks: Vec<_> = profile
+            .entries
+            .iter()
+            .map(|entry| (entry.stack.join(";"), entry.gas_used))
+            .collect();
+        assert_eq!(
+            stacks,
+            vec![
+                ("intrinsic".to_string(), 7),
+                (format!("prologue;{}::prologue", account), 3),
+                (format!("script_function;{}::transfer", account), 10),
+                (
+                    format!("script_function;{}::transfer;instruction", account),
+                    4
+                ),
+                (format!("script_function;{}::transfer;native", account), 1),
+                (format!("epilogue;{}::epilogue", account), 2),
+                ("global_write".to_string(), 4),
+            ]
+        );
+        assert_eq!(profile.gas_used(), 31);
+    }
+}
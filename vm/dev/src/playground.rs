@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::trace::{dry_run_with_trace, trace_transaction, TransactionTrace};
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
@@ -10,7 +11,7 @@ use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
 use starcoin_vm_types::state_view::StateView;
-use starcoin_vm_types::transaction::{DryRunTransaction, TransactionOutput};
+use starcoin_vm_types::transaction::{DryRunTransaction, Transaction, TransactionOutput};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
 use starcoin_vm_types::vm_status::VMStatus;
@@ -37,6 +38,25 @@ impl PlaygroudService {
         dry_run(&state_view, txn)
     }
 
+    pub fn dry_run_with_trace(
+        &self,
+        state_root: HashValue,
+        txn: DryRunTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, TransactionTrace)> {
+        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        dry_run_with_trace(&state_view, txn)
+    }
+
+    /// Re-execute the transaction on the state of `state_root` and trace it.
+    pub fn trace_transaction(
+        &self,
+        state_root: HashValue,
+        txn: Transaction,
+    ) -> Result<(VMStatus, TransactionOutput, TransactionTrace)> {
+        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        trace_transaction(&state_view, txn)
+    }
+
     pub fn call_contract(
         &self,
         state_root: HashValue,
@@ -0,0 +1,217 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::gas_profile::GasProfile;
+use anyhow::Result;
+use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
+use starcoin_vm_runtime::data_cache::{RecordingStateView, StateViewCache};
+use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_runtime::trace::{CallTrace, ExecutionTrace, GasCategory};
+use starcoin_vm_types::access_path::{AccessPath, DataPath};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::contract_event::ContractEvent;
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::transaction::{DryRunTransaction, Transaction, TransactionOutput};
+use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::write_set::WriteOp;
+use std::collections::BTreeSet;
+
+/// A resource read or written by a transaction.
+#[derive(Clone, Debug)]
+pub struct ResourceTrace {
+    pub address: AccountAddress,
+    pub struct_tag: StructTag,
+    /// The value before the transaction, None if the resource does not exist.
+    pub before: Option<AnnotatedMoveStruct>,
+    /// The value after the transaction, None if the resource is deleted or only read.
+    pub after: Option<AnnotatedMoveStruct>,
+}
+
+/// An event emitted by a transaction.
+#[derive(Clone, Debug)]
+pub struct EventTrace {
+    pub event: ContractEvent,
+    pub data: AnnotatedMoveValue,
+}
+
+/// The trace of a transaction executed on a state.
+#[derive(Clone, Debug)]
+pub struct TransactionTrace {
+    /// The calls the adapter made into the Move VM, see `CallTrace`.
+    pub calls: Vec<CallTrace>,
+    pub intrinsic_gas: u64,
+    pub global_write_gas: u64,
+    /// The resources read but not written by the transaction.
+    pub resources_read: Vec<ResourceTrace>,
+    pub resources_written: Vec<ResourceTrace>,
+    pub events: Vec<EventTrace>,
+}
+
+impl TransactionTrace {
+    pub fn gas_profile(&self) -> GasProfile {
+        GasProfile::new(self)
+    }
+}
+
+/// Dry run the transaction on the state and trace it.
+pub fn dry_run_with_trace(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+) -> Result<(VMStatus, TransactionOutput, TransactionTrace)> {
+    let recording_view = RecordingStateView::new(state_view);
+    let mut vm = StarcoinVM::new();
+    vm.enable_trace();
+    let (status, output) = vm.dry_run_transaction(&recording_view, txn.clone())?;
+    let mut execution_trace = vm.take_trace().unwrap_or_default();
+    attribute_gas_by_category(&mut execution_trace.calls, |vm| {
+        vm.dry_run_transaction(state_view, txn).map(|_| ())
+    })?;
+    let trace = build_trace(
+        state_view,
+        recording_view.into_reads(),
+        &output,
+        execution_trace,
+    )?;
+    Ok((status, output, trace))
+}
+
+/// Re-execute the transaction on the state before it and trace it, nothing is saved.
+pub fn trace_transaction(
+    state_view: &dyn StateView,
+    txn: Transaction,
+) -> Result<(VMStatus, TransactionOutput, TransactionTrace)> {
+    let recording_view = RecordingStateView::new(state_view);
+    let mut vm = StarcoinVM::new();
+    vm.enable_trace();
+    let (status, output) = vm
+        .execute_block_transactions(&recording_view, vec![txn.clone()], None)?
+        .pop()
+        .ok_or_else(|| anyhow::format_err!("The transaction is not executed"))?;
+    let mut execution_trace = vm.take_trace().unwrap_or_default();
+    attribute_gas_by_category(&mut execution_trace.calls, |vm| {
+        vm.execute_block_transactions(state_view, vec![txn], None)
+            .map(|_| ())
+    })?;
+    let trace = build_trace(
+        state_view,
+        recording_view.into_reads(),
+        &output,
+        execution_trace,
+    )?;
+    Ok((status, output, trace))
+}
+
+/// Execute the transaction again charging only the instruction gas, and attribute the gas of every
+/// call to the instruction and the native categories by the difference. The gas is not attributed
+/// if the executions diverge, e.g. the transaction runs out of gas only when natives are charged.
+fn attribute_gas_by_category<F>(calls: &mut [CallTrace], execute: F) -> Result<()>
+where
+    F: FnOnce(&mut StarcoinVM) -> Result<()>,
+{
+    let mut vm = StarcoinVM::new();
+    vm.enable_trace();
+    vm.charge_only(GasCategory::Instruction);
+    execute(&mut vm)?;
+    let instruction_calls = vm.take_trace().unwrap_or_default().calls;
+    let same_calls = calls.len() == instruction_calls.len()
+        && calls
+            .iter()
+            .zip(instruction_calls.iter())
+            .all(|(call, instruction_call)| {
+                call.kind == instruction_call.kind
+                    && call.module == instruction_call.module
+                    && call.function == instruction_call.function
+                    && call.error == instruction_call.error
+            });
+    if !same_calls {
+        return Ok(());
+    }
+    for (call, instruction_call) in calls.iter_mut().zip(instruction_calls) {
+        // The gas of a call is rounded to the external gas units, so the instruction gas may
+        // exceed the gas of the call by one unit.
+        let instruction_gas = instruction_call.gas_used.min(call.gas_used);
+        call.gas_by_category = vec![
+            (GasCategory::Instruction, instruction_gas),
+            (GasCategory::Native, call.gas_used - instruction_gas),
+        ];
+    }
+    Ok(())
+}
+
+fn build_trace(
+    state_view: &dyn StateView,
+    reads: BTreeSet<AccessPath>,
+    output: &TransactionOutput,
+    execution_trace: ExecutionTrace,
+) -> Result<TransactionTrace> {
+    // The resources and events may be defined by the modules published in the transaction,
+    // so the values after the transaction are decoded on the state after it.
+    let mut state_after = StateViewCache::new(state_view);
+    state_after.push_write_set(output.write_set());
+    let annotator_before = MoveValueAnnotator::new(state_view);
+    let annotator_after = MoveValueAnnotator::new(&state_after);
+
+    let mut written = BTreeSet::new();
+    let mut resources_written = vec![];
+    for (access_path, write_op) in output.write_set().iter() {
+        written.insert(access_path.clone());
+        if let DataPath::Resource(struct_tag) = &access_path.path {
+            let before = view_resource(&annotator_before, state_view, access_path, struct_tag)?;
+            let after = match write_op {
+                WriteOp::Value(data) => {
+                    Some(annotator_after.view_struct(struct_tag.clone(), data.as_slice())?)
+                }
+                WriteOp::Deletion => None,
+            };
+            resources_written.push(ResourceTrace {
+                address: access_path.address,
+                struct_tag: struct_tag.clone(),
+                before,
+                after,
+            });
+        }
+    }
+    let mut resources_read = vec![];
+    for access_path in reads.difference(&written) {
+        if let DataPath::Resource(struct_tag) = &access_path.path {
+            let before = view_resource(&annotator_before, state_view, access_path, struct_tag)?;
+            resources_read.push(ResourceTrace {
+                address: access_path.address,
+                struct_tag: struct_tag.clone(),
+                before,
+                after: None,
+            });
+        }
+    }
+    let events = output
+        .events()
+        .iter()
+        .map(|event| {
+            Ok(EventTrace {
+                event: event.clone(),
+                data: annotator_after.view_contract_event(event)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(TransactionTrace {
+        calls: execution_trace.calls,
+        intrinsic_gas: execution_trace.intrinsic_gas,
+        global_write_gas: execution_trace.global_write_gas,
+        resources_read,
+        resources_written,
+        events,
+    })
+}
+
+fn view_resource(
+    annotator: &MoveValueAnnotator,
+    state_view: &dyn StateView,
+    access_path: &AccessPath,
+    struct_tag: &StructTag,
+) -> Result<Option<AnnotatedMoveStruct>> {
+    state_view
+        .get(access_path)?
+        .map(|data| annotator.view_struct(struct_tag.clone(), data.as_slice()))
+        .transpose()
+}
@@ -75,8 +75,9 @@ impl fmt::Display for Error {
             AlreadyImported => "Already imported".into(),
             Old => "No longer valid".into(),
             TooCheapToReplace { prev, new } => format!(
-                "Gas price too low to replace, previous tx gas: {:?}, new tx gas: {:?}",
-                prev, new
+                "Gas price too low to replace, previous tx gas price: {}, new tx gas price: {}",
+                display_gas_price(prev),
+                display_gas_price(new)
             ),
             LimitReached => "Transaction limit reached".into(),
             InsufficientGasPrice { minimal, got } => {
@@ -109,6 +110,12 @@ impl fmt::Display for Error {
     }
 }
 
+fn display_gas_price(gas_price: &Option<GasPrice>) -> String {
+    gas_price
+        .map(|gas_price| gas_price.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 impl error::Error for Error {
     fn description(&self) -> &str {
         "Transaction error"
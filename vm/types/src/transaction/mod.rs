@@ -806,6 +806,8 @@ pub enum TxStatus {
     Canceled,
     /// Culled transaction
     Culled,
+    /// Replaced by another transaction with the same sender and sequence number
+    Replaced,
 }
 
 impl std::fmt::Display for TxStatus {
@@ -817,6 +819,7 @@ impl std::fmt::Display for TxStatus {
             TxStatus::Invalid => "invalid",
             TxStatus::Canceled => "canceled",
             TxStatus::Culled => "culled",
+            TxStatus::Replaced => "replaced",
         };
         write!(f, "{}", s)
     }
@@ -15,7 +15,9 @@ use starcoin_vm_types::{
     vm_status::StatusCode,
     write_set::{WriteOp, WriteSet},
 };
+use std::cell::RefCell;
 use std::collections::btree_map::BTreeMap;
+use std::collections::BTreeSet;
 
 /// A local cache for a given a `StateView`. The cache is private to the Diem layer
 /// but can be used as a one shot cache for systems that need a simple `RemoteCache`
@@ -48,7 +50,7 @@ impl<'a> StateViewCache<'a> {
     // Publishes a `WriteSet` computed at the end of a transaction.
     // The effect is to build a layer in front of the `StateView` which keeps
     // track of the data as if the changes were applied immediately.
-    pub(crate) fn push_write_set(&mut self, write_set: &WriteSet) {
+    pub fn push_write_set(&mut self, write_set: &WriteSet) {
         for (ref ap, ref write_op) in write_set.iter() {
             match write_op {
                 WriteOp::Value(blob) => {
@@ -139,3 +141,38 @@ impl<'a> ConfigStorage for RemoteStorage<'a> {
         self.get(&access_path).ok()?
     }
 }
+
+/// A `StateView` which records the access paths read through it.
+pub struct RecordingStateView<'a> {
+    inner: &'a dyn StateView,
+    reads: RefCell<BTreeSet<AccessPath>>,
+}
+
+impl<'a> RecordingStateView<'a> {
+    pub fn new(inner: &'a dyn StateView) -> Self {
+        Self {
+            inner,
+            reads: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    pub fn into_reads(self) -> BTreeSet<AccessPath> {
+        self.reads.into_inner()
+    }
+}
+
+impl<'a> StateView for RecordingStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
+        self.reads.borrow_mut().insert(access_path.clone());
+        self.inner.get(access_path)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        self.reads.borrow_mut().extend(access_paths.iter().cloned());
+        self.inner.multi_get(access_paths)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.inner.is_genesis()
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optimistic parallel execution of the block transactions.
+//!
+//! The user transactions between two block metadata transactions are executed in parallel on
+//! the state before the first of them, recording the access paths every transaction read.
+//! Then the transactions are validated and committed in the block order: a transaction which
+//! read an access path written by a previous committed transaction is executed again on the
+//! committed state, so the outputs are the same as executing the transactions one by one.
+//!
+//! Every transaction epilogue deposits the gas fee into the `TransactionFee` resource, so the
+//! resource is excluded from the conflict detection, and the amount a speculative transaction
+//! deposited is added to the committed fee when the transaction is committed.
+
+use crate::data_cache::{RecordingStateView, StateViewCache};
+use crate::starcoin_vm::{
+    chunk_block_transactions, discard_error_vm_status, StarcoinVM, TransactionBlock,
+};
+use anyhow::{format_err, Result};
+use rayon::prelude::*;
+use starcoin_types::transaction::{
+    SignedUserTransaction, Transaction, TransactionOutput, TransactionPayload, TransactionStatus,
+};
+use starcoin_vm_types::access_path::{AccessPath, DataPath};
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::language_storage::CORE_CODE_ADDRESS;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use starcoin_vm_types::write_set::{WriteOp, WriteSet, WriteSetMut};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The state view with the write sets of the committed transactions of the block applied.
+struct BlockStateView<'a> {
+    base: &'a (dyn StateView + Sync),
+    writes: BTreeMap<AccessPath, Option<Vec<u8>>>,
+}
+
+impl<'a> BlockStateView<'a> {
+    fn new(base: &'a (dyn StateView + Sync)) -> Self {
+        Self {
+            base,
+            writes: BTreeMap::new(),
+        }
+    }
+
+    fn apply_write_set(&mut self, write_set: &WriteSet) {
+        for (access_path, write_op) in write_set.iter() {
+            let value = match write_op {
+                WriteOp::Value(blob) => Some(blob.clone()),
+                WriteOp::Deletion => None,
+            };
+            self.writes.insert(access_path.clone(), value);
+        }
+    }
+}
+
+impl<'a> StateView for BlockStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        match self.writes.get(access_path) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get(access_path),
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+}
+
+/// Whether the access path is a `0x1::TransactionFee::TransactionFee<TokenType>` resource,
+/// which only changes by the fee deposits of the transaction epilogues.
+fn is_transaction_fee(access_path: &AccessPath) -> bool {
+    access_path.address == genesis_address()
+        && matches!(&access_path.path, DataPath::Resource(tag)
+            if tag.address == CORE_CODE_ADDRESS
+                && tag.module.as_str() == "TransactionFee"
+                && tag.name.as_str() == "TransactionFee")
+}
+
+/// The fee value of a `TransactionFee` resource, it is a `Token` whose only field is the value.
+fn fee_value(blob: Option<&[u8]>) -> Result<u128> {
+    match blob {
+        Some(blob) => bcs_ext::from_bytes(blob),
+        None => Err(format_err!("The transaction fee resource does not exist.")),
+    }
+}
+
+struct SpeculativeOutput {
+    /// The access paths read by the transaction, except the transaction fees.
+    reads: BTreeSet<AccessPath>,
+    /// The amounts deposited into the transaction fees by the transaction.
+    fee_deposits: BTreeMap<AccessPath, u128>,
+    status_and_output: (VMStatus, TransactionOutput),
+}
+
+impl SpeculativeOutput {
+    fn new(
+        base: &dyn StateView,
+        mut reads: BTreeSet<AccessPath>,
+        status_and_output: (VMStatus, TransactionOutput),
+    ) -> Result<Self> {
+        reads.retain(|access_path| !is_transaction_fee(access_path));
+        let mut fee_deposits = BTreeMap::new();
+        for (access_path, write_op) in status_and_output.1.write_set().iter() {
+            if !is_transaction_fee(access_path) {
+                continue;
+            }
+            let fee = match write_op {
+                WriteOp::Value(blob) => fee_value(Some(blob))?,
+                WriteOp::Deletion => return Err(format_err!("Unexpected fee deletion.")),
+            };
+            let deposit = fee
+                .checked_sub(fee_value(base.get(access_path)?.as_deref())?)
+                .ok_or_else(|| format_err!("The transaction fee decreased."))?;
+            fee_deposits.insert(access_path.clone(), deposit);
+        }
+        Ok(Self {
+            reads,
+            fee_deposits,
+            status_and_output,
+        })
+    }
+
+    /// The status and output with the deposits added to the committed fees of `block_state`.
+    fn commit(self, block_state: &BlockStateView) -> Result<(VMStatus, TransactionOutput)> {
+        if self.fee_deposits.is_empty() {
+            return Ok(self.status_and_output);
+        }
+        let (status, output) = self.status_and_output;
+        let (write_set, events, gas_used, txn_status) = output.into_inner();
+        let write_set = write_set
+            .iter()
+            .map(|(access_path, write_op)| {
+                Ok(match self.fee_deposits.get(access_path) {
+                    Some(deposit) => {
+                        let fee = fee_value(block_state.get(access_path)?.as_deref())?
+                            .checked_add(*deposit)
+                            .ok_or_else(|| format_err!("The transaction fee overflow."))?;
+                        (
+                            access_path.clone(),
+                            WriteOp::Value(bcs_ext::to_bytes(&fee)?),
+                        )
+                    }
+                    None => (access_path.clone(), write_op.clone()),
+                })
+            })
+            .collect::<Result<WriteSetMut>>()?
+            .freeze()?;
+        Ok((
+            status,
+            TransactionOutput::new(write_set, events, gas_used, txn_status),
+        ))
+    }
+}
+
+impl StarcoinVM {
+    /// Execute a block transactions with gas_limit like `execute_block_transactions`, and return
+    /// the same outputs, but execute the user transactions optimistically in parallel.
+    pub fn execute_block_transactions_parallel(
+        &mut self,
+        state_view: &(dyn StateView + Sync),
+        transactions: Vec<Transaction>,
+        block_gas_limit: Option<u64>,
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+        // The trace records the calls in execution order, so only trace the sequential execution.
+        if self.is_trace_enabled() {
+            return self.execute_block_transactions(state_view, transactions, block_gas_limit);
+        }
+        let mut block_state = BlockStateView::new(state_view);
+        let mut result = vec![];
+        //TODO load config by config change event.
+        self.load_configs(&block_state)?;
+
+        let mut gas_left = block_gas_limit.unwrap_or(u64::MAX);
+
+        let blocks = chunk_block_transactions(transactions);
+        'outer: for block in blocks {
+            match block {
+                TransactionBlock::UserTransaction(txns) => {
+                    let speculative_outputs = self.speculate(&block_state, &txns);
+                    // The access paths written by the transactions committed after the speculation.
+                    let mut written = BTreeSet::new();
+                    let mut code_written = false;
+                    for (transaction, speculative_output) in
+                        txns.into_iter().zip(speculative_outputs)
+                    {
+                        let gas_unit_price = transaction.gas_unit_price();
+                        let speculative_output = speculative_output
+                            .filter(|output| !code_written && output.reads.is_disjoint(&written))
+                            .and_then(|output| output.commit(&block_state).ok());
+                        let (status, output) = match speculative_output {
+                            Some(status_and_output) => status_and_output,
+                            None => {
+                                let mut data_cache = StateViewCache::new(&block_state);
+                                self.execute_user_transaction(transaction, &mut data_cache)
+                            }
+                        };
+                        // only need to check for user transactions.
+                        match gas_left.checked_sub(output.gas_used()) {
+                            Some(l) => gas_left = l,
+                            None => break 'outer,
+                        }
+
+                        if let TransactionStatus::Keep(_) = output.status() {
+                            if gas_unit_price > 0 {
+                                debug_assert_ne!(
+                                    output.gas_used(),
+                                    0,
+                                    "Keep transaction gas used must not be zero"
+                                );
+                            }
+                            for (access_path, _) in output.write_set().iter() {
+                                code_written |= access_path.path.is_code();
+                                if !is_transaction_fee(access_path) {
+                                    written.insert(access_path.clone());
+                                }
+                            }
+                            block_state.apply_write_set(output.write_set());
+                        }
+                        result.push((status, output));
+                    }
+                }
+                TransactionBlock::BlockPrologue(block_metadata) => {
+                    let (status, output) = {
+                        let mut data_cache = StateViewCache::new(&block_state);
+                        match self.process_block_metadata(&mut data_cache, block_metadata) {
+                            Ok(output) => (VMStatus::Executed, output),
+                            Err(vm_status) => discard_error_vm_status(vm_status),
+                        }
+                    };
+                    debug_assert_eq!(
+                        output.gas_used(),
+                        0,
+                        "Block metadata transaction gas_used must be zero."
+                    );
+                    if let TransactionStatus::Keep(status) = output.status() {
+                        debug_assert_eq!(
+                            status,
+                            &KeptVMStatus::Executed,
+                            "Block metadata transaction keep status must been Executed."
+                        );
+                        block_state.apply_write_set(output.write_set())
+                    }
+                    result.push((status, output));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Execute the transactions in parallel on the block state, None if the transaction is not
+    /// executed speculatively, or the fee deposits of it can not be resolved.
+    fn speculate(
+        &self,
+        block_state: &BlockStateView,
+        txns: &[SignedUserTransaction],
+    ) -> Vec<Option<SpeculativeOutput>> {
+        // The modules published by a package are cached by the Move VM shared by all the
+        // transactions, so the transactions are executed one by one if there is a package.
+        if txns.len() < 2
+            || txns
+                .iter()
+                .any(|txn| matches!(txn.payload(), TransactionPayload::Package(_)))
+        {
+            return txns.iter().map(|_| None).collect();
+        }
+        txns.par_iter()
+            .map_with(self.clone(), |vm, txn| {
+                let recording_view = RecordingStateView::new(block_state);
+                let status_and_output = {
+                    let mut data_cache = StateViewCache::new(&recording_view);
+                    vm.execute_user_transaction(txn.clone(), &mut data_cache)
+                };
+                SpeculativeOutput::new(block_state, recording_view.into_reads(), status_and_output)
+                    .ok()
+            })
+            .collect()
+    }
+}
@@ -7,6 +7,7 @@ use crate::errors::{
     convert_normal_success_epilogue_error, convert_prologue_runtime_error, error_split,
 };
 use crate::metrics::{BLOCK_UNCLES, TXN_EXECUTION_GAS_USAGE};
+use crate::trace::{CallKind, CallTrace, ExecutionTrace, GasCategory};
 use anyhow::{format_err, Error, Result};
 use crypto::HashValue;
 use move_vm_runtime::data_cache::RemoteCache;
@@ -54,7 +55,7 @@ use starcoin_vm_types::{
     vm_status::{StatusCode, VMStatus},
 };
 use std::convert::TryFrom;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
 #[allow(clippy::upper_case_acronyms)]
@@ -63,6 +64,8 @@ pub struct StarcoinVM {
     move_vm: Arc<MoveVMAdapter>,
     vm_config: Option<VMConfig>,
     version: Option<Version>,
+    trace: Option<Arc<Mutex<ExecutionTrace>>>,
+    gas_category: Option<GasCategory>,
 }
 
 impl Default for StarcoinVM {
@@ -78,19 +81,109 @@ impl StarcoinVM {
             move_vm: Arc::new(inner),
             vm_config: None,
             version: None,
+            trace: None,
+            gas_category: None,
         }
     }
 
-    fn load_configs(&mut self, state: &dyn StateView) -> Result<(), Error> {
+    /// Record the calls the adapter makes into the Move VM and the gas charged by them when
+    /// executing transactions. The Move functions called inside a call are not recorded.
+    pub fn enable_trace(&mut self) {
+        self.trace = Some(Arc::new(Mutex::new(ExecutionTrace::default())));
+    }
+
+    /// Take the trace recorded since the last take, None if the trace is not enabled.
+    pub fn take_trace(&mut self) -> Option<ExecutionTrace> {
+        self.trace.as_ref().map(|trace| {
+            std::mem::take(&mut *trace.lock().expect("Trace lock should not be poisoned"))
+        })
+    }
+
+    /// Only charge the gas of the category when executing transactions, the costs of the other
+    /// categories are zero. The outputs do not charge the real gas, it is used to attribute the
+    /// gas of a trace by category, by executing the transactions again.
+    pub fn charge_only(&mut self, category: GasCategory) {
+        self.gas_category = Some(category);
+    }
+
+    pub(crate) fn is_trace_enabled(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    fn record_trace<F>(&self, f: F)
+    where
+        F: FnOnce(&mut ExecutionTrace),
+    {
+        if let Some(trace) = &self.trace {
+            f(&mut *trace.lock().expect("Trace lock should not be poisoned"));
+        }
+    }
+
+    fn trace_call<T, F>(
+        &self,
+        cost_strategy: &mut CostStrategy,
+        kind: CallKind,
+        module: Option<&ModuleId>,
+        function: Option<&IdentStr>,
+        ty_args: &[TypeTag],
+        f: F,
+    ) -> Result<T, VMStatus>
+    where
+        F: FnOnce(&mut CostStrategy) -> Result<T, VMStatus>,
+    {
+        let gas_before = cost_strategy.remaining_gas();
+        let result = f(cost_strategy);
+        self.record_trace(|trace| {
+            let mut call = CallTrace::new(kind, module, function, ty_args);
+            call.gas_used = gas_before.sub(cost_strategy.remaining_gas()).get();
+            call.error = result.as_ref().err().map(|e| format!("{:?}", e));
+            trace.calls.push(call);
+        });
+        result
+    }
+
+    fn charge_intrinsic_gas(
+        &self,
+        cost_strategy: &mut CostStrategy,
+        txn_data: &TransactionMetadata,
+    ) -> Result<(), VMStatus> {
+        let gas_before = cost_strategy.remaining_gas();
+        cost_strategy
+            .charge_intrinsic_gas(txn_data.transaction_size())
+            .map_err(|e| e.into_vm_status())?;
+        self.record_trace(|trace| {
+            trace.intrinsic_gas = gas_before.sub(cost_strategy.remaining_gas()).get();
+        });
+        Ok(())
+    }
+
+    fn charge_global_write_gas_usage<R: RemoteCache>(
+        &self,
+        cost_strategy: &mut CostStrategy,
+        session: &SessionAdapter<R>,
+        txn_data: &TransactionMetadata,
+    ) -> Result<(), VMStatus> {
+        let gas_before = cost_strategy.remaining_gas();
+        charge_global_write_gas_usage(cost_strategy, session, &txn_data.sender())?;
+        self.record_trace(|trace| {
+            trace.global_write_gas = gas_before.sub(cost_strategy.remaining_gas()).get();
+        });
+        Ok(())
+    }
+
+    pub(crate) fn load_configs(&mut self, state: &dyn StateView) -> Result<(), Error> {
         if state.is_genesis() {
             self.vm_config = Some(VMConfig {
                 gas_schedule: INITIAL_GAS_SCHEDULE.clone(),
             });
             self.version = Some(Version { major: 0 });
-            Ok(())
         } else {
-            self.load_configs_impl(state)
+            self.load_configs_impl(state)?;
+        }
+        if let (Some(category), Some(vm_config)) = (self.gas_category, self.vm_config.as_mut()) {
+            vm_config.gas_schedule = category.cost_table(&vm_config.gas_schedule);
         }
+        Ok(())
     }
 
     fn load_configs_impl(&mut self, state: &dyn StateView) -> Result<(), Error> {
@@ -349,9 +442,7 @@ impl StarcoinVM {
             if !remote_cache.is_genesis() {
                 cost_strategy.enable_metering();
             }
-            cost_strategy
-                .charge_intrinsic_gas(txn_data.transaction_size())
-                .map_err(|e| e.into_vm_status())?;
+            self.charge_intrinsic_gas(cost_strategy, txn_data)?;
 
             let package_address = package.package_address();
             let enforced = match Self::is_enforced(remote_cache, package_address) {
@@ -394,9 +485,22 @@ impl StarcoinVM {
                             .verify_module(module.code())
                             .map_err(|e| e.into_vm_status())?;
 
-                        session
-                            .publish_module(module.code().to_vec(), txn_data.sender, cost_strategy)
-                            .map_err(|e| e.into_vm_status())?;
+                        self.trace_call(
+                            cost_strategy,
+                            CallKind::PublishModule,
+                            Some(&module_id),
+                            None,
+                            &[],
+                            |cost_strategy| {
+                                session
+                                    .publish_module(
+                                        module.code().to_vec(),
+                                        txn_data.sender,
+                                        cost_strategy,
+                                    )
+                                    .map_err(|e| e.into_vm_status())
+                            },
+                        )?;
                     }
                 }
             }
@@ -417,18 +521,27 @@ impl StarcoinVM {
                     init_script.function(),
                     sender
                 );
-                session
-                    .execute_script_function(
-                        init_script.module(),
-                        init_script.function(),
-                        init_script.ty_args().to_vec(),
-                        init_script.args().to_vec(),
-                        vec![sender],
-                        cost_strategy,
-                    )
-                    .map_err(|e| e.into_vm_status())?;
+                self.trace_call(
+                    cost_strategy,
+                    CallKind::InitScript,
+                    Some(init_script.module()),
+                    Some(init_script.function()),
+                    init_script.ty_args(),
+                    |cost_strategy| {
+                        session
+                            .execute_script_function(
+                                init_script.module(),
+                                init_script.function(),
+                                init_script.ty_args().to_vec(),
+                                init_script.args().to_vec(),
+                                vec![sender],
+                                cost_strategy,
+                            )
+                            .map_err(|e| e.into_vm_status())
+                    },
+                )?;
             }
-            charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
+            self.charge_global_write_gas_usage(cost_strategy, &session, txn_data)?;
 
             cost_strategy.disable_metering();
             self.success_transaction_cleanup(
@@ -462,33 +575,51 @@ impl StarcoinVM {
         {
             //let _timer = TXN_EXECUTION_SECONDS.start_timer();
             cost_strategy.enable_metering();
-            cost_strategy
-                .charge_intrinsic_gas(txn_data.transaction_size())
-                .map_err(|e| e.into_vm_status())?;
+            self.charge_intrinsic_gas(cost_strategy, txn_data)?;
             match payload {
-                TransactionPayload::Script(script) => session.execute_script(
-                    script.code().to_vec(),
-                    script.ty_args().to_vec(),
-                    script.args().to_vec(),
-                    vec![txn_data.sender()],
+                TransactionPayload::Script(script) => self.trace_call(
+                    cost_strategy,
+                    CallKind::Script,
+                    None,
+                    None,
+                    script.ty_args(),
+                    |cost_strategy| {
+                        session
+                            .execute_script(
+                                script.code().to_vec(),
+                                script.ty_args().to_vec(),
+                                script.args().to_vec(),
+                                vec![txn_data.sender()],
+                                cost_strategy,
+                            )
+                            .map_err(|e| e.into_vm_status())
+                    },
+                ),
+                TransactionPayload::ScriptFunction(script_function) => self.trace_call(
                     cost_strategy,
+                    CallKind::ScriptFunction,
+                    Some(script_function.module()),
+                    Some(script_function.function()),
+                    script_function.ty_args(),
+                    |cost_strategy| {
+                        session
+                            .execute_script_function(
+                                script_function.module(),
+                                script_function.function(),
+                                script_function.ty_args().to_vec(),
+                                script_function.args().to_vec(),
+                                vec![txn_data.sender()],
+                                cost_strategy,
+                            )
+                            .map_err(|e| e.into_vm_status())
+                    },
                 ),
-                TransactionPayload::ScriptFunction(script_function) => session
-                    .execute_script_function(
-                        script_function.module(),
-                        script_function.function(),
-                        script_function.ty_args().to_vec(),
-                        script_function.args().to_vec(),
-                        vec![txn_data.sender()],
-                        cost_strategy,
-                    ),
                 TransactionPayload::Package(_) => {
                     return Err(VMStatus::Error(StatusCode::UNREACHABLE));
                 }
-            }
-            .map_err(|e| e.into_vm_status())?;
+            }?;
 
-            charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
+            self.charge_global_write_gas_usage(cost_strategy, &session, txn_data)?;
 
             cost_strategy.disable_metering();
             self.success_transaction_cleanup(
@@ -531,28 +662,38 @@ impl StarcoinVM {
         };
 
         // Run prologue by genesis account
-        session
-            .execute_function(
-                &account_config::TRANSACTION_MANAGER_MODULE,
-                &PROLOGUE_NAME,
-                vec![gas_token_ty],
-                serialize_values(&vec![
-                    MoveValue::Signer(genesis_address),
-                    MoveValue::Address(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::vector_u8(txn_public_key),
-                    MoveValue::U64(txn_gas_price),
-                    MoveValue::U64(txn_max_gas_amount),
-                    MoveValue::U64(txn_expiration_time),
-                    MoveValue::U8(chain_id),
-                    MoveValue::U8(payload_type.into()),
-                    MoveValue::vector_u8(script_or_package_hash.to_vec()),
-                    MoveValue::Address(package_address),
-                ]),
-                cost_strategy,
-            )
-            .map(|_return_vals| ())
-            .or_else(convert_prologue_runtime_error)
+        let ty_args = vec![gas_token_ty];
+        self.trace_call(
+            cost_strategy,
+            CallKind::Prologue,
+            Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+            Some(PROLOGUE_NAME.as_ident_str()),
+            &ty_args,
+            |cost_strategy| {
+                session
+                    .execute_function(
+                        &account_config::TRANSACTION_MANAGER_MODULE,
+                        &PROLOGUE_NAME,
+                        ty_args.clone(),
+                        serialize_values(&vec![
+                            MoveValue::Signer(genesis_address),
+                            MoveValue::Address(txn_data.sender),
+                            MoveValue::U64(txn_sequence_number),
+                            MoveValue::vector_u8(txn_public_key),
+                            MoveValue::U64(txn_gas_price),
+                            MoveValue::U64(txn_max_gas_amount),
+                            MoveValue::U64(txn_expiration_time),
+                            MoveValue::U8(chain_id),
+                            MoveValue::U8(payload_type.into()),
+                            MoveValue::vector_u8(script_or_package_hash.to_vec()),
+                            MoveValue::Address(package_address),
+                        ]),
+                        cost_strategy,
+                    )
+                    .map(|_return_vals| ())
+                    .or_else(convert_prologue_runtime_error)
+            },
+        )
     }
 
     /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
@@ -584,30 +725,40 @@ impl StarcoinVM {
             ),
         };
         // Run epilogue by genesis account
-        session
-            .execute_function(
-                &account_config::TRANSACTION_MANAGER_MODULE,
-                &EPILOGUE_NAME,
-                vec![gas_token_ty],
-                serialize_values(&vec![
-                    MoveValue::Signer(genesis_address),
-                    MoveValue::Address(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price),
-                    MoveValue::U64(txn_max_gas_amount),
-                    MoveValue::U64(gas_remaining),
-                    MoveValue::U8(payload_type.into()),
-                    MoveValue::vector_u8(script_or_package_hash.to_vec()),
-                    MoveValue::Address(package_address),
-                    MoveValue::Bool(success),
-                ]),
-                cost_strategy,
-            )
-            .map(|_return_vals| ())
-            .or_else(convert_normal_success_epilogue_error)
+        let ty_args = vec![gas_token_ty];
+        self.trace_call(
+            cost_strategy,
+            CallKind::Epilogue,
+            Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+            Some(EPILOGUE_NAME.as_ident_str()),
+            &ty_args,
+            |cost_strategy| {
+                session
+                    .execute_function(
+                        &account_config::TRANSACTION_MANAGER_MODULE,
+                        &EPILOGUE_NAME,
+                        ty_args.clone(),
+                        serialize_values(&vec![
+                            MoveValue::Signer(genesis_address),
+                            MoveValue::Address(txn_data.sender),
+                            MoveValue::U64(txn_sequence_number),
+                            MoveValue::U64(txn_gas_price),
+                            MoveValue::U64(txn_max_gas_amount),
+                            MoveValue::U64(gas_remaining),
+                            MoveValue::U8(payload_type.into()),
+                            MoveValue::vector_u8(script_or_package_hash.to_vec()),
+                            MoveValue::Address(package_address),
+                            MoveValue::Bool(success),
+                        ]),
+                        cost_strategy,
+                    )
+                    .map(|_return_vals| ())
+                    .or_else(convert_normal_success_epilogue_error)
+            },
+        )
     }
 
-    fn process_block_metadata(
+    pub(crate) fn process_block_metadata(
         &self,
         remote_cache: &mut StateViewCache<'_>,
         block_metadata: BlockMetadata,
@@ -643,16 +794,25 @@ impl StarcoinVM {
             MoveValue::U64(parent_gas_used),
         ]);
         let mut session = self.move_vm.new_session(remote_cache);
-        session
-            .execute_function(
-                &account_config::TRANSACTION_MANAGER_MODULE,
-                &account_config::BLOCK_PROLOGUE_NAME,
-                vec![],
-                args,
-                &mut cost_strategy,
-            )
-            .map(|_return_vals| ())
-            .or_else(convert_prologue_runtime_error)?;
+        self.trace_call(
+            &mut cost_strategy,
+            CallKind::BlockPrologue,
+            Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+            Some(account_config::BLOCK_PROLOGUE_NAME.as_ident_str()),
+            &[],
+            |cost_strategy| {
+                session
+                    .execute_function(
+                        &account_config::TRANSACTION_MANAGER_MODULE,
+                        &account_config::BLOCK_PROLOGUE_NAME,
+                        vec![],
+                        args,
+                        cost_strategy,
+                    )
+                    .map(|_return_vals| ())
+                    .or_else(convert_prologue_runtime_error)
+            },
+        )?;
         BLOCK_UNCLES.observe(uncles as f64);
         get_transaction_output(
             &mut (),
@@ -663,7 +823,7 @@ impl StarcoinVM {
         )
     }
 
-    fn execute_user_transaction(
+    pub(crate) fn execute_user_transaction(
         &mut self,
         txn: SignedUserTransaction,
         remote_cache: &mut StateViewCache<'_>,
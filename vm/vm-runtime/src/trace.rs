@@ -0,0 +1,119 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use starcoin_vm_types::gas_schedule::{CostTable, GasCost};
+use starcoin_vm_types::identifier::{IdentStr, Identifier};
+use starcoin_vm_types::language_storage::{ModuleId, TypeTag};
+use std::fmt;
+
+/// The kind of a call from the adapter into the Move VM.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CallKind {
+    Prologue,
+    Script,
+    ScriptFunction,
+    PublishModule,
+    InitScript,
+    Epilogue,
+    BlockPrologue,
+}
+
+impl fmt::Display for CallKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self {
+            CallKind::Prologue => "prologue",
+            CallKind::Script => "script",
+            CallKind::ScriptFunction => "script_function",
+            CallKind::PublishModule => "publish_module",
+            CallKind::InitScript => "init_script",
+            CallKind::Epilogue => "epilogue",
+            CallKind::BlockPrologue => "block_prologue",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// The category of the gas charged by the Move VM.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GasCategory {
+    /// The gas of the bytecode instructions.
+    Instruction,
+    /// The gas of the native functions.
+    Native,
+}
+
+impl GasCategory {
+    /// The cost table which only charges the gas of the category, the costs of the other
+    /// categories are zero. The gas constants are kept.
+    pub fn cost_table(self, cost_table: &CostTable) -> CostTable {
+        let zero = |table: &[GasCost]| vec![GasCost::new(0, 0); table.len()];
+        let mut cost_table = cost_table.clone();
+        match self {
+            GasCategory::Instruction => cost_table.native_table = zero(&cost_table.native_table),
+            GasCategory::Native => {
+                cost_table.instruction_table = zero(&cost_table.instruction_table)
+            }
+        }
+        cost_table
+    }
+}
+
+impl fmt::Display for GasCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let category = match self {
+            GasCategory::Instruction => "instruction",
+            GasCategory::Native => "native",
+        };
+        write!(f, "{}", category)
+    }
+}
+
+/// An execution step of the adapter, a call from the adapter into the Move VM, and the gas charged
+/// by it. The gas includes all the Move functions called inside the step, which are not recorded:
+/// the frames the Move VM enters are not visible to the adapter, so this is not a call stack.
+//TODO record the Move function frames with the gas of every frame, which requires a hook in the
+// call/return path of the interpreter in move-vm-runtime, it is not provided by the pinned version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallTrace {
+    pub kind: CallKind,
+    /// None for a script, which is not published in a module.
+    pub module: Option<ModuleId>,
+    /// None for a script or a module publishing.
+    pub function: Option<Identifier>,
+    pub ty_args: Vec<TypeTag>,
+    pub gas_used: u64,
+    /// The gas of the call by category, empty if the gas is not attributed.
+    pub gas_by_category: Vec<(GasCategory, u64)>,
+    /// The error returned by the call, None if the call succeeded.
+    pub error: Option<String>,
+}
+
+impl CallTrace {
+    pub fn new(
+        kind: CallKind,
+        module: Option<&ModuleId>,
+        function: Option<&IdentStr>,
+        ty_args: &[TypeTag],
+    ) -> Self {
+        Self {
+            kind,
+            module: module.cloned(),
+            function: function.map(ToOwned::to_owned),
+            ty_args: ty_args.to_vec(),
+            gas_used: 0,
+            gas_by_category: vec![],
+            error: None,
+        }
+    }
+}
+
+/// The execution steps of a transaction recorded by the `StarcoinVM`, at the level of the adapter.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionTrace {
+    /// The calls into the Move VM in execution order, they are not nested.
+    pub calls: Vec<CallTrace>,
+    /// The gas charged for the transaction size before executing it.
+    pub intrinsic_gas: u64,
+    /// The gas charged for the resources written to the global storage.
+    pub global_write_gas: u64,
+}